@@ -47,10 +47,10 @@ fn spawn(c: &mut Criterion) {
         );
 
         // Insert an asset that the fake loader can fake read.
-        dir.insert_asset_text(Path::new("button.bsn"), "");
+        dir.insert_asset_text(Path::new("button.fake_bsn"), "");
 
         let asset_server = app.world().resource::<AssetServer>().clone();
-        let handle = asset_server.load("button.bsn");
+        let handle = asset_server.load("button.fake_bsn");
 
         run_app_until(&mut app, || asset_server.is_loaded(&handle));
 
@@ -91,17 +91,17 @@ fn spawn(c: &mut Criterion) {
             },
         );
 
-        dir.insert_asset_text(Path::new("a.bsn"), "");
+        dir.insert_asset_text(Path::new("a.fake_bsn"), "");
 
         let asset_server = app.world().resource::<AssetServer>().clone();
-        let handle = asset_server.load::<ScenePatch>("a.bsn");
+        let handle = asset_server.load::<ScenePatch>("a.fake_bsn");
 
         run_app_until(&mut app, || asset_server.is_loaded(&handle));
 
         let world = app.world_mut();
         b.iter(|| {
             for _ in 0..100 {
-                world.spawn_scene(bsn! { :"a.bsn" }).unwrap();
+                world.spawn_scene(bsn! { :"a.fake_bsn" }).unwrap();
             }
         });
     });
@@ -118,17 +118,17 @@ fn spawn(c: &mut Criterion) {
             },
         );
 
-        dir.insert_asset_text(Path::new("a.bsn"), "");
+        dir.insert_asset_text(Path::new("a.fake_bsn"), "");
 
         let asset_server = app.world().resource::<AssetServer>().clone();
-        let handle = asset_server.load::<ScenePatch>("a.bsn");
+        let handle = asset_server.load::<ScenePatch>("a.fake_bsn");
 
         run_app_until(&mut app, || asset_server.is_loaded(&handle));
 
         let world = app.world_mut();
         b.iter(|| {
             for _ in 0..100 {
-                world.spawn_scene(bsn! { :"a.bsn" }).unwrap();
+                world.spawn_scene(bsn! { :"a.fake_bsn" }).unwrap();
             }
         });
     });
@@ -212,16 +212,16 @@ fn ui_loaded_asset() -> impl Scene {
     bsn! {
         Node
         Children [
-            (:"button.bsn" Node { width: Val::Px(200.) }),
-            (:"button.bsn" Node { width: Val::Px(200.) }),
-            (:"button.bsn" Node { width: Val::Px(200.) }),
-            (:"button.bsn" Node { width: Val::Px(200.) }),
-            (:"button.bsn" Node { width: Val::Px(200.) }),
-            (:"button.bsn" Node { width: Val::Px(200.) }),
-            (:"button.bsn" Node { width: Val::Px(200.) }),
-            (:"button.bsn" Node { width: Val::Px(200.) }),
-            (:"button.bsn" Node { width: Val::Px(200.) }),
-            (:"button.bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
+            (:"button.fake_bsn" Node { width: Val::Px(200.) }),
         ]
     }
 }
@@ -417,6 +417,10 @@ impl AssetLoader for FakeSceneLoader {
     type Error = std::io::Error;
     type Settings = ();
 
+    fn extensions(&self) -> &[&str] {
+        &["fake_bsn"]
+    }
+
     async fn load(
         &self,
         _reader: &mut dyn bevy_asset::io::Reader,
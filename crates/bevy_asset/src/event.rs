@@ -23,6 +23,23 @@ impl<A: Asset> AssetLoadFailedEvent<A> {
     }
 }
 
+/// A [`Message`] emitted when a partial version of an [`Asset`] that is still loading replaces
+/// the previous one, such as a more detailed level of an image or mesh.
+///
+/// Partial versions are published by asset loaders with
+/// [`LoadContext::publish_partial`](crate::LoadContext::publish_partial). The asset is added to
+/// (or modified in) [`Assets`](crate::Assets) as usual, so [`AssetEvent::Added`] and
+/// [`AssetEvent::Modified`] are emitted as well. Once the loader returns the final version,
+/// [`AssetEvent::LoadedWithDependencies`] is emitted instead of this event.
+#[derive(Message, Clone, Debug)]
+pub struct AssetUpgradedEvent<A: Asset> {
+    /// The stable identifier of the asset that was upgraded.
+    pub id: AssetId<A>,
+    /// The number of partial versions published before this one, starting at 0 for the first
+    /// partial version of the asset.
+    pub level: u32,
+}
+
 /// An untyped version of [`AssetLoadFailedEvent`].
 #[derive(Message, Clone, Debug)]
 pub struct UntypedAssetLoadFailedEvent {
@@ -0,0 +1,534 @@
+//! A snapshot of the assets tracked by the [`AssetServer`] and the dependencies between them.
+//!
+//! See [`AssetServer::dependency_graph`] to take a snapshot, which can be exported to
+//! [Graphviz](https://graphviz.org/) with [`AssetDependencyGraph::to_dot`] or serialized with
+//! `serde`, and [`AssetDependencyGraph::unused_files`] to find the files of an asset source that
+//! aren't used by any asset.
+
+use crate::{
+    io::{
+        AssetReaderError, AssetSourceId, ErasedAssetReader, MissingAssetSourceError,
+        MissingProcessedAssetReaderError,
+    },
+    meta::ProcessedInfoMinimal,
+    AssetPath, AssetServer, AssetServerMode, DependencyLoadState, ErasedAssetIndex, LoadState,
+    RecursiveDependencyLoadState,
+};
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use bevy_platform::collections::HashMap;
+use core::fmt::Write;
+use futures_lite::{AsyncSeekExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::SeekFrom,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// A snapshot of the assets tracked by an [`AssetServer`] and the dependencies between them.
+///
+/// This is returned by [`AssetServer::dependency_graph`]. Besides assets, the graph contains the
+/// files that were read by asset loaders or used to process assets without being loaded as
+/// assets themselves.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AssetDependencyGraph {
+    /// The assets and files in the graph, sorted by path.
+    pub nodes: Vec<AssetGraphNode>,
+    /// The dependencies between the nodes.
+    pub edges: Vec<AssetGraphEdge>,
+}
+
+/// An asset, or a file that an asset depends on, in an [`AssetDependencyGraph`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AssetGraphNode {
+    /// The path of the asset, or `None` if it wasn't loaded from a path.
+    pub path: Option<AssetPath<'static>>,
+    /// The type path of the asset, or `None` if this node is a file that isn't loaded as an
+    /// asset, such as a file read by an asset loader.
+    pub type_path: Option<String>,
+    /// The [`LoadState`] of the asset.
+    pub load_state: AssetGraphLoadState,
+    /// The [`DependencyLoadState`] of the asset.
+    pub dependency_load_state: AssetGraphLoadState,
+    /// The [`RecursiveDependencyLoadState`] of the asset.
+    pub recursive_dependency_load_state: AssetGraphLoadState,
+    /// The size in bytes of the file at [`path`](Self::path), or `None` if the asset isn't
+    /// stored in a file of its own, like labeled assets.
+    pub size: Option<u64>,
+}
+
+/// The load state of an [`AssetGraphNode`].
+///
+/// This mirrors [`LoadState`], [`DependencyLoadState`] and [`RecursiveDependencyLoadState`],
+/// with errors stored as strings so that the graph can be serialized.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssetGraphLoadState {
+    /// The asset has not started loading yet, or the node is a file that isn't loaded as an
+    /// asset.
+    NotLoaded,
+    /// The asset is loading.
+    Loading,
+    /// The asset has loaded.
+    Loaded,
+    /// The asset failed to load, with the given error.
+    Failed(String),
+}
+
+impl From<&LoadState> for AssetGraphLoadState {
+    fn from(state: &LoadState) -> Self {
+        match state {
+            LoadState::NotLoaded => Self::NotLoaded,
+            LoadState::Loading => Self::Loading,
+            LoadState::Loaded => Self::Loaded,
+            LoadState::Failed(error) => Self::Failed(error.to_string()),
+        }
+    }
+}
+
+impl From<&DependencyLoadState> for AssetGraphLoadState {
+    fn from(state: &DependencyLoadState) -> Self {
+        match state {
+            DependencyLoadState::NotLoaded => Self::NotLoaded,
+            DependencyLoadState::Loading => Self::Loading,
+            DependencyLoadState::Loaded => Self::Loaded,
+            DependencyLoadState::Failed(error) => Self::Failed(error.to_string()),
+        }
+    }
+}
+
+impl From<&RecursiveDependencyLoadState> for AssetGraphLoadState {
+    fn from(state: &RecursiveDependencyLoadState) -> Self {
+        match state {
+            RecursiveDependencyLoadState::NotLoaded => Self::NotLoaded,
+            RecursiveDependencyLoadState::Loading => Self::Loading,
+            RecursiveDependencyLoadState::Loaded => Self::Loaded,
+            RecursiveDependencyLoadState::Failed(error) => Self::Failed(error.to_string()),
+        }
+    }
+}
+
+/// A dependency of one [`AssetGraphNode`] on another.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetGraphEdge {
+    /// The index in [`AssetDependencyGraph::nodes`] of the dependent.
+    pub from: usize,
+    /// The index in [`AssetDependencyGraph::nodes`] of the dependency.
+    pub to: usize,
+    /// The kind of dependency.
+    pub kind: AssetGraphEdgeKind,
+}
+
+/// The kind of an [`AssetGraphEdge`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssetGraphEdgeKind {
+    /// The asset holds a handle to the dependency, as reported by
+    /// [`VisitAssetDependencies`](crate::VisitAssetDependencies).
+    Handle,
+    /// The dependency was read by the asset loader while loading the asset.
+    /// See [`LoadContext::read_asset_bytes`](crate::LoadContext::read_asset_bytes).
+    Loader,
+    /// The dependency was used to process the asset.
+    /// See [`ProcessedInfo::process_dependencies`](crate::meta::ProcessedInfo::process_dependencies).
+    Process,
+}
+
+/// An error returned by [`AssetDependencyGraph::unused_files`].
+#[derive(Error, Debug)]
+pub enum UnusedFilesError {
+    /// The asset source does not exist.
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    /// The [`AssetServer`] loads processed assets, but the asset source has no processed reader.
+    #[error(transparent)]
+    MissingProcessedAssetReader(#[from] MissingProcessedAssetReaderError),
+    /// The files of the asset source could not be read.
+    #[error(transparent)]
+    AssetReaderError(#[from] AssetReaderError),
+}
+
+/// A file of an asset source that isn't used by any asset, returned by
+/// [`AssetDependencyGraph::unused_files`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnusedFile {
+    /// The path of the file.
+    pub path: AssetPath<'static>,
+    /// The size of the file in bytes.
+    pub size: u64,
+}
+
+impl AssetDependencyGraph {
+    /// Takes a snapshot of the assets tracked by `server`. See [`AssetServer::dependency_graph`].
+    pub(crate) async fn from_server(server: &AssetServer) -> Self {
+        let mut graph = Self::default();
+        // The node of each asset path. Used to find the targets of loader and process
+        // dependencies, which never have labels.
+        let mut path_nodes = HashMap::<AssetPath<'static>, usize>::default();
+        {
+            let infos = server.read_infos();
+            let mut assets: Vec<_> = infos.iter().collect();
+            let type_path =
+                |index: &ErasedAssetIndex| infos.type_paths.get(&index.type_id).copied();
+            assets.sort_by_key(|(index, info)| {
+                (
+                    info.path.as_ref().map(ToString::to_string),
+                    type_path(index),
+                )
+            });
+
+            let nodes: HashMap<ErasedAssetIndex, usize> = assets
+                .iter()
+                .enumerate()
+                .map(|(node, (index, _))| (**index, node))
+                .collect();
+            for (index, info) in &assets {
+                if let Some(path) = &info.path {
+                    path_nodes.entry(path.clone()).or_insert(graph.nodes.len());
+                }
+                graph.nodes.push(AssetGraphNode {
+                    path: info.path.clone(),
+                    type_path: type_path(index).map(ToOwned::to_owned),
+                    load_state: (&info.load_state).into(),
+                    dependency_load_state: (&info.dep_load_state).into(),
+                    recursive_dependency_load_state: (&info.rec_dep_load_state).into(),
+                    size: None,
+                });
+            }
+
+            for (node, (_, info)) in assets.iter().enumerate() {
+                let mut dependencies: Vec<usize> = info
+                    .dependencies
+                    .iter()
+                    .filter_map(|dependency| nodes.get(dependency).copied())
+                    .collect();
+                dependencies.sort_unstable();
+                for dependency in dependencies {
+                    graph.add_edge(node, dependency, AssetGraphEdgeKind::Handle);
+                }
+
+                let mut loader_dependencies: Vec<_> = info.loader_dependencies.keys().collect();
+                loader_dependencies.sort_by_key(ToString::to_string);
+                for path in loader_dependencies {
+                    let dependency = graph.file_node(&mut path_nodes, path.clone());
+                    graph.add_edge(node, dependency, AssetGraphEdgeKind::Loader);
+                }
+            }
+        }
+
+        // Every file is read at most once, including those added as process dependencies.
+        let mut pending: Vec<usize> = (0..graph.nodes.len()).rev().collect();
+        let mut visited = BTreeSet::new();
+        while let Some(node) = pending.pop() {
+            let Some(path) = graph.nodes[node].path.clone() else {
+                continue;
+            };
+            if path.label().is_some() || !visited.insert(path.to_string()) {
+                continue;
+            }
+            let Ok(source) = server.get_source(path.source()) else {
+                continue;
+            };
+            let reader = match server.mode() {
+                AssetServerMode::Unprocessed => source.reader(),
+                AssetServerMode::Processed => match source.processed_reader() {
+                    Ok(reader) => reader,
+                    Err(_) => continue,
+                },
+            };
+            graph.nodes[node].size = file_size(reader, path.path()).await.ok();
+
+            // Processed assets record the files they were processed from in their meta file.
+            if server.mode() != AssetServerMode::Processed {
+                continue;
+            }
+            let Some(processed_info) = read_meta_bytes(reader, path.path())
+                .await
+                .ok()
+                .and_then(|bytes| ron::de::from_bytes::<ProcessedInfoMinimal>(&bytes).ok())
+                .and_then(|minimal| minimal.processed_info)
+            else {
+                continue;
+            };
+            for dependency in processed_info.process_dependencies {
+                let dependency = graph.file_node(&mut path_nodes, dependency.path);
+                graph.add_edge(node, dependency, AssetGraphEdgeKind::Process);
+                pending.push(dependency);
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the node of the asset at `path`, ignoring its label, adding a node for the file
+    /// if there isn't one.
+    fn file_node(
+        &mut self,
+        path_nodes: &mut HashMap<AssetPath<'static>, usize>,
+        path: AssetPath<'static>,
+    ) -> usize {
+        let path = path.without_label().into_owned();
+        *path_nodes.entry(path.clone()).or_insert_with(|| {
+            self.nodes.push(AssetGraphNode {
+                path: Some(path),
+                type_path: None,
+                load_state: AssetGraphLoadState::NotLoaded,
+                dependency_load_state: AssetGraphLoadState::NotLoaded,
+                recursive_dependency_load_state: AssetGraphLoadState::NotLoaded,
+                size: None,
+            });
+            self.nodes.len() - 1
+        })
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, kind: AssetGraphEdgeKind) {
+        let edge = AssetGraphEdge { from, to, kind };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Returns the graph in the [DOT](https://graphviz.org/doc/info/lang.html) format of
+    /// Graphviz.
+    ///
+    /// Each node is labeled with its path, type, load state and size. Handle dependencies are
+    /// drawn as solid lines, loader dependencies as dashed lines and process dependencies as
+    /// dotted lines. Assets that failed to load are drawn in red.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph assets {\n    node [shape=box];\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let mut label = match &node.path {
+                Some(path) => path.to_string(),
+                None => String::from("<no path>"),
+            };
+            if let Some(type_path) = &node.type_path {
+                let _ = write!(label, "\n{type_path}");
+                let state = match &node.load_state {
+                    AssetGraphLoadState::NotLoaded => "not loaded",
+                    AssetGraphLoadState::Loading => "loading",
+                    AssetGraphLoadState::Loaded => "loaded",
+                    AssetGraphLoadState::Failed(_) => "failed",
+                };
+                let _ = write!(label, "\n{state}");
+            }
+            if let Some(size) = node.size {
+                let _ = write!(label, "\n{size} bytes");
+            }
+            let style = match (&node.type_path, &node.load_state) {
+                (_, AssetGraphLoadState::Failed(_)) => ", color=red",
+                (None, _) => ", style=dashed",
+                _ => "",
+            };
+            let _ = writeln!(dot, "    n{index} [label=\"{}\"{style}];", escape(&label));
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                AssetGraphEdgeKind::Handle => "",
+                AssetGraphEdgeKind::Loader => " [style=dashed]",
+                AssetGraphEdgeKind::Process => " [style=dotted]",
+            };
+            let _ = writeln!(dot, "    n{} -> n{}{style};", edge.from, edge.to);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Returns the files of the asset source `source` that no node of this graph refers to,
+    /// sorted by path.
+    ///
+    /// These are the files that are neither loaded as assets, read by asset loaders, nor used
+    /// to process the loaded assets, and are candidates for removal from shipped content. Files
+    /// are listed from the reader the [`AssetServer`] loads assets from, so process
+    /// dependencies are only taken into account when loading processed assets. `.meta` files
+    /// are never listed.
+    ///
+    /// This is only as accurate as the graph: assets that are loaded later on, such as those of
+    /// a level that hasn't been loaded when the graph was taken, will be listed.
+    pub async fn unused_files<'a>(
+        &self,
+        server: &AssetServer,
+        source: impl Into<AssetSourceId<'a>>,
+    ) -> Result<Vec<UnusedFile>, UnusedFilesError> {
+        let source = server.get_source(source.into())?;
+        let reader = match server.mode() {
+            AssetServerMode::Unprocessed => source.reader(),
+            AssetServerMode::Processed => source.processed_reader()?,
+        };
+        let used: BTreeSet<&Path> = self
+            .nodes
+            .iter()
+            .filter_map(|node| node.path.as_ref())
+            .filter(|path| *path.source() == source.id())
+            .map(AssetPath::path)
+            .collect();
+
+        let mut files = Vec::new();
+        let mut pending = vec![PathBuf::new()];
+        while let Some(directory) = pending.pop() {
+            let mut entries = reader.read_directory(&directory).await?;
+            while let Some(path) = entries.next().await {
+                let is_meta = path
+                    .extension()
+                    .is_some_and(|extension| extension == "meta");
+                if reader.is_directory(&path).await? {
+                    pending.push(path);
+                } else if !is_meta && !used.contains(path.as_path()) {
+                    let size = file_size(reader, &path).await?;
+                    let path = AssetPath::from_path_buf(path).with_source(source.id());
+                    files.push(UnusedFile { path, size });
+                }
+            }
+        }
+        files.sort_by(|a, b| a.path.path().cmp(b.path.path()));
+        Ok(files)
+    }
+}
+
+/// Returns the size in bytes of the file at `path`.
+///
+/// The size is found by seeking to the end of the file, and the file is only read if its reader
+/// isn't seekable.
+async fn file_size(reader: &dyn ErasedAssetReader, path: &Path) -> Result<u64, AssetReaderError> {
+    let mut reader = reader.read(path).await?;
+    if let Ok(seekable) = reader.seekable() {
+        return Ok(seekable.seek(SeekFrom::End(0)).await?);
+    }
+    Ok(futures_lite::io::copy(reader, futures_lite::io::sink()).await?)
+}
+
+async fn read_meta_bytes(
+    reader: &dyn ErasedAssetReader,
+    path: &Path,
+) -> Result<Vec<u8>, AssetReaderError> {
+    let mut meta_reader = reader.read_meta(path).await?;
+    let mut bytes = Vec::new();
+    meta_reader.read_to_end(&mut bytes).await?;
+    Ok(bytes)
+}
+
+/// Escapes `label` for use in a quoted DOT string.
+fn escape(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for char in label.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssetGraphEdge, AssetGraphEdgeKind, AssetGraphLoadState, UnusedFile};
+    use crate::{
+        io::AssetSourceId,
+        tests::{create_app, run_app_until, CoolText, CoolTextLoader, SubText},
+        AssetApp, AssetPath, AssetServer,
+    };
+    use alloc::{string::ToString, vec, vec::Vec};
+    use bevy_reflect::TypePath;
+    use bevy_tasks::block_on;
+    use std::path::Path;
+
+    #[test]
+    fn dependency_graph() {
+        let (mut app, dir) = create_app();
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        app.world()
+            .resource::<AssetServer>()
+            .set_tracking_dependencies(true);
+
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            r#"
+(
+    text: "a",
+    dependencies: ["b.cool.ron"],
+    embedded_dependencies: ["c.cool.ron"],
+    sub_texts: ["sub"],
+)"#,
+        );
+        let leaf = |text: &str| {
+            alloc::format!(
+                "(text: \"{text}\", dependencies: [], embedded_dependencies: [], sub_texts: [])"
+            )
+        };
+        dir.insert_asset_text(Path::new("b.cool.ron"), &leaf("b"));
+        dir.insert_asset_text(Path::new("c.cool.ron"), &leaf("c"));
+        dir.insert_asset_text(Path::new("unused/d.txt"), "unused");
+
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load::<CoolText>("a.cool.ron");
+        run_app_until(&mut app, |world| {
+            let server = world.resource::<AssetServer>();
+            server.is_loaded_with_dependencies(&handle).then_some(())
+        });
+
+        let server = app.world().resource::<AssetServer>().clone();
+        let graph = block_on(server.dependency_graph());
+
+        let paths: Vec<_> = graph
+            .nodes
+            .iter()
+            .map(|node| node.path.as_ref().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            ["a.cool.ron", "a.cool.ron#sub", "b.cool.ron", "c.cool.ron"]
+        );
+        assert_eq!(
+            graph.nodes[0].type_path.as_deref(),
+            Some(CoolText::type_path())
+        );
+        assert_eq!(
+            graph.nodes[1].type_path.as_deref(),
+            Some(SubText::type_path())
+        );
+        assert!(graph.nodes[..3]
+            .iter()
+            .all(|node| node.load_state == AssetGraphLoadState::Loaded));
+        // c.cool.ron is only read by the loader of a.cool.ron.
+        assert_eq!(graph.nodes[3].type_path, None);
+        assert_eq!(graph.nodes[3].load_state, AssetGraphLoadState::NotLoaded);
+        // Labeled assets aren't stored in a file of their own.
+        assert_eq!(graph.nodes[1].size, None);
+        assert_eq!(graph.nodes[2].size, Some(leaf("b").len() as u64));
+
+        let edge = |from, to, kind| AssetGraphEdge { from, to, kind };
+        let mut edges = graph.edges.clone();
+        edges.sort_by_key(|edge| (edge.from, edge.to));
+        assert_eq!(
+            edges,
+            [
+                edge(0, 1, AssetGraphEdgeKind::Handle),
+                edge(0, 2, AssetGraphEdgeKind::Handle),
+                edge(0, 3, AssetGraphEdgeKind::Loader),
+            ]
+        );
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("n1 [label=\"a.cool.ron#sub\\n"));
+        assert!(dot.contains("n0 -> n2;"));
+        assert!(dot.contains("n0 -> n3 [style=dashed];"));
+
+        let unused = block_on(graph.unused_files(&server, AssetSourceId::Default)).unwrap();
+        assert_eq!(
+            unused,
+            vec![UnusedFile {
+                path: AssetPath::parse("unused/d.txt").into_owned(),
+                size: 6,
+            }]
+        );
+    }
+}
@@ -0,0 +1,491 @@
+//! A backend for reading [`Asset`]s out of a single packaged archive.
+//!
+//! [`ArchiveAssetReader`] mounts an archive as an asset source, so that a release build can ship
+//! its assets as one file rather than a directory tree. Two formats are supported:
+//!
+//! - The Bevy pak format, a minimal uncompressed format with an index of its files, which can be
+//!   written with a [`PakBuilder`]. See the [`pak`] module for its layout.
+//! - Zip archives, if the `zip` feature is enabled. These can be created with any zip tool.
+//!
+//! The `.meta` files of assets are read from the archive alongside them, so an archive of the
+//! `imported_assets` folder written by the asset processor can be used as the processed source:
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_asset::{io::{AssetSourceBuilder, AssetSourceId}, AssetApp, AssetMode, AssetPlugin};
+//! let mut app = App::new();
+//! app.register_asset_source(AssetSourceId::Default, AssetSourceBuilder::archive("assets.pak"))
+//!     .add_plugins(AssetPlugin {
+//!         mode: AssetMode::Processed,
+//!         ..Default::default()
+//!     });
+//! ```
+//!
+//! [`Asset`]: crate::Asset
+
+pub mod pak;
+#[cfg(feature = "zip")]
+mod zip;
+
+pub use pak::PakBuilder;
+
+use crate::io::{get_meta_path, AssetReader, AssetReaderError, PathStream, Reader, VecReader};
+use alloc::{borrow::ToOwned, boxed::Box, collections::BTreeSet, sync::Arc, vec::Vec};
+use bevy_platform::{collections::HashMap, sync::OnceLock};
+use std::{
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+/// An [`AssetReader`] that reads assets out of a pak or zip archive.
+///
+/// The format of the archive is detected from its contents. Its index is read the first time
+/// the reader is used, and clones of the reader share it. If the archive can't be opened, every
+/// read fails with the error that occurred.
+///
+/// Every read opens its own reader over the archive, so several assets can be read at once, and
+/// the blocking reads run on a separate thread pool rather than on the async task pools.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Clone)]
+pub struct ArchiveAssetReader(Arc<ArchiveState>);
+
+struct ArchiveState {
+    source: ArchiveSource,
+    archive: OnceLock<Result<Archive, Arc<io::Error>>>,
+}
+
+#[derive(Clone)]
+enum ArchiveSource {
+    #[cfg(not(target_arch = "wasm32"))]
+    File(PathBuf),
+    Bytes(Arc<[u8]>),
+}
+
+impl ArchiveSource {
+    /// Opens a new reader over the raw bytes of the archive.
+    fn open(&self) -> io::Result<Box<dyn ReadSeek>> {
+        Ok(match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            ArchiveSource::File(path) => Box::new(io::BufReader::new(std::fs::File::open(path)?)),
+            ArchiveSource::Bytes(bytes) => Box::new(Cursor::new(bytes.clone())),
+        })
+    }
+}
+
+impl ArchiveAssetReader {
+    /// Creates a new [`ArchiveAssetReader`] for the archive file at `path`, relative to the
+    /// executable's directory.
+    ///
+    /// See [`FileAssetReader::get_base_path`](crate::io::file::FileAssetReader::get_base_path).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        let path = crate::io::file::get_base_path().join(path);
+        debug!(
+            "Asset Server using archive {} as its source.",
+            path.display()
+        );
+        Self::from_source(ArchiveSource::File(path))
+    }
+
+    /// Creates a new [`ArchiveAssetReader`] for an archive that is already in memory.
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> Self {
+        Self::from_source(ArchiveSource::Bytes(bytes.into()))
+    }
+
+    fn from_source(source: ArchiveSource) -> Self {
+        Self(Arc::new(ArchiveState {
+            source,
+            archive: OnceLock::new(),
+        }))
+    }
+
+    /// Returns the opened archive, opening it if this is the first time it is used.
+    async fn archive(&self) -> Result<&Archive, AssetReaderError> {
+        if self.0.archive.get().is_none() {
+            let reader = self.clone();
+            unblock(move || {
+                // The result is kept in the `OnceLock`.
+                let _ = reader.archive_blocking();
+            })
+            .await;
+        }
+        self.archive_blocking()
+    }
+
+    /// Returns the opened archive, opening it on the current thread if this is the first time it
+    /// is used.
+    fn archive_blocking(&self) -> Result<&Archive, AssetReaderError> {
+        self.0
+            .archive
+            .get_or_init(|| Archive::open(&self.0.source).map_err(Arc::new))
+            .as_ref()
+            .map_err(|error| AssetReaderError::Io(error.clone()))
+    }
+
+    async fn read_file(&self, path: &Path) -> Result<VecReader, AssetReaderError> {
+        let index = *self
+            .archive()
+            .await?
+            .files
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+        let reader = self.clone();
+        let bytes = unblock(move || -> Result<_, AssetReaderError> {
+            Ok(reader.archive_blocking()?.format.read_entry(index)?)
+        })
+        .await?;
+        Ok(VecReader::new(bytes))
+    }
+}
+
+/// Runs the blocking function `f` on the thread pool of the `blocking` crate.
+#[cfg(not(target_arch = "wasm32"))]
+async fn unblock<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    blocking::unblock(f).await
+}
+
+/// Runs the blocking function `f` directly, since only archives in memory can be read on the web.
+#[cfg(target_arch = "wasm32")]
+async fn unblock<T>(f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+impl AssetReader for ArchiveAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.read_file(path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.read_file(&get_meta_path(path)).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let entries = self
+            .archive()
+            .await?
+            .directories
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?
+            .iter()
+            .filter(|path| {
+                // filter out meta files as they are not considered assets
+                let is_meta = path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("meta"));
+                // filter out hidden files. they are not listed by default but are directly targetable
+                let is_hidden = path
+                    .file_name()
+                    .and_then(|file_name| file_name.to_str())
+                    .is_some_and(|file_name| file_name.starts_with('.'));
+                !is_meta && !is_hidden
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        Ok(Box::new(futures_lite::stream::iter(entries)))
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        let archive = self.archive().await?;
+        if archive.directories.contains_key(path) {
+            Ok(true)
+        } else if archive.files.contains_key(path) {
+            Ok(false)
+        } else {
+            Err(AssetReaderError::NotFound(path.to_owned()))
+        }
+    }
+}
+
+/// A reader over the raw bytes of an archive.
+trait ReadSeek: Read + Seek + Send + Sync {}
+
+impl<T: Read + Seek + Send + Sync> ReadSeek for T {}
+
+/// Reads the contents of the files in an archive of a given format.
+trait ArchiveFormat: Send + Sync {
+    /// Returns the contents of the file at `index` in the list of files returned when the
+    /// archive was opened.
+    ///
+    /// Each call reads through its own reader, so entries can be read concurrently.
+    fn read_entry(&self, index: usize) -> io::Result<Vec<u8>>;
+}
+
+/// The index of an opened archive.
+struct Archive {
+    /// The index of every file in the archive, by path.
+    files: HashMap<PathBuf, usize>,
+    /// The contents of every directory in the archive, including the root `""`, by path.
+    directories: HashMap<PathBuf, BTreeSet<PathBuf>>,
+    format: Box<dyn ArchiveFormat>,
+}
+
+impl Archive {
+    fn open(source: &ArchiveSource) -> io::Result<Self> {
+        let mut reader = source.open()?;
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        reader.seek(SeekFrom::Start(0))?;
+        match magic {
+            _ if magic == pak::PAK_MAGIC[..4] => {
+                let (format, files) = pak::PakArchive::open(source.clone(), reader)?;
+                Ok(Self::new(Box::new(format), files, Vec::new()))
+            }
+            #[cfg(feature = "zip")]
+            [b'P', b'K', ..] => {
+                let (format, files, directories) = zip::ZipArchive::open(source.clone(), reader)?;
+                Ok(Self::new(Box::new(format), files, directories))
+            }
+            #[cfg(not(feature = "zip"))]
+            [b'P', b'K', ..] => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "reading zip archives requires the `zip` feature",
+            )),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a pak or zip archive",
+            )),
+        }
+    }
+
+    fn new(format: Box<dyn ArchiveFormat>, files: Vec<PathBuf>, directories: Vec<PathBuf>) -> Self {
+        let mut archive = Self {
+            files: HashMap::default(),
+            directories: HashMap::default(),
+            format,
+        };
+        archive.directories.insert(PathBuf::new(), BTreeSet::new());
+        for directory in directories {
+            archive.insert_path(directory.clone());
+            archive.directories.entry(directory).or_default();
+        }
+        for (index, path) in files.into_iter().enumerate() {
+            archive.insert_path(path.clone());
+            archive.files.insert(path, index);
+        }
+        archive
+    }
+
+    /// Adds `path` to the listing of its parent directory, creating its ancestors as needed.
+    fn insert_path(&mut self, mut path: PathBuf) {
+        while let Some(parent) = path.parent() {
+            let parent = parent.to_owned();
+            let listing = self.directories.entry(parent.clone()).or_default();
+            if !listing.insert(path) {
+                break;
+            }
+            path = parent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        pak::{PAK_MAGIC, PAK_VERSION},
+        ArchiveAssetReader, PakBuilder,
+    };
+    use crate::{
+        io::{AssetReader, AssetReaderError, Reader},
+        tests::create_temp_dir,
+    };
+    use alloc::{vec, vec::Vec};
+    use bevy_tasks::block_on;
+    use futures_lite::StreamExt;
+    use std::path::{Path, PathBuf};
+
+    fn read(reader: &ArchiveAssetReader, path: &str) -> Result<Vec<u8>, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(bytes)
+        })
+    }
+
+    fn list(reader: &ArchiveAssetReader, path: &str) -> Vec<PathBuf> {
+        block_on(async {
+            let mut entries: Vec<_> = reader
+                .read_directory(Path::new(path))
+                .await
+                .unwrap()
+                .collect()
+                .await;
+            entries.sort();
+            entries
+        })
+    }
+
+    fn is_directory(reader: &ArchiveAssetReader, path: &str) -> Result<bool, AssetReaderError> {
+        block_on(reader.is_directory(Path::new(path)))
+    }
+
+    fn check_archive(reader: &ArchiveAssetReader) {
+        assert_eq!(read(reader, "a.txt").unwrap(), b"a");
+        assert_eq!(read(reader, "x/y/b.txt").unwrap(), b"b");
+        assert_eq!(
+            block_on(reader.read_meta_bytes(Path::new("x/y/b.txt"))).unwrap(),
+            b"b meta"
+        );
+        assert_eq!(
+            read(reader, "missing.txt").unwrap_err(),
+            AssetReaderError::NotFound(PathBuf::from("missing.txt"))
+        );
+
+        assert_eq!(
+            list(reader, ""),
+            vec![PathBuf::from("a.txt"), PathBuf::from("x")]
+        );
+        assert_eq!(list(reader, "x"), vec![PathBuf::from("x/y")]);
+        assert_eq!(list(reader, "x/y"), vec![PathBuf::from("x/y/b.txt")]);
+
+        assert!(is_directory(reader, "").unwrap());
+        assert!(is_directory(reader, "x/y").unwrap());
+        assert!(!is_directory(reader, "x/y/b.txt").unwrap());
+        assert!(is_directory(reader, "z").is_err());
+    }
+
+    #[test]
+    fn pak_archive() {
+        let mut pak = Vec::new();
+        PakBuilder::new()
+            .add_file("a.txt", b"a".as_slice())
+            .add_file("x/y/b.txt", b"b".as_slice())
+            .add_file("x/y/b.txt.meta", b"b meta".as_slice())
+            .add_file(".hidden", b"hidden".as_slice())
+            .write(&mut pak)
+            .unwrap();
+
+        let reader = ArchiveAssetReader::from_bytes(pak);
+        check_archive(&reader);
+        assert_eq!(read(&reader, ".hidden").unwrap(), b"hidden");
+    }
+
+    #[test]
+    fn pack_directory() {
+        let directory = create_temp_dir("pack_directory");
+        std::fs::create_dir_all(directory.join("x/y")).unwrap();
+        std::fs::write(directory.join("a.txt"), "a").unwrap();
+        std::fs::write(directory.join("x/y/b.txt"), "b").unwrap();
+        std::fs::write(directory.join("x/y/b.txt.meta"), "b meta").unwrap();
+
+        let mut pak = Vec::new();
+        PakBuilder::new()
+            .add_directory(&directory)
+            .unwrap()
+            .write(&mut pak)
+            .unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        check_archive(&ArchiveAssetReader::from_bytes(pak));
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn zip_archive() {
+        use std::io::{Cursor, Write};
+        use zip::{write::SimpleFileOptions, ZipWriter};
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        for (path, contents) in [
+            ("a.txt", "a"),
+            ("x/y/b.txt", "b"),
+            ("x/y/b.txt.meta", "b meta"),
+        ] {
+            zip.start_file(path, options).unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.add_directory("x/", options).unwrap();
+        let zip = zip.finish().unwrap().into_inner();
+
+        check_archive(&ArchiveAssetReader::from_bytes(zip));
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn zip_entry_with_wrong_size() {
+        use std::io::{Cursor, Write};
+        use zip::{write::SimpleFileOptions, ZipWriter};
+
+        fn zip_with_size(size: u32) -> Vec<u8> {
+            let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+            zip.start_file("a.txt", SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(b"abc").unwrap();
+            let mut zip = zip.finish().unwrap().into_inner();
+            // Overwrite the uncompressed size in the local and central directory headers.
+            for (signature, offset) in [(b"PK\x03\x04", 22), (b"PK\x01\x02", 24)] {
+                let header = zip
+                    .windows(4)
+                    .position(|window| window == signature)
+                    .unwrap();
+                zip[header + offset..header + offset + 4].copy_from_slice(&size.to_le_bytes());
+            }
+            zip
+        }
+
+        let reader = ArchiveAssetReader::from_bytes(zip_with_size(3));
+        assert_eq!(read(&reader, "a.txt").unwrap(), b"abc");
+
+        // A size far larger than the entry must not be allocated up front.
+        for size in [1, 0xFFFF_FFFE] {
+            let reader = ArchiveAssetReader::from_bytes(zip_with_size(size));
+            assert!(matches!(
+                read(&reader, "a.txt"),
+                Err(AssetReaderError::Io(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_archive() {
+        let reader = ArchiveAssetReader::from_bytes(b"not an archive".as_slice());
+        assert!(matches!(
+            read(&reader, "a.txt"),
+            Err(AssetReaderError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn corrupted_pak_index() {
+        fn pak_with_entry(path_len: u32, offset: u64, len: u64) -> Vec<u8> {
+            let mut pak = PAK_MAGIC.to_vec();
+            pak.extend(PAK_VERSION.to_le_bytes());
+            pak.extend(1u32.to_le_bytes());
+            pak.extend(path_len.to_le_bytes());
+            pak.extend(b"a.txt");
+            pak.extend(offset.to_le_bytes());
+            pak.extend(len.to_le_bytes());
+            pak.extend(b"a");
+            pak
+        }
+
+        let valid = pak_with_entry(5, 41, 1);
+        assert_eq!(valid.len(), 42);
+        let reader = ArchiveAssetReader::from_bytes(valid);
+        assert_eq!(read(&reader, "a.txt").unwrap(), b"a");
+
+        for pak in [
+            pak_with_entry(u32::MAX, 41, 1),
+            pak_with_entry(5, 41, u64::MAX),
+            pak_with_entry(5, u64::MAX, 1),
+            pak_with_entry(5, 41, 2),
+        ] {
+            let reader = ArchiveAssetReader::from_bytes(pak);
+            assert!(matches!(
+                read(&reader, "a.txt"),
+                Err(AssetReaderError::Io(_))
+            ));
+        }
+    }
+}
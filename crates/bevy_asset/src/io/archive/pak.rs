@@ -0,0 +1,239 @@
+//! The Bevy pak format: a minimal, uncompressed archive with an index of its files.
+//!
+//! A pak file starts with a header and an index, followed by the contents of every file:
+//!
+//! | Field         | Type            |
+//! |---------------|-----------------|
+//! | magic         | `b"BEVYPAK\0"`  |
+//! | version       | `u32`           |
+//! | entry count   | `u32`           |
+//! | entries       | see below       |
+//! | file contents | bytes           |
+//!
+//! Each entry is the length of the file's path as a `u32`, the path itself as UTF-8 using `/`
+//! separators, followed by the offset of the file's contents from the start of the archive and
+//! their length, both as `u64`. All integers are little-endian.
+
+use super::{ArchiveFormat, ArchiveSource, ReadSeek};
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+use bevy_platform::collections::HashMap;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// The bytes that every pak archive starts with.
+pub const PAK_MAGIC: [u8; 8] = *b"BEVYPAK\0";
+
+/// The version of the pak format written by [`PakBuilder`].
+pub const PAK_VERSION: u32 = 1;
+
+/// The contents of a file added to a [`PakBuilder`].
+enum PakFile {
+    Bytes(Vec<u8>),
+    Path(PathBuf),
+}
+
+/// Builds a pak archive, which can be read back with an
+/// [`ArchiveAssetReader`](super::ArchiveAssetReader).
+///
+/// This is meant to package the processed assets of a release build into a single file:
+///
+/// ```no_run
+/// # use bevy_asset::io::archive::PakBuilder;
+/// let mut builder = PakBuilder::new();
+/// builder.add_directory("imported_assets/Default")?;
+/// builder.write_to_file("assets.pak")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Default)]
+pub struct PakBuilder {
+    files: HashMap<String, PakFile>,
+}
+
+impl PakBuilder {
+    /// Creates an empty [`PakBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file with the given `bytes` at `path` in the archive, replacing any file
+    /// already added at that path.
+    pub fn add_file(&mut self, path: impl AsRef<Path>, bytes: impl Into<Vec<u8>>) -> &mut Self {
+        self.files
+            .insert(archive_path(path.as_ref()), PakFile::Bytes(bytes.into()));
+        self
+    }
+
+    /// Adds every file inside `directory` and its subdirectories, including `.meta` files,
+    /// at their path relative to `directory`.
+    ///
+    /// The files are only read when the archive is written.
+    pub fn add_directory(&mut self, directory: impl AsRef<Path>) -> io::Result<&mut Self> {
+        let directory = directory.as_ref();
+        let mut pending = vec![directory.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            for entry in std::fs::read_dir(&current)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else {
+                    let relative_path = path.strip_prefix(directory).unwrap();
+                    self.files
+                        .insert(archive_path(relative_path), PakFile::Path(path));
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Writes the archive to `writer`.
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut paths: Vec<&String> = self.files.keys().collect();
+        paths.sort();
+
+        let lengths = paths
+            .iter()
+            .map(|path| match &self.files[*path] {
+                PakFile::Bytes(bytes) => Ok(bytes.len() as u64),
+                PakFile::Path(path) => Ok(std::fs::metadata(path)?.len()),
+            })
+            .collect::<io::Result<Vec<u64>>>()?;
+
+        let index_len: usize = paths.iter().map(|path| 4 + path.len() + 16).sum();
+        let mut offset = (PAK_MAGIC.len() + 8 + index_len) as u64;
+
+        writer.write_all(&PAK_MAGIC)?;
+        writer.write_all(&PAK_VERSION.to_le_bytes())?;
+        writer.write_all(&to_u32(paths.len())?.to_le_bytes())?;
+        for (path, len) in paths.iter().zip(&lengths) {
+            writer.write_all(&to_u32(path.len())?.to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&len.to_le_bytes())?;
+            offset += len;
+        }
+
+        for (path, len) in paths.iter().zip(&lengths) {
+            match &self.files[*path] {
+                PakFile::Bytes(bytes) => writer.write_all(bytes)?,
+                PakFile::Path(file_path) => {
+                    let copied = io::copy(&mut File::open(file_path)?.take(*len), &mut writer)?;
+                    if copied != *len {
+                        return Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            format!("{} changed while it was being packed", file_path.display()),
+                        ));
+                    }
+                }
+            }
+        }
+        writer.flush()
+    }
+
+    /// Writes the archive to a new file at `path`, replacing it if it already exists.
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Converts `path` to the `/`-separated form stored in the index.
+fn archive_path(path: &Path) -> String {
+    let components: Vec<_> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect();
+    components.join("/")
+}
+
+fn to_u32(value: usize) -> io::Result<u32> {
+    u32::try_from(value).map_err(|_| invalid_data("too many files for a pak archive"))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// A pak archive opened for reading.
+pub(super) struct PakArchive {
+    source: ArchiveSource,
+    entries: Vec<(u64, u64)>,
+}
+
+impl PakArchive {
+    /// Reads the index of the pak archive in `reader`, a reader over `source`, returning the
+    /// archive and the paths of its files, in the same order as their entries.
+    ///
+    /// The lengths and offsets in the index are checked against the size of the archive, so a
+    /// corrupted index returns an error instead of allocating for data that doesn't exist.
+    pub(super) fn open(
+        source: ArchiveSource,
+        mut reader: Box<dyn ReadSeek>,
+    ) -> io::Result<(Self, Vec<PathBuf>)> {
+        let size = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
+        let mut magic = [0; PAK_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != PAK_MAGIC {
+            return Err(invalid_data("not a pak archive"));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != PAK_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported pak version {version}"),
+            ));
+        }
+
+        let count = read_u32(&mut reader)?;
+        let mut paths = Vec::new();
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let path_len = read_u32(&mut reader)?;
+            if u64::from(path_len) > size - reader.stream_position()? {
+                return Err(invalid_data("pak path is longer than the archive"));
+            }
+            let mut path = vec![0; path_len as usize];
+            reader.read_exact(&mut path)?;
+            let path =
+                String::from_utf8(path).map_err(|_| invalid_data("pak path is not UTF-8"))?;
+            paths.push(PathBuf::from(path));
+
+            let (offset, len) = (read_u64(&mut reader)?, read_u64(&mut reader)?);
+            if offset.checked_add(len).is_none_or(|end| end > size) {
+                return Err(invalid_data("pak entry is outside of the archive"));
+            }
+            entries.push((offset, len));
+        }
+
+        Ok((Self { source, entries }, paths))
+    }
+}
+
+impl ArchiveFormat for PakArchive {
+    fn read_entry(&self, index: usize) -> io::Result<Vec<u8>> {
+        let (offset, len) = self.entries[index];
+        let mut reader = self.source.open()?;
+        reader.seek(SeekFrom::Start(offset))?;
+        let len = usize::try_from(len).map_err(|_| invalid_data("pak entry is too large"))?;
+        let mut bytes = vec![0; len];
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
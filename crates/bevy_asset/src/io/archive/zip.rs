@@ -0,0 +1,121 @@
+//! Support for reading assets out of zip archives, enabled by the `zip` feature.
+
+use super::{ArchiveFormat, ArchiveSource, ReadSeek};
+use ::zip::ZipArchive as ZipFile;
+use alloc::{boxed::Box, vec::Vec};
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    path::PathBuf,
+};
+
+/// A zip archive opened for reading.
+pub(super) struct ZipArchive {
+    /// The central directory of the archive. Each read works on a clone of it, which shares the
+    /// central directory but opens its own reader.
+    archive: ZipFile<LazyReader>,
+    /// The index in `archive` of each file, in the order their paths were returned by `open`.
+    indices: Vec<usize>,
+}
+
+impl ZipArchive {
+    /// Reads the central directory of the zip archive in `reader`, a reader over `source`,
+    /// returning the archive, the paths of its files and the paths of the directories it
+    /// explicitly contains.
+    ///
+    /// Entries whose path would escape the archive are ignored.
+    pub(super) fn open(
+        source: ArchiveSource,
+        reader: Box<dyn ReadSeek>,
+    ) -> io::Result<(Self, Vec<PathBuf>, Vec<PathBuf>)> {
+        let mut archive = ZipFile::new(LazyReader {
+            source,
+            position: 0,
+            reader: Some(reader),
+        })?;
+        let mut indices = Vec::new();
+        let mut files = Vec::new();
+        let mut directories = Vec::new();
+        for index in 0..archive.len() {
+            let entry = archive.by_index_raw(index)?;
+            let Some(path) = entry.enclosed_name() else {
+                continue;
+            };
+            if entry.is_dir() {
+                directories.push(path);
+            } else {
+                indices.push(index);
+                files.push(path);
+            }
+        }
+
+        Ok((Self { archive, indices }, files, directories))
+    }
+}
+
+impl ArchiveFormat for ZipArchive {
+    fn read_entry(&self, index: usize) -> io::Result<Vec<u8>> {
+        let mut archive = self.archive.clone();
+        let mut entry = archive.by_index(self.indices[index])?;
+        // The size comes from the archive, so it is only trusted to stop reading, not to
+        // allocate ahead of time.
+        let size = entry.size();
+        if usize::try_from(size).is_err() {
+            return Err(invalid_data("zip entry is too large"));
+        }
+        let mut bytes = Vec::new();
+        entry.by_ref().take(size).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != size || entry.read(&mut [0])? != 0 {
+            return Err(invalid_data("zip entry doesn't match its size"));
+        }
+        Ok(bytes)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// A reader over an [`ArchiveSource`] that is opened the first time it is used, so that
+/// clones of a [`ZipFile`] each read through their own reader.
+struct LazyReader {
+    source: ArchiveSource,
+    /// The position of the reader, kept so that clones can seek their new reader to it.
+    position: u64,
+    reader: Option<Box<dyn ReadSeek>>,
+}
+
+impl LazyReader {
+    fn reader(&mut self) -> io::Result<&mut Box<dyn ReadSeek>> {
+        if self.reader.is_none() {
+            let mut reader = self.source.open()?;
+            reader.seek(SeekFrom::Start(self.position))?;
+            self.reader = Some(reader);
+        }
+        Ok(self.reader.as_mut().unwrap())
+    }
+}
+
+impl Clone for LazyReader {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            position: self.position,
+            reader: None,
+        }
+    }
+}
+
+impl Read for LazyReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader()?.read(buf)?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for LazyReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = self.reader()?.seek(pos)?;
+        Ok(self.position)
+    }
+}
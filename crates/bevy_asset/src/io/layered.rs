@@ -0,0 +1,323 @@
+//! An [`AssetReader`] that stacks several other readers on top of each other.
+//!
+//! This is useful to support mods, DLC and patches: each of them can be a layer on top of the
+//! base game's assets, replacing some of its assets or adding new ones, without any change to
+//! the asset paths used in code.
+//!
+//! See [`AssetSourceBuilder::layered`](crate::io::AssetSourceBuilder::layered) to build a whole
+//! asset source out of layers, including their watchers.
+
+use crate::io::{
+    get_meta_path, AssetReader, AssetReaderError, AssetWatcher, ErasedAssetReader, PathStream,
+    Reader,
+};
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+use futures_lite::StreamExt;
+use std::path::{Path, PathBuf};
+
+/// An [`AssetReader`] that resolves each path from the highest-priority layer that has it.
+///
+/// Each layer takes priority over the layers added before it. The listing of a directory is
+/// the union of its listings in every layer, and a path is a directory if it is one in any layer.
+///
+/// The meta file of an asset is always read from the same layer as the asset, so that an asset
+/// replaced by a higher layer is never loaded with the settings of the asset it replaced.
+#[derive(Default)]
+pub struct LayeredAssetReader {
+    layers: Vec<Box<dyn ErasedAssetReader>>,
+}
+
+impl LayeredAssetReader {
+    /// Creates a new [`LayeredAssetReader`] without any layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `layer` on top of the existing layers, giving it the highest priority.
+    pub fn with_layer(mut self, layer: Box<dyn ErasedAssetReader>) -> Self {
+        self.add_layer(layer);
+        self
+    }
+
+    /// Adds `layer` on top of the existing layers, giving it the highest priority.
+    pub fn add_layer(&mut self, layer: Box<dyn ErasedAssetReader>) -> &mut Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Returns the layers of this reader, from the lowest to the highest priority.
+    pub fn layers(&self) -> &[Box<dyn ErasedAssetReader>] {
+        &self.layers
+    }
+
+    /// Iterates over the layers from the highest to the lowest priority.
+    fn layers_by_priority(&self) -> impl Iterator<Item = &dyn ErasedAssetReader> {
+        self.layers.iter().rev().map(AsRef::as_ref)
+    }
+}
+
+impl AssetReader for LayeredAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        for layer in self.layers_by_priority() {
+            match layer.read(path).await {
+                Err(AssetReaderError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        Err(AssetReaderError::NotFound(path.to_path_buf()))
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        for layer in self.layers_by_priority() {
+            match layer.read_meta(path).await {
+                // Without a meta file, the asset is loaded with its default settings if this
+                // layer has it, even if a lower layer has a meta file for it.
+                Err(AssetReaderError::NotFound(meta_path)) => {
+                    if layer.read(path).await.is_ok() {
+                        return Err(AssetReaderError::NotFound(meta_path));
+                    }
+                }
+                result => return result,
+            }
+        }
+        Err(AssetReaderError::NotFound(get_meta_path(path)))
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let mut entries = BTreeSet::<PathBuf>::new();
+        let mut found = false;
+        for layer in self.layers_by_priority() {
+            match layer.read_directory(path).await {
+                Ok(stream) => {
+                    found = true;
+                    entries.extend(stream.collect::<Vec<_>>().await);
+                }
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        if !found {
+            return Err(AssetReaderError::NotFound(path.to_path_buf()));
+        }
+        Ok(Box::new(futures_lite::stream::iter(entries)))
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        let mut found = false;
+        for layer in self.layers_by_priority() {
+            match layer.is_directory(path).await {
+                Ok(true) => return Ok(true),
+                Ok(false) => found = true,
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        if found {
+            Ok(false)
+        } else {
+            Err(AssetReaderError::NotFound(path.to_path_buf()))
+        }
+    }
+}
+
+/// An [`AssetWatcher`] that keeps the watchers of every layer of a layered source alive.
+///
+/// See [`AssetSourceBuilder::layered`](crate::io::AssetSourceBuilder::layered).
+pub struct LayeredAssetWatcher {
+    /// The watchers of the layers that have one.
+    pub watchers: Vec<Box<dyn AssetWatcher>>,
+}
+
+impl AssetWatcher for LayeredAssetWatcher {}
+
+#[cfg(test)]
+mod tests {
+    use super::LayeredAssetReader;
+    use crate::io::{
+        memory::{Dir, MemoryAssetReader},
+        AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceEvent, AssetSourceId,
+        AssetWatcher, Reader,
+    };
+    use crate::LoadBudget;
+    use alloc::{boxed::Box, string::String, vec, vec::Vec};
+    use bevy_tasks::block_on;
+    use futures_lite::StreamExt;
+    use std::path::{Path, PathBuf};
+
+    fn read_text(reader: &impl AssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    #[test]
+    fn layered_reader() {
+        let base = Dir::default();
+        base.insert_asset_text(Path::new("a.txt"), "base a");
+        base.insert_meta_text(Path::new("a.txt"), "base a meta");
+        base.insert_asset_text(Path::new("b.txt"), "base b");
+        base.insert_meta_text(Path::new("b.txt"), "base b meta");
+        base.insert_asset_text(Path::new("x/c.txt"), "base c");
+
+        let patch = Dir::default();
+        patch.insert_asset_text(Path::new("a.txt"), "patch a");
+        patch.insert_asset_text(Path::new("x/d.txt"), "patch d");
+
+        let reader = LayeredAssetReader::new()
+            .with_layer(Box::new(MemoryAssetReader { root: base }))
+            .with_layer(Box::new(MemoryAssetReader { root: patch }));
+
+        assert_eq!(read_text(&reader, "a.txt").unwrap(), "patch a");
+        assert_eq!(read_text(&reader, "b.txt").unwrap(), "base b");
+        assert_eq!(read_text(&reader, "x/d.txt").unwrap(), "patch d");
+        assert_eq!(
+            read_text(&reader, "missing.txt").unwrap_err(),
+            AssetReaderError::NotFound(PathBuf::from("missing.txt"))
+        );
+
+        // The replaced asset doesn't use the meta of the asset it replaces.
+        assert!(matches!(
+            block_on(reader.read_meta_bytes(Path::new("a.txt"))),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        assert_eq!(
+            block_on(reader.read_meta_bytes(Path::new("b.txt"))).unwrap(),
+            b"base b meta"
+        );
+        assert_eq!(
+            block_on(reader.read_meta_bytes(Path::new("missing.txt"))).unwrap_err(),
+            AssetReaderError::NotFound(PathBuf::from("missing.txt.meta"))
+        );
+
+        let entries: Vec<_> = block_on(async {
+            reader
+                .read_directory(Path::new("x"))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        assert_eq!(
+            entries,
+            vec![PathBuf::from("x/c.txt"), PathBuf::from("x/d.txt")]
+        );
+        assert!(block_on(reader.is_directory(Path::new("x"))).unwrap());
+        assert!(!block_on(reader.is_directory(Path::new("a.txt"))).unwrap());
+    }
+
+    #[test]
+    fn layered_source_with_missing_processed_readers() {
+        let layer = |text: Option<&'static str>| {
+            let source = AssetSourceBuilder::new(|| {
+                Box::new(MemoryAssetReader {
+                    root: Dir::default(),
+                })
+            });
+            match text {
+                Some(text) => source.with_processed_reader(move || {
+                    let root = Dir::default();
+                    root.insert_asset_text(Path::new("a.txt"), text);
+                    root.insert_asset_text(Path::new(text), text);
+                    Box::new(MemoryAssetReader { root })
+                }),
+                None => source,
+            }
+        };
+
+        let source =
+            AssetSourceBuilder::layered([layer(Some("base")), layer(None), layer(Some("patch"))])
+                .build(AssetSourceId::Default, false, false);
+        let processed_reader = source.processed_reader().unwrap();
+        let read = |path: &str| {
+            block_on(async {
+                let mut bytes = Vec::new();
+                processed_reader
+                    .read(Path::new(path))
+                    .await?
+                    .read_to_end(&mut bytes)
+                    .await?;
+                Ok::<_, AssetReaderError>(String::from_utf8(bytes).unwrap())
+            })
+        };
+        assert_eq!(read("a.txt").unwrap(), "patch");
+        assert_eq!(read("base").unwrap(), "base");
+        assert!(matches!(read("b.txt"), Err(AssetReaderError::NotFound(_))));
+    }
+
+    #[test]
+    fn layered_source_keeps_to_the_strictest_load_budget() {
+        let layer = |load_budget| {
+            AssetSourceBuilder::new(|| {
+                Box::new(MemoryAssetReader {
+                    root: Dir::default(),
+                })
+            })
+            .with_load_budget(load_budget)
+        };
+
+        let source = AssetSourceBuilder::layered([
+            layer(LoadBudget {
+                max_concurrent_loads: Some(2),
+                max_bytes_per_frame: None,
+            }),
+            layer(LoadBudget {
+                max_concurrent_loads: Some(4),
+                max_bytes_per_frame: Some(1024),
+            }),
+            layer(LoadBudget::default()),
+        ])
+        .build(AssetSourceId::Default, false, false);
+        assert_eq!(
+            source.load_budget(),
+            LoadBudget {
+                max_concurrent_loads: Some(2),
+                max_bytes_per_frame: Some(1024),
+            }
+        );
+    }
+
+    #[test]
+    fn layered_source_forwards_watcher_events() {
+        struct TestWatcher;
+
+        impl AssetWatcher for TestWatcher {}
+
+        let layer = |path: &'static str| {
+            AssetSourceBuilder::new(|| {
+                Box::new(MemoryAssetReader {
+                    root: Dir::default(),
+                })
+            })
+            .with_watcher(move |sender| {
+                sender
+                    .try_send(AssetSourceEvent::ModifiedAsset(PathBuf::from(path)))
+                    .unwrap();
+                Some(Box::new(TestWatcher))
+            })
+        };
+
+        let source = AssetSourceBuilder::layered([layer("base.txt"), layer("patch.txt")]).build(
+            AssetSourceId::Default,
+            true,
+            false,
+        );
+        let receiver = source.event_receiver().unwrap();
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(AssetSourceEvent::ModifiedAsset(PathBuf::from("base.txt")))
+        );
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(AssetSourceEvent::ModifiedAsset(PathBuf::from("patch.txt")))
+        );
+    }
+}
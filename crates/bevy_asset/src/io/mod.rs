@@ -12,9 +12,11 @@ compile_error!(
 
 #[cfg(target_os = "android")]
 pub mod android;
+pub mod archive;
 pub mod embedded;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod file;
+pub mod layered;
 pub mod memory;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
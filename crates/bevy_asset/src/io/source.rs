@@ -1,11 +1,18 @@
 use crate::{
-    io::{processor_gated::ProcessorGatedReader, AssetSourceEvent, AssetWatcher},
+    io::{
+        layered::{LayeredAssetReader, LayeredAssetWatcher},
+        memory::{Dir, MemoryAssetReader},
+        processor_gated::ProcessorGatedReader,
+        AssetSourceEvent, AssetWatcher,
+    },
     processor::ProcessingState,
+    LoadBudget,
 };
 use alloc::{
     boxed::Box,
     string::{String, ToString},
     sync::Arc,
+    vec::Vec,
 };
 use atomicow::CowArc;
 use bevy_ecs::resource::Resource;
@@ -146,6 +153,8 @@ pub struct AssetSourceBuilder {
     pub watch_warning: Option<&'static str>,
     /// The warning message to display when watching a processed asset fails.
     pub processed_watch_warning: Option<&'static str>,
+    /// The limits on how much the [`AssetServer`](crate::AssetServer) loads from this source at once.
+    pub load_budget: LoadBudget,
 }
 
 impl AssetSourceBuilder {
@@ -162,6 +171,7 @@ impl AssetSourceBuilder {
             processed_watcher: None,
             watch_warning: None,
             processed_watch_warning: None,
+            load_budget: LoadBudget::default(),
         }
     }
 
@@ -191,6 +201,7 @@ impl AssetSourceBuilder {
             watcher: None,
             processed_event_receiver: None,
             processed_watcher: None,
+            load_budget: self.load_budget,
         };
 
         if watch {
@@ -297,6 +308,13 @@ impl AssetSourceBuilder {
         self
     }
 
+    /// Limits how much the [`AssetServer`](crate::AssetServer) loads from this source at once.
+    /// See [`LoadBudget`].
+    pub fn with_load_budget(mut self, load_budget: LoadBudget) -> Self {
+        self.load_budget = load_budget;
+        self
+    }
+
     /// Returns a builder containing the "platform default source" for the given `path` and `processed_path`.
     /// For most platforms, this will use [`FileAssetReader`](crate::io::file::FileAssetReader) / [`FileAssetWriter`](crate::io::file::FileAssetWriter),
     /// but some platforms (such as Android) have their own default readers / writers / watchers.
@@ -321,6 +339,113 @@ impl AssetSourceBuilder {
             default
         }
     }
+
+    /// Returns a builder for a source that reads both unprocessed and processed assets out of
+    /// the archive at `path`, using an [`ArchiveAssetReader`](crate::io::archive::ArchiveAssetReader).
+    ///
+    /// The source has no writers or watchers, so it is meant for release builds.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn archive(path: &str) -> Self {
+        let reader = crate::io::archive::ArchiveAssetReader::new(path);
+        let processed_reader = reader.clone();
+        Self::new(move || Box::new(reader.clone()))
+            .with_processed_reader(move || Box::new(processed_reader.clone()))
+    }
+
+    /// Returns a builder for a source that stacks the sources of `layers` on top of each other,
+    /// using a [`LayeredAssetReader`]. Each layer takes priority over the layers before it.
+    ///
+    /// The processed reader is layered the same way, with an empty layer in place of the
+    /// layers that don't have one, and the events of the watchers of every layer are forwarded.
+    /// Writes go to the last layer that has a writer, and the source keeps to the strictest
+    /// [`LoadBudget`] limits of its layers.
+    pub fn layered(layers: impl IntoIterator<Item = AssetSourceBuilder>) -> Self {
+        let mut readers = Vec::new();
+        let mut watchers = Vec::new();
+        let mut processed_readers = Vec::new();
+        let mut processed_watchers = Vec::new();
+        let mut layered = Self::new(|| Box::new(LayeredAssetReader::new()));
+        for layer in layers {
+            readers.push(layer.reader);
+            watchers.extend(layer.watcher);
+            processed_readers.push(layer.processed_reader);
+            processed_watchers.extend(layer.processed_watcher);
+            layered.writer = layer.writer.or(layered.writer);
+            layered.processed_writer = layer.processed_writer.or(layered.processed_writer);
+            layered.watch_warning = layer.watch_warning.or(layered.watch_warning);
+            layered.processed_watch_warning = layer
+                .processed_watch_warning
+                .or(layered.processed_watch_warning);
+            layered.load_budget = layered.load_budget.strictest(layer.load_budget);
+        }
+
+        layered.reader = layered_reader(readers);
+        layered.watcher = layered_watcher(watchers);
+        if processed_readers.iter().any(Option::is_some) {
+            // Layers without a processed reader still take their place in the stack, so that
+            // every layer has the same priority for processed and unprocessed assets.
+            let processed_readers = processed_readers
+                .into_iter()
+                .map(|reader| reader.unwrap_or_else(|| Box::new(empty_reader)))
+                .collect();
+            layered.processed_reader = Some(layered_reader(processed_readers));
+        }
+        layered.processed_watcher = layered_watcher(processed_watchers);
+        layered
+    }
+}
+
+/// Combines the `readers` of the layers of a source into a [`LayeredAssetReader`].
+fn layered_reader(
+    mut readers: Vec<Box<dyn FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync>>,
+) -> Box<dyn FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync> {
+    Box::new(move || {
+        let mut reader = LayeredAssetReader::new();
+        for layer in &mut readers {
+            reader.add_layer(layer());
+        }
+        Box::new(reader)
+    })
+}
+
+/// Returns a reader without any assets, used for the missing processed readers of layers.
+fn empty_reader() -> Box<dyn ErasedAssetReader> {
+    Box::new(MemoryAssetReader {
+        root: Dir::default(),
+    })
+}
+
+/// Combines the `watchers` of the layers of a source into a [`LayeredAssetWatcher`], which
+/// sends the events of every layer.
+fn layered_watcher(
+    mut watchers: Vec<
+        Box<
+            dyn FnMut(async_channel::Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>>
+                + Send
+                + Sync,
+        >,
+    >,
+) -> Option<
+    Box<
+        dyn FnMut(async_channel::Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>>
+            + Send
+            + Sync,
+    >,
+> {
+    if watchers.is_empty() {
+        return None;
+    }
+    Some(Box::new(move |sender| {
+        let watchers: Vec<_> = watchers
+            .iter_mut()
+            .filter_map(|watcher| watcher(sender.clone()))
+            .collect();
+        if watchers.is_empty() {
+            None
+        } else {
+            Some(Box::new(LayeredAssetWatcher { watchers }))
+        }
+    }))
 }
 
 /// A [`Resource`] that hold (repeatable) functions capable of producing new [`AssetReader`](crate::io::AssetReader) and [`AssetWriter`](crate::io::AssetWriter) instances
@@ -402,6 +527,7 @@ pub struct AssetSource {
     processed_watcher: Option<Box<dyn AssetWatcher>>,
     event_receiver: Option<async_channel::Receiver<AssetSourceEvent>>,
     processed_event_receiver: Option<async_channel::Receiver<AssetSourceEvent>>,
+    load_budget: LoadBudget,
 }
 
 impl AssetSource {
@@ -411,6 +537,13 @@ impl AssetSource {
         self.id.clone()
     }
 
+    /// Returns the limits on how much the [`AssetServer`](crate::AssetServer) loads from this
+    /// source at once.
+    #[inline]
+    pub fn load_budget(&self) -> LoadBudget {
+        self.load_budget
+    }
+
     /// Return's this source's unprocessed [`AssetReader`](crate::io::AssetReader).
     #[inline]
     pub fn reader(&self) -> &dyn ErasedAssetReader {
@@ -151,6 +151,7 @@ extern crate std;
 extern crate self as bevy_asset;
 
 pub mod asset_changed;
+pub mod graph;
 pub mod io;
 pub mod meta;
 pub mod processor;
@@ -250,6 +251,13 @@ pub struct AssetPlugin {
     /// Most use cases should leave this set to [`None`] and enable the `asset_processor` cargo
     /// feature.
     pub use_asset_processor_override: Option<bool>,
+    /// If set, the asset processor will store processed assets in a
+    /// [`FileProcessorCache`](processor::FileProcessorCache) at this path (relative to the project
+    /// root), and restore them from it instead of processing assets it has already processed.
+    ///
+    /// The directory can be shared between checkouts of a project or restored between CI runs,
+    /// so that switching branches doesn't reprocess every asset.
+    pub processor_cache_path: Option<String>,
     /// The [`AssetMode`] to use for this server.
     pub mode: AssetMode,
     /// How/If asset meta files should be checked.
@@ -259,6 +267,15 @@ pub struct AssetPlugin {
     /// Approved folders are [`AssetPlugin::file_path`] and the folder of each
     /// [`AssetSource`](io::AssetSource). Subfolders within these folders are also valid.
     pub unapproved_path_mode: UnapprovedPathMode,
+    /// The maximum number of bytes of partial asset versions that loaders can stream per frame,
+    /// or `None` to not limit streaming. See [`AssetServer::set_streaming_budget`].
+    pub streaming_budget: Option<usize>,
+    /// If `true`, the asset server keeps the dependencies of every asset after it loads even when
+    /// it isn't watching for changes, so that [`AssetServer::dependency_graph`] can report them.
+    /// See [`AssetServer::set_tracking_dependencies`].
+    ///
+    /// This is meant for debugging and build tooling, and is `false` by default to save memory.
+    pub track_dependencies: bool,
 }
 
 /// Determines how to react to attempts to load assets not inside the approved folders.
@@ -336,8 +353,11 @@ impl Default for AssetPlugin {
             processed_file_path: Self::DEFAULT_PROCESSED_FILE_PATH.to_string(),
             watch_for_changes_override: None,
             use_asset_processor_override: None,
+            processor_cache_path: None,
             meta_check: AssetMetaCheck::default(),
             unapproved_path_mode: UnapprovedPathMode::default(),
+            streaming_budget: None,
+            track_dependencies: false,
         }
     }
 }
@@ -387,6 +407,11 @@ impl Plugin for AssetPlugin {
                     if use_asset_processor {
                         let mut builders = app.world_mut().resource_mut::<AssetSourceBuilders>();
                         let (processor, sources) = AssetProcessor::new(&mut builders, watch);
+                        if let Some(path) = &self.processor_cache_path {
+                            processor
+                                .data()
+                                .set_cache(Box::new(processor::FileProcessorCache::new(path)));
+                        }
                         // the main asset server shares loaders with the processor asset server
                         app.insert_resource(AssetServer::new_with_loaders(
                             sources,
@@ -412,6 +437,9 @@ impl Plugin for AssetPlugin {
                 }
             }
         }
+        let asset_server = app.world().resource::<AssetServer>();
+        asset_server.set_streaming_budget(self.streaming_budget);
+        asset_server.set_tracking_dependencies(self.track_dependencies);
         app.insert_resource(embedded)
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
@@ -674,6 +702,7 @@ impl AssetApp for App {
             .allow_ambiguous_resource::<Assets<A>>()
             .add_message::<AssetEvent<A>>()
             .add_message::<AssetLoadFailedEvent<A>>()
+            .add_message::<AssetUpgradedEvent<A>>()
             .register_type::<Handle<A>>()
             .add_systems(
                 PostUpdate,
@@ -738,9 +767,9 @@ mod tests {
         },
         loader::{AssetLoader, LoadContext},
         Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, InvalidGenerationError, LoadState, LoadedAsset,
-        LoadedUntypedAsset, UnapprovedPathMode, UntypedHandle, VisitAssetDependencies,
-        WriteDefaultMetaError,
+        AssetPlugin, AssetServer, AssetUpgradedEvent, Assets, InvalidGenerationError, LoadBudget,
+        LoadPriority, LoadState, LoadedAsset, LoadedUntypedAsset, UnapprovedPathMode,
+        UntypedHandle, VisitAssetDependencies, WriteDefaultMetaError,
     };
     use alloc::{
         boxed::Box,
@@ -764,7 +793,11 @@ mod tests {
     };
     use bevy_reflect::{Reflect, TypePath};
     use bevy_tasks::block_on;
-    use core::{any::TypeId, time::Duration};
+    use core::{
+        any::TypeId,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
     use futures_lite::AsyncReadExt;
     use ron::ser::PrettyConfig;
     use serde::{Deserialize, Serialize};
@@ -1764,6 +1797,321 @@ mod tests {
         assert_eq!(events, expected_events);
     }
 
+    #[test]
+    fn stream_partial_asset_versions() {
+        /// Publishes every prefix of the text as a partial version, one byte at a time.
+        #[derive(TypePath)]
+        struct StreamedTextLoader;
+
+        impl AssetLoader for StreamedTextLoader {
+            type Asset = SubText;
+            type Settings = ();
+            type Error = std::io::Error;
+
+            async fn load(
+                &self,
+                reader: &mut dyn Reader,
+                _settings: &Self::Settings,
+                load_context: &mut LoadContext<'_>,
+            ) -> Result<Self::Asset, Self::Error> {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await?;
+                let text = String::from_utf8(bytes).unwrap();
+                for len in 1..text.len() {
+                    let text = text[..len].to_string();
+                    load_context.publish_partial(SubText { text }, 1).await;
+                }
+                Ok(SubText { text })
+            }
+
+            fn extensions(&self) -> &[&str] {
+                &["streamed"]
+            }
+        }
+
+        let (mut app, dir) = create_app();
+        dir.insert_asset_text(Path::new("a.streamed"), "abcd");
+        app.init_asset::<SubText>()
+            .register_asset_loader(StreamedTextLoader);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        asset_server.set_streaming_budget(Some(1));
+        let handle: Handle<SubText> = asset_server.load("a.streamed");
+
+        let mut upgrade_cursor = MessageCursor::default();
+        let mut event_cursor = MessageCursor::default();
+        let mut levels = Vec::new();
+        run_app_until(&mut app, |world| {
+            let upgrades = world.resource::<Messages<AssetUpgradedEvent<SubText>>>();
+            let upgrades: Vec<_> = upgrade_cursor.read(upgrades).collect();
+            // The budget only lets one byte through every frame.
+            assert!(upgrades.len() <= 1);
+            for upgrade in upgrades {
+                assert_eq!(upgrade.id, handle.id());
+                levels.push(upgrade.level);
+            }
+            if !levels.is_empty() {
+                // Partial versions are available behind the handle while the asset is loading.
+                let text = &world
+                    .resource::<Assets<SubText>>()
+                    .get(&handle)
+                    .unwrap()
+                    .text;
+                assert!("abcd".starts_with(text.as_str()));
+            }
+            let events = world.resource::<Messages<AssetEvent<SubText>>>();
+            event_cursor
+                .read(events)
+                .any(|event| event.is_loaded_with_dependencies(&handle))
+                .then_some(())
+        });
+
+        assert_eq!(levels, [0, 1, 2]);
+        let text = &app
+            .world()
+            .resource::<Assets<SubText>>()
+            .get(&handle)
+            .unwrap()
+            .text;
+        assert_eq!(text, "abcd");
+    }
+
+    /// Creates an asset app whose in-memory source has the given `budget`.
+    fn create_app_with_load_budget(budget: LoadBudget) -> (App, Dir) {
+        let mut app = App::new();
+        let dir = Dir::default();
+        let dir_clone = dir.clone();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || {
+                Box::new(MemoryAssetReader {
+                    root: dir_clone.clone(),
+                })
+            })
+            .with_load_budget(budget),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(false),
+                use_asset_processor_override: Some(false),
+                ..Default::default()
+            },
+            DiagnosticsPlugin,
+        ));
+        (app, dir)
+    }
+
+    /// Records the order in which loads start, and blocks the load of `blocker.queued` until it is
+    /// released.
+    #[derive(TypePath)]
+    struct QueuedTextLoader {
+        started: Arc<Mutex<Vec<String>>>,
+        release: Receiver<()>,
+    }
+
+    impl AssetLoader for QueuedTextLoader {
+        type Asset = SubText;
+        type Settings = ();
+        type Error = std::io::Error;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            _settings: &Self::Settings,
+            load_context: &mut LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            let path = load_context.path().to_string();
+            self.started.lock().unwrap().push(path.clone());
+            if path == "blocker.queued" {
+                self.release.recv().await.unwrap();
+            }
+            let mut text = String::new();
+            reader.read_to_string(&mut text).await?;
+            Ok(SubText { text })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["queued"]
+        }
+    }
+
+    #[test]
+    fn queued_loads_start_in_order_of_priority() {
+        let (mut app, dir) = create_app_with_load_budget(LoadBudget {
+            max_concurrent_loads: Some(1),
+            max_bytes_per_frame: None,
+        });
+        for name in ["blocker", "low", "normal", "high", "dropped"] {
+            dir.insert_asset_text(Path::new(&format!("{name}.queued")), name);
+        }
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let (release_sender, release) = async_channel::bounded(1);
+        app.init_asset::<SubText>()
+            .register_asset_loader(QueuedTextLoader {
+                started: started.clone(),
+                release,
+            });
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let blocker: Handle<SubText> = asset_server.load("blocker.queued");
+        run_app_until(&mut app, |_| {
+            (!started.lock().unwrap().is_empty()).then_some(())
+        });
+
+        // These wait for the blocker to finish, since the source only loads one asset at once.
+        let low: Handle<SubText> = asset_server.load("low.queued");
+        asset_server.set_load_priority(&low, LoadPriority::Low);
+        let normal: Handle<SubText> = asset_server.load("normal.queued");
+        let high: Handle<SubText> = asset_server
+            .load_builder()
+            .with_priority(LoadPriority::High)
+            .load("high.queued");
+        let dropped: Handle<SubText> = asset_server
+            .load_builder()
+            .with_priority(LoadPriority::High)
+            .load("dropped.queued");
+        assert_eq!(
+            asset_server.get_load_priority(&high),
+            Some(LoadPriority::High)
+        );
+        drop(dropped);
+        app.update();
+
+        release_sender.try_send(()).unwrap();
+        run_app_until(&mut app, |_| {
+            [&blocker, &low, &normal, &high]
+                .iter()
+                .all(|handle| asset_server.is_loaded(*handle))
+                .then_some(())
+        });
+
+        assert_eq!(
+            *started.lock().unwrap(),
+            [
+                "blocker.queued",
+                "high.queued",
+                "normal.queued",
+                "low.queued"
+            ]
+        );
+    }
+
+    #[test]
+    fn set_load_priority_reorders_queued_untyped_loads() {
+        let (mut app, dir) = create_app_with_load_budget(LoadBudget {
+            max_concurrent_loads: Some(1),
+            max_bytes_per_frame: None,
+        });
+        for name in ["blocker", "first", "second"] {
+            dir.insert_asset_text(Path::new(&format!("{name}.queued")), name);
+        }
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let (release_sender, release) = async_channel::bounded(1);
+        app.init_asset::<SubText>()
+            .register_asset_loader(QueuedTextLoader {
+                started: started.clone(),
+                release,
+            });
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let blocker: Handle<SubText> = asset_server.load("blocker.queued");
+        run_app_until(&mut app, |_| {
+            (!started.lock().unwrap().is_empty()).then_some(())
+        });
+
+        let first = asset_server.load_builder().load_untyped("first.queued");
+        let second = asset_server.load_builder().load_untyped("second.queued");
+        // Let both loads start waiting for their turn before raising the priority of the second.
+        app.update();
+        asset_server.set_load_priority(&second, LoadPriority::High);
+
+        release_sender.try_send(()).unwrap();
+        run_app_until(&mut app, |_| {
+            (asset_server.is_loaded(&blocker)
+                && asset_server.is_loaded(&first)
+                && asset_server.is_loaded(&second))
+            .then_some(())
+        });
+
+        assert_eq!(
+            *started.lock().unwrap(),
+            ["blocker.queued", "second.queued", "first.queued"]
+        );
+    }
+
+    #[test]
+    fn load_budget_limits_bytes_per_frame() {
+        /// Records the number of bytes returned by every read of the asset.
+        #[derive(TypePath)]
+        struct ChunkedTextLoader {
+            reads: Arc<Mutex<Vec<usize>>>,
+        }
+
+        impl AssetLoader for ChunkedTextLoader {
+            type Asset = SubText;
+            type Settings = ();
+            type Error = std::io::Error;
+
+            async fn load(
+                &self,
+                reader: &mut dyn Reader,
+                _settings: &Self::Settings,
+                _load_context: &mut LoadContext<'_>,
+            ) -> Result<Self::Asset, Self::Error> {
+                let mut text = Vec::new();
+                let mut buffer = [0; 16];
+                loop {
+                    let read = reader.read(&mut buffer).await?;
+                    if read == 0 {
+                        break;
+                    }
+                    self.reads.lock().unwrap().push(read);
+                    text.extend_from_slice(&buffer[..read]);
+                }
+                Ok(SubText {
+                    text: String::from_utf8(text).unwrap(),
+                })
+            }
+
+            fn extensions(&self) -> &[&str] {
+                &["chunked"]
+            }
+        }
+
+        let (mut app, dir) = create_app_with_load_budget(LoadBudget {
+            max_concurrent_loads: None,
+            max_bytes_per_frame: Some(4),
+        });
+        dir.insert_asset_text(Path::new("a.chunked"), "0123456789");
+        let reads = Arc::new(Mutex::new(Vec::new()));
+        app.init_asset::<SubText>()
+            .register_asset_loader(ChunkedTextLoader {
+                reads: reads.clone(),
+            });
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<SubText> = asset_server.load("a.chunked");
+        let mut frames = 0;
+        run_app_until(&mut app, |_| {
+            frames += 1;
+            asset_server.is_loaded(&handle).then_some(())
+        });
+
+        // Every read uses up the budget of its frame, except for the last one, so the file is
+        // read 4 bytes per frame even though the loader asks for 16 bytes at a time.
+        assert_eq!(*reads.lock().unwrap(), [4, 4, 2]);
+        assert!(frames >= 3);
+        assert_eq!(
+            app.world()
+                .resource::<Assets<SubText>>()
+                .get(&handle)
+                .unwrap()
+                .text,
+            "0123456789"
+        );
+    }
+
     #[test]
     fn load_folder() {
         let dir = Dir::default();
@@ -2953,6 +3301,20 @@ mod tests {
         str::from_utf8(bytes.value()).unwrap().to_string()
     }
 
+    /// Creates an empty directory on disk that is unique to this test run, so tests running
+    /// concurrently (or in other processes) never share files.
+    pub(crate) fn create_temp_dir(name: &str) -> PathBuf {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "bevy_asset_{name}_{}_{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
     #[test]
     fn writes_default_meta_for_loader() {
         let (mut app, source) = create_app();
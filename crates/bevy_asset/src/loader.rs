@@ -4,7 +4,7 @@ use crate::{
     meta::{AssetHash, AssetMeta, AssetMetaDyn, ProcessedInfo, ProcessedInfoMinimal, Settings},
     path::AssetPath,
     Asset, AssetIndex, AssetLoadError, AssetServer, AssetServerMode, Assets, ErasedAssetIndex,
-    Handle, UntypedAssetId, UntypedHandle,
+    Handle, InternalAssetEvent, LoadPriority, UntypedAssetId, UntypedHandle,
 };
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use atomicow::CowArc;
@@ -21,7 +21,7 @@ use ron::error::SpannedError;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
-use tracing::error;
+use tracing::{error, warn};
 
 /// Loads an [`Asset`] from a given byte [`Reader`]. This can accept [`AssetLoader::Settings`], which configure how the [`Asset`]
 /// should be loaded.
@@ -395,6 +395,13 @@ pub struct LoadContext<'a> {
     /// This is entirely redundant with [`Self::labeled_assets`], but it allows looking up the
     /// labeled asset by its asset ID.
     pub(crate) asset_id_to_asset_index: HashMap<UntypedAssetId, usize>,
+    /// The asset that partial versions published with [`Self::publish_partial`] replace, or
+    /// `None` if they are ignored.
+    pub(crate) partial_asset_index: Option<ErasedAssetIndex>,
+    /// The number of partial versions published so far.
+    partial_level: u32,
+    /// The priority of the load, which deferred loads started by the loader inherit.
+    pub(crate) priority: LoadPriority,
 }
 
 impl<'a> LoadContext<'a> {
@@ -415,6 +422,9 @@ impl<'a> LoadContext<'a> {
             labeled_assets: Default::default(),
             label_to_asset_index: Default::default(),
             asset_id_to_asset_index: Default::default(),
+            partial_asset_index: None,
+            partial_level: 0,
+            priority: LoadPriority::default(),
         }
     }
 
@@ -448,12 +458,14 @@ impl<'a> LoadContext<'a> {
     /// }
     /// ```
     pub fn begin_labeled_asset(&self) -> LoadContext<'_> {
-        LoadContext::new(
+        let mut load_context = LoadContext::new(
             self.asset_server,
             self.asset_path.clone(),
             self.should_load_dependencies,
             self.populate_hashes,
-        )
+        );
+        load_context.priority = self.priority;
+        load_context
     }
 
     /// Creates a new [`LoadContext`] for the given `label`. The `load` function is responsible for loading an [`Asset`] of
@@ -542,6 +554,45 @@ impl<'a> LoadContext<'a> {
         !self.asset_server.get_handles_untyped(&path).is_empty()
     }
 
+    /// Publishes `asset` as a partial version of the asset being loaded, such as an image with only
+    /// its smallest mips or a mesh with only its coarsest level of detail, so that it can be used
+    /// before the loader returns the final version.
+    ///
+    /// Each partial version replaces the previous one behind the same [`Handle`], and emits an
+    /// [`AssetUpgradedEvent`](crate::AssetUpgradedEvent). The [`LoadState`](crate::LoadState) of
+    /// the asset stays [`Loading`](crate::LoadState::Loading) until the loader returns.
+    /// Dependencies held by partial versions are not tracked: only those of the final version are.
+    ///
+    /// `bytes` is the amount of data streamed in this version, which counts against the
+    /// [streaming budget](AssetServer::set_streaming_budget) of the [`AssetServer`]. This waits
+    /// until the budget allows this version to be published, so loaders that exceed it are
+    /// slowed down to the budget.
+    ///
+    /// Partial versions are ignored if the asset isn't loaded through the [`AssetServer`]
+    /// directly, such as when it is loaded as a nested asset or processed, or if `A` isn't the
+    /// type of the asset being loaded.
+    pub async fn publish_partial<A: Asset>(&mut self, asset: A, bytes: usize) {
+        let Some(index) = self.partial_asset_index else {
+            return;
+        };
+        if index.type_id != TypeId::of::<A>() {
+            warn!(
+                "Ignoring a partial version of {} for {}, which is not of that type",
+                core::any::type_name::<A>(),
+                self.asset_path
+            );
+            return;
+        }
+        self.asset_server.data.streaming_budget.acquire(bytes).await;
+        self.asset_server
+            .send_asset_event(InternalAssetEvent::Upgraded {
+                index,
+                asset: Box::new(asset),
+                level: self.partial_level,
+            });
+        self.partial_level += 1;
+    }
+
     /// "Finishes" this context by populating the final [`Asset`] value.
     pub fn finish<A: Asset>(mut self, value: A) -> LoadedAsset<A> {
         // At this point, we assume the asset/subasset is "locked in" and won't be changed, so we
@@ -665,6 +716,7 @@ impl<'a> LoadContext<'a> {
                 reader,
                 self.should_load_dependencies,
                 self.populate_hashes,
+                None,
             )
             .await
             .map_err(|error| LoadDirectError::LoadError {
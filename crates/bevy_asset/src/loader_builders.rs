@@ -124,6 +124,7 @@ impl<'ctx, 'builder> NestedLoadBuilder<'ctx, 'builder> {
                     self.meta_transform,
                     (),
                     self.override_unapproved,
+                    self.load_context.priority,
                 )
         } else {
             self.load_context
@@ -243,6 +244,7 @@ impl<'ctx, 'builder> NestedLoadBuilder<'ctx, 'builder> {
                 self.meta_transform,
                 (),
                 self.override_unapproved,
+                self.load_context.priority,
             )
         } else {
             self.load_context
@@ -0,0 +1,146 @@
+use crate::meta::AssetHash;
+use alloc::{boxed::Box, format, vec::Vec};
+use bevy_ecs::error::BevyError;
+use bevy_tasks::BoxedFuture;
+use std::path::PathBuf;
+
+use super::ErasedProcessor;
+
+/// A processed asset stored in a [`ProcessorCache`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedProcessedAsset {
+    /// The bytes of the processed asset.
+    pub asset: Vec<u8>,
+    /// The bytes of the `.meta` file of the processed asset, including its
+    /// [`ProcessedInfo`](crate::meta::ProcessedInfo).
+    pub meta: Vec<u8>,
+}
+
+/// A content-addressed store of processed assets, which lets the [`AssetProcessor`] reuse the
+/// results of processing an asset across runs, checkouts or machines.
+///
+/// Entries are keyed by a [`ProcessorCacheKey`], which identifies the source asset and its
+/// `.meta` file, and the processor used on them. Before the [`AssetProcessor`] processes an
+/// asset, it looks up its key in the cache, and if the cached result was produced with the same
+/// process dependencies, writes it to the processed source instead of processing the asset.
+/// Every asset that is processed is then stored in the cache.
+///
+/// A cache is set with [`AssetProcessorData::set_cache`](super::AssetProcessorData::set_cache),
+/// or by setting [`AssetPlugin::processor_cache_path`](crate::AssetPlugin::processor_cache_path)
+/// to use a [`FileProcessorCache`].
+///
+/// [`AssetProcessor`]: super::AssetProcessor
+pub trait ProcessorCache: Send + Sync + 'static {
+    /// Returns the processed asset stored for `key`, or `None` if there isn't one.
+    fn get<'a>(
+        &'a self,
+        key: &'a ProcessorCacheKey,
+    ) -> BoxedFuture<'a, Result<Option<CachedProcessedAsset>, BevyError>>;
+
+    /// Stores the processed `asset` for `key`, replacing any asset already stored for it.
+    fn put<'a>(
+        &'a self,
+        key: &'a ProcessorCacheKey,
+        asset: &'a CachedProcessedAsset,
+    ) -> BoxedFuture<'a, Result<(), BevyError>>;
+}
+
+/// The key of a processed asset in a [`ProcessorCache`].
+///
+/// This is a hash of the source asset and its `.meta` file (which includes the settings of the
+/// processor), the type path of the processor, and its [`Process::VERSION`](super::Process::VERSION).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProcessorCacheKey(pub AssetHash);
+
+impl ProcessorCacheKey {
+    /// Creates the key of an asset whose source and `.meta` file have the given `hash`, when
+    /// processed by `processor`.
+    pub fn new(hash: AssetHash, processor: &dyn ErasedProcessor) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&hash);
+        hasher.update(processor.type_path().as_bytes());
+        hasher.update(&processor.version().to_le_bytes());
+        Self(*hasher.finalize().as_bytes())
+    }
+
+    /// Returns the key as a lowercase hexadecimal string.
+    pub fn to_hex(&self) -> impl core::fmt::Display {
+        blake3::Hash::from_bytes(self.0).to_hex()
+    }
+}
+
+/// A [`ProcessorCache`] that stores processed assets in a directory, which can be shared between
+/// checkouts of a project or restored between CI runs.
+///
+/// Each asset is stored in a file named after its key, with its `.meta` file next to it. Files are
+/// written to a temporary path first and then renamed, so that several processors can share
+/// the same directory.
+pub struct FileProcessorCache {
+    /// The directory that processed assets are stored in.
+    pub root_path: PathBuf,
+}
+
+impl FileProcessorCache {
+    /// Creates a new [`FileProcessorCache`] storing assets in the directory at `path`. Relative
+    /// paths are relative to the project root.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let root_path = crate::io::file::get_base_path().join(path.into());
+        #[cfg(target_arch = "wasm32")]
+        let root_path = path.into();
+        Self { root_path }
+    }
+
+    /// Returns the path of the asset stored for `key`. Assets are spread across subdirectories
+    /// named after the first byte of their key, to keep directories small.
+    fn asset_path(&self, key: &ProcessorCacheKey) -> PathBuf {
+        let hex = format!("{}", key.to_hex());
+        self.root_path.join(&hex[..2]).join(hex)
+    }
+}
+
+impl ProcessorCache for FileProcessorCache {
+    fn get<'a>(
+        &'a self,
+        key: &'a ProcessorCacheKey,
+    ) -> BoxedFuture<'a, Result<Option<CachedProcessedAsset>, BevyError>> {
+        Box::pin(async move {
+            let path = self.asset_path(key);
+            // The meta file is written last, so the asset is complete if it exists.
+            let meta = match async_fs::read(path.with_extension("meta")).await {
+                Ok(meta) => meta,
+                Err(err) if err.kind() == futures_io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(err.into()),
+            };
+            let asset = async_fs::read(path).await?;
+            Ok(Some(CachedProcessedAsset { asset, meta }))
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a ProcessorCacheKey,
+        asset: &'a CachedProcessedAsset,
+    ) -> BoxedFuture<'a, Result<(), BevyError>> {
+        Box::pin(async move {
+            let path = self.asset_path(key);
+            if let Some(parent_folder) = path.parent() {
+                async_fs::create_dir_all(parent_folder).await?;
+            }
+            write_atomic(path.clone(), &asset.asset).await?;
+            write_atomic(path.with_extension("meta"), &asset.meta).await?;
+            Ok(())
+        })
+    }
+}
+
+/// Writes `bytes` to a temporary file next to `path`, then renames it to `path`.
+async fn write_atomic(path: PathBuf, bytes: &[u8]) -> Result<(), BevyError> {
+    let temporary_path = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4().simple()));
+    async_fs::write(&temporary_path, bytes).await?;
+    if let Err(err) = async_fs::rename(&temporary_path, &path).await {
+        let _ = async_fs::remove_file(&temporary_path).await;
+        return Err(err.into());
+    }
+    Ok(())
+}
@@ -37,17 +37,20 @@
 //!
 //! In most cases, [`LoadTransformAndSave`] should be sufficient.
 
+mod cache;
 mod log;
 mod process;
 
 use async_lock::RwLockReadGuardArc;
+pub use cache::*;
 pub use log::*;
 pub use process::*;
 
 use crate::{
     io::{
         AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent, AssetSourceId,
-        AssetSources, AssetWriterError, ErasedAssetReader, MissingAssetSourceError,
+        AssetSources, AssetWriterError, ErasedAssetReader, ErasedAssetWriter,
+        MissingAssetSourceError,
     },
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
@@ -88,7 +91,8 @@ use {
 /// [`AssetProcessor`] can be run in the background while a Bevy App is running. Changes to assets will be automatically detected and hot-reloaded.
 ///
 /// Assets will only be re-processed if they have been changed. A hash of each asset source is stored in the metadata of the processed version of the
-/// asset, which is used to determine if the asset source has actually changed.
+/// asset, which is used to determine if the asset source has actually changed. Processed assets can also be shared across runs, checkouts
+/// and machines with a [`ProcessorCache`], so that they are only processed once for a given input.
 ///
 /// A [`ProcessorTransactionLog`] is produced, which uses "write-ahead logging" to make the [`AssetProcessor`] crash and failure resistant. If a failed/unfinished
 /// transaction from a previous run is detected, the affected asset(s) will be re-processed.
@@ -111,6 +115,8 @@ pub struct AssetProcessorData {
     /// avoids needing to use [`block_on`](bevy_tasks::block_on) to set the factory).
     log_factory: Mutex<Option<Box<dyn ProcessorTransactionLogFactory>>>,
     log: async_lock::RwLock<Option<Box<dyn ProcessorTransactionLog>>>,
+    /// The cache of processed assets, if any.
+    cache: Mutex<Option<Arc<dyn ProcessorCache>>>,
     /// The processors that will be used to process assets.
     processors: RwLock<Processors>,
     sources: Arc<AssetSources>,
@@ -1126,21 +1132,9 @@ impl AssetProcessor {
                 .get(asset_path)
                 .and_then(|i| i.processed_info.as_ref())
                 && current_processed_info.hash == new_hash
+                && !infos.process_dependency_changed(current_processed_info)
             {
-                let mut dependency_changed = false;
-                for current_dep_info in &current_processed_info.process_dependencies {
-                    let live_hash = infos
-                        .get(&current_dep_info.path)
-                        .and_then(|i| i.processed_info.as_ref())
-                        .map(|i| i.full_hash);
-                    if live_hash != Some(current_dep_info.full_hash) {
-                        dependency_changed = true;
-                        break;
-                    }
-                }
-                if !dependency_changed {
-                    return Ok(ProcessResult::SkippedNotChanged);
-                }
+                return Ok(ProcessResult::SkippedNotChanged);
             }
         }
 
@@ -1163,65 +1157,88 @@ impl AssetProcessor {
         // TODO: this class of failure can be recovered via re-processing + smarter log validation that allows for duplicate transactions in the event of failures
         self.log_begin_processing(asset_path).await;
         if let Some(processor) = processor {
-            // Unwrap is ok since we have a processor, so the `AssetAction` must have been
-            // `AssetAction::Process` (which includes its settings).
-            let settings = source_meta.process_settings().unwrap();
-
-            // Create a reader just for the actual process. Note: this means that we're performing
-            // two reads for the same file (but we avoid having to load the whole file into memory).
-            // For some sources (like local file systems), this is not a big deal, but for other
-            // sources like an HTTP asset sources, this could be an entire additional download (if
-            // the asset source doesn't do any caching). In practice, most sources being processed
-            // are likely to be local, and processing in general is a publish-time operation, so
-            // it's not likely to be too big a deal. If in the future, we decide we want to avoid
-            // this repeated read, we could "ask" the asset source if it prefers avoiding repeated
-            // reads or not.
-            let reader_for_process = reader.read(path).await.map_err(reader_err)?;
-
-            let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
-            let mut processed_meta = {
-                let mut context = ProcessContext::new(
-                    self,
-                    asset_path,
-                    reader_for_process,
-                    &mut new_processed_info,
-                );
-                let process = processor.process(&mut context, settings, &mut *writer);
-                #[cfg(feature = "trace")]
-                let process = {
-                    let span = info_span!(
-                        "asset processing",
-                        processor = processor.type_path(),
-                        asset = asset_path.to_string(),
+            let cache = self.data.cache();
+            let cache_key = ProcessorCacheKey::new(new_hash, processor.as_ref());
+            let restored = match &cache {
+                Some(cache) => {
+                    self.restore_from_cache(
+                        cache.as_ref(),
+                        &cache_key,
+                        asset_path,
+                        processed_writer,
+                    )
+                    .await?
+                }
+                None => None,
+            };
+            if let Some(processed_info) = restored {
+                new_processed_info = processed_info;
+            } else {
+                // Unwrap is ok since we have a processor, so the `AssetAction` must have been
+                // `AssetAction::Process` (which includes its settings).
+                let settings = source_meta.process_settings().unwrap();
+
+                // Create a reader just for the actual process. Note: this means that we're performing
+                // two reads for the same file (but we avoid having to load the whole file into memory).
+                // For some sources (like local file systems), this is not a big deal, but for other
+                // sources like an HTTP asset sources, this could be an entire additional download (if
+                // the asset source doesn't do any caching). In practice, most sources being processed
+                // are likely to be local, and processing in general is a publish-time operation, so
+                // it's not likely to be too big a deal. If in the future, we decide we want to avoid
+                // this repeated read, we could "ask" the asset source if it prefers avoiding repeated
+                // reads or not.
+                let reader_for_process = reader.read(path).await.map_err(reader_err)?;
+
+                let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
+                let mut processed_meta = {
+                    let mut context = ProcessContext::new(
+                        self,
+                        asset_path,
+                        reader_for_process,
+                        &mut new_processed_info,
                     );
-                    process.instrument(span)
+                    let process = processor.process(&mut context, settings, &mut *writer);
+                    #[cfg(feature = "trace")]
+                    let process = {
+                        let span = info_span!(
+                            "asset processing",
+                            processor = processor.type_path(),
+                            asset = asset_path.to_string(),
+                        );
+                        process.instrument(span)
+                    };
+                    process.await?
                 };
-                process.await?
-            };
 
-            writer
-                .flush()
-                .await
-                .map_err(|e| ProcessError::AssetWriterError {
-                    path: asset_path.clone(),
-                    err: AssetWriterError::Io(e),
-                })?;
+                writer
+                    .flush()
+                    .await
+                    .map_err(|e| ProcessError::AssetWriterError {
+                        path: asset_path.clone(),
+                        err: AssetWriterError::Io(e),
+                    })?;
+
+                let full_hash = get_full_asset_hash(
+                    new_hash,
+                    new_processed_info
+                        .process_dependencies
+                        .iter()
+                        .map(|i| i.full_hash),
+                );
+                new_processed_info.full_hash = full_hash;
+                *processed_meta.processed_info_mut() = Some(new_processed_info.clone());
+                let meta_bytes = processed_meta.serialize();
 
-            let full_hash = get_full_asset_hash(
-                new_hash,
-                new_processed_info
-                    .process_dependencies
-                    .iter()
-                    .map(|i| i.full_hash),
-            );
-            new_processed_info.full_hash = full_hash;
-            *processed_meta.processed_info_mut() = Some(new_processed_info.clone());
-            let meta_bytes = processed_meta.serialize();
+                processed_writer
+                    .write_meta_bytes(path, &meta_bytes)
+                    .await
+                    .map_err(writer_err)?;
 
-            processed_writer
-                .write_meta_bytes(path, &meta_bytes)
-                .await
-                .map_err(writer_err)?;
+                if let Some(cache) = &cache {
+                    self.store_in_cache(cache.as_ref(), &cache_key, source, asset_path, meta_bytes)
+                        .await;
+                }
+            }
         } else {
             // See the reasoning for processing why it's ok to do a second read here.
             let mut reader_for_copy = reader.read(path).await.map_err(reader_err)?;
@@ -1244,6 +1261,91 @@ impl AssetProcessor {
         Ok(ProcessResult::Processed(new_processed_info))
     }
 
+    /// Writes the processed asset stored in `cache` for `key` to the processed source, returning
+    /// its [`ProcessedInfo`]. Returns `None` if there is no usable asset for `key` in the cache.
+    async fn restore_from_cache(
+        &self,
+        cache: &dyn ProcessorCache,
+        key: &ProcessorCacheKey,
+        asset_path: &AssetPath<'static>,
+        processed_writer: &dyn ErasedAssetWriter,
+    ) -> Result<Option<ProcessedInfo>, ProcessError> {
+        let cached = match cache.get(key).await {
+            Ok(Some(cached)) => cached,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                warn!("Failed to read {asset_path} from the processor cache: {err}");
+                return Ok(None);
+            }
+        };
+        let Some(processed_info) = ron::de::from_bytes::<ProcessedInfoMinimal>(&cached.meta)
+            .ok()
+            .and_then(|meta| meta.processed_info)
+        else {
+            warn!("The processor cache entry for {asset_path} has an invalid meta file");
+            return Ok(None);
+        };
+
+        // The cached asset can only be used if it was processed with the same dependencies.
+        for dependency in &processed_info.process_dependencies {
+            self.data
+                .wait_until_processed(dependency.path.clone())
+                .await;
+        }
+        if self
+            .data
+            .processing_state
+            .asset_infos
+            .read()
+            .await
+            .process_dependency_changed(&processed_info)
+        {
+            return Ok(None);
+        }
+
+        let writer_err = |err| ProcessError::AssetWriterError {
+            path: asset_path.clone(),
+            err,
+        };
+        processed_writer
+            .write_bytes(asset_path.path(), &cached.asset)
+            .await
+            .map_err(writer_err)?;
+        processed_writer
+            .write_meta_bytes(asset_path.path(), &cached.meta)
+            .await
+            .map_err(writer_err)?;
+        debug!("Restored {} from the processor cache", asset_path);
+        Ok(Some(processed_info))
+    }
+
+    /// Stores the asset that was just processed at `asset_path` in `cache`.
+    async fn store_in_cache(
+        &self,
+        cache: &dyn ProcessorCache,
+        key: &ProcessorCacheKey,
+        source: &AssetSource,
+        asset_path: &AssetPath<'static>,
+        meta: Vec<u8>,
+    ) {
+        let Some(processed_reader) = source.ungated_processed_reader() else {
+            return;
+        };
+        let mut asset = Vec::new();
+        let read = async {
+            let mut reader = processed_reader.read(asset_path.path()).await?;
+            reader.read_to_end(&mut asset).await?;
+            Ok::<_, AssetReaderError>(())
+        };
+        if let Err(err) = read.await {
+            warn!("Failed to read {asset_path} to store it in the processor cache: {err}");
+            return;
+        }
+        if let Err(err) = cache.put(key, &CachedProcessedAsset { asset, meta }).await {
+            warn!("Failed to store {asset_path} in the processor cache: {err}");
+        }
+    }
+
     async fn validate_transaction_log_and_recover(&self) {
         let log_factory = self
             .data
@@ -1347,6 +1449,7 @@ impl AssetProcessorData {
             sources,
             log_factory: Mutex::new(Some(Box::new(FileTransactionLogFactory::default()))),
             log: Default::default(),
+            cache: Default::default(),
             processors: Default::default(),
         }
     }
@@ -1373,6 +1476,22 @@ impl AssetProcessorData {
         Ok(())
     }
 
+    /// Sets the [`ProcessorCache`] that processed assets are restored from and stored in.
+    ///
+    /// By default, the processor doesn't use a cache. This only affects assets processed after
+    /// it is called.
+    pub fn set_cache(&self, cache: Box<dyn ProcessorCache>) {
+        *self.cache.lock().unwrap_or_else(PoisonError::into_inner) = Some(cache.into());
+    }
+
+    /// Returns the [`ProcessorCache`] used by the processor, if any.
+    pub fn cache(&self) -> Option<Arc<dyn ProcessorCache>> {
+        self.cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
     /// Returns a future that will not finish until the path has been processed.
     pub async fn wait_until_processed(&self, path: AssetPath<'static>) -> ProcessStatus {
         self.processing_state.wait_until_processed(path).await
@@ -1596,6 +1715,21 @@ impl ProcessorAssetInfos {
         self.infos.get(asset_path)
     }
 
+    /// Returns whether any of the process dependencies of `processed_info` no longer matches
+    /// the current processed version of that dependency.
+    fn process_dependency_changed(&self, processed_info: &ProcessedInfo) -> bool {
+        processed_info
+            .process_dependencies
+            .iter()
+            .any(|dependency| {
+                let live_hash = self
+                    .get(&dependency.path)
+                    .and_then(|i| i.processed_info.as_ref())
+                    .map(|i| i.full_hash);
+                live_hash != Some(dependency.full_hash)
+            })
+    }
+
     fn get_mut(&mut self, asset_path: &AssetPath<'static>) -> Option<&mut ProcessorAssetInfo> {
         self.infos.get_mut(asset_path)
     }
@@ -33,6 +33,10 @@ pub trait Process: TypePath + Send + Sync + Sized + 'static {
     type Settings: Settings + Default + Serialize + for<'a> Deserialize<'a>;
     /// The [`AssetLoader`] that will be used to load the final processed asset.
     type OutputLoader: AssetLoader;
+    /// The version of this processor's output. Bump it whenever a change to the processor changes
+    /// its output for the same input and settings, so that results stored in a
+    /// [`ProcessorCache`](crate::processor::ProcessorCache) are no longer used.
+    const VERSION: u32 = 0;
     /// Processes the asset stored on `context` in some way using the settings stored on `meta`. The results are written to `writer`. The
     /// final written processed asset is loadable using [`Process::OutputLoader`]. This load will use the returned [`AssetLoader::Settings`].
     fn process(
@@ -244,6 +248,8 @@ pub trait ErasedProcessor: Send + Sync {
     fn type_path(&self) -> &'static str;
     /// Returns the short type path of this processor.
     fn short_type_path(&self) -> &'static str;
+    /// Returns the [`Process::VERSION`] of the original [`Process`].
+    fn version(&self) -> u32;
     /// Returns the default type-erased [`AssetMeta`] for the underlying [`Process`] impl.
     fn default_meta(&self, processor_path_kind: MetaTypePathKind) -> Box<dyn AssetMetaDyn>;
 }
@@ -288,6 +294,10 @@ impl<P: Process> ErasedProcessor for P {
         P::short_type_path()
     }
 
+    fn version(&self) -> u32 {
+        P::VERSION
+    }
+
     fn default_meta(&self, processor_path_kind: MetaTypePathKind) -> Box<dyn AssetMetaDyn> {
         let type_path = match processor_path_kind {
             MetaTypePathKind::Short => P::short_type_path(),
@@ -360,6 +370,7 @@ impl<'a> ProcessContext<'a> {
                 &mut self.reader,
                 false,
                 true,
+                None,
             )
             .await?;
         for (path, full_hash) in &loaded_asset.loader_dependencies {
@@ -29,13 +29,14 @@ use crate::{
         AssetSourceId, AssetWatcher, PathStream, Reader,
     },
     processor::{
-        AssetProcessor, GetProcessorError, LoadTransformAndSave, LogEntry, Process, ProcessContext,
-        ProcessError, ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
+        AssetProcessor, CachedProcessedAsset, FileProcessorCache, GetProcessorError,
+        LoadTransformAndSave, LogEntry, Process, ProcessContext, ProcessError, ProcessorCache,
+        ProcessorCacheKey, ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
     },
     saver::{tests::CoolTextSaver, AssetSaver},
     tests::{
-        read_asset_as_string, read_meta_as_string, run_app_until, serialize_as_cool_text, CoolText,
-        CoolTextLoader, CoolTextRon, SubText,
+        create_temp_dir, read_asset_as_string, read_meta_as_string, run_app_until,
+        serialize_as_cool_text, CoolText, CoolTextLoader, CoolTextRon, SubText,
     },
     transformer::{AssetTransformer, TransformedAsset},
     Asset, AssetApp, AssetLoader, AssetMode, AssetPath, AssetPlugin, LoadContext,
@@ -546,6 +547,121 @@ fn asset_processor_transforms_asset_default_processor() {
     );
 }
 
+#[test]
+fn asset_processor_restores_assets_from_cache() {
+    /// A processor cache that keeps everything in memory, and can be shared between apps.
+    #[derive(Clone, Default)]
+    struct MemoryProcessorCache(Arc<Mutex<HashMap<ProcessorCacheKey, CachedProcessedAsset>>>);
+
+    impl ProcessorCache for MemoryProcessorCache {
+        fn get<'a>(
+            &'a self,
+            key: &'a ProcessorCacheKey,
+        ) -> BoxedFuture<'a, Result<Option<CachedProcessedAsset>, BevyError>> {
+            Box::pin(async move {
+                let assets = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+                Ok(assets.get(key).cloned())
+            })
+        }
+
+        fn put<'a>(
+            &'a self,
+            key: &'a ProcessorCacheKey,
+            asset: &'a CachedProcessedAsset,
+        ) -> BoxedFuture<'a, Result<(), BevyError>> {
+            Box::pin(async move {
+                let mut assets = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+                assets.insert(*key, asset.clone());
+                Ok(())
+            })
+        }
+    }
+
+    type CoolTextProcessor = LoadTransformAndSave<
+        CoolTextLoader,
+        RootAssetTransformer<AddText, CoolText>,
+        CoolTextSaver,
+    >;
+
+    let cache = MemoryProcessorCache::default();
+    let path = Path::new("abc.cool.ron");
+
+    let process_with_suffix = |suffix: &str| {
+        let AppWithProcessor {
+            mut app,
+            source_gate,
+            default_source_dirs:
+                ProcessingDirs {
+                    source: source_dir,
+                    processed: processed_dir,
+                    ..
+                },
+            ..
+        } = create_app_with_asset_processor(&[]);
+
+        app.register_asset_loader(CoolTextLoader)
+            .register_asset_processor(CoolTextProcessor::new(
+                RootAssetTransformer::new(AddText(suffix.into())),
+                CoolTextSaver,
+            ))
+            .set_default_asset_processor::<CoolTextProcessor>("cool.ron");
+        app.world()
+            .resource::<AssetProcessor>()
+            .data()
+            .set_cache(Box::new(cache.clone()));
+
+        let guard = source_gate.write_blocking();
+        source_dir.insert_asset_text(
+            path,
+            r#"(
+    text: "abc",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+        run_app_until_finished_processing(&mut app, guard);
+
+        assert!(processed_dir.get_metadata(path).is_some());
+        let processed_asset = processed_dir.get_asset(path).unwrap();
+        String::from_utf8(processed_asset.value().to_vec()).unwrap()
+    };
+
+    let expected = r#"(
+    text: "abc_first",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+    assert_eq!(process_with_suffix("_first"), expected);
+    assert_eq!(cache.0.lock().unwrap().len(), 1);
+
+    // The processor has the same type path, version and settings, so the asset is restored from
+    // the cache instead of being processed again.
+    assert_eq!(process_with_suffix("_second"), expected);
+}
+
+#[test]
+fn file_processor_cache_round_trip() {
+    let root = create_temp_dir("file_processor_cache");
+    let cache = FileProcessorCache::new(&root);
+
+    let key = ProcessorCacheKey([7; 32]);
+    let asset = CachedProcessedAsset {
+        asset: b"asset".to_vec(),
+        meta: b"meta".to_vec(),
+    };
+    assert_eq!(bevy_tasks::block_on(cache.get(&key)).unwrap(), None);
+    bevy_tasks::block_on(cache.put(&key, &asset)).unwrap();
+    assert_eq!(bevy_tasks::block_on(cache.get(&key)).unwrap(), Some(asset));
+    assert_eq!(
+        bevy_tasks::block_on(cache.get(&ProcessorCacheKey([8; 32]))).unwrap(),
+        None
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
 #[test]
 fn asset_processor_transforms_asset_with_meta() {
     let AppWithProcessor {
@@ -0,0 +1,321 @@
+use super::streaming::StreamingBudget;
+use crate::{
+    io::{Reader, ReaderNotSeekableError, SeekableReader},
+    ErasedAssetIndex,
+};
+use alloc::vec::Vec;
+use bevy_platform::sync::{Mutex, PoisonError};
+use core::{
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use futures_io::{AsyncRead, AsyncSeek, SeekFrom};
+
+/// The priority of a load started with [`LoadBuilder::with_priority`](crate::LoadBuilder::with_priority).
+///
+/// When an [`AssetSource`](crate::io::AssetSource) limits how many assets it loads at once (see
+/// [`LoadBudget`]), loads waiting for their turn start in order of priority, then in the order
+/// they were requested. Dependencies loaded by an asset's loader inherit its priority.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LoadPriority {
+    /// For assets that aren't needed yet, such as those prefetched for the next level.
+    Low,
+    /// The priority of loads that don't set one.
+    #[default]
+    Normal,
+    /// For assets that are needed right away, such as those that are visible now.
+    High,
+}
+
+/// Limits on how much an [`AssetSource`](crate::io::AssetSource) loads at once, set with
+/// [`AssetSourceBuilder::with_load_budget`](crate::io::AssetSourceBuilder::with_load_budget).
+///
+/// These only apply to loads started by the [`AssetServer`](crate::AssetServer), and not to
+/// assets loaded directly by asset loaders or by the asset processor. Loads that are awaited,
+/// such as with [`LoadBuilder::load_untyped_async`](crate::LoadBuilder::load_untyped_async), and
+/// reloads don't wait for their turn, but still count against the bytes read per frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadBudget {
+    /// The maximum number of assets that can be loading from the source at once, or `None` to
+    /// not limit it. Other loads wait for their turn in order of [`LoadPriority`].
+    pub max_concurrent_loads: Option<usize>,
+    /// The maximum number of bytes that can be read from the source per frame, or `None` to not
+    /// limit it. Loads that exceed it wait for the next frame to read more.
+    pub max_bytes_per_frame: Option<usize>,
+}
+
+impl LoadBudget {
+    /// Returns the budget that keeps to the limits of both `self` and `other`.
+    pub(crate) fn strictest(self, other: LoadBudget) -> LoadBudget {
+        fn min(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                _ => a.or(b),
+            }
+        }
+        LoadBudget {
+            max_concurrent_loads: min(self.max_concurrent_loads, other.max_concurrent_loads),
+            max_bytes_per_frame: min(self.max_bytes_per_frame, other.max_bytes_per_frame),
+        }
+    }
+}
+
+/// A [`Reader`] that takes the bytes it reads out of a [`StreamingBudget`].
+///
+/// Seeking doesn't read any bytes, but it waits for the next frame once the budget of the frame
+/// is used up, like reading does.
+pub(crate) struct BudgetedReader<'a> {
+    reader: &'a mut dyn Reader,
+    budget: &'a StreamingBudget,
+}
+
+impl<'a> BudgetedReader<'a> {
+    pub(crate) fn new(reader: &'a mut dyn Reader, budget: &'a StreamingBudget) -> Self {
+        Self { reader, budget }
+    }
+}
+
+impl AsyncRead for BudgetedReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let Poll::Ready(taken) = self.budget.poll_take(buf.len(), cx) else {
+            return Poll::Pending;
+        };
+        let result = Pin::new(&mut *self.reader).poll_read(cx, &mut buf[..taken]);
+        let read = match &result {
+            Poll::Ready(Ok(read)) => *read,
+            _ => 0,
+        };
+        self.budget.refund(taken - read);
+        result
+    }
+}
+
+impl AsyncSeek for BudgetedReader<'_> {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<futures_io::Result<u64>> {
+        if self.budget.poll_take(0, cx).is_pending() {
+            return Poll::Pending;
+        }
+        match self.reader.seekable() {
+            Ok(reader) => Pin::new(reader).poll_seek(cx, pos),
+            Err(err) => Poll::Ready(Err(futures_io::Error::other(err))),
+        }
+    }
+}
+
+impl Reader for BudgetedReader<'_> {
+    fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
+        self.reader.seekable()?;
+        Ok(self)
+    }
+}
+
+/// Limits how many assets an [`AssetSource`](crate::io::AssetSource) loads at once, starting
+/// the loads waiting for their turn in order of [`LoadPriority`].
+pub(crate) struct LoadQueue {
+    max_concurrent_loads: Option<usize>,
+    state: Mutex<LoadQueueState>,
+}
+
+#[derive(Default)]
+struct LoadQueueState {
+    /// The number of loads that have started and not finished yet.
+    running: usize,
+    /// The ticket of the next load to wait in the queue.
+    next_ticket: u64,
+    /// The loads waiting for their turn.
+    waiting: Vec<QueuedLoad>,
+}
+
+struct QueuedLoad {
+    ticket: u64,
+    index: ErasedAssetIndex,
+    priority: LoadPriority,
+    waker: Option<Waker>,
+}
+
+impl LoadQueueState {
+    /// Returns the position in `waiting` of the load that should start next.
+    fn next(&self) -> Option<usize> {
+        self.waiting
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| {
+                a.priority
+                    .cmp(&b.priority)
+                    .then_with(|| b.ticket.cmp(&a.ticket))
+            })
+            .map(|(position, _)| position)
+    }
+
+    /// Wakes up the load that should start next, if it can start.
+    fn wake_next(&mut self, max_concurrent_loads: usize) {
+        if self.running >= max_concurrent_loads {
+            return;
+        }
+        if let Some(next) = self.next()
+            && let Some(waker) = self.waiting[next].waker.take()
+        {
+            waker.wake();
+        }
+    }
+}
+
+impl LoadQueue {
+    pub(crate) fn new(max_concurrent_loads: Option<usize>) -> Self {
+        Self {
+            max_concurrent_loads,
+            state: Mutex::new(LoadQueueState::default()),
+        }
+    }
+
+    fn lock(&self) -> impl core::ops::DerefMut<Target = LoadQueueState> + '_ {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Adds the load of the asset at `index` to the queue. Await [`QueuedLoadGuard::wait`] on
+    /// the result to wait for its turn.
+    ///
+    /// This doesn't wait, so that the load can be queued while the priority it was read from is
+    /// locked, without missing a change to the priority made before the load is in the queue.
+    pub(crate) fn enqueue(
+        &self,
+        index: ErasedAssetIndex,
+        priority: LoadPriority,
+    ) -> QueuedLoadGuard<'_> {
+        let Some(max_concurrent_loads) = self.max_concurrent_loads else {
+            return QueuedLoadGuard {
+                queue: self,
+                max_concurrent_loads: 0,
+                ticket: None,
+            };
+        };
+        let mut state = self.lock();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.waiting.push(QueuedLoad {
+            ticket,
+            index,
+            priority,
+            waker: None,
+        });
+        QueuedLoadGuard {
+            queue: self,
+            max_concurrent_loads,
+            ticket: Some(ticket),
+        }
+    }
+
+    /// Changes the priority of the load of the asset at `index` if it is waiting for its turn.
+    pub(crate) fn set_priority(&self, index: ErasedAssetIndex, priority: LoadPriority) {
+        let Some(max_concurrent_loads) = self.max_concurrent_loads else {
+            return;
+        };
+        let mut state = self.lock();
+        for load in &mut state.waiting {
+            if load.index == index {
+                load.priority = priority;
+            }
+        }
+        state.wake_next(max_concurrent_loads);
+    }
+}
+
+/// A load in a [`LoadQueue`], which leaves the queue if it is dropped while waiting for its turn.
+pub(crate) struct QueuedLoadGuard<'a> {
+    queue: &'a LoadQueue,
+    max_concurrent_loads: usize,
+    ticket: Option<u64>,
+}
+
+impl<'a> QueuedLoadGuard<'a> {
+    /// Waits for the turn of the load, returning a permit that lets the next load start when it
+    /// is dropped.
+    pub(crate) async fn wait(mut self) -> LoadPermit<'a> {
+        let Some(ticket) = self.ticket else {
+            return LoadPermit { queue: None };
+        };
+        let queue = self.queue;
+        let max_concurrent_loads = self.max_concurrent_loads;
+        core::future::poll_fn(|cx| {
+            let mut state = queue.lock();
+            let next = state.next().expect("the load is in the queue");
+            if state.waiting[next].ticket == ticket && state.running < max_concurrent_loads {
+                state.waiting.remove(next);
+                state.running += 1;
+                return Poll::Ready(());
+            }
+            let position = state
+                .waiting
+                .iter()
+                .position(|load| load.ticket == ticket)
+                .expect("the load is in the queue");
+            state.waiting[position].waker = Some(cx.waker().clone());
+            Poll::Pending
+        })
+        .await;
+        self.ticket = None;
+        LoadPermit {
+            queue: Some((queue, max_concurrent_loads)),
+        }
+    }
+}
+
+impl Drop for QueuedLoadGuard<'_> {
+    fn drop(&mut self) {
+        let Some(ticket) = self.ticket else {
+            return;
+        };
+        let mut state = self.queue.lock();
+        state.waiting.retain(|load| load.ticket != ticket);
+        state.wake_next(self.max_concurrent_loads);
+    }
+}
+
+/// Lets the next load of a [`LoadQueue`] start when dropped.
+pub(crate) struct LoadPermit<'a> {
+    queue: Option<(&'a LoadQueue, usize)>,
+}
+
+impl LoadPermit<'_> {
+    /// Returns a permit for a load from a source that doesn't limit its loads.
+    pub(crate) fn unlimited() -> Self {
+        Self { queue: None }
+    }
+}
+
+impl Drop for LoadPermit<'_> {
+    fn drop(&mut self) {
+        let Some((queue, max_concurrent_loads)) = self.queue else {
+            return;
+        };
+        let mut state = queue.lock();
+        state.running -= 1;
+        state.wake_next(max_concurrent_loads);
+    }
+}
+
+/// The [`LoadQueue`] and read [`StreamingBudget`] of an [`AssetSource`](crate::io::AssetSource).
+pub(crate) struct SourceBudget {
+    pub(crate) queue: LoadQueue,
+    pub(crate) bytes: StreamingBudget,
+}
+
+impl SourceBudget {
+    pub(crate) fn new(budget: LoadBudget) -> Self {
+        Self {
+            queue: LoadQueue::new(budget.max_concurrent_loads),
+            bytes: StreamingBudget::new(budget.max_bytes_per_frame),
+        }
+    }
+}
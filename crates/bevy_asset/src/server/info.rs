@@ -1,7 +1,7 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
     Asset, AssetHandleProvider, AssetIndex, AssetLoadError, AssetPath, DependencyLoadState,
-    ErasedAssetIndex, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
+    ErasedAssetIndex, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadPriority, LoadState,
     RecursiveDependencyLoadState, StrongHandle, UntypedHandle,
 };
 use alloc::{
@@ -33,20 +33,29 @@ pub(crate) struct AssetInfo {
     failed_dependencies: HashSet<ErasedAssetIndex>,
     loading_rec_dependencies: HashSet<ErasedAssetIndex>,
     failed_rec_dependencies: HashSet<ErasedAssetIndex>,
+    /// Every dependency of this asset, whether it has loaded or not. This is set using the value
+    /// from [`LoadedAsset`].
+    /// This will only be populated if [`AssetInfos::watching_for_changes`] or
+    /// [`AssetInfos::tracking_dependencies`] is set to `true` to save memory.
+    ///
+    /// [`LoadedAsset`]: crate::loader::LoadedAsset
+    pub(crate) dependencies: HashSet<ErasedAssetIndex>,
     dependents_waiting_on_load: HashSet<ErasedAssetIndex>,
     dependents_waiting_on_recursive_dep_load: HashSet<ErasedAssetIndex>,
     /// The asset paths required to load this asset. Hashes will only be set for processed assets.
     /// This is set using the value from [`LoadedAsset`].
-    /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
-    /// save memory.
+    /// This will only be populated if [`AssetInfos::watching_for_changes`] or
+    /// [`AssetInfos::tracking_dependencies`] is set to `true` to save memory.
     ///
     /// [`LoadedAsset`]: crate::loader::LoadedAsset
-    loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
+    pub(crate) loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
     /// List of tasks waiting for this asset to complete loading
     pub(crate) waiting_tasks: Vec<Waker>,
+    /// The priority of the load of this asset.
+    pub(crate) priority: LoadPriority,
 }
 
 impl AssetInfo {
@@ -61,11 +70,13 @@ impl AssetInfo {
             failed_dependencies: HashSet::default(),
             loading_rec_dependencies: HashSet::default(),
             failed_rec_dependencies: HashSet::default(),
+            dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
             dependents_waiting_on_load: HashSet::default(),
             dependents_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
             waiting_tasks: Vec::new(),
+            priority: LoadPriority::default(),
         }
     }
 }
@@ -84,6 +95,9 @@ pub(crate) struct AssetInfos {
     /// If set to `true`, this informs [`AssetInfos`] to track data relevant to watching for changes (such as `load_dependents`)
     /// This should only be set at startup.
     pub(crate) watching_for_changes: bool,
+    /// If set to `true`, the dependencies of every asset are kept after it loads, even when not
+    /// watching for changes, so that they are included in the dependency graph.
+    pub(crate) tracking_dependencies: bool,
     /// Tracks assets that depend on the "key" asset path inside their asset loaders ("loader dependencies")
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) loader_dependents: HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
@@ -91,9 +105,12 @@ pub(crate) struct AssetInfos {
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) living_labeled_assets: HashMap<AssetPath<'static>, HashSet<Box<str>>>,
     pub(crate) handle_providers: TypeIdHashMap<AssetHandleProvider>,
+    /// The type paths of registered asset types.
+    pub(crate) type_paths: TypeIdHashMap<&'static str>,
     pub(crate) dependency_loaded_event_sender: TypeIdHashMap<fn(&mut World, AssetIndex)>,
     pub(crate) dependency_failed_event_sender:
         TypeIdHashMap<fn(&mut World, AssetIndex, AssetPath<'static>, AssetLoadError)>,
+    pub(crate) upgraded_event_sender: TypeIdHashMap<fn(&mut World, AssetIndex, u32)>,
     pub(crate) pending_tasks: HashMap<ErasedAssetIndex, Task<()>>,
     /// The stats that have collected during usage of the asset server.
     pub(crate) stats: AssetServerStats,
@@ -294,6 +311,10 @@ impl AssetInfos {
         self.infos.get(&index)
     }
 
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&ErasedAssetIndex, &AssetInfo)> {
+        self.infos.iter()
+    }
+
     pub(crate) fn contains_key(&self, index: ErasedAssetIndex) -> bool {
         self.infos.contains_key(&index)
     }
@@ -425,6 +446,8 @@ impl AssetInfos {
 
         loaded_asset.value.insert(loaded_asset_index.index, world);
         let mut loading_deps = loaded_asset.dependencies;
+        let dependencies =
+            (self.watching_for_changes || self.tracking_dependencies).then(|| loading_deps.clone());
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
         let mut loading_rec_deps = loading_deps.clone();
@@ -527,7 +550,8 @@ impl AssetInfos {
             info.load_state = LoadState::Loaded;
             info.dep_load_state = dep_load_state;
             info.rec_dep_load_state = rec_dep_load_state.clone();
-            if watching_for_changes {
+            if let Some(dependencies) = dependencies {
+                info.dependencies = dependencies;
                 info.loader_dependencies = loaded_asset.loader_dependencies;
             }
 
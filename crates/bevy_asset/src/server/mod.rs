@@ -1,23 +1,26 @@
+mod budget;
 mod info;
 mod loaders;
+mod streaming;
 
 use crate::{
     folder::LoadedFolder,
+    graph::AssetDependencyGraph,
     io::{
         AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId, AssetSources,
         AssetWriterError, ErasedAssetReader, MissingAssetSourceError, MissingAssetWriterError,
         MissingProcessedAssetReaderError, Reader,
     },
-    loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
+    loader::{AssetContainer, AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
     meta::{
         loader_settings_meta_transform, AssetActionMinimal, AssetMetaDyn, AssetMetaMinimal,
         MetaTransform, Settings,
     },
     path::AssetPath,
     Asset, AssetEvent, AssetHandleProvider, AssetId, AssetIndex, AssetLoadFailedEvent,
-    AssetMetaCheck, Assets, DeserializeMetaError, ErasedAssetIndex, ErasedLoadedAsset, Handle,
-    LoadedUntypedAsset, UnapprovedPathMode, UntypedAssetId, UntypedAssetLoadFailedEvent,
-    UntypedHandle, VisitAssetDependencies,
+    AssetMetaCheck, AssetUpgradedEvent, Assets, DeserializeMetaError, ErasedAssetIndex,
+    ErasedLoadedAsset, Handle, LoadedUntypedAsset, UnapprovedPathMode, UntypedAssetId,
+    UntypedAssetLoadFailedEvent, UntypedHandle, VisitAssetDependencies,
 };
 use alloc::{borrow::ToOwned, boxed::Box, vec, vec::Vec};
 use alloc::{
@@ -33,6 +36,7 @@ use bevy_platform::{
     sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 use bevy_tasks::IoTaskPool;
+use budget::{BudgetedReader, LoadPermit, SourceBudget};
 use core::{
     any::{type_name, TypeId},
     future::Future,
@@ -44,9 +48,12 @@ use futures_lite::{FutureExt, StreamExt};
 use info::*;
 use loaders::*;
 use std::path::{Path, PathBuf};
+use streaming::StreamingBudget;
 use thiserror::Error;
 use tracing::{error, info, warn};
 
+pub use budget::{LoadBudget, LoadPriority};
+
 /// Loads and tracks the state of [`Asset`] values from a configured [`AssetReader`](crate::io::AssetReader).
 /// This can be used to kick off new asset loads and retrieve their current load states.
 ///
@@ -77,6 +84,9 @@ pub(crate) struct AssetServerData {
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
     unapproved_path_mode: UnapprovedPathMode,
+    pub(crate) streaming_budget: StreamingBudget,
+    /// The budgets of the sources that limit how much is loaded from them at once.
+    source_budgets: Vec<(AssetSourceId<'static>, SourceBudget)>,
 }
 
 /// The "asset mode" the server is currently in.
@@ -140,6 +150,11 @@ impl AssetServer {
         let (asset_event_sender, asset_event_receiver) = crossbeam_channel::unbounded();
         let mut infos = AssetInfos::default();
         infos.watching_for_changes = watching_for_changes;
+        let source_budgets = sources
+            .iter()
+            .filter(|source| source.load_budget() != LoadBudget::default())
+            .map(|source| (source.id(), SourceBudget::new(source.load_budget())))
+            .collect();
         Self {
             data: Arc::new(AssetServerData {
                 sources,
@@ -150,6 +165,8 @@ impl AssetServer {
                 loaders,
                 infos: RwLock::new(infos),
                 unapproved_path_mode,
+                streaming_budget: StreamingBudget::default(),
+                source_budgets,
             }),
         }
     }
@@ -195,6 +212,23 @@ impl AssetServer {
         self.read_infos().watching_for_changes
     }
 
+    /// Sets whether the dependencies of every asset are kept after it loads, so that they show up
+    /// in the [`dependency_graph`](AssetServer::dependency_graph).
+    ///
+    /// Dependencies are always kept when [watching for changes](AssetServer::watching_for_changes).
+    /// Otherwise they are dropped to save memory, unless this is enabled. Only assets loaded after
+    /// this is enabled have their dependencies kept.
+    pub fn set_tracking_dependencies(&self, tracking: bool) {
+        self.write_infos().tracking_dependencies = tracking;
+    }
+
+    /// Returns true if the [`AssetServer`] keeps the dependencies of every asset after it loads.
+    /// See [`AssetServer::set_tracking_dependencies`].
+    pub fn tracking_dependencies(&self) -> bool {
+        let infos = self.read_infos();
+        infos.watching_for_changes || infos.tracking_dependencies
+    }
+
     /// Registers a new [`AssetLoader`]. [`AssetLoader`]s must be registered before they can be used.
     pub fn register_loader<L: AssetLoader>(&self, loader: L) {
         self.write_loaders().push(loader);
@@ -223,8 +257,19 @@ impl AssetServer {
                 });
         }
 
+        fn upgraded_sender<A: Asset>(world: &mut World, index: AssetIndex, level: u32) {
+            world
+                .resource_mut::<Messages<AssetUpgradedEvent<A>>>()
+                .write(AssetUpgradedEvent {
+                    id: index.into(),
+                    level,
+                });
+        }
+
         let mut infos = self.write_infos();
 
+        infos.type_paths.insert(TypeId::of::<A>(), A::type_path());
+
         infos
             .dependency_loaded_event_sender
             .insert(TypeId::of::<A>(), sender::<A>);
@@ -232,6 +277,10 @@ impl AssetServer {
         infos
             .dependency_failed_event_sender
             .insert(TypeId::of::<A>(), failed_sender::<A>);
+
+        infos
+            .upgraded_event_sender
+            .insert(TypeId::of::<A>(), upgraded_sender::<A>);
     }
 
     pub(crate) fn register_handle_provider(&self, handle_provider: AssetHandleProvider) {
@@ -380,6 +429,7 @@ impl AssetServer {
         meta_transform: Option<MetaTransform>,
         guard: G,
         override_unapproved: bool,
+        priority: LoadPriority,
     ) -> UntypedHandle {
         let path = path.into().into_owned();
         if path.path() == Path::new("") {
@@ -409,6 +459,10 @@ impl AssetServer {
             meta_transform,
         );
 
+        // `get_or_create_path_handle_erased` always returns Strong variant, so this is safe.
+        let index = (&handle).try_into().unwrap();
+        self.request_priority(&mut infos, index, path.source(), priority, should_load);
+
         if should_load {
             self.spawn_load_task(handle.clone(), path, infos, guard);
         }
@@ -416,6 +470,29 @@ impl AssetServer {
         handle
     }
 
+    /// Sets the priority of the load of the asset at `index` when it is requested. Requesting an
+    /// asset that is already loading can only raise its priority.
+    fn request_priority(
+        &self,
+        infos: &mut AssetInfos,
+        index: ErasedAssetIndex,
+        source: &AssetSourceId<'_>,
+        priority: LoadPriority,
+        should_load: bool,
+    ) {
+        let Some(info) = infos.get_mut(index) else {
+            return;
+        };
+        if should_load {
+            info.priority = priority;
+        } else if priority > info.priority {
+            info.priority = priority;
+            if let Some(budget) = self.source_budget(source) {
+                budget.queue.set_priority(index, priority);
+            }
+        }
+    }
+
     pub(crate) fn spawn_load_task<G: Send + Sync + 'static>(
         &self,
         handle: UntypedHandle,
@@ -432,6 +509,11 @@ impl AssetServer {
         let owned_handle = handle.clone();
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
+            let Some((_permit, owned_handle)) =
+                server.wait_for_load_turn(owned_handle, path.source()).await
+            else {
+                return;
+            };
             if let Err(err) = server
                 .load_internal(Some(owned_handle), path, false, None)
                 .await
@@ -459,6 +541,7 @@ impl AssetServer {
         meta_transform: Option<MetaTransform>,
         guard: G,
         override_unapproved: bool,
+        priority: LoadPriority,
     ) -> Handle<LoadedUntypedAsset> {
         let path = path.into().into_owned();
         if path.path() == Path::new("") {
@@ -476,23 +559,18 @@ impl AssetServer {
             }
         }
 
-        let untyped_source = AssetSourceId::Name(match path.source() {
-            AssetSourceId::Default => CowArc::Static(UNTYPED_SOURCE_SUFFIX),
-            AssetSourceId::Name(source) => {
-                CowArc::Owned(format!("{source}--{UNTYPED_SOURCE_SUFFIX}").into())
-            }
-        });
         let mut infos = self.write_infos();
         let (handle, should_load) = infos.get_or_create_path_handle::<LoadedUntypedAsset>(
-            path.clone().with_source(untyped_source),
+            path.clone().with_source(untyped_source(path.source())),
             HandleLoadingMode::Request,
             meta_transform,
         );
 
+        let index = (&handle).try_into().unwrap();
+        self.request_priority(&mut infos, index, path.source(), priority, should_load);
         if !should_load {
             return handle;
         }
-        let index = (&handle).try_into().unwrap();
 
         infos.stats.started_load_tasks += 1;
 
@@ -500,8 +578,16 @@ impl AssetServer {
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
         drop(infos);
 
+        let owned_handle = handle.clone().untyped();
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
+            let Some((_permit, owned_handle)) =
+                server.wait_for_load_turn(owned_handle, path.source()).await
+            else {
+                return;
+            };
+            // Keeping the handle would keep the asset alive until it is loaded.
+            drop(owned_handle);
             let path_clone = path.clone();
             match server
                 .load_internal(None, path, false, None)
@@ -535,6 +621,46 @@ impl AssetServer {
         handle
     }
 
+    /// Returns the budget of the given `source`, if it limits how much is loaded from it at once.
+    fn source_budget(&self, source: &AssetSourceId<'_>) -> Option<&SourceBudget> {
+        self.data
+            .source_budgets
+            .iter()
+            .find_map(|(id, budget)| (id == source).then_some(budget))
+    }
+
+    /// Waits for the turn of the load of the asset of `handle` if its source limits how many
+    /// assets it loads at once, returning the permit that lets the next load start once dropped
+    /// along with a strong handle to the asset.
+    ///
+    /// Returns `None` if every strong handle of the asset was dropped while its load waited, in
+    /// which case the load should be skipped.
+    async fn wait_for_load_turn(
+        &self,
+        handle: UntypedHandle,
+        source: &AssetSourceId<'_>,
+    ) -> Option<(LoadPermit<'_>, UntypedHandle)> {
+        let Some(budget) = self.source_budget(source) else {
+            return Some((LoadPermit::unlimited(), handle));
+        };
+        // `handle` is always a Strong variant, so this is safe.
+        let index = (&handle).try_into().unwrap();
+        // Queue the load while its priority is locked, so that `set_load_priority` either changes
+        // the priority before it is read or finds the load in the queue.
+        let queued = {
+            let infos = self.read_infos();
+            budget.queue.enqueue(index, infos.get(index)?.priority)
+        };
+        // Don't keep the asset alive while it waits, so that dropping its last handle cancels
+        // the load.
+        drop(handle);
+        let permit = queued.wait().await;
+        // On platforms where dropping the last handle doesn't cancel the load task, the task
+        // keeps waiting for its turn even though the asset is no longer needed.
+        let handle = self.read_infos().get_index_handle(index)?;
+        Some((permit, handle))
+    }
+
     /// Performs an async asset load.
     ///
     /// `input_handle` must only be [`Some`] if `should_load` was true when retrieving
@@ -673,14 +799,24 @@ impl AssetServer {
             (asset_id.unwrap(), None, path.clone())
         };
 
+        let mut budgeted_reader;
+        let reader: &mut dyn Reader = match self.source_budget(path.source()) {
+            Some(budget) if budget.bytes.bytes_per_frame().is_some() => {
+                budgeted_reader = BudgetedReader::new(&mut *reader, &budget.bytes);
+                &mut budgeted_reader
+            }
+            _ => &mut *reader,
+        };
+
         match self
             .load_with_settings_loader_and_reader(
                 &base_path,
                 meta.loader_settings().expect("meta is set to Load"),
                 &*loader,
-                &mut *reader,
+                reader,
                 true,
                 false,
+                Some(base_asset_id),
             )
             .await
         {
@@ -1024,7 +1160,7 @@ impl AssetServer {
             .detach();
     }
 
-    fn send_asset_event(&self, event: InternalAssetEvent) {
+    pub(crate) fn send_asset_event(&self, event: InternalAssetEvent) {
         self.data.asset_event_sender.send(event).unwrap();
     }
 
@@ -1305,6 +1441,79 @@ impl AssetServer {
         Some(info.path.as_ref()?.clone())
     }
 
+    /// Returns a snapshot of every asset tracked by this server, the files they were loaded or
+    /// processed from, and the dependencies between them, along with their load states and the
+    /// sizes of their files.
+    ///
+    /// The dependencies of assets are only kept when the server is
+    /// [watching for changes](AssetServer::watching_for_changes) or
+    /// [tracking dependencies](AssetServer::set_tracking_dependencies), so the graph has no
+    /// edges between assets otherwise.
+    ///
+    /// This reads the metadata of every asset, so it is meant for debugging and build tooling
+    /// rather than to be called every frame. See [`AssetDependencyGraph`] for how to export the graph,
+    /// and [`AssetDependencyGraph::unused_files`] to find the files that aren't used by any asset.
+    pub async fn dependency_graph(&self) -> AssetDependencyGraph {
+        AssetDependencyGraph::from_server(self).await
+    }
+
+    /// Sets the maximum number of bytes of partial asset versions that loaders can stream per
+    /// frame with [`LoadContext::publish_partial`], or `None` to not limit streaming.
+    ///
+    /// Loaders that exceed the budget wait for the next frame before publishing their next
+    /// version, which spreads the cost of uploading the new versions (for example, to the GPU)
+    /// across frames. The first version published in a frame is always accepted, even if it is
+    /// larger than the budget.
+    pub fn set_streaming_budget(&self, bytes_per_frame: Option<usize>) {
+        self.data
+            .streaming_budget
+            .set_bytes_per_frame(bytes_per_frame);
+    }
+
+    /// Returns the maximum number of bytes of partial asset versions that loaders can stream per
+    /// frame. See [`AssetServer::set_streaming_budget`].
+    pub fn streaming_budget(&self) -> Option<usize> {
+        self.data.streaming_budget.bytes_per_frame()
+    }
+
+    /// Changes the [`LoadPriority`] of the load of the asset with the given `id`, for example to
+    /// deprioritize assets that are no longer needed soon.
+    ///
+    /// This only affects loads that are still waiting for their turn because their
+    /// [`AssetSource`] limits how many assets it loads at once. See [`LoadBudget`].
+    pub fn set_load_priority(&self, id: impl Into<UntypedAssetId>, priority: LoadPriority) {
+        let Ok(index) = id.into().try_into() else {
+            return;
+        };
+        let mut infos = self.write_infos();
+        let Some(info) = infos.get_mut(index) else {
+            return;
+        };
+        info.priority = priority;
+        let Some(path) = &info.path else {
+            return;
+        };
+        // Untyped loads are tracked under their own source, but wait in the queue of the source
+        // they were requested from.
+        let source = if index.type_id == TypeId::of::<LoadedUntypedAsset>() {
+            requested_source(path.source())
+        } else {
+            path.source().clone()
+        };
+        if let Some(budget) = self.source_budget(&source) {
+            budget.queue.set_priority(index, priority);
+        }
+    }
+
+    /// Returns the [`LoadPriority`] of the load of the asset with the given `id`, if it is
+    /// tracked by the server.
+    pub fn get_load_priority(&self, id: impl Into<UntypedAssetId>) -> Option<LoadPriority> {
+        let Ok(index) = id.into().try_into() else {
+            return None;
+        };
+        self.read_infos().get(index).map(|info| info.priority)
+    }
+
     /// Returns the [`AssetServerMode`] this server is currently in.
     pub fn mode(&self) -> AssetServerMode {
         self.data.mode
@@ -1465,11 +1674,18 @@ impl AssetServer {
         reader: &mut dyn Reader,
         load_dependencies: bool,
         populate_hashes: bool,
+        partial_asset_index: Option<ErasedAssetIndex>,
     ) -> Result<ErasedLoadedAsset, AssetLoadError> {
         // TODO: experiment with this
         let asset_path = asset_path.clone_owned();
-        let load_context =
+        let mut load_context =
             LoadContext::new(self, asset_path.clone(), load_dependencies, populate_hashes);
+        load_context.partial_asset_index = partial_asset_index;
+        if let Some(index) = partial_asset_index
+            && let Some(info) = self.read_infos().get(index)
+        {
+            load_context.priority = info.priority;
+        }
         let load = AssertUnwindSafe(loader.load(reader, settings, load_context)).catch_unwind();
         #[cfg(feature = "trace")]
         let load = {
@@ -1691,6 +1907,8 @@ pub struct LoadBuilder<'a> {
     override_unapproved: bool,
     /// A "guard" that is held until the load has fully completed.
     guard: Option<Box<dyn Send + Sync + 'static>>,
+    /// The priority of the load.
+    priority: LoadPriority,
 }
 
 impl<'a> LoadBuilder<'a> {
@@ -1702,6 +1920,7 @@ impl<'a> LoadBuilder<'a> {
             meta_transform: None,
             override_unapproved: false,
             guard: None,
+            priority: LoadPriority::default(),
         }
     }
 
@@ -1756,6 +1975,17 @@ impl<'a> LoadBuilder<'a> {
         self
     }
 
+    /// Sets the [`LoadPriority`] of the load, which decides when it starts if the asset's
+    /// [`AssetSource`] limits how many assets it loads at once. See [`LoadBudget`].
+    ///
+    /// If the asset is already loading, its priority is only changed if this one is higher. Use
+    /// [`AssetServer::set_load_priority`] to lower it.
+    #[must_use = "the load doesn't start until LoadBuilder has been consumed"]
+    pub fn with_priority(mut self, priority: LoadPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Begins loading an [`Asset`] of type `A` stored at `path`. This will not block on the asset load. Instead,
     /// it returns a "strong" [`Handle`]. When the [`Asset`] is loaded (and enters [`LoadState::Loaded`]), it will be added to the
     /// associated [`Assets`] resource.
@@ -1815,6 +2045,7 @@ impl<'a> LoadBuilder<'a> {
             self.meta_transform,
             self.guard,
             self.override_unapproved,
+            self.priority,
         )
     }
 
@@ -1859,6 +2090,7 @@ impl<'a> LoadBuilder<'a> {
             self.meta_transform,
             self.guard,
             self.override_unapproved,
+            self.priority,
         )
     }
 }
@@ -1911,9 +2143,32 @@ pub fn handle_internal_asset_events(world: &mut World) {
                         .expect("Asset failed event sender should exist");
                     sender(world, index.index, path, error);
                 }
+                InternalAssetEvent::Upgraded {
+                    index,
+                    asset,
+                    level,
+                } => {
+                    // Check whether the handle has been dropped since the version was published.
+                    if !infos.contains_key(index) {
+                        continue;
+                    }
+                    asset.insert(index.index, world);
+                    let sender = infos
+                        .upgraded_event_sender
+                        .get(&index.type_id)
+                        .expect("Asset upgraded event sender should exist");
+                    sender(world, index.index, level);
+                }
             }
         }
 
+        // Refill the streaming budget after handling the partial versions streamed last frame,
+        // so that at most one frame's worth of them is handled every frame.
+        server.data.streaming_budget.start_frame();
+        for (_, budget) in &server.data.source_budgets {
+            budget.bytes.start_frame();
+        }
+
         if !untyped_failures.is_empty() {
             world.write_message_batch(untyped_failures);
         }
@@ -2052,6 +2307,11 @@ pub(crate) enum InternalAssetEvent {
         path: AssetPath<'static>,
         error: AssetLoadError,
     },
+    Upgraded {
+        index: ErasedAssetIndex,
+        asset: Box<dyn AssetContainer>,
+        level: u32,
+    },
 }
 
 /// The load state of an asset.
@@ -2314,6 +2574,31 @@ impl core::fmt::Debug for AssetServer {
 /// source for a given [`AssetPath`].
 const UNTYPED_SOURCE_SUFFIX: &str = "--untyped";
 
+/// Returns the source a [`LoadedUntypedAsset`] loaded from `source` is tracked under.
+fn untyped_source(source: &AssetSourceId<'_>) -> AssetSourceId<'static> {
+    AssetSourceId::Name(match source {
+        AssetSourceId::Default => CowArc::Static(UNTYPED_SOURCE_SUFFIX),
+        AssetSourceId::Name(source) => {
+            CowArc::Owned(format!("{source}--{UNTYPED_SOURCE_SUFFIX}").into())
+        }
+    })
+}
+
+/// Returns the source a [`LoadedUntypedAsset`] tracked under `source` was loaded from, undoing
+/// [`untyped_source`].
+fn requested_source<'a>(source: &'a AssetSourceId<'_>) -> AssetSourceId<'a> {
+    let AssetSourceId::Name(name) = source else {
+        return AssetSourceId::Default;
+    };
+    match name
+        .strip_suffix(UNTYPED_SOURCE_SUFFIX)
+        .and_then(|name| name.strip_suffix("--"))
+    {
+        Some(name) => AssetSourceId::Name(CowArc::Borrowed(name)),
+        None => AssetSourceId::Default,
+    }
+}
+
 /// An error when attempting to wait asynchronously for an [`Asset`] to load.
 #[derive(Error, Debug, Clone)]
 pub enum WaitForAssetError {
@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+use bevy_platform::sync::{Mutex, PoisonError};
+use core::task::{Context, Poll, Waker};
+
+/// Limits how many bytes of partial asset versions loaders can stream per frame.
+///
+/// See [`AssetServer::set_streaming_budget`](crate::AssetServer::set_streaming_budget). This is
+/// also used to limit the bytes read per frame from sources with a
+/// [`LoadBudget`](crate::LoadBudget).
+#[derive(Default)]
+pub(crate) struct StreamingBudget {
+    state: Mutex<StreamingState>,
+}
+
+#[derive(Default)]
+struct StreamingState {
+    /// The number of bytes that can be streamed per frame, or `None` if streaming is unlimited.
+    bytes_per_frame: Option<usize>,
+    /// The number of bytes that can still be streamed this frame.
+    remaining: usize,
+    /// Whether a partial version has been streamed this frame.
+    streamed_this_frame: bool,
+    /// The loaders waiting for the next frame to stream their partial versions.
+    waiting: Vec<Waker>,
+}
+
+impl StreamingBudget {
+    pub(crate) fn new(bytes_per_frame: Option<usize>) -> Self {
+        let budget = Self::default();
+        budget.set_bytes_per_frame(bytes_per_frame);
+        budget
+    }
+
+    pub(crate) fn bytes_per_frame(&self) -> Option<usize> {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .bytes_per_frame
+    }
+
+    pub(crate) fn set_bytes_per_frame(&self, bytes_per_frame: Option<usize>) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.bytes_per_frame = bytes_per_frame;
+        state.remaining = bytes_per_frame.unwrap_or_default();
+        state.waiting.drain(..).for_each(Waker::wake);
+    }
+
+    /// Refills the budget for a new frame, waking up the loaders waiting for it.
+    pub(crate) fn start_frame(&self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.remaining = state.bytes_per_frame.unwrap_or_default();
+        state.streamed_this_frame = false;
+        state.waiting.drain(..).for_each(Waker::wake);
+    }
+
+    /// Waits until `bytes` can be streamed, and takes them out of the budget of the frame.
+    pub(crate) async fn acquire(&self, bytes: usize) {
+        core::future::poll_fn(|cx| self.poll_acquire(bytes, cx)).await;
+    }
+
+    fn poll_acquire(&self, bytes: usize, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.bytes_per_frame.is_none() {
+            return Poll::Ready(());
+        }
+        // The first partial version of every frame is streamed even if it exceeds the budget,
+        // so that versions larger than the budget don't wait forever.
+        if !state.streamed_this_frame || bytes <= state.remaining {
+            state.remaining = state.remaining.saturating_sub(bytes);
+            state.streamed_this_frame = true;
+            return Poll::Ready(());
+        }
+        state.waiting.push(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Takes up to `max` bytes out of the budget of the frame, waiting for the next frame if it
+    /// is exhausted.
+    pub(crate) fn poll_take(&self, max: usize, cx: &mut Context<'_>) -> Poll<usize> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.bytes_per_frame.is_none() {
+            return Poll::Ready(max);
+        }
+        if state.remaining == 0 {
+            state.waiting.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        let taken = max.min(state.remaining);
+        state.remaining -= taken;
+        state.streamed_this_frame = true;
+        Poll::Ready(taken)
+    }
+
+    /// Gives back `bytes` that were taken but not used.
+    pub(crate) fn refund(&self, bytes: usize) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.bytes_per_frame.is_some() {
+            state.remaining += bytes;
+        }
+    }
+}
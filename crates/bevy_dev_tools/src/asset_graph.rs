@@ -0,0 +1,136 @@
+//! Commands to export the asset dependency graph and find unused asset files.
+//!
+//! ```no_run
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_dev_tools::asset_graph::{AssetGraphFormat, WriteAssetGraph, WriteUnusedAssetFiles};
+//! # use bevy_asset::io::AssetSourceId;
+//! fn dump_assets(mut commands: Commands) {
+//!     commands.queue(WriteAssetGraph {
+//!         path: "assets.dot".into(),
+//!         format: AssetGraphFormat::Dot,
+//!     });
+//!     commands.queue(WriteUnusedAssetFiles {
+//!         source: AssetSourceId::Default,
+//!         path: "unused_assets.json".into(),
+//!     });
+//! }
+//! ```
+
+use std::path::PathBuf;
+
+use bevy_asset::{io::AssetSourceId, AssetServer};
+use bevy_ecs::{system::Command, world::World};
+use bevy_tasks::IoTaskPool;
+use tracing::{error, info};
+
+/// The format [`WriteAssetGraph`] writes the graph in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AssetGraphFormat {
+    /// The [DOT](https://graphviz.org/doc/info/lang.html) format of Graphviz.
+    /// See [`AssetDependencyGraph::to_dot`](bevy_asset::graph::AssetDependencyGraph::to_dot).
+    #[default]
+    Dot,
+    /// JSON, using the serialized form of
+    /// [`AssetDependencyGraph`](bevy_asset::graph::AssetDependencyGraph).
+    Json,
+}
+
+/// A [`Command`] that writes the dependency graph of every asset tracked by the [`AssetServer`],
+/// with their load states and file sizes, to a file.
+///
+/// The graph is built and written in the background. See [`AssetServer::dependency_graph`].
+/// The dependencies between assets are only included if the server is watching for changes or
+/// [`AssetPlugin::track_dependencies`](bevy_asset::AssetPlugin::track_dependencies) is set.
+pub struct WriteAssetGraph {
+    /// The path of the file to write the graph to.
+    pub path: PathBuf,
+    /// The format to write the graph in.
+    pub format: AssetGraphFormat,
+}
+
+impl Command for WriteAssetGraph {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let server = world.resource::<AssetServer>().clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let graph = server.dependency_graph().await;
+                let contents = match self.format {
+                    AssetGraphFormat::Dot => graph.to_dot(),
+                    AssetGraphFormat::Json => match serde_json::to_string_pretty(&graph) {
+                        Ok(json) => json,
+                        Err(err) => {
+                            error!("Failed to serialize the asset graph: {err}");
+                            return;
+                        }
+                    },
+                };
+                match std::fs::write(&self.path, contents) {
+                    Ok(()) => info!(
+                        "Wrote the graph of {} assets to {}",
+                        graph.nodes.len(),
+                        self.path.display()
+                    ),
+                    Err(err) => error!(
+                        "Failed to write the asset graph to {}: {err}",
+                        self.path.display()
+                    ),
+                }
+            })
+            .detach();
+    }
+}
+
+/// A [`Command`] that writes the files of an asset source that aren't used by any asset tracked
+/// by the [`AssetServer`] to a JSON file, along with their sizes.
+///
+/// The report only accounts for the assets loaded when the command is applied, so it should be
+/// queued once every asset the app needs has been loaded. See
+/// [`AssetDependencyGraph::unused_files`](bevy_asset::graph::AssetDependencyGraph::unused_files).
+pub struct WriteUnusedAssetFiles {
+    /// The asset source whose files are checked.
+    pub source: AssetSourceId<'static>,
+    /// The path of the file to write the report to.
+    pub path: PathBuf,
+}
+
+impl Command for WriteUnusedAssetFiles {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let server = world.resource::<AssetServer>().clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let graph = server.dependency_graph().await;
+                let files = match graph.unused_files(&server, self.source.clone()).await {
+                    Ok(files) => files,
+                    Err(err) => {
+                        error!("Failed to list the unused files of {}: {err}", self.source);
+                        return;
+                    }
+                };
+                let json = match serde_json::to_string_pretty(&files) {
+                    Ok(json) => json,
+                    Err(err) => {
+                        error!("Failed to serialize the unused asset files: {err}");
+                        return;
+                    }
+                };
+                match std::fs::write(&self.path, json) {
+                    Ok(()) => info!(
+                        "Found {} unused files taking {} bytes in {}, written to {}",
+                        files.len(),
+                        files.iter().map(|file| file.size).sum::<u64>(),
+                        self.source,
+                        self.path.display()
+                    ),
+                    Err(err) => error!(
+                        "Failed to write the unused asset files to {}: {err}",
+                        self.path.display()
+                    ),
+                }
+            })
+            .detach();
+    }
+}
@@ -0,0 +1,626 @@
+//! Tools for recording the input an app receives and replaying it deterministically.
+//!
+//! [`InputRecordingPlugin`] captures every input message that [`InputPlugin`] processes, together
+//! with the frame it arrived on and the [`Time<Real>`] delta of that frame, into an
+//! [`InputRecording`]. Recordings can be written to and read from [`ron`] files, which makes them
+//! useful as bug report repro files.
+//!
+//! [`InputPlaybackPlugin`] feeds a recording back into an app. Each frame's delta is applied through
+//! [`TimeUpdateStrategy::ManualDuration`], so a headless app replaying a recording sees the same
+//! inputs on the same frames with the same timing as the app that recorded it. This can be used to
+//! drive automated gameplay tests.
+//!
+//! [`InputPlugin`]: bevy_input::InputPlugin
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy_app::{prelude::*, AppExit};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_input::{
+    gamepad::{
+        GamepadConnectionEvent, RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent,
+        RawGamepadEvent,
+    },
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    InputSystems,
+};
+use bevy_time::{Real, Time, TimeSystems, TimeUpdateStrategy};
+use bevy_window::{CursorMoved, PrimaryWindow};
+use core::time::Duration;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{error, info};
+
+/// A single input message captured by an [`InputRecorder`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedInput {
+    /// A [`KeyboardInput`] message.
+    Keyboard(KeyboardInput),
+    /// A [`MouseButtonInput`] message.
+    MouseButton(MouseButtonInput),
+    /// A [`MouseMotion`] message.
+    MouseMotion(MouseMotion),
+    /// A [`MouseWheel`] message.
+    MouseWheel(MouseWheel),
+    /// A [`TouchInput`] message.
+    Touch(TouchInput),
+    /// A [`RawGamepadEvent`] message.
+    Gamepad(RawGamepadEvent),
+    /// A [`CursorMoved`] message.
+    CursorMoved(CursorMoved),
+}
+
+/// The inputs received during one frame of an [`InputRecording`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// The frame these inputs were received on, counted from the start of the recording.
+    pub frame: u32,
+    /// The [`Time<Real>`] delta of this frame.
+    pub delta: Duration,
+    /// The inputs received during this frame, in the order they were received.
+    pub inputs: Vec<RecordedInput>,
+}
+
+/// A sequence of recorded frames that can be saved to disk and replayed with an [`InputPlayer`].
+///
+/// Frames are stored in order. Frames without any input are kept so that their delta is replayed
+/// too, but a recording that skips frames is still valid: missing frames replay with no input and
+/// the delta of the previous frame.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputRecording {
+    /// The recorded frames, ordered by [`RecordedFrame::frame`].
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    /// Returns the number of frames this recording spans.
+    pub fn len(&self) -> u32 {
+        self.frames.last().map_or(0, |frame| frame.frame + 1)
+    }
+
+    /// Returns `true` if this recording contains no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Serializes this recording to a [`ron`] string.
+    pub fn to_ron(&self) -> Result<String, InputRecordingError> {
+        Ok(ron::ser::to_string_pretty(
+            self,
+            PrettyConfig::default().new_line("\n"),
+        )?)
+    }
+
+    /// Deserializes a recording from a [`ron`] string.
+    pub fn from_ron(ron: &str) -> Result<Self, InputRecordingError> {
+        Ok(ron::from_str(ron)?)
+    }
+
+    /// Writes this recording to the file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), InputRecordingError> {
+        fs::write(path, self.to_ron()?)?;
+        Ok(())
+    }
+
+    /// Reads a recording from the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, InputRecordingError> {
+        Self::from_ron(&fs::read_to_string(path)?)
+    }
+}
+
+/// An error that occurs when saving or loading an [`InputRecording`].
+#[derive(Error, Debug)]
+pub enum InputRecordingError {
+    /// The recording file could not be read or written.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The recording could not be serialized.
+    #[error("failed to serialize input recording: {0}")]
+    Serialize(#[from] ron::Error),
+    /// The recording file is not a valid recording.
+    #[error("failed to parse input recording: {0}")]
+    Deserialize(#[from] ron::error::SpannedError),
+}
+
+/// Records the input messages an app receives while it is recording.
+///
+/// Added by [`InputRecordingPlugin`].
+#[derive(Resource, Debug, Default)]
+pub struct InputRecorder {
+    recording: Option<InputRecording>,
+    frame: u32,
+}
+
+impl InputRecorder {
+    /// Starts a new recording, discarding any recording in progress.
+    pub fn start(&mut self) {
+        self.recording = Some(InputRecording::default());
+        self.frame = 0;
+    }
+
+    /// Stops recording and returns what was recorded, if a recording was in progress.
+    pub fn stop(&mut self) -> Option<InputRecording> {
+        self.recording.take()
+    }
+
+    /// Returns `true` if a recording is in progress.
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Returns the recording in progress, if any.
+    pub fn recording(&self) -> Option<&InputRecording> {
+        self.recording.as_ref()
+    }
+}
+
+/// Plugin that records the input messages processed by [`InputPlugin`](bevy_input::InputPlugin).
+///
+/// When [`path`](Self::path) is set, recording starts with the app and the recording is written to
+/// that file when an [`AppExit`] message is sent. Otherwise, use [`InputRecorder::start`] and
+/// [`InputRecorder::stop`] to control recording manually.
+#[derive(Debug, Clone, Default)]
+pub struct InputRecordingPlugin {
+    /// The file the recording is saved to when the app exits.
+    pub path: Option<PathBuf>,
+}
+
+impl Plugin for InputRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        add_input_messages(app);
+
+        let mut recorder = InputRecorder::default();
+        if let Some(path) = self.path.clone() {
+            recorder.start();
+            app.insert_resource(RecordingPath(path))
+                .add_systems(Last, save_recording_on_exit);
+        }
+
+        app.insert_resource(recorder).add_systems(
+            PreUpdate,
+            record_inputs
+                .in_set(InputRecordingSystems)
+                .before(InputSystems),
+        );
+    }
+}
+
+/// Plugin that replays an [`InputRecording`] into an app, one recorded frame per app update.
+///
+/// Each frame's recorded delta is applied with [`TimeUpdateStrategy::ManualDuration`]. Recorded
+/// window entities are mapped to the [`PrimaryWindow`] if there is one, and every recorded gamepad
+/// is mapped to a new entity, so recordings can be replayed into a different app, including a
+/// headless one. Once the recording is exhausted, time keeps advancing by the last recorded delta;
+/// use [`InputPlayer::is_finished`] to detect the end of playback.
+#[derive(Debug, Clone)]
+pub struct InputPlaybackPlugin {
+    /// The recording to replay.
+    pub recording: InputRecording,
+}
+
+impl InputPlaybackPlugin {
+    /// Creates a plugin that replays `recording`.
+    pub fn new(recording: InputRecording) -> Self {
+        Self { recording }
+    }
+}
+
+impl Plugin for InputPlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        add_input_messages(app);
+
+        app.insert_resource(InputPlayer::new(self.recording.clone()))
+            .add_systems(
+                First,
+                update_playback_time
+                    .in_set(InputRecordingSystems)
+                    .before(TimeSystems),
+            )
+            .add_systems(
+                PreUpdate,
+                play_inputs
+                    .in_set(InputRecordingSystems)
+                    .before(InputSystems),
+            );
+    }
+}
+
+/// System set for the systems added by [`InputRecordingPlugin`] and [`InputPlaybackPlugin`].
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputRecordingSystems;
+
+/// Replays an [`InputRecording`] into the app.
+///
+/// Added by [`InputPlaybackPlugin`].
+#[derive(Resource, Debug)]
+pub struct InputPlayer {
+    recording: InputRecording,
+    frame: u32,
+    next: usize,
+    gamepads: EntityHashMap<Entity>,
+}
+
+impl InputPlayer {
+    /// Creates a player that starts replaying `recording` from its first frame.
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            frame: 0,
+            next: 0,
+            gamepads: EntityHashMap::default(),
+        }
+    }
+
+    /// Returns the recording being replayed.
+    pub fn recording(&self) -> &InputRecording {
+        &self.recording
+    }
+
+    /// Returns the number of frames replayed so far.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Returns `true` once every recorded frame has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.frames.len()
+    }
+
+    /// Restarts playback from the first frame of the recording.
+    ///
+    /// Gamepads keep the entities they were mapped to.
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.next = 0;
+    }
+
+    fn current_frame(&self) -> Option<&RecordedFrame> {
+        self.recording
+            .frames
+            .get(self.next)
+            .filter(|frame| frame.frame == self.frame)
+    }
+}
+
+#[derive(Resource)]
+struct RecordingPath(PathBuf);
+
+fn add_input_messages(app: &mut App) {
+    app.add_message::<KeyboardInput>()
+        .add_message::<MouseButtonInput>()
+        .add_message::<MouseMotion>()
+        .add_message::<MouseWheel>()
+        .add_message::<TouchInput>()
+        .add_message::<RawGamepadEvent>()
+        .add_message::<CursorMoved>();
+}
+
+fn record_inputs(
+    mut recorder: ResMut<InputRecorder>,
+    time: Res<Time<Real>>,
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut mouse_buttons: MessageReader<MouseButtonInput>,
+    mut mouse_motion: MessageReader<MouseMotion>,
+    mut mouse_wheel: MessageReader<MouseWheel>,
+    mut touches: MessageReader<TouchInput>,
+    mut gamepads: MessageReader<RawGamepadEvent>,
+    mut cursor_moved: MessageReader<CursorMoved>,
+) {
+    let InputRecorder { recording, frame } = &mut *recorder;
+    let Some(recording) = recording else {
+        // Drain the readers so that a recording started later doesn't pick up stale messages.
+        keyboard.clear();
+        mouse_buttons.clear();
+        mouse_motion.clear();
+        mouse_wheel.clear();
+        touches.clear();
+        gamepads.clear();
+        cursor_moved.clear();
+        return;
+    };
+
+    let mut inputs = Vec::new();
+    inputs.extend(keyboard.read().cloned().map(RecordedInput::Keyboard));
+    inputs.extend(
+        mouse_buttons
+            .read()
+            .cloned()
+            .map(RecordedInput::MouseButton),
+    );
+    inputs.extend(mouse_motion.read().cloned().map(RecordedInput::MouseMotion));
+    inputs.extend(mouse_wheel.read().cloned().map(RecordedInput::MouseWheel));
+    inputs.extend(touches.read().cloned().map(RecordedInput::Touch));
+    inputs.extend(gamepads.read().cloned().map(RecordedInput::Gamepad));
+    inputs.extend(cursor_moved.read().cloned().map(RecordedInput::CursorMoved));
+
+    recording.frames.push(RecordedFrame {
+        frame: *frame,
+        delta: time.delta(),
+        inputs,
+    });
+    *frame += 1;
+}
+
+fn save_recording_on_exit(
+    mut exit: MessageReader<AppExit>,
+    mut recorder: ResMut<InputRecorder>,
+    path: Res<RecordingPath>,
+) {
+    if exit.read().last().is_none() {
+        return;
+    }
+    let Some(recording) = recorder.stop() else {
+        return;
+    };
+    match recording.save(&path.0) {
+        Ok(()) => info!("Saved input recording to {}", path.0.display()),
+        Err(err) => error!(
+            "Failed to save input recording to {}: {err}",
+            path.0.display()
+        ),
+    }
+}
+
+fn update_playback_time(player: Res<InputPlayer>, mut strategy: ResMut<TimeUpdateStrategy>) {
+    if let Some(frame) = player.current_frame() {
+        *strategy = TimeUpdateStrategy::ManualDuration(frame.delta);
+    }
+}
+
+fn play_inputs(world: &mut World) {
+    let window = world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .iter(world)
+        .next()
+        .unwrap_or(Entity::PLACEHOLDER);
+
+    world.resource_scope(|world, mut player: Mut<InputPlayer>| {
+        let InputPlayer {
+            recording,
+            frame,
+            next,
+            gamepads,
+        } = &mut *player;
+
+        if let Some(recorded) = recording.frames.get(*next)
+            && recorded.frame == *frame
+        {
+            for input in &recorded.inputs {
+                let mut gamepad = |world: &mut World, entity| {
+                    *gamepads
+                        .entry(entity)
+                        .or_insert_with(|| world.spawn_empty().id())
+                };
+                match input.clone() {
+                    RecordedInput::Keyboard(input) => {
+                        world.write_message(KeyboardInput { window, ..input });
+                    }
+                    RecordedInput::MouseButton(input) => {
+                        world.write_message(MouseButtonInput { window, ..input });
+                    }
+                    RecordedInput::MouseMotion(input) => {
+                        world.write_message(input);
+                    }
+                    RecordedInput::MouseWheel(input) => {
+                        world.write_message(MouseWheel { window, ..input });
+                    }
+                    RecordedInput::Touch(input) => {
+                        world.write_message(TouchInput { window, ..input });
+                    }
+                    // Gamepad backends write the typed messages alongside `RawGamepadEvent`,
+                    // and `InputPlugin` relies on `GamepadConnectionEvent` to set up gamepads.
+                    RecordedInput::Gamepad(RawGamepadEvent::Connection(event)) => {
+                        let event = GamepadConnectionEvent {
+                            gamepad: gamepad(world, event.gamepad),
+                            ..event
+                        };
+                        world.write_message(RawGamepadEvent::from(event.clone()));
+                        world.write_message(event);
+                    }
+                    RecordedInput::Gamepad(RawGamepadEvent::Button(event)) => {
+                        let event = RawGamepadButtonChangedEvent {
+                            gamepad: gamepad(world, event.gamepad),
+                            ..event
+                        };
+                        world.write_message(RawGamepadEvent::from(event));
+                        world.write_message(event);
+                    }
+                    RecordedInput::Gamepad(RawGamepadEvent::Axis(event)) => {
+                        let event = RawGamepadAxisChangedEvent {
+                            gamepad: gamepad(world, event.gamepad),
+                            ..event
+                        };
+                        world.write_message(RawGamepadEvent::from(event));
+                        world.write_message(event);
+                    }
+                    RecordedInput::CursorMoved(input) => {
+                        world.write_message(CursorMoved { window, ..input });
+                    }
+                }
+            }
+            *next += 1;
+        }
+        *frame += 1;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_input::{
+        gamepad::{Gamepad, GamepadButton, GamepadConnection},
+        keyboard::{Key, KeyCode},
+        ButtonInput, ButtonState, InputPlugin,
+    };
+    use bevy_time::TimePlugin;
+
+    fn key(key_code: KeyCode, state: ButtonState) -> RecordedInput {
+        RecordedInput::Keyboard(KeyboardInput {
+            key_code,
+            logical_key: Key::Space,
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        })
+    }
+
+    fn record(frames: impl IntoIterator<Item = Vec<RecordedInput>>) -> InputRecording {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin, InputRecordingPlugin::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                16,
+            )));
+        app.world_mut().resource_mut::<InputRecorder>().start();
+
+        for inputs in frames {
+            for input in inputs {
+                let RecordedInput::Keyboard(input) = input else {
+                    unreachable!();
+                };
+                app.world_mut().write_message(input);
+            }
+            app.update();
+        }
+
+        app.world_mut()
+            .resource_mut::<InputRecorder>()
+            .stop()
+            .unwrap()
+    }
+
+    fn playback_app(recording: InputRecording) -> App {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin, InputPlaybackPlugin::new(recording)));
+        app
+    }
+
+    #[test]
+    fn recorded_frames_round_trip_through_ron() {
+        let recording = record([
+            vec![],
+            vec![key(KeyCode::Space, ButtonState::Pressed)],
+            vec![key(KeyCode::Space, ButtonState::Released)],
+        ]);
+
+        assert_eq!(recording.len(), 3);
+        assert!(recording.frames[0].inputs.is_empty());
+        assert_eq!(
+            recording.frames[1].inputs,
+            [key(KeyCode::Space, ButtonState::Pressed)]
+        );
+
+        let ron = recording.to_ron().unwrap();
+        assert_eq!(InputRecording::from_ron(&ron).unwrap(), recording);
+    }
+
+    #[test]
+    fn playback_replays_inputs_on_the_recorded_frames() {
+        let recording = InputRecording {
+            frames: vec![
+                RecordedFrame {
+                    frame: 0,
+                    delta: Duration::ZERO,
+                    inputs: vec![],
+                },
+                RecordedFrame {
+                    frame: 1,
+                    delta: Duration::from_millis(10),
+                    inputs: vec![key(KeyCode::KeyA, ButtonState::Pressed)],
+                },
+                RecordedFrame {
+                    frame: 3,
+                    delta: Duration::from_millis(30),
+                    inputs: vec![key(KeyCode::KeyA, ButtonState::Released)],
+                },
+            ],
+        };
+        let mut app = playback_app(recording);
+
+        app.update();
+        assert!(!app
+            .world()
+            .resource::<ButtonInput<KeyCode>>()
+            .pressed(KeyCode::KeyA));
+
+        app.update();
+        let keys = app.world().resource::<ButtonInput<KeyCode>>();
+        assert!(keys.just_pressed(KeyCode::KeyA));
+        assert_eq!(
+            app.world().resource::<Time<Real>>().delta(),
+            Duration::from_millis(10)
+        );
+
+        // Frame 2 was not recorded, so it replays no input with the previous delta.
+        app.update();
+        assert!(app
+            .world()
+            .resource::<ButtonInput<KeyCode>>()
+            .pressed(KeyCode::KeyA));
+        assert_eq!(
+            app.world().resource::<Time<Real>>().delta(),
+            Duration::from_millis(10)
+        );
+        assert!(!app.world().resource::<InputPlayer>().is_finished());
+
+        app.update();
+        assert!(app
+            .world()
+            .resource::<ButtonInput<KeyCode>>()
+            .just_released(KeyCode::KeyA));
+        assert_eq!(
+            app.world().resource::<Time<Real>>().delta(),
+            Duration::from_millis(30)
+        );
+        assert!(app.world().resource::<InputPlayer>().is_finished());
+    }
+
+    #[test]
+    fn playback_maps_recorded_gamepads_to_new_entities() {
+        let recorded_gamepad = Entity::from_raw_u32(1234).unwrap();
+        let recording = InputRecording {
+            frames: vec![
+                RecordedFrame {
+                    frame: 0,
+                    delta: Duration::ZERO,
+                    inputs: vec![RecordedInput::Gamepad(RawGamepadEvent::Connection(
+                        GamepadConnectionEvent::new(
+                            recorded_gamepad,
+                            GamepadConnection::Connected {
+                                name: "Recorded".into(),
+                                vendor_id: None,
+                                product_id: None,
+                            },
+                        ),
+                    ))],
+                },
+                RecordedFrame {
+                    frame: 2,
+                    delta: Duration::ZERO,
+                    inputs: vec![RecordedInput::Gamepad(RawGamepadEvent::Button(
+                        RawGamepadButtonChangedEvent::new(
+                            recorded_gamepad,
+                            GamepadButton::South,
+                            1.0,
+                        ),
+                    ))],
+                },
+            ],
+        };
+        let mut app = playback_app(recording);
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let mut gamepads = app.world_mut().query::<(Entity, &Gamepad)>();
+        let (entity, gamepad) = gamepads.single(app.world()).unwrap();
+        assert_ne!(entity, recorded_gamepad);
+        assert!(gamepad.pressed(GamepadButton::South));
+    }
+}
@@ -13,6 +13,9 @@ extern crate alloc;
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
 
+#[cfg(feature = "asset_graph")]
+pub mod asset_graph;
+
 pub mod diagnostics_overlay;
 mod easy_screenshot;
 pub mod fps_overlay;
@@ -20,6 +23,9 @@ pub mod frame_time_graph;
 
 pub mod inspection;
 
+#[cfg(feature = "input_recording")]
+pub mod input_recording;
+
 pub mod picking_debug;
 
 #[cfg(feature = "schedule_data")]
@@ -47,6 +47,8 @@ pub struct DeriveComponent {
     pub immutable: bool,
     /// Whether or not this component tracks a summary tick.
     pub summary_tick: bool,
+    /// Whether or not this component is looked up by value through a `ComponentIndex`.
+    pub index: bool,
     /// The clone behavior for this component.
     pub clone_behavior: Option<Expr>,
     /// The `map_entities` attribute information.
@@ -70,6 +72,7 @@ impl DeriveComponent {
             relationship_target: None,
             immutable: false,
             summary_tick: false,
+            index: false,
             clone_behavior: None,
             map_entities: None,
             additional_requires: Vec::new(),
@@ -123,6 +126,9 @@ impl DeriveComponent {
                     } else if nested.path.is_ident(SUMMARY_TICK) {
                         attrs.summary_tick = true;
                         Ok(())
+                    } else if nested.path.is_ident(INDEX) {
+                        attrs.index = true;
+                        Ok(())
                     } else if nested.path.is_ident(CLONE_BEHAVIOR) {
                         attrs.clone_behavior = Some(nested.value()?.parse()?);
                         Ok(())
@@ -166,6 +172,13 @@ impl DeriveComponent {
             ));
         }
 
+        if attrs.index && !attrs.immutable {
+            return Err(syn::Error::new(
+                ast.span(),
+                "Indexed components must be immutable, please add `#[component(immutable)]`",
+            ));
+        }
+
         if attrs.summary_tick && matches!(attrs.storage, Some(StorageTy::SparseSet)) {
             return Err(syn::Error::new(
                 ast.span(),
@@ -250,14 +263,25 @@ impl DeriveComponent {
         let mut on_despawn_path =
             Vec::from_iter(self.on_despawn.map(|path| path.to_token_stream(bevy_ecs)));
 
+        let is_many = self.relationship.as_ref().is_some_and(|r| r.many)
+            || self.relationship_target.as_ref().is_some_and(|t| t.many);
+
         if relationship.is_some() {
-            on_insert_path.push(quote!(<Self as #bevy_ecs::relationship::Relationship>::on_insert));
-            on_discard_path
-                .push(quote!(<Self as #bevy_ecs::relationship::Relationship>::on_discard));
+            let relationship_trait = if is_many {
+                quote!(#bevy_ecs::relationship::ManyRelationship)
+            } else {
+                quote!(#bevy_ecs::relationship::Relationship)
+            };
+            on_insert_path.push(quote!(<Self as #relationship_trait>::on_insert));
+            on_discard_path.push(quote!(<Self as #relationship_trait>::on_discard));
         }
         if let Some(target) = self.relationship_target {
-            on_discard_path
-                .push(quote!(<Self as #bevy_ecs::relationship::RelationshipTarget>::on_discard));
+            let relationship_target_trait = if is_many {
+                quote!(#bevy_ecs::relationship::ManyRelationshipTarget)
+            } else {
+                quote!(#bevy_ecs::relationship::RelationshipTarget)
+            };
+            on_discard_path.push(quote!(<Self as #relationship_target_trait>::on_discard));
             if target.linked_spawn {
                 on_despawn_path.push(
                     quote!(<Self as #bevy_ecs::relationship::RelationshipTarget>::on_despawn),
@@ -265,6 +289,11 @@ impl DeriveComponent {
             }
         }
 
+        if self.index {
+            on_insert_path.push(quote!(#bevy_ecs::component::ComponentIndex::<Self>::on_insert));
+            on_discard_path.push(quote!(#bevy_ecs::component::ComponentIndex::<Self>::on_discard));
+        }
+
         let on_add = hook_register_function_call(bevy_ecs, quote! {on_add}, &on_add_path);
         let on_insert = hook_register_function_call(bevy_ecs, quote! {on_insert}, &on_insert_path);
         let on_discard =
@@ -311,7 +340,15 @@ impl DeriveComponent {
             .then_some(quote! { #bevy_ecs::component::Immutable })
             .unwrap_or(quote! { #bevy_ecs::component::Mutable });
 
-        let clone_behavior = if relationship_target.is_some() || relationship.is_some() {
+        let clone_behavior = if is_many && relationship_target.is_some() {
+            // Sources are re-linked by their `ManyRelationship` hooks when cloned
+            quote!(#bevy_ecs::component::ComponentCloneBehavior::Ignore)
+        } else if is_many {
+            quote!(
+                use #bevy_ecs::component::{DefaultCloneBehaviorBase, DefaultCloneBehaviorViaClone};
+                (&&&#bevy_ecs::component::DefaultCloneBehaviorSpecialization::<Self>::default()).default_clone_behavior()
+            )
+        } else if relationship_target.is_some() || relationship.is_some() {
             quote!(
                 use #bevy_ecs::relationship::{
                     RelationshipCloneBehaviorBase, RelationshipCloneBehaviorViaClone, RelationshipCloneBehaviorViaReflect,
@@ -328,7 +365,8 @@ impl DeriveComponent {
             )
         };
 
-        let relationship_accessor = if (relationship.is_some() || relationship_target.is_some())
+        let relationship_accessor = if !is_many
+            && (relationship.is_some() || relationship_target.is_some())
             && let Data::Struct(DataStruct {
                 fields,
                 struct_token,
@@ -427,6 +465,35 @@ impl DeriveComponent {
 
         let fqdefault = FQDefault.into_token_stream();
 
+        if relationship.many {
+            let collection = &field.ty;
+            return Ok(Some(quote! {
+                impl #impl_generics #bevy_ecs::relationship::ManyRelationship for #struct_name #type_generics #where_clause {
+                    type RelationshipTarget = #relationship_target;
+                    type Collection = #collection;
+                    const ALLOW_SELF_REFERENTIAL: bool = #allow_self_referential;
+
+                    #[inline]
+                    fn collection(&self) -> &Self::Collection {
+                        &self.#relationship_member
+                    }
+
+                    #[inline]
+                    fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+                        &mut self.#relationship_member
+                    }
+
+                    #[inline]
+                    fn from_collection_risky(collection: Self::Collection) -> Self {
+                        Self {
+                            #(#members: #fqdefault::default(),)*
+                            #relationship_member: collection
+                        }
+                    }
+                }
+            }));
+        }
+
         Ok(Some(quote! {
             impl #impl_generics #bevy_ecs::relationship::Relationship for #struct_name #type_generics #where_clause {
                 type RelationshipTarget = #relationship_target;
@@ -490,9 +557,17 @@ impl DeriveComponent {
         let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
         let linked_spawn = relationship_target.linked_spawn;
         let fqdefault = FQDefault.into_token_stream();
+        let (relationship_target_trait, linked_spawn) = if relationship_target.many {
+            (quote!(ManyRelationshipTarget), None)
+        } else {
+            (
+                quote!(RelationshipTarget),
+                Some(quote!(const LINKED_SPAWN: bool = #linked_spawn;)),
+            )
+        };
         Ok(Some(quote! {
-            impl #impl_generics #bevy_ecs::relationship::RelationshipTarget for #struct_name #type_generics #where_clause {
-                const LINKED_SPAWN: bool = #linked_spawn;
+            impl #impl_generics #bevy_ecs::relationship::#relationship_target_trait for #struct_name #type_generics #where_clause {
+                #linked_spawn
                 type Relationship = #relationship;
                 type Collection = #collection;
 
@@ -533,6 +608,7 @@ const ON_DESPAWN: &str = "on_despawn";
 
 const IMMUTABLE: &str = "immutable";
 const SUMMARY_TICK: &str = "summary_tick";
+const INDEX: &str = "index";
 const CLONE_BEHAVIOR: &str = "clone_behavior";
 
 /// All allowed attribute value expression kinds for component hooks.
@@ -614,12 +690,14 @@ pub struct Require {
 pub struct Relationship {
     relationship_target: Type,
     allow_self_referential: bool,
+    many: bool,
 }
 
 /// Derived `#[relationship_target]` attribute information.
 pub struct RelationshipTarget {
     relationship: Type,
     linked_spawn: bool,
+    many: bool,
 }
 
 // values for `storage` attribute
@@ -727,18 +805,23 @@ mod kw {
     syn::custom_keyword!(relationship);
     syn::custom_keyword!(linked_spawn);
     syn::custom_keyword!(allow_self_referential);
+    syn::custom_keyword!(many);
 }
 
 impl Parse for Relationship {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         let mut relationship_target: Option<Type> = None;
         let mut allow_self_referential: bool = false;
+        let mut many: bool = false;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(kw::allow_self_referential) {
                 input.parse::<kw::allow_self_referential>()?;
                 allow_self_referential = true;
+            } else if lookahead.peek(kw::many) {
+                input.parse::<kw::many>()?;
+                many = true;
             } else if lookahead.peek(kw::relationship_target) {
                 input.parse::<kw::relationship_target>()?;
                 input.parse::<Token![=]>()?;
@@ -755,6 +838,7 @@ impl Parse for Relationship {
                 syn::Error::new(input.span(), "Missing `relationship_target = X` attribute")
             })?,
             allow_self_referential,
+            many,
         })
     }
 }
@@ -763,12 +847,16 @@ impl Parse for RelationshipTarget {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         let mut relationship: Option<Type> = None;
         let mut linked_spawn: bool = false;
+        let mut many: bool = false;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(kw::linked_spawn) {
                 input.parse::<kw::linked_spawn>()?;
                 linked_spawn = true;
+            } else if lookahead.peek(kw::many) {
+                input.parse::<kw::many>()?;
+                many = true;
             } else if lookahead.peek(kw::relationship) {
                 input.parse::<kw::relationship>()?;
                 input.parse::<Token![=]>()?;
@@ -780,11 +868,18 @@ impl Parse for RelationshipTarget {
                 input.parse::<Token![,]>()?;
             }
         }
+        if many && linked_spawn {
+            return Err(syn::Error::new(
+                input.span(),
+                "`linked_spawn` is not supported for `many` relationships",
+            ));
+        }
         Ok(RelationshipTarget {
             relationship: relationship.ok_or_else(|| {
                 syn::Error::new(input.span(), "Missing `relationship = X` attribute")
             })?,
             linked_spawn,
+            many,
         })
     }
 }
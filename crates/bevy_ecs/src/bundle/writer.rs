@@ -176,6 +176,12 @@ impl<'a> BundleWriter<'a> {
     pub fn is_empty(&self) -> bool {
         self.0.component_ids.is_empty()
     }
+
+    /// Returns the [`ComponentId`]s of the components that are currently in the bundle, in the order they were pushed.
+    #[inline]
+    pub fn component_ids(&self) -> &[ComponentId] {
+        &self.0.component_ids
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,318 @@
+use bevy_platform::collections::HashMap;
+use core::{hash::Hash, ops::Deref};
+
+use crate::{
+    change_detection::{DetectChanges, Tick},
+    component::{Component, ComponentId, Immutable},
+    entity::{Entity, EntityHashSet},
+    lifecycle::HookContext,
+    query::FilteredAccessSet,
+    resource::Resource,
+    system::{ReadOnlySystemParam, Res, SystemMeta, SystemParam, SystemParamValidationError},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, FromWorld, World},
+};
+
+/// A [`Component`] that can be looked up by value through a [`ComponentIndex`].
+///
+/// This is implemented for every immutable [`Component`] that is [`Eq`], [`Hash`] and [`Clone`].
+/// [`Eq`] and [`Hash`] are what values are looked up by, and the other two bounds are required to keep
+/// the index correct:
+/// - [`Immutable`]: the index is maintained by component hooks, which only run when a component is
+///   inserted or removed. Mutating a component in place (through `&mut C` or `Mut<C>`) would change its
+///   value without running any hook, leaving the entity under its old value in the index. Immutable
+///   components can only change by being re-inserted, which runs `on_discard` for the old value and
+///   `on_insert` for the new one.
+/// - [`Clone`]: the index owns a copy of every distinct value as the key of its map, since the
+///   component itself lives in the entity's storage and can be removed at any time.
+///
+/// To maintain the index, the component must also be derived with the `#[component(index)]` attribute:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component, PartialEq, Eq, Hash, Clone)]
+/// #[component(immutable, index)]
+/// struct Team(u32);
+/// ```
+pub trait IndexedComponent: Component<Mutability = Immutable> + Eq + Hash + Clone {}
+
+impl<C: Component<Mutability = Immutable> + Eq + Hash + Clone> IndexedComponent for C {}
+
+/// A [`Resource`] mapping each value of the [`IndexedComponent`] `C` to the entities that currently hold it.
+///
+/// The index is built from the existing entities when it is first initialized (for example by a system using
+/// [`Indexed`]) and then maintained by the `on_insert` and `on_discard` hooks of `C`, so it stays correct as
+/// components are inserted, replaced, removed and despawned.
+#[derive(Resource)]
+pub struct ComponentIndex<C: IndexedComponent> {
+    entities: HashMap<C, EntityHashSet>,
+}
+
+impl<C: IndexedComponent> FromWorld for ComponentIndex<C> {
+    fn from_world(world: &mut World) -> Self {
+        let mut index = Self {
+            entities: HashMap::default(),
+        };
+        let Some(component_id) = world.component_id::<C>() else {
+            return index;
+        };
+        // Walk the archetypes directly so that disabled entities are indexed as well, matching the hooks.
+        for archetype in world.archetypes().iter() {
+            if !archetype.contains(component_id) {
+                continue;
+            }
+            for archetype_entity in archetype.entities() {
+                let entity = archetype_entity.id();
+                if let Some(value) = world.get::<C>(entity) {
+                    index.insert(value.clone(), entity);
+                }
+            }
+        }
+        index
+    }
+}
+
+impl<C: IndexedComponent> ComponentIndex<C> {
+    /// Returns the entities whose `C` component is equal to `value`.
+    pub fn get(&self, value: &C) -> impl Iterator<Item = Entity> + '_ {
+        self.entities
+            .get(value)
+            .into_iter()
+            .flat_map(|entities| entities.iter().copied())
+    }
+
+    /// Returns the entity whose `C` component is equal to `value`, if exactly one such entity exists.
+    pub fn get_single(&self, value: &C) -> Option<Entity> {
+        let entities = self.entities.get(value)?;
+        if entities.len() == 1 {
+            entities.iter().next().copied()
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if at least one entity has a `C` component equal to `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns the number of entities whose `C` component is equal to `value`.
+    pub fn count(&self, value: &C) -> usize {
+        self.entities
+            .get(value)
+            .map_or(0, |entities| entities.len())
+    }
+
+    /// Iterates every distinct value of `C` along with the entities that hold it.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &EntityHashSet)> {
+        self.entities.iter()
+    }
+
+    /// Returns the number of distinct values of `C`.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity has a `C` component.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn insert(&mut self, value: C, entity: Entity) {
+        self.entities.entry(value).or_default().insert(entity);
+    }
+
+    fn remove(&mut self, value: &C, entity: Entity) {
+        if let Some(entities) = self.entities.get_mut(value) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                self.entities.remove(value);
+            }
+        }
+    }
+
+    /// The `on_insert` component hook that adds the entity to the index.
+    pub fn on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        if let Some(mut index) = world.get_resource_mut::<Self>() {
+            index.insert(value, entity);
+        }
+    }
+
+    /// The `on_discard` component hook that removes the entity from the index.
+    // note: think of this as "on_drop"
+    pub fn on_discard(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        if let Some(mut index) = world.get_resource_mut::<Self>() {
+            index.remove(&value, entity);
+        }
+    }
+}
+
+/// A [`SystemParam`] that looks up entities by the value of their [`IndexedComponent`] `C` in constant time.
+///
+/// The [`ComponentIndex`] resource is initialized from the existing entities when the system is first
+/// initialized, so no additional setup is needed beyond deriving `C` with `#[component(immutable, index)]`.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::component::Indexed;
+/// #[derive(Component, PartialEq, Eq, Hash, Clone)]
+/// #[component(immutable, index)]
+/// struct Team(u32);
+///
+/// fn count_team_three(teams: Indexed<Team>) {
+///     let members = teams.count(&Team(3));
+///     // ...
+/// }
+/// # bevy_ecs::system::assert_is_system(count_team_three);
+/// ```
+///
+/// Like [`Res`], [`Indexed::is_changed`] reports whether the index was modified since the system last ran.
+pub struct Indexed<'w, C: IndexedComponent> {
+    index: Res<'w, ComponentIndex<C>>,
+}
+
+impl<'w, C: IndexedComponent> Indexed<'w, C> {
+    /// Returns `true` if any entity was added to or removed from the index since the system last ran.
+    pub fn is_changed(&self) -> bool {
+        self.index.is_changed()
+    }
+
+    /// Returns the underlying [`ComponentIndex`] with the lifetime of the world borrow.
+    pub fn into_inner(self) -> &'w ComponentIndex<C> {
+        self.index.into_inner()
+    }
+}
+
+impl<'w, C: IndexedComponent> Deref for Indexed<'w, C> {
+    type Target = ComponentIndex<C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+// SAFETY: Indexed only reads the `ComponentIndex<C>` resource.
+unsafe impl<'a, C: IndexedComponent> ReadOnlySystemParam for Indexed<'a, C> {}
+
+// SAFETY: Access is registered by the inner `Res` param.
+unsafe impl<'a, C: IndexedComponent> SystemParam for Indexed<'a, C> {
+    type State = ComponentId;
+    type Item<'w, 's> = Indexed<'w, C>;
+
+    fn init_state(world: &mut World) -> Self::State {
+        world.init_resource::<ComponentIndex<C>>();
+        <Res<ComponentIndex<C>> as SystemParam>::init_state(world)
+    }
+
+    fn init_access(
+        state: &Self::State,
+        system_meta: &mut SystemMeta,
+        component_access_set: &mut FilteredAccessSet,
+        world: &mut World,
+    ) {
+        <Res<ComponentIndex<C>> as SystemParam>::init_access(
+            state,
+            system_meta,
+            component_access_set,
+            world,
+        );
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Result<Self::Item<'w, 's>, SystemParamValidationError> {
+        // SAFETY: Upheld by the caller.
+        let index = unsafe {
+            <Res<ComponentIndex<C>> as SystemParam>::get_param(
+                state,
+                system_meta,
+                world,
+                change_tick,
+            )
+        }?;
+        Ok(Indexed { index })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::{Component, ComponentIndex, Indexed},
+        entity::Entity,
+        entity_disabling::Disabled,
+        system::{IntoSystem, RunSystemOnce, System},
+        world::World,
+    };
+    use alloc::vec::Vec;
+
+    #[derive(Component, PartialEq, Eq, Hash, Clone, Debug)]
+    #[component(immutable, index)]
+    struct Team(u32);
+
+    fn sorted<const N: usize>(mut entities: [Entity; N]) -> [Entity; N] {
+        entities.sort();
+        entities
+    }
+
+    fn team(world: &mut World, team: u32) -> Vec<Entity> {
+        world
+            .run_system_once(move |teams: Indexed<Team>| {
+                let mut entities = teams.get(&Team(team)).collect::<Vec<_>>();
+                entities.sort();
+                entities
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn index_tracks_insert_replace_and_despawn() {
+        let mut world = World::new();
+        // Spawned before the index exists, and picked up when it is initialized
+        let a = world.spawn(Team(1)).id();
+        let disabled = world.spawn((Team(1), Disabled)).id();
+        assert_eq!(team(&mut world, 1), sorted([a, disabled]));
+
+        let b = world.spawn(Team(1)).id();
+        let c = world.spawn(Team(2)).id();
+        assert_eq!(team(&mut world, 1), sorted([a, disabled, b]));
+        assert_eq!(team(&mut world, 2), [c]);
+
+        world.entity_mut(b).insert(Team(2));
+        assert_eq!(team(&mut world, 1), sorted([a, disabled]));
+        assert_eq!(team(&mut world, 2), sorted([b, c]));
+
+        world.entity_mut(a).remove::<Team>();
+        world.despawn(disabled);
+        world.despawn(c);
+        assert_eq!(team(&mut world, 1), []);
+        assert_eq!(team(&mut world, 2), [b]);
+
+        let index = world.resource::<ComponentIndex<Team>>();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.get_single(&Team(2)), Some(b));
+        assert!(!index.contains(&Team(1)));
+    }
+
+    #[test]
+    fn index_change_detection() {
+        let mut world = World::new();
+        world.spawn(Team(1));
+
+        let mut system = IntoSystem::into_system(|teams: Indexed<Team>| teams.is_changed());
+        system.initialize(&mut world);
+        assert!(system.run((), &mut world).unwrap());
+        assert!(!system.run((), &mut world).unwrap());
+
+        world.spawn(Team(2));
+        assert!(system.run((), &mut world).unwrap());
+    }
+}
@@ -2,12 +2,14 @@
 
 mod clone;
 mod constants;
+mod index;
 mod info;
 mod register;
 mod required;
 
 pub use clone::*;
 pub use constants::*;
+pub use index::*;
 pub use info::*;
 pub use register::*;
 pub use required::*;
@@ -71,6 +73,9 @@ use core::{fmt::Debug, marker::PhantomData, ops::Deref};
 /// See the documentation for [`ComponentMutability`] for more details around this
 /// feature.
 ///
+/// Immutable components can also be looked up by value by adding `index` to the attribute, as in
+/// `#[component(immutable, index)]`. See [`Indexed`] for more details.
+///
 /// See the [`entity`] module level documentation to learn how to add or remove components from an entity.
 ///
 /// See the documentation for [`Query`] to learn how to access component data from a system.
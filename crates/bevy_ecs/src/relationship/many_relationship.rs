@@ -0,0 +1,515 @@
+use alloc::{format, vec::Vec};
+use bevy_utils::prelude::DebugName;
+use log::warn;
+
+use crate::{
+    change_detection::MaybeLocation,
+    component::{Component, Mutable},
+    entity::{hash_set::EntityHashSet, Entity},
+    lifecycle::HookContext,
+    relationship::{RelationshipHookMode, RelationshipSourceCollection},
+    system::EntityCommand,
+    world::{DeferredWorld, EntityWorldMut, World},
+};
+
+/// A [`Component`] on a "source" [`Entity`] that references any number of "target" entities, creating a many-to-many "relationship"
+/// between them. Every [`ManyRelationship`] has a corresponding [`ManyRelationshipTarget`] type (and vice-versa), which exists on each
+/// "target" entity and contains the list of all "source" entities that relate to it.
+///
+/// This is the many-to-many counterpart of [`Relationship`](crate::relationship::Relationship). Like [`Relationship`], the
+/// [`ManyRelationship`] component is the "source of truth" and the [`ManyRelationshipTarget`] component reflects that source of truth.
+/// When a [`ManyRelationship`] component is inserted on an [`Entity`], the source entity is added to the [`ManyRelationshipTarget`] of
+/// every target entity (inserting it if it does not exist). When it is removed (or the source entity is despawned), the source entity is
+/// removed from the [`ManyRelationshipTarget`] of every target entity. When a [`ManyRelationshipTarget`] is removed (or the target entity is
+/// despawned), the target entity is removed from every source entity's [`ManyRelationship`].
+///
+/// [`ManyRelationship`] and [`ManyRelationshipTarget`] should always be derived via the [`Component`] trait (using the `many` attribute)
+/// to ensure the hooks are set up properly:
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// # use bevy_ecs::entity::Entity;
+/// #[derive(Component)]
+/// #[relationship(relationship_target = GroupMembers, many)]
+/// pub struct MemberOf(pub Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = MemberOf, many)]
+/// pub struct GroupMembers(Vec<Entity>);
+/// ```
+///
+/// [`ManyRelationship`] components are immutable. Use [`EntityWorldMut::add_relationship_targets`] and
+/// [`EntityWorldMut::remove_relationship_targets`] to change the targets of an existing relationship.
+///
+/// [`Relationship`]: crate::relationship::Relationship
+pub trait ManyRelationship: Component + Sized {
+    /// The [`Component`] added to the "target" entities of this [`ManyRelationship`], which contains the list of all "source"
+    /// entities that relate to the "target".
+    type RelationshipTarget: ManyRelationshipTarget<Relationship = Self>;
+
+    /// The collection type that stores the "target" entities for this [`ManyRelationship`] component.
+    type Collection: RelationshipSourceCollection;
+
+    /// If `true`, a relationship is allowed to point to its own entity.
+    ///
+    /// See [`Relationship::ALLOW_SELF_REFERENTIAL`](crate::relationship::Relationship::ALLOW_SELF_REFERENTIAL).
+    const ALLOW_SELF_REFERENTIAL: bool = false;
+
+    /// Returns a reference to the stored [`ManyRelationship::Collection`] of target entities.
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`ManyRelationship::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyRelationship`] from the given [`ManyRelationship::Collection`] of target entities.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// Creates this [`ManyRelationship`] targeting the given entities.
+    fn from_targets(targets: impl IntoIterator<Item = Entity>) -> Self {
+        let mut collection = <Self::Collection as RelationshipSourceCollection>::new();
+        collection.extend_from_iter(targets);
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the target entities stored in this relationship.
+    #[inline]
+    fn iter(&self) -> ManyTargetIter<'_, Self> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of target entities.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if there are no target entities.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+
+    /// The `on_insert` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    fn on_insert(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            caller,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        if let RelationshipHookMode::Skip = relationship_hook_mode {
+            return;
+        }
+        let relationship = world.entity(entity).get::<Self>().unwrap();
+        let mut targets = EntityHashSet::with_capacity(relationship.len());
+        let mut ordered_targets = Vec::with_capacity(relationship.len());
+        for target in relationship.iter() {
+            if targets.insert(target) {
+                ordered_targets.push(target);
+            }
+        }
+        if ordered_targets.len() != relationship.len() {
+            // Matches `EntityWorldMut::add_relationship_targets`, which never adds a target twice
+            world
+                .commands()
+                .queue_silenced(deduplicate_targets::<Self>(entity, ordered_targets.clone()));
+        }
+        for target in ordered_targets {
+            link_target::<Self>(&mut world, entity, target, caller);
+        }
+    }
+
+    /// The `on_discard` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_discard(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        if let RelationshipHookMode::Skip = relationship_hook_mode {
+            return;
+        }
+        let targets = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .iter()
+            .collect::<EntityHashSet>();
+        for target in targets {
+            unlink_target::<Self>(&mut world, entity, target);
+        }
+    }
+}
+
+/// The iterator type for the target entities in a [`ManyRelationship`] collection.
+pub type ManyTargetIter<'w, R> =
+    <<R as ManyRelationship>::Collection as RelationshipSourceCollection>::SourceIter<'w>;
+
+/// The iterator type for the source entities in a [`ManyRelationshipTarget`] collection.
+pub type ManySourceIter<'w, S> =
+    <<S as ManyRelationshipTarget>::Collection as RelationshipSourceCollection>::SourceIter<'w>;
+
+/// A [`Component`] containing the collection of entities that relate to this [`Entity`] via the associated [`ManyRelationship`] type.
+/// See the [`ManyRelationship`] documentation for more information.
+pub trait ManyRelationshipTarget: Component<Mutability = Mutable> + Sized {
+    /// The [`ManyRelationship`] that populates this [`ManyRelationshipTarget`] collection.
+    type Relationship: ManyRelationship<RelationshipTarget = Self>;
+
+    /// The collection type that stores the "source" entities for this [`ManyRelationshipTarget`] component.
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`ManyRelationshipTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyRelationshipTarget`] from the given [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as constructing the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// Creates this [`ManyRelationshipTarget`] with the given pre-allocated entity capacity.
+    fn with_capacity(capacity: usize) -> Self {
+        let collection =
+            <Self::Collection as RelationshipSourceCollection>::with_capacity(capacity);
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the source entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> ManySourceIter<'_, Self> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of source entities in this collection.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this entity collection is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+
+    /// The `on_discard` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_discard(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            // Matches `RelationshipTarget::on_discard`: the source of truth is only updated when hooks always run.
+            RelationshipHookMode::Skip | RelationshipHookMode::RunIfNotLinked => return,
+        }
+        let (entities, mut commands) = world.entities_and_commands();
+        let relationship_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source_entity in relationship_target.iter() {
+            commands.queue_silenced(remove_target_from_source::<Self::Relationship>(
+                source_entity,
+                entity,
+            ));
+        }
+    }
+}
+
+/// Adds `source` to the [`ManyRelationshipTarget`] of `target`. If `target` is not a valid target, it is removed from the source's
+/// [`ManyRelationship`] instead.
+pub(crate) fn link_target<R: ManyRelationship>(
+    world: &mut DeferredWorld,
+    source: Entity,
+    target: Entity,
+    caller: MaybeLocation,
+) {
+    if !R::ALLOW_SELF_REFERENTIAL && target == source {
+        warn!(
+            "{}The {} relationship on entity {source:?} points to itself. The invalid target has been removed.\nIf this is intended behavior self-referential relations can be enabled with the allow_self_referential attribute: #[relationship(allow_self_referential)]",
+            caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+            DebugName::type_name::<R>(),
+        );
+        world
+            .commands()
+            .queue_silenced(remove_target_from_source::<R>(source, target));
+        return;
+    }
+
+    if let Ok(mut entity_commands) = world.commands().get_entity(target) {
+        // Deferring is necessary for batch mode
+        entity_commands
+            .entry::<R::RelationshipTarget>()
+            .and_modify(move |mut relationship_target| {
+                let collection = relationship_target.collection_mut_risky();
+                if !collection.iter().any(|existing| existing == source) {
+                    collection.add(source);
+                }
+            })
+            .or_insert_with(move || {
+                let mut relationship_target = R::RelationshipTarget::with_capacity(1);
+                relationship_target.collection_mut_risky().add(source);
+                relationship_target
+            });
+    } else {
+        warn!(
+            "{}The {} relationship on entity {source:?} relates to an entity that does not exist ({target:?}). The invalid target has been removed.",
+            caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+            DebugName::type_name::<R>(),
+        );
+        world
+            .commands()
+            .queue_silenced(remove_target_from_source::<R>(source, target));
+    }
+}
+
+/// Removes `source` from the [`ManyRelationshipTarget`] of `target`, removing the [`ManyRelationshipTarget`] if it becomes empty.
+pub(crate) fn unlink_target<R: ManyRelationship>(
+    world: &mut DeferredWorld,
+    source: Entity,
+    target: Entity,
+) {
+    if let Ok(mut target_entity_mut) = world.get_entity_mut(target)
+        && let Some(mut relationship_target) = target_entity_mut.get_mut::<R::RelationshipTarget>()
+    {
+        relationship_target.collection_mut_risky().remove(source);
+        if relationship_target.is_empty() {
+            let command = |mut entity: EntityWorldMut| {
+                // Like `Relationship::on_discard`, this must check emptiness in case the source was re-added in the meantime
+                if entity
+                    .get::<R::RelationshipTarget>()
+                    .is_some_and(ManyRelationshipTarget::is_empty)
+                {
+                    entity.remove::<R::RelationshipTarget>();
+                }
+            };
+
+            world.commands().queue_silenced(command.with_entity(target));
+        }
+    }
+}
+
+/// Returns a command that replaces the targets of the [`ManyRelationship`] of `source` (without running the relationship hooks) with
+/// `targets`, the same targets without duplicates.
+fn deduplicate_targets<R: ManyRelationship>(
+    source: Entity,
+    targets: Vec<Entity>,
+) -> impl FnOnce(&mut World) {
+    move |world: &mut World| {
+        // The relationship may have been replaced or removed in the meantime, in which case its hooks handled it already
+        let _ = DeferredWorld::from(&mut *world)
+            .modify_component_with_relationship_hook_mode::<R, _>(
+                source,
+                RelationshipHookMode::Skip,
+                |relationship| {
+                    if relationship.len() > targets.len()
+                        && relationship.iter().all(|target| targets.contains(&target))
+                    {
+                        let collection = relationship.collection_mut_risky();
+                        collection.clear();
+                        collection.extend_from_iter(targets);
+                    }
+                },
+            );
+    }
+}
+
+/// Returns an [`EntityCommand`] that removes `target` from the [`ManyRelationship`] of `source` (without running the relationship hooks),
+/// removing the [`ManyRelationship`] if it becomes empty.
+fn remove_target_from_source<R: ManyRelationship>(
+    source: Entity,
+    target: Entity,
+) -> impl FnOnce(&mut World) {
+    move |world: &mut World| {
+        let result = DeferredWorld::from(&mut *world)
+            .modify_component_with_relationship_hook_mode::<R, _>(
+                source,
+                RelationshipHookMode::Skip,
+                |relationship| {
+                    // The source collection may list the same target more than once
+                    while relationship.collection_mut_risky().remove(target) {}
+                },
+            );
+        if let Ok(Some(_)) = result
+            && let Ok(mut source) = world.get_entity_mut(source)
+            && source.get::<R>().is_some_and(ManyRelationship::is_empty)
+        {
+            source.remove::<R>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::Component,
+        entity::Entity,
+        relationship::{ManyRelationship, ManyRelationshipTarget},
+        system::{Query, RunSystemOnce},
+        world::World,
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component)]
+    #[relationship(relationship_target = GroupMembers, many)]
+    struct MemberOf(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = MemberOf, many)]
+    struct GroupMembers(Vec<Entity>);
+
+    fn members(world: &World, group: Entity) -> Vec<Entity> {
+        world
+            .get::<GroupMembers>(group)
+            .map(|members| members.iter().collect())
+            .unwrap_or_default()
+    }
+
+    fn groups(world: &World, member: Entity) -> Vec<Entity> {
+        world
+            .get::<MemberOf>(member)
+            .map(|member_of| member_of.iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn many_relationship_insert_and_remove() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(MemberOf(vec![a, b])).id();
+        let y = world.spawn(MemberOf::from_targets([a])).id();
+
+        assert_eq!(members(&world, a), vec![x, y]);
+        assert_eq!(members(&world, b), vec![x]);
+
+        // Replacing the relationship updates both the old and new targets
+        world.entity_mut(x).insert(MemberOf(vec![b]));
+        assert_eq!(members(&world, a), vec![y]);
+        assert_eq!(members(&world, b), vec![x]);
+
+        world.entity_mut(x).remove::<MemberOf>();
+        assert!(!world.entity(b).contains::<GroupMembers>());
+        assert_eq!(members(&world, a), vec![y]);
+    }
+
+    #[test]
+    fn many_relationship_despawn() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(MemberOf(vec![a, b])).id();
+        let y = world.spawn(MemberOf(vec![a])).id();
+
+        // Despawning a target removes it from every source, removing sources that have no targets left
+        world.despawn(a);
+        assert_eq!(groups(&world, x), vec![b]);
+        assert!(!world.entity(y).contains::<MemberOf>());
+
+        // Despawning a source removes it from every target
+        world.despawn(x);
+        assert!(!world.entity(b).contains::<GroupMembers>());
+    }
+
+    #[test]
+    fn many_relationship_invalid_targets() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+
+        let x = world.spawn_empty().id();
+        world.entity_mut(x).insert(MemberOf(vec![a, x, missing, a]));
+        world.flush();
+
+        // Duplicates are removed, like with `add_relationship_targets`
+        assert_eq!(groups(&world, x), vec![a]);
+        assert_eq!(members(&world, a), vec![x]);
+    }
+
+    #[test]
+    fn add_and_remove_relationship_targets() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn_empty().id();
+
+        world
+            .entity_mut(x)
+            .add_relationship_targets::<MemberOf>(&[a, b, a]);
+        assert_eq!(groups(&world, x), vec![a, b]);
+        assert_eq!(members(&world, a), vec![x]);
+        assert_eq!(members(&world, b), vec![x]);
+
+        world
+            .entity_mut(x)
+            .remove_relationship_targets::<MemberOf>(&[a]);
+        assert_eq!(groups(&world, x), vec![b]);
+        assert!(!world.entity(a).contains::<GroupMembers>());
+
+        world
+            .commands()
+            .entity(x)
+            .remove_relationship_targets::<MemberOf>(&[b]);
+        world.flush();
+        assert!(!world.entity(x).contains::<MemberOf>());
+        assert!(!world.entity(b).contains::<GroupMembers>());
+    }
+
+    #[test]
+    fn many_relationship_traversal() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(MemberOf(vec![a])).id();
+        let c = world.spawn(MemberOf(vec![a, b])).id();
+        // Cycles are only visited once
+        world.entity_mut(a).insert(MemberOf(vec![c]));
+
+        world
+            .run_system_once(move |query: Query<&MemberOf>| {
+                assert_eq!(query.many_related(c).collect::<Vec<_>>(), vec![a, b]);
+                assert_eq!(
+                    query.iter_many_related::<MemberOf>(c).collect::<Vec<_>>(),
+                    vec![a, b, c]
+                );
+                assert_eq!(
+                    query.iter_many_related::<MemberOf>(b).collect::<Vec<_>>(),
+                    vec![a, c, b]
+                );
+            })
+            .unwrap();
+
+        world
+            .run_system_once(move |query: Query<&GroupMembers>| {
+                assert_eq!(
+                    query.many_relationship_sources(a).collect::<Vec<_>>(),
+                    vec![b, c]
+                );
+                assert_eq!(
+                    query
+                        .iter_many_relationship_sources::<GroupMembers>(a)
+                        .collect::<Vec<_>>(),
+                    vec![b, c, a]
+                );
+            })
+            .unwrap();
+    }
+}
@@ -1,9 +1,12 @@
 use crate::{
     bundle::Bundle,
+    change_detection::MaybeLocation,
     entity::{hash_set::EntityHashSet, Entity},
     prelude::Children,
     relationship::{
-        Relationship, RelationshipHookMode, RelationshipSourceCollection, RelationshipTarget,
+        many_relationship::{link_target, unlink_target},
+        ManyRelationship, Relationship, RelationshipHookMode, RelationshipSourceCollection,
+        RelationshipTarget,
     },
     system::{Commands, EntityCommands},
     world::{DeferredWorld, EntityWorldMut, World},
@@ -372,6 +375,112 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Adds the given entities to the targets of this entity's `R` [`ManyRelationship`], inserting `R` if it does not exist.
+    ///
+    /// Entities that are already targets of this relationship are skipped.
+    #[track_caller]
+    pub fn add_relationship_targets<R: ManyRelationship>(
+        &mut self,
+        targets: &[Entity],
+    ) -> &mut Self {
+        let caller = MaybeLocation::caller();
+        let id = self.id();
+        let mut existing = self
+            .get::<R>()
+            .map(|relationship| relationship.iter().collect::<EntityHashSet>());
+        let new_targets = targets
+            .iter()
+            .copied()
+            .filter(|target| match &mut existing {
+                Some(existing) => existing.insert(*target),
+                None => {
+                    existing = Some(EntityHashSet::from_iter([*target]));
+                    true
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if !self.contains::<R>() {
+            self.insert(R::from_targets(new_targets));
+            return self;
+        }
+        if new_targets.is_empty() {
+            return self;
+        }
+
+        self.world_scope(|world| {
+            let mut world = DeferredWorld::from(&mut *world);
+            world
+                .modify_component_with_relationship_hook_mode::<R, _>(
+                    id,
+                    RelationshipHookMode::Skip,
+                    |relationship| {
+                        let collection = relationship.collection_mut_risky();
+                        for target in &new_targets {
+                            collection.add(*target);
+                        }
+                    },
+                )
+                .expect("entity access must be valid");
+            for target in new_targets {
+                link_target::<R>(&mut world, id, target, caller);
+            }
+        });
+        self.world_scope(World::flush);
+
+        self
+    }
+
+    /// Removes the given entities from the targets of this entity's `R` [`ManyRelationship`].
+    ///
+    /// `R` is removed if it has no targets left.
+    pub fn remove_relationship_targets<R: ManyRelationship>(
+        &mut self,
+        targets: &[Entity],
+    ) -> &mut Self {
+        let id = self.id();
+        let Some(relationship) = self.get::<R>() else {
+            return self;
+        };
+        let existing = relationship.iter().collect::<EntityHashSet>();
+        let removed_targets = targets
+            .iter()
+            .copied()
+            .filter(|target| existing.contains(target))
+            .collect::<EntityHashSet>();
+        if removed_targets.is_empty() {
+            return self;
+        }
+
+        let is_empty = self.world_scope(|world| {
+            let mut world = DeferredWorld::from(&mut *world);
+            let is_empty = world
+                .modify_component_with_relationship_hook_mode::<R, _>(
+                    id,
+                    RelationshipHookMode::Skip,
+                    |relationship| {
+                        let collection = relationship.collection_mut_risky();
+                        for target in &removed_targets {
+                            while collection.remove(*target) {}
+                        }
+                        collection.is_empty()
+                    },
+                )
+                .expect("entity access must be valid")
+                .unwrap_or(false);
+            for target in removed_targets {
+                unlink_target::<R>(&mut world, id, target);
+            }
+            is_empty
+        });
+        if is_empty {
+            self.remove::<R>();
+        }
+        self.world_scope(World::flush);
+
+        self
+    }
+
     fn modify_or_insert_relation_with_relationship_hook_mode<R: Relationship>(
         &mut self,
         entity: Entity,
@@ -558,6 +667,34 @@ impl<'a> EntityCommands<'a> {
             entity.remove_recursive::<S, B>();
         })
     }
+
+    /// Adds the given entities to the targets of this entity's `R` [`ManyRelationship`], inserting `R` if it does not exist.
+    ///
+    /// Entities that are already targets of this relationship are skipped.
+    pub fn add_relationship_targets<R: ManyRelationship>(
+        &mut self,
+        targets: &[Entity],
+    ) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_relationship_targets::<R>(&targets);
+        })
+    }
+
+    /// Removes the given entities from the targets of this entity's `R` [`ManyRelationship`].
+    ///
+    /// `R` is removed if it has no targets left.
+    pub fn remove_relationship_targets<R: ManyRelationship>(
+        &mut self,
+        targets: &[Entity],
+    ) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_relationship_targets::<R>(&targets);
+        })
+    }
 }
 
 /// Directly spawns related "source" entities with the given [`Relationship`], targeting
@@ -1,7 +1,7 @@
 use crate::{
-    entity::Entity,
+    entity::{Entity, EntityHashSet},
     query::{QueryData, QueryFilter},
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{ManyRelationship, ManyRelationshipTarget, Relationship, RelationshipTarget},
     system::Query,
 };
 use alloc::collections::VecDeque;
@@ -141,6 +141,64 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     {
         AncestorIter::new(self, entity)
     }
+
+    /// If the given `entity` contains the `R` [`ManyRelationship`] component, returns the
+    /// target entities of that relationship.
+    pub fn many_related<R: ManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyRelationship::iter)
+    }
+
+    /// If the given `entity` contains the `S` [`ManyRelationshipTarget`] component, returns the
+    /// source entities stored on that component.
+    pub fn many_relationship_sources<S: ManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyRelationshipTarget::iter)
+    }
+
+    /// Iterates all entities reachable from the given `entity` by following the targets of the `R` [`ManyRelationship`],
+    /// in breadth-first order.
+    ///
+    /// Each entity is returned at most once, so this is safe to use on relationship graphs that contain loops.
+    /// `entity` itself is only returned if it can be reached from one of its targets.
+    pub fn iter_many_related<R: ManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> ManyRelatedIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        ManyRelatedIter::new(self, entity)
+    }
+
+    /// Iterates all entities that can reach the given `entity` through the [`ManyRelationship`] of the `S`
+    /// [`ManyRelationshipTarget`], by following the stored sources in breadth-first order.
+    ///
+    /// Each entity is returned at most once, so this is safe to use on relationship graphs that contain loops.
+    /// `entity` itself is only returned if it can be reached from one of its sources.
+    pub fn iter_many_relationship_sources<S: ManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> ManyRelationshipSourcesIter<'w, 's, D, F, S>
+    where
+        D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+    {
+        ManyRelationshipSourcesIter::new(self, entity)
+    }
 }
 
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
@@ -270,3 +328,119 @@ where
         self.next
     }
 }
+
+/// An [`Iterator`] of [`Entity`]s over everything reachable through the targets of a [`ManyRelationship`].
+///
+/// Traverses the relationship graph breadth-first, visiting every entity once.
+pub struct ManyRelatedIter<'w, 's, D: QueryData, F: QueryFilter, R: ManyRelationship>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    related_query: &'w Query<'w, 's, D, F>,
+    vecdeque: VecDeque<Entity>,
+    visited: EntityHashSet,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: ManyRelationship> ManyRelatedIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    /// Returns a new [`ManyRelatedIter`].
+    pub fn new(related_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        let mut visited = EntityHashSet::new();
+        let vecdeque = related_query
+            .get(entity)
+            .into_iter()
+            .flat_map(ManyRelationship::iter)
+            .filter(|target| visited.insert(*target))
+            .collect();
+        ManyRelatedIter {
+            related_query,
+            vecdeque,
+            visited,
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: ManyRelationship> Iterator
+    for ManyRelatedIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+
+        if let Ok(related) = self.related_query.get(entity) {
+            for target in related.iter() {
+                if self.visited.insert(target) {
+                    self.vecdeque.push_back(target);
+                }
+            }
+        }
+
+        Some(entity)
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s over everything reachable through the sources of a [`ManyRelationshipTarget`].
+///
+/// Traverses the relationship graph breadth-first, visiting every entity once.
+pub struct ManyRelationshipSourcesIter<
+    'w,
+    's,
+    D: QueryData,
+    F: QueryFilter,
+    S: ManyRelationshipTarget,
+> where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+{
+    sources_query: &'w Query<'w, 's, D, F>,
+    vecdeque: VecDeque<Entity>,
+    visited: EntityHashSet,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, S: ManyRelationshipTarget>
+    ManyRelationshipSourcesIter<'w, 's, D, F, S>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+{
+    /// Returns a new [`ManyRelationshipSourcesIter`].
+    pub fn new(sources_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        let mut visited = EntityHashSet::new();
+        let vecdeque = sources_query
+            .get(entity)
+            .into_iter()
+            .flat_map(ManyRelationshipTarget::iter)
+            .filter(|source| visited.insert(*source))
+            .collect();
+        ManyRelationshipSourcesIter {
+            sources_query,
+            vecdeque,
+            visited,
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, S: ManyRelationshipTarget> Iterator
+    for ManyRelationshipSourcesIter<'w, 's, D, F, S>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+
+        if let Ok(sources) = self.sources_query.get(entity) {
+            for source in sources.iter() {
+                if self.visited.insert(source) {
+                    self.vecdeque.push_back(source);
+                }
+            }
+        }
+
+        Some(entity)
+    }
+}
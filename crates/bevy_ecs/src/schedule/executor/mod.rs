@@ -38,10 +38,42 @@ pub trait SystemExecutor: Send + Sync {
         skip_systems: Option<&FixedBitSet>,
         error_handler: fn(BevyError, ErrorContext),
     );
+    /// Runs the systems in the schedule, stopping before the first system
+    /// `breakpoints` breaks on. That system and any systems that have not run
+    /// yet are skipped.
+    ///
+    /// The default implementation ignores `breakpoints`.
+    fn run_with_breakpoints(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        breakpoints: &mut dyn SystemBreakpoints,
+        error_handler: fn(BevyError, ErrorContext),
+    ) {
+        let _ = breakpoints;
+        self.run(schedule, world, skip_systems, error_handler);
+    }
     /// Sets whether deferred system buffers should be applied after all systems have run.
     fn set_apply_final_deferred(&mut self, value: bool);
 }
 
+/// Breakpoints a [`SystemExecutor`] checks before running each system.
+///
+/// Used by [`Stepping`](crate::schedule::Stepping) to stop before a system
+/// once its conditional breakpoint is met.
+pub trait SystemBreakpoints: Send + Sync {
+    /// Returns `true` if [`should_break`](Self::should_break) needs to be
+    /// called before the system at `system_index` runs.
+    ///
+    /// Executors only call `should_break` for such a system once no other
+    /// system is running.
+    fn may_break(&self, system_index: usize) -> bool;
+    /// Returns `true` if execution should stop before the system at
+    /// `system_index` runs.
+    fn should_break(&mut self, world: &World, system_index: usize) -> bool;
+}
+
 /// Returns the default executor for the current platform.
 ///
 /// On Wasm or when the `multi_threaded` feature is disabled, this returns a
@@ -22,7 +22,8 @@ use crate::{
     },
     prelude::Resource,
     schedule::{
-        is_apply_deferred, ConditionWithAccess, SystemExecutor, SystemSchedule, SystemWithAccess,
+        is_apply_deferred, ConditionWithAccess, SystemBreakpoints, SystemExecutor, SystemSchedule,
+        SystemWithAccess,
     },
     system::{BoxedSystem, RunSystemError, ScheduleSystem},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
@@ -45,6 +46,7 @@ struct Conditions<'a> {
     set_conditions: &'a mut [Vec<ConditionWithAccess>],
     sets_with_conditions_of_systems: &'a [FixedBitSet],
     systems_in_sets_with_conditions: &'a [FixedBitSet],
+    breakpoints: Option<&'a mut dyn SystemBreakpoints>,
 }
 
 impl<'env, 'sys> Environment<'env, 'sys> {
@@ -52,6 +54,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
         executor: &'env MultiThreadedExecutor,
         schedule: &'sys mut SystemSchedule,
         world: &'env mut World,
+        breakpoints: Option<&'sys mut dyn SystemBreakpoints>,
     ) -> Self {
         Environment {
             executor,
@@ -61,6 +64,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 set_conditions: &mut schedule.set_conditions,
                 sets_with_conditions_of_systems: &schedule.sets_with_conditions_of_systems,
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
+                breakpoints,
             }),
             world_cell: world.as_unsafe_world_cell(),
         }
@@ -242,7 +246,41 @@ impl SystemExecutor for MultiThreadedExecutor {
         &mut self,
         schedule: &mut SystemSchedule,
         world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        error_handler: ErrorHandler,
+    ) {
+        self.run_inner(schedule, world, skip_systems, None, error_handler);
+    }
+
+    fn run_with_breakpoints(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        breakpoints: &mut dyn SystemBreakpoints,
+        error_handler: ErrorHandler,
+    ) {
+        self.run_inner(
+            schedule,
+            world,
+            skip_systems,
+            Some(breakpoints),
+            error_handler,
+        );
+    }
+
+    fn set_apply_final_deferred(&mut self, value: bool) {
+        self.apply_final_deferred = value;
+    }
+}
+
+impl MultiThreadedExecutor {
+    fn run_inner<'sys>(
+        &mut self,
+        schedule: &'sys mut SystemSchedule,
+        world: &mut World,
         _skip_systems: Option<&FixedBitSet>,
+        _breakpoints: Option<&'sys mut dyn SystemBreakpoints>,
         error_handler: ErrorHandler,
     ) {
         let state = self.state.get_mut().unwrap();
@@ -277,7 +315,10 @@ impl SystemExecutor for MultiThreadedExecutor {
             .map(|e| e.0.clone());
         let thread_executor = thread_executor.as_deref();
 
-        let environment = &Environment::new(self, schedule, world);
+        // Breakpoints are only checked when stepping is compiled in.
+        #[cfg(not(feature = "bevy_debug_stepping"))]
+        let _breakpoints = None;
+        let environment = &Environment::new(self, schedule, world, _breakpoints);
 
         ComputeTaskPool::get_or_init(TaskPool::default).scope_with_executor(
             false,
@@ -322,10 +363,6 @@ impl SystemExecutor for MultiThreadedExecutor {
         state.skipped_systems.clear();
         state.completed_systems.clear();
     }
-
-    fn set_apply_final_deferred(&mut self, value: bool) {
-        self.apply_final_deferred = value;
-    }
 }
 
 impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
@@ -524,6 +561,24 @@ impl ExecutorState {
                     continue;
                 }
 
+                // If stepping breaks before this system, skip it and every
+                // system that has not run yet.
+                #[cfg(feature = "bevy_debug_stepping")]
+                if let Some(breakpoints) = conditions.breakpoints.as_deref_mut()
+                    && breakpoints.may_break(system_index)
+                    && breakpoints.should_break(
+                        // SAFETY: `can_run` returned true for a system that may
+                        // break, which means no systems are currently running.
+                        unsafe { context.environment.world_cell.world() },
+                        system_index,
+                    )
+                {
+                    self.completed_systems.insert_range(..);
+                    self.ready_systems.clear();
+                    check_for_new_ready_systems = false;
+                    break;
+                }
+
                 self.running_systems.insert(system_index);
                 self.num_running_systems += 1;
 
@@ -558,6 +613,16 @@ impl ExecutorState {
             return false;
         }
 
+        // Breakpoints read the whole world, so wait for other systems to finish.
+        if conditions
+            .breakpoints
+            .as_deref()
+            .is_some_and(|breakpoints| breakpoints.may_break(system_index))
+            && self.num_running_systems > 0
+        {
+            return false;
+        }
+
         if !system_meta.is_send && self.local_thread_running {
             return false;
         }
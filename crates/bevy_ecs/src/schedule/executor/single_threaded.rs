@@ -18,7 +18,8 @@ use crate::{
 use crate::{
     error::{ErrorContext, ErrorHandler},
     schedule::{
-        is_apply_deferred, BoxedCondition, ConditionWithAccess, SystemExecutor, SystemSchedule,
+        is_apply_deferred, BoxedCondition, ConditionWithAccess, SystemBreakpoints, SystemExecutor,
+        SystemSchedule,
     },
     system::{RunSystemError, ScheduleSystem},
     world::World,
@@ -56,10 +57,44 @@ impl SystemExecutor for SingleThreadedExecutor {
     }
 
     fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        error_handler: ErrorHandler,
+    ) {
+        self.run_inner(schedule, world, skip_systems, None, error_handler);
+    }
+
+    fn run_with_breakpoints(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        skip_systems: Option<&FixedBitSet>,
+        breakpoints: &mut dyn SystemBreakpoints,
+        error_handler: ErrorHandler,
+    ) {
+        self.run_inner(
+            schedule,
+            world,
+            skip_systems,
+            Some(breakpoints),
+            error_handler,
+        );
+    }
+
+    fn set_apply_final_deferred(&mut self, apply_final_deferred: bool) {
+        self.apply_final_deferred = apply_final_deferred;
+    }
+}
+
+impl SingleThreadedExecutor {
+    fn run_inner(
         &mut self,
         schedule: &mut SystemSchedule,
         world: &mut World,
         _skip_systems: Option<&FixedBitSet>,
+        mut _breakpoints: Option<&mut dyn SystemBreakpoints>,
         error_handler: ErrorHandler,
     ) {
         // If stepping is enabled, make sure we skip those systems that should
@@ -139,6 +174,16 @@ impl SystemExecutor for SingleThreadedExecutor {
                 continue;
             }
 
+            // If stepping breaks before this system, skip it and every system
+            // after it.
+            #[cfg(feature = "bevy_debug_stepping")]
+            if let Some(breakpoints) = _breakpoints.as_deref_mut()
+                && breakpoints.may_break(system_index)
+                && breakpoints.should_break(world, system_index)
+            {
+                break;
+            }
+
             let f = |system: &mut _| {
                 if let Err(RunSystemError::Failed(err)) =
                     __rust_begin_short_backtrace::run_without_applying_deferred(system, world)
@@ -174,12 +219,6 @@ impl SystemExecutor for SingleThreadedExecutor {
         self.completed_systems.clear();
     }
 
-    fn set_apply_final_deferred(&mut self, apply_final_deferred: bool) {
-        self.apply_final_deferred = apply_final_deferred;
-    }
-}
-
-impl SingleThreadedExecutor {
     /// Creates a new single-threaded executor for use in a [`Schedule`].
     ///
     /// [`Schedule`]: crate::schedule::Schedule
@@ -1321,5 +1321,57 @@ mod tests {
         fn multi_threaded_executor() {
             assert_executor_supports_stepping(MultiThreadedExecutor::new());
         }
+
+        fn assert_executor_supports_conditional_breakpoints(
+            executor: impl SystemExecutor + 'static,
+        ) {
+            #[derive(Resource)]
+            struct Armed(bool);
+
+            fn arm(mut armed: ResMut<Armed>) {
+                armed.0 = true;
+            }
+            fn target() {
+                panic!("Executor ignored conditional breakpoint");
+            }
+
+            // the breakpoint condition is only met after `arm` runs
+            let mut schedule = Schedule::new(TestSchedule);
+            schedule.set_executor(executor);
+            schedule.add_systems((arm, target).chain());
+
+            let mut stepping = Stepping::default();
+            stepping.add_schedule(TestSchedule).break_when(
+                TestSchedule,
+                target,
+                |world: &World| world.resource::<Armed>().0,
+            );
+
+            let mut world = World::default();
+            world.insert_resource(Armed(false));
+            world.insert_resource(stepping);
+
+            let mut system_state: SystemState<Option<ResMut<Stepping>>> =
+                SystemState::new(&mut world);
+            let res = system_state.get_mut(&mut world).unwrap();
+            Stepping::begin_frame(res);
+
+            // this will panic if the executor doesn't check the breakpoint
+            // before running `target`
+            schedule.run(&mut world);
+            assert!(world.resource::<Stepping>().is_enabled());
+        }
+
+        /// verify the [`SingleThreadedExecutor`] checks conditional breakpoints
+        #[test]
+        fn single_threaded_executor_conditional_breakpoints() {
+            assert_executor_supports_conditional_breakpoints(SingleThreadedExecutor::new());
+        }
+
+        /// verify the [`MultiThreadedExecutor`] checks conditional breakpoints
+        #[test]
+        fn multi_threaded_executor_conditional_breakpoints() {
+            assert_executor_supports_conditional_breakpoints(MultiThreadedExecutor::new());
+        }
     }
 }
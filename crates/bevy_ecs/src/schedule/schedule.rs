@@ -587,17 +587,26 @@ impl Schedule {
 
         #[cfg(feature = "bevy_debug_stepping")]
         {
-            let skip_systems = match world.get_resource_mut::<Stepping>() {
-                None => None,
-                Some(mut stepping) => stepping.skipped_systems(self),
-            };
+            let (skip_systems, mut breakpoints) = Stepping::begin_schedule_run(world, self);
+            let start = world.read_change_tick();
 
-            self.executor.run(
-                &mut self.executable,
-                world,
-                skip_systems.as_ref(),
-                error_handler,
-            );
+            match breakpoints.as_mut() {
+                Some(breakpoints) => self.executor.run_with_breakpoints(
+                    &mut self.executable,
+                    world,
+                    skip_systems.as_ref(),
+                    breakpoints,
+                    error_handler,
+                ),
+                None => self.executor.run(
+                    &mut self.executable,
+                    world,
+                    skip_systems.as_ref(),
+                    error_handler,
+                ),
+            }
+
+            Stepping::end_schedule_run(world, self, breakpoints, start);
         }
     }
 
@@ -769,6 +778,7 @@ pub struct ScheduleGraph {
     /// Nodes that are allowed to have ambiguous ordering relationship with any other systems.
     pub ambiguous_with_all: HashSet<NodeId>,
     conflicting_systems: ConflictingSystems,
+    resolved_ambiguities: ConflictingSystems,
     /// Dependency edges marked weak (from `chain_weak`/`before_weak`/`after_weak`), before flattening.
     ///
     /// During the build, edges between nodes that don't conflict are ignored.
@@ -795,6 +805,7 @@ impl ScheduleGraph {
             ambiguous_with: UnGraph::default(),
             ambiguous_with_all: HashSet::default(),
             conflicting_systems: ConflictingSystems::default(),
+            resolved_ambiguities: ConflictingSystems::default(),
             weak_node_edges: HashSet::default(),
             strict_node_edges: HashSet::default(),
             anonymous_sets: 0,
@@ -828,6 +839,15 @@ impl ScheduleGraph {
         &self.conflicting_systems
     }
 
+    /// Returns the conflicting systems that were given a fixed order because
+    /// [`ScheduleBuildSettings::deterministic_ambiguity_order`] is enabled.
+    ///
+    /// In each pair, the first system runs before the second.
+    /// Must be called after [`ScheduleGraph::build_schedule`] to be non-empty.
+    pub fn resolved_ambiguities(&self) -> &ConflictingSystems {
+        &self.resolved_ambiguities
+    }
+
     fn process_config<T: ProcessScheduleConfig + Schedulable>(
         &mut self,
         config: ScheduleConfig<T>,
@@ -1353,6 +1373,24 @@ impl ScheduleGraph {
             &self.ambiguous_with_all,
             ignored_ambiguities,
         );
+
+        // Order the ambiguities by a stable key, so that conflicting systems
+        // always run in the same order, regardless of the executor. Once
+        // ordered, they are no longer ambiguous.
+        self.resolved_ambiguities = if self.settings.deterministic_ambiguity_order {
+            let resolved = self.order_ambiguities(&mut flat_dependency);
+            self.conflicting_systems = ConflictingSystems::default();
+            resolved
+        } else {
+            ConflictingSystems::default()
+        };
+        if !self.resolved_ambiguities.is_empty() {
+            let analysis = flat_dependency
+                .analyze()
+                .map_err(ScheduleBuildError::FlatDependencySort)?;
+            flat_dependency.remove_redundant_edges(&analysis);
+        }
+
         // If there are any ambiguities, log warnings or return errors as configured.
         if self.settings.ambiguity_detection != LogLevel::Ignore
             && let Err(e) = self.conflicting_systems.check_if_not_empty()
@@ -1520,6 +1558,58 @@ impl ScheduleGraph {
             || conditions_conflict(&to_system.access, from)
     }
 
+    /// Adds a dependency edge between each pair of [conflicting systems](Self::conflicting_systems),
+    /// returning the pairs with the system that now runs first on the left.
+    ///
+    /// Pairs are ordered by the system name, falling back to the order the systems were added in.
+    /// When the edges added for earlier pairs already order a pair, that order is kept instead of
+    /// introducing a cycle.
+    fn order_ambiguities(&self, flat_dependency: &mut Dag<SystemKey>) -> ConflictingSystems {
+        let stable_key = |key: SystemKey| (self.systems[key].system.name().to_string(), key);
+
+        let mut pairs = self
+            .conflicting_systems
+            .iter()
+            .map(|(a, b, conflicts)| {
+                let (a_key, b_key) = (stable_key(*a), stable_key(*b));
+                if a_key <= b_key {
+                    ((a_key, b_key), (*a, *b, conflicts.clone()))
+                } else {
+                    ((b_key, a_key), (*b, *a, conflicts.clone()))
+                }
+            })
+            .collect::<Vec<_>>();
+        pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let is_reachable = |dag: &Dag<SystemKey>, from: SystemKey, to: SystemKey| {
+            let mut visited = HashSet::new();
+            let mut stack = vec![from];
+            while let Some(node) = stack.pop() {
+                if node == to {
+                    return true;
+                }
+                stack.extend(
+                    dag.neighbors_directed(node, Outgoing)
+                        .filter(|&next| visited.insert(next)),
+                );
+            }
+            false
+        };
+
+        let mut resolved = Vec::with_capacity(pairs.len());
+        for (_, (first, second, conflicts)) in pairs {
+            if is_reachable(flat_dependency, second, first) {
+                resolved.push((second, first, conflicts));
+            } else {
+                if !is_reachable(flat_dependency, first, second) {
+                    flat_dependency.add_edge(first, second);
+                }
+                resolved.push((first, second, conflicts));
+            }
+        }
+        ConflictingSystems(resolved)
+    }
+
     fn build_schedule_inner(
         &self,
         flat_dependency: Dag<SystemKey>,
@@ -1664,6 +1754,21 @@ impl ScheduleGraph {
             );
         }
 
+        if !self.resolved_ambiguities.is_empty() {
+            let mut message = format!(
+                "{:?} schedule gave {} pairs of ambiguous systems a deterministic order:\n",
+                schedule_label,
+                self.resolved_ambiguities.len()
+            );
+            for (first, second, _) in self
+                .resolved_ambiguities
+                .to_string(self, world.components())
+            {
+                writeln!(message, " -- {first} before {second}").unwrap();
+            }
+            info!("{message}");
+        }
+
         // move systems into new schedule
         for &key in &schedule.system_ids {
             let system = self.systems.node_mut(key).unwrap().inner.take().unwrap();
@@ -1880,6 +1985,20 @@ pub struct ScheduleBuildSettings {
     // the fact their constraints allow that.
     #[cfg(feature = "debug")]
     pub shuffle_seed: Option<u64>,
+    /// If set to true, every pair of systems with conflicting access but no ordering between
+    /// them is given a fixed order, sorted by system name and then by the order the systems
+    /// were added in. Both the single-threaded and multi-threaded executors then run them in
+    /// that order, which keeps replays and tests reproducible. System names are only available
+    /// with the `debug` feature; without it, systems are only ordered by when they were added.
+    ///
+    /// Ambiguities accepted with `ambiguous_with` or
+    /// [`Schedule::ignore_ambiguity`](crate::schedule::Schedule::ignore_ambiguity) are left
+    /// unordered. Ordered pairs are no longer ambiguous, so they are not reported by
+    /// [`ambiguity_detection`](Self::ambiguity_detection). Instead, they are logged at the info
+    /// level when the schedule is built, and returned by [`ScheduleGraph::resolved_ambiguities`].
+    ///
+    /// Defaults to `false`.
+    pub deterministic_ambiguity_order: bool,
 }
 
 impl Default for ScheduleBuildSettings {
@@ -1900,6 +2019,7 @@ impl ScheduleBuildSettings {
             report_sets: true,
             #[cfg(feature = "debug")]
             shuffle_seed: None,
+            deterministic_ambiguity_order: false,
         }
     }
 }
@@ -1946,7 +2066,7 @@ mod tests {
         prelude::{ApplyDeferred, IntoSystemSet, Res, Resource},
         schedule::{
             passes::AutoInsertApplyDeferredPass, tests::ResMut, FlattenedDependencies,
-            IntoScheduleConfigs, MultiThreadedExecutor, Schedule, ScheduleBuildPass,
+            IntoScheduleConfigs, LogLevel, MultiThreadedExecutor, Schedule, ScheduleBuildPass,
             ScheduleBuildSettings, ScheduleCleanupPolicy, SystemSet,
         },
         system::Commands,
@@ -3073,6 +3193,67 @@ mod tests {
         // panic!("unique={unique:?}");
     }
 
+    #[test]
+    fn deterministic_ambiguity_order() {
+        fn run_schedule(settings: ScheduleBuildSettings, multi_threaded: bool) -> Vec<u32> {
+            #[derive(Resource, Default)]
+            struct Counters(Vec<u32>);
+
+            fn system<const N: u32>(mut counters: ResMut<Counters>) {
+                counters.0.push(N);
+            }
+
+            // Same graph as `schedule_builds_randomly_with_shuffler`, where 10 and 11,
+            // and all of 11, 20 and 21 are ambiguous with each other.
+            let mut schedule = Schedule::default();
+            if multi_threaded {
+                schedule.set_executor(MultiThreadedExecutor::new());
+            }
+            schedule.add_systems(system::<0>);
+            schedule.add_systems((system::<11>, system::<10>).after(system::<0>));
+            schedule.add_systems((system::<21>, system::<20>).after(system::<10>));
+            schedule.set_build_settings(settings);
+
+            let mut world = World::new();
+            world.init_resource::<Counters>();
+            schedule.initialize(&mut world).unwrap();
+            assert_eq!(schedule.graph().resolved_ambiguities().len(), 4);
+            assert!(schedule.graph().conflicting_systems().is_empty());
+            schedule.run(&mut world);
+
+            world.remove_resource::<Counters>().unwrap().0
+        }
+
+        // Ordered systems are no longer ambiguous, so this builds even though
+        // ambiguities are errors.
+        let settings = ScheduleBuildSettings {
+            deterministic_ambiguity_order: true,
+            ambiguity_detection: LogLevel::Error,
+            ..Default::default()
+        };
+
+        // Ambiguous systems are sorted by name, regardless of the order they
+        // were added in or the executor. Without the `debug` feature names are
+        // unavailable, so they keep the order they were added in.
+        #[cfg(feature = "debug")]
+        let expected = [0, 10, 11, 20, 21];
+        #[cfg(not(feature = "debug"))]
+        let expected = [0, 11, 10, 21, 20];
+        assert_eq!(run_schedule(settings.clone(), false), expected);
+        assert_eq!(run_schedule(settings.clone(), true), expected);
+
+        // ... or the shuffler.
+        #[cfg(feature = "debug")]
+        for seed in [100000001, 100000030, 100000063] {
+            let settings = ScheduleBuildSettings {
+                shuffle_seed: Some(seed),
+                ..settings.clone()
+            };
+            assert_eq!(run_schedule(settings.clone(), false), expected);
+            assert_eq!(run_schedule(settings, true), expected);
+        }
+    }
+
     /// Total number of dependency edges in the built schedule.
     fn total_dependencies(schedule: &Schedule) -> usize {
         schedule.executable.system_dependencies.iter().sum()
@@ -1,12 +1,18 @@
 use crate::{
+    change_detection::Tick,
+    component::{Component, ComponentId},
+    entity::Entity,
     resource::Resource,
-    schedule::{InternedScheduleLabel, NodeId, Schedule, ScheduleLabel, SystemKey},
+    schedule::{
+        InternedScheduleLabel, NodeId, Schedule, ScheduleLabel, SystemBreakpoints, SystemKey,
+    },
     system::{IntoSystem, ResMut},
+    world::World,
 };
-use alloc::vec::Vec;
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
 use bevy_platform::collections::HashMap;
-use bevy_utils::TypeIdHashMap;
-use core::any::TypeId;
+use bevy_utils::{prelude::DebugName, TypeIdHashMap};
+use core::{any::TypeId, mem};
 use fixedbitset::FixedBitSet;
 use log::{info, warn};
 use thiserror::Error;
@@ -65,6 +71,7 @@ struct Cursor {
 }
 
 // Two methods of referring to Systems, via TypeId, or per-Schedule NodeId
+#[derive(PartialEq, Eq)]
 enum SystemIdentifier {
     Type(TypeId),
     Node(NodeId),
@@ -85,6 +92,106 @@ enum Update {
     SetBehavior(InternedScheduleLabel, SystemIdentifier, SystemBehavior),
     /// Clear any system-specific behavior for this schedule & system
     ClearBehavior(InternedScheduleLabel, SystemIdentifier),
+    /// Add a conditional breakpoint for this schedule & system
+    AddConditionalBreakpoint(InternedScheduleLabel, SystemIdentifier, BreakCondition),
+}
+
+/// Condition that must be met for a conditional breakpoint to stop execution
+enum BreakCondition {
+    /// The component changed on the entity since the condition was last checked
+    ComponentChanged {
+        entity: Entity,
+        component: TypeId,
+        last_checked: Option<Tick>,
+    },
+    /// The predicate returned true
+    Predicate(Box<dyn FnMut(&World) -> bool + Send + Sync>),
+}
+
+impl BreakCondition {
+    fn evaluate(&mut self, world: &World) -> bool {
+        match self {
+            BreakCondition::ComponentChanged {
+                entity,
+                component,
+                last_checked,
+            } => {
+                let this_run = world.read_change_tick();
+                let changed = last_checked.is_some_and(|last_checked| {
+                    world
+                        .components()
+                        .get_id(*component)
+                        .and_then(|id| world.get_entity(*entity).ok()?.get_change_ticks_by_id(id))
+                        .is_some_and(|ticks| ticks.is_changed(last_checked, this_run))
+                });
+                *last_checked = Some(this_run);
+                changed
+            }
+            BreakCondition::Predicate(predicate) => predicate(world),
+        }
+    }
+}
+
+struct ConditionalBreakpoint {
+    system: SystemIdentifier,
+    condition: BreakCondition,
+}
+
+/// The conditional breakpoints of a [`Schedule`], lent to its executor while
+/// the schedule runs
+pub(crate) struct ScheduleBreakpoints {
+    breakpoints: Vec<ConditionalBreakpoint>,
+    /// systems each breakpoint can stop before during this run
+    systems: Vec<FixedBitSet>,
+    /// union of `systems`
+    eligible: FixedBitSet,
+    /// the system execution stopped before
+    hit: Option<usize>,
+}
+
+impl SystemBreakpoints for ScheduleBreakpoints {
+    fn may_break(&self, system_index: usize) -> bool {
+        self.eligible.contains(system_index)
+    }
+
+    fn should_break(&mut self, world: &World, system_index: usize) -> bool {
+        if !self.eligible.contains(system_index) {
+            return false;
+        }
+        // evaluate every matching condition so they all see this check
+        let mut hit = false;
+        for (breakpoint, systems) in self.breakpoints.iter_mut().zip(&self.systems) {
+            if systems.contains(system_index) {
+                hit |= breakpoint.condition.evaluate(world);
+            }
+        }
+        if hit {
+            self.hit = Some(system_index);
+        }
+        hit
+    }
+}
+
+/// A single run of a system, recorded by [`Stepping::record_timeline`].
+#[derive(Debug, Clone)]
+pub struct SystemRunRecord {
+    /// The schedule the system ran in.
+    pub schedule: InternedScheduleLabel,
+    /// The system instance within the schedule.
+    pub node: NodeId,
+    /// The name of the system.
+    pub name: DebugName,
+    /// The watched components the system changed, along with the entity they are stored on.
+    pub mutations: Vec<(Entity, ComponentId)>,
+}
+
+/// The systems that ran during a single render frame, recorded by [`Stepping::record_timeline`].
+#[derive(Debug, Clone, Default)]
+pub struct TimelineFrame {
+    /// The number of render frames [`Stepping`] had seen when this frame was recorded.
+    pub frame: u64,
+    /// The systems that ran this frame, in the order they started.
+    pub systems: Vec<SystemRunRecord>,
 }
 
 #[derive(Error, Debug)]
@@ -111,6 +218,18 @@ pub struct Stepping {
 
     // Updates apply at the start of the next render frame
     updates: Vec<Update>,
+
+    // number of render frames seen by `begin_frame()`
+    frame: u64,
+
+    // maximum number of frames kept in `timeline`; recording is disabled when zero
+    timeline_capacity: usize,
+
+    // recorded system runs for the most recent frames
+    timeline: VecDeque<TimelineFrame>,
+
+    // components whose changes are recorded in `timeline`
+    watched: Vec<(Entity, TypeId)>,
 }
 
 impl core::fmt::Debug for Stepping {
@@ -363,6 +482,144 @@ impl Stepping {
         self
     }
 
+    /// Add a breakpoint that stops before the system runs if the component `C`
+    /// on `entity` changed since the breakpoint was last checked.
+    ///
+    /// Unlike [`set_breakpoint`](Self::set_breakpoint), this breakpoint also
+    /// applies when stepping is disabled: the first time it is hit, stepping is
+    /// enabled and execution stops before the system.
+    ///
+    /// The condition is checked right before the system runs, so changes made
+    /// by earlier systems in the same run are noticed. The component is also
+    /// [watched](Self::watch) by the timeline.
+    pub fn break_on_change<C: Component, Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+        entity: Entity,
+    ) -> &mut Self {
+        let type_id = system.system_type_id();
+        self.updates.push(Update::AddConditionalBreakpoint(
+            schedule.intern(),
+            SystemIdentifier::Type(type_id),
+            BreakCondition::ComponentChanged {
+                entity,
+                component: TypeId::of::<C>(),
+                last_checked: None,
+            },
+        ));
+        self
+    }
+
+    /// Add a breakpoint that stops before the system runs if `predicate`
+    /// returns `true`.
+    ///
+    /// Like [`break_on_change`](Self::break_on_change), the predicate is
+    /// called right before the system runs.
+    pub fn break_when<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystem<(), (), Marker>,
+        predicate: impl FnMut(&World) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        let type_id = system.system_type_id();
+        self.updates.push(Update::AddConditionalBreakpoint(
+            schedule.intern(),
+            SystemIdentifier::Type(type_id),
+            BreakCondition::Predicate(Box::new(predicate)),
+        ));
+        self
+    }
+
+    /// Add a breakpoint that stops before the system instance runs if
+    /// `predicate` returns `true`.
+    pub fn break_node_when(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        node: NodeId,
+        predicate: impl FnMut(&World) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.updates.push(Update::AddConditionalBreakpoint(
+            schedule.intern(),
+            SystemIdentifier::Node(node),
+            BreakCondition::Predicate(Box::new(predicate)),
+        ));
+        self
+    }
+
+    /// Record which systems run each frame, and which [watched](Self::watch)
+    /// components they change, keeping the most recent `frames` frames.
+    ///
+    /// Only schedules with stepping enabled are recorded, but recording works
+    /// whether or not stepping is enabled.
+    ///
+    /// Note: changes made by commands are attributed to the most recent system
+    /// that started before the commands were applied.
+    pub fn record_timeline(&mut self, frames: usize) -> &mut Self {
+        self.timeline_capacity = frames;
+        while self.timeline.len() > frames {
+            self.timeline.pop_front();
+        }
+        self
+    }
+
+    /// Record changes to the component `C` on `entity` in the timeline.
+    ///
+    /// Components watched by [`break_on_change`](Self::break_on_change) are
+    /// recorded as well.
+    pub fn watch<C: Component>(&mut self, entity: Entity) -> &mut Self {
+        let watched = (entity, TypeId::of::<C>());
+        if !self.watched.contains(&watched) {
+            self.watched.push(watched);
+        }
+        self
+    }
+
+    /// Stop recording changes to the component `C` on `entity`.
+    pub fn unwatch<C: Component>(&mut self, entity: Entity) -> &mut Self {
+        let watched = (entity, TypeId::of::<C>());
+        self.watched.retain(|w| *w != watched);
+        self
+    }
+
+    /// Stop recording the timeline. Already recorded frames are kept.
+    pub fn stop_recording(&mut self) -> &mut Self {
+        self.timeline_capacity = 0;
+        self
+    }
+
+    /// Check if the timeline is being recorded
+    pub fn is_recording(&self) -> bool {
+        self.timeline_capacity > 0
+    }
+
+    /// Return the recorded frames, oldest first
+    pub fn timeline(&self) -> impl DoubleEndedIterator<Item = &TimelineFrame> {
+        self.timeline.iter()
+    }
+
+    /// Discard all recorded frames
+    pub fn clear_timeline(&mut self) -> &mut Self {
+        self.timeline.clear();
+        self
+    }
+
+    /// Return every recorded system run that changed `component` on `entity`,
+    /// oldest first, along with the frame it ran in.
+    pub fn find_mutations(
+        &self,
+        entity: Entity,
+        component: ComponentId,
+    ) -> impl Iterator<Item = (u64, &SystemRunRecord)> {
+        self.timeline.iter().flat_map(move |frame| {
+            frame
+                .systems
+                .iter()
+                .filter(move |record| record.mutations.contains(&(entity, component)))
+                .map(move |record| (frame.frame, record))
+        })
+    }
+
     /// lookup the first system for the supplied schedule index
     fn first_system_index_for_schedule(&self, index: usize) -> usize {
         let Some(label) = self.schedule_order.get(index) else {
@@ -384,6 +641,17 @@ impl Stepping {
 
     /// Advance schedule states for the next render frame
     fn next_frame(&mut self) {
+        self.frame += 1;
+        if self.timeline_capacity > 0 {
+            if self.timeline.len() >= self.timeline_capacity {
+                self.timeline.pop_front();
+            }
+            self.timeline.push_back(TimelineFrame {
+                frame: self.frame,
+                systems: Vec::new(),
+            });
+        }
+
         // if stepping is enabled; reset our internal state for the start of
         // the next frame
         if self.action != Action::RunAll {
@@ -500,6 +768,19 @@ impl Stepping {
                         }
                     }
                 }
+                Update::AddConditionalBreakpoint(label, system, condition) => {
+                    match self.schedule_states.get_mut(&label) {
+                        Some(state) => state
+                            .conditional_breakpoints
+                            .push(ConditionalBreakpoint { system, condition }),
+                        None => {
+                            warn!(
+                                "stepping is not enabled for schedule {label:?}; \
+                                use `.add_stepping({label:?})` to enable stepping"
+                            );
+                        }
+                    }
+                }
             }
         }
 
@@ -517,32 +798,12 @@ impl Stepping {
 
         // grab the label and state for this schedule
         let label = schedule.label();
+        if !self.schedule_states.contains_key(&label) {
+            return None;
+        }
+        let index = self.schedule_order_index(label);
         let state = self.schedule_states.get_mut(&label)?;
 
-        // Stepping is enabled, and this schedule is supposed to be stepped.
-        //
-        // We need to maintain a list of schedules in the order that they call
-        // this function. We'll check the ordered list now to see if this
-        // schedule is present. If not, we'll add it after the last schedule
-        // that called this function. Finally we want to save off the index of
-        // this schedule in the ordered schedule list. This is used to
-        // determine if this is the schedule the cursor is pointed at.
-        let index = self.schedule_order.iter().position(|l| *l == label);
-        let index = match (index, self.previous_schedule) {
-            (Some(index), _) => index,
-            (None, None) => {
-                self.schedule_order.insert(0, label);
-                0
-            }
-            (None, Some(last)) => {
-                self.schedule_order.insert(last + 1, label);
-                last + 1
-            }
-        };
-        // Update the index of the previous schedule to be the index of this
-        // schedule for the next call
-        self.previous_schedule = Some(index);
-
         #[cfg(test)]
         debug!(
             "cursor {:?}, index {}, label {:?}",
@@ -592,6 +853,277 @@ impl Stepping {
 
         Some(skip_list)
     }
+
+    /// Get the list of systems `schedule` should skip for this render frame,
+    /// and take the conditional breakpoints its executor should check before
+    /// each system runs.
+    #[cfg_attr(
+        not(feature = "bevy_debug_stepping"),
+        expect(
+            dead_code,
+            reason = "only called by `Schedule::run` when stepping is compiled in"
+        )
+    )]
+    pub(crate) fn begin_schedule_run(
+        world: &mut World,
+        schedule: &Schedule,
+    ) -> (Option<FixedBitSet>, Option<ScheduleBreakpoints>) {
+        let Some(mut stepping) = world.get_resource_mut::<Stepping>() else {
+            return (None, None);
+        };
+        let action = stepping.action;
+        let cursor = stepping.cursor;
+        let skip_list = stepping.skipped_systems(schedule);
+        let breakpoints = stepping.take_breakpoints(schedule, action, cursor, skip_list.as_ref());
+        (skip_list, breakpoints)
+    }
+
+    /// Give back the conditional breakpoints taken by
+    /// [`begin_schedule_run`](Self::begin_schedule_run), and record the
+    /// systems that ran in `schedule` since `start` into the timeline.
+    #[cfg_attr(
+        not(feature = "bevy_debug_stepping"),
+        expect(
+            dead_code,
+            reason = "only called by `Schedule::run` when stepping is compiled in"
+        )
+    )]
+    pub(crate) fn end_schedule_run(
+        world: &mut World,
+        schedule: &Schedule,
+        breakpoints: Option<ScheduleBreakpoints>,
+        start: Tick,
+    ) {
+        if let Some(breakpoints) = breakpoints
+            && let Some(mut stepping) = world.get_resource_mut::<Stepping>()
+        {
+            stepping.return_breakpoints(schedule, breakpoints);
+        }
+        Self::record_schedule_run(world, schedule, start);
+    }
+
+    /// Take the conditional breakpoints of `schedule` that can stop a system
+    /// during this run, given the `action` and `cursor` from before the skip
+    /// list was computed.
+    fn take_breakpoints(
+        &mut self,
+        schedule: &Schedule,
+        action: Action,
+        cursor: Cursor,
+        skip_list: Option<&FixedBitSet>,
+    ) -> Option<ScheduleBreakpoints> {
+        let label = schedule.label();
+
+        // Conditional breakpoints only stop systems that would otherwise run:
+        // any system while stepping is disabled, or the systems we continue
+        // through in the cursor schedule.  Like regular breakpoints, they
+        // don't stop the system under the cursor.
+        let start = match action {
+            Action::RunAll => None,
+            Action::Continue if self.schedule_order.get(cursor.schedule) == Some(&label) => {
+                Some(cursor.system)
+            }
+            _ => return None,
+        };
+        let state = self.schedule_states.get_mut(&label)?;
+        if state.conditional_breakpoints.is_empty() {
+            return None;
+        }
+
+        let mut eligible = FixedBitSet::with_capacity(schedule.systems_len());
+        let systems = state
+            .conditional_breakpoints
+            .iter()
+            .map(|breakpoint| {
+                let mut systems = FixedBitSet::with_capacity(schedule.systems_len());
+                for (i, (key, system)) in schedule.systems().unwrap().enumerate() {
+                    let node = NodeId::System(key);
+                    let matches = match breakpoint.system {
+                        SystemIdentifier::Type(type_id) => system.system_type() == type_id,
+                        SystemIdentifier::Node(id) => id == node,
+                    };
+                    let runs = start.is_none_or(|start| {
+                        i != start
+                            && !skip_list.is_some_and(|skip| skip.contains(i))
+                            && matches!(
+                                state.behaviors.get(&node),
+                                None | Some(SystemBehavior::Continue)
+                            )
+                    });
+                    if matches && runs {
+                        systems.insert(i);
+                    }
+                }
+                eligible.union_with(&systems);
+                systems
+            })
+            .collect();
+        if eligible.is_clear() {
+            return None;
+        }
+
+        Some(ScheduleBreakpoints {
+            breakpoints: mem::take(&mut state.conditional_breakpoints),
+            systems,
+            eligible,
+            hit: None,
+        })
+    }
+
+    /// Put the conditional breakpoints of `schedule` back after it ran. If one
+    /// of them was hit, stepping is enabled with the cursor on its system.
+    fn return_breakpoints(&mut self, schedule: &Schedule, breakpoints: ScheduleBreakpoints) {
+        let label = schedule.label();
+        let Some(state) = self.schedule_states.get_mut(&label) else {
+            return;
+        };
+        state.conditional_breakpoints = breakpoints.breakpoints;
+        let Some(system) = breakpoints.hit else {
+            return;
+        };
+        state.sync_schedule(schedule);
+
+        if let Some((_, s)) = schedule.systems().unwrap().nth(system) {
+            if self.action == Action::RunAll {
+                info!(
+                    "conditional breakpoint hit before {}; enabled stepping",
+                    s.name()
+                );
+            } else {
+                info!("conditional breakpoint hit before {}", s.name());
+            }
+        }
+        let index = self.schedule_order_index(label);
+        self.action = Action::Waiting;
+        self.cursor = Cursor {
+            schedule: index,
+            system,
+        };
+    }
+
+    /// Record the systems that ran in `schedule` since `start`, and the
+    /// watched components they changed, into the current timeline frame.
+    fn record_schedule_run(world: &mut World, schedule: &Schedule, start: Tick) {
+        let label = schedule.label();
+        let Some(stepping) = world.get_resource::<Stepping>() else {
+            return;
+        };
+        if !stepping.is_recording() || !stepping.schedule_states.contains_key(&label) {
+            return;
+        }
+
+        // Only components that are watched, directly or by a breakpoint, are
+        // checked for changes
+        let mut watched = stepping.watched.clone();
+        let watched_by_breakpoints = stepping
+            .schedule_states
+            .values()
+            .flat_map(|state| &state.conditional_breakpoints)
+            .filter_map(|breakpoint| match breakpoint.condition {
+                BreakCondition::ComponentChanged {
+                    entity, component, ..
+                } => Some((entity, component)),
+                BreakCondition::Predicate(_) => None,
+            });
+        for pair in watched_by_breakpoints {
+            if !watched.contains(&pair) {
+                watched.push(pair);
+            }
+        }
+
+        // The first system to run during the schedule claims `start` as its
+        // tick, so look for anything newer than the tick before it.
+        let start = Tick::new(start.get().wrapping_sub(1));
+        let this_run = world.read_change_tick();
+        let mut records = Vec::new();
+        for (key, system) in schedule.systems().unwrap() {
+            let last_run = system.get_last_run();
+            if last_run.is_newer_than(start, this_run) {
+                records.push((
+                    last_run,
+                    SystemRunRecord {
+                        schedule: label,
+                        node: NodeId::System(key),
+                        name: system.name(),
+                        mutations: Vec::new(),
+                    },
+                ));
+            }
+        }
+        if records.is_empty() {
+            return;
+        }
+        records.sort_by_key(|(last_run, _)| last_run.relative_to(start).get());
+
+        // Every system run gets its own change tick, so each changed component
+        // can be attributed to the system whose run tick matches
+        let by_tick = records
+            .iter()
+            .enumerate()
+            .map(|(i, (last_run, _))| (*last_run, i))
+            .collect::<HashMap<_, _>>();
+        for (entity, component) in watched {
+            let Some(component) = world.components().get_id(component) else {
+                continue;
+            };
+            let Some(ticks) = world
+                .get_entity(entity)
+                .ok()
+                .and_then(|entity| entity.get_change_ticks_by_id(component))
+            else {
+                continue;
+            };
+            if ticks.is_changed(start, this_run)
+                && let Some(&i) = by_tick.get(&ticks.changed)
+            {
+                records[i].1.mutations.push((entity, component));
+            }
+        }
+
+        let mut stepping = world.resource_mut::<Stepping>();
+        let frame = stepping.frame;
+        if stepping.timeline.is_empty() {
+            stepping.timeline.push_back(TimelineFrame {
+                frame,
+                systems: Vec::new(),
+            });
+        }
+        stepping
+            .timeline
+            .back_mut()
+            .unwrap()
+            .systems
+            .extend(records.into_iter().map(|(_, record)| record));
+    }
+
+    /// Find the index of the schedule in the stepping frame, adding it to the
+    /// schedule order if it has not been seen before
+    fn schedule_order_index(&mut self, label: InternedScheduleLabel) -> usize {
+        // Stepping is enabled, and this schedule is supposed to be stepped.
+        //
+        // We need to maintain a list of schedules in the order that they call
+        // this function. We'll check the ordered list now to see if this
+        // schedule is present. If not, we'll add it after the last schedule
+        // that called this function. Finally we want to save off the index of
+        // this schedule in the ordered schedule list. This is used to
+        // determine if this is the schedule the cursor is pointed at.
+        let index = self.schedule_order.iter().position(|l| *l == label);
+        let index = match (index, self.previous_schedule) {
+            (Some(index), _) => index,
+            (None, None) => {
+                self.schedule_order.insert(0, label);
+                0
+            }
+            (None, Some(last)) => {
+                self.schedule_order.insert(last + 1, label);
+                last + 1
+            }
+        };
+        // Update the index of the previous schedule to be the index of this
+        // schedule for the next call
+        self.previous_schedule = Some(index);
+        index
+    }
 }
 
 #[derive(Default)]
@@ -612,6 +1144,9 @@ struct ScheduleState {
 
     /// This field contains the first steppable system in the schedule.
     first: Option<usize>,
+
+    /// breakpoints that only stop execution when their condition is met
+    conditional_breakpoints: Vec<ConditionalBreakpoint>,
 }
 
 impl ScheduleState {
@@ -643,12 +1178,15 @@ impl ScheduleState {
                 self.behavior_updates.insert(type_id, None);
             }
         }
+        self.conditional_breakpoints
+            .retain(|breakpoint| breakpoint.system != system);
     }
 
     // clear all system behaviors
     fn clear_behaviors(&mut self) {
         self.behaviors.clear();
         self.behavior_updates.clear();
+        self.conditional_breakpoints.clear();
         self.first = None;
     }
 
@@ -678,14 +1216,8 @@ impl ScheduleState {
         debug!("apply_updates(): {:?}", self.behaviors);
     }
 
-    fn skipped_systems(
-        &mut self,
-        schedule: &Schedule,
-        start: usize,
-        mut action: Action,
-    ) -> (FixedBitSet, Option<usize>) {
-        use core::cmp::Ordering;
-
+    // refresh the cached node ids and apply pending behavior updates
+    fn sync_schedule(&mut self, schedule: &Schedule) {
         // if our NodeId list hasn't been populated, copy it over from the
         // schedule
         if self.node_ids.len() != schedule.systems_len() {
@@ -698,6 +1230,17 @@ impl ScheduleState {
         if !self.behavior_updates.is_empty() {
             self.apply_behavior_updates(schedule);
         }
+    }
+
+    fn skipped_systems(
+        &mut self,
+        schedule: &Schedule,
+        start: usize,
+        mut action: Action,
+    ) -> (FixedBitSet, Option<usize>) {
+        use core::cmp::Ordering;
+
+        self.sync_schedule(schedule);
 
         // if we don't have a first system set, set it now
         if self.first.is_none() {
@@ -1559,4 +2102,166 @@ mod tests {
             ]
         );
     }
+
+    #[derive(Resource, Default)]
+    struct RunLog(Vec<&'static str>);
+
+    #[derive(Resource, Default)]
+    struct Armed(bool);
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    fn log_first(mut log: ResMut<RunLog>) {
+        log.0.push("first");
+    }
+    fn log_second(mut log: ResMut<RunLog>) {
+        log.0.push("second");
+    }
+    fn damage(mut log: ResMut<RunLog>, mut query: Query<&mut Health>) {
+        log.0.push("damage");
+        for mut health in &mut query {
+            health.0 -= 1;
+        }
+    }
+
+    fn run_frame(world: &mut World, schedule: &mut Schedule) -> Vec<&'static str> {
+        world.resource_mut::<Stepping>().next_frame();
+        schedule.run(world);
+        mem::take(&mut world.resource_mut::<RunLog>().0)
+    }
+
+    #[test]
+    fn conditional_breakpoint_enables_stepping() {
+        let mut world = World::new();
+        world.init_resource::<RunLog>();
+        world.init_resource::<Armed>();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((log_first, log_second).chain());
+
+        let mut stepping = Stepping::new();
+        stepping.add_schedule(TestSchedule).break_when(
+            TestSchedule,
+            log_second,
+            |world: &World| world.resource::<Armed>().0,
+        );
+        world.insert_resource(stepping);
+
+        assert_eq!(run_frame(&mut world, &mut schedule), ["first", "second"]);
+        assert!(!world.resource::<Stepping>().is_enabled());
+
+        world.resource_mut::<Armed>().0 = true;
+        assert_eq!(run_frame(&mut world, &mut schedule), ["first"]);
+        assert!(world.resource::<Stepping>().is_enabled());
+        assert_eq!(run_frame(&mut world, &mut schedule), Vec::<&str>::new());
+
+        // continuing runs the system the breakpoint stopped at
+        world.resource_mut::<Stepping>().continue_frame();
+        assert_eq!(run_frame(&mut world, &mut schedule), ["second"]);
+
+        // clearing the system removes the conditional breakpoint
+        world
+            .resource_mut::<Stepping>()
+            .clear_system(TestSchedule, log_second)
+            .disable();
+        assert_eq!(run_frame(&mut world, &mut schedule), ["first", "second"]);
+    }
+
+    #[test]
+    fn break_on_component_change() {
+        let mut world = World::new();
+        world.init_resource::<RunLog>();
+        let entity = world.spawn(Health(10)).id();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((log_first, log_second).chain());
+
+        let mut stepping = Stepping::new();
+        stepping
+            .add_schedule(TestSchedule)
+            .break_on_change::<Health, _>(TestSchedule, log_second, entity);
+        world.insert_resource(stepping);
+
+        assert_eq!(run_frame(&mut world, &mut schedule), ["first", "second"]);
+        assert_eq!(run_frame(&mut world, &mut schedule), ["first", "second"]);
+
+        world.get_mut::<Health>(entity).unwrap().0 = 5;
+        assert_eq!(run_frame(&mut world, &mut schedule), ["first"]);
+        assert!(world.resource::<Stepping>().is_enabled());
+    }
+
+    #[test]
+    fn break_on_change_in_same_run() {
+        let mut world = World::new();
+        world.init_resource::<RunLog>();
+        let entity = world.spawn(Health(10)).id();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((log_first, damage, log_second).chain());
+
+        let mut stepping = Stepping::new();
+        stepping
+            .add_schedule(TestSchedule)
+            .break_on_change::<Health, _>(TestSchedule, log_second, entity);
+        world.insert_resource(stepping);
+
+        // the first check only records the current tick
+        assert_eq!(
+            run_frame(&mut world, &mut schedule),
+            ["first", "damage", "second"]
+        );
+
+        // `damage` changes the health before `log_second` runs
+        assert_eq!(run_frame(&mut world, &mut schedule), ["first", "damage"]);
+        assert!(world.resource::<Stepping>().is_enabled());
+        let (_, node) = world.resource::<Stepping>().cursor().unwrap();
+        let (_, system) = schedule
+            .systems()
+            .unwrap()
+            .find(|(key, _)| NodeId::System(*key) == node)
+            .unwrap();
+        assert!(system.name().as_string().ends_with("log_second"));
+
+        // continuing runs the system the breakpoint stopped at
+        world.resource_mut::<Stepping>().continue_frame();
+        assert_eq!(run_frame(&mut world, &mut schedule), ["second"]);
+    }
+
+    #[test]
+    fn timeline_records_mutations() {
+        let mut world = World::new();
+        world.init_resource::<RunLog>();
+        let entity = world.spawn(Health(10)).id();
+        let health = world.component_id::<Health>().unwrap();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((log_first, damage, log_second).chain());
+
+        let mut stepping = Stepping::new();
+        stepping
+            .add_schedule(TestSchedule)
+            .record_timeline(2)
+            .watch::<Health>(entity);
+        world.insert_resource(stepping);
+
+        for _ in 0..3 {
+            run_frame(&mut world, &mut schedule);
+        }
+
+        let stepping = world.resource::<Stepping>();
+        let frames = stepping
+            .timeline()
+            .map(|frame| frame.frame)
+            .collect::<Vec<_>>();
+        assert_eq!(frames, [2, 3]);
+        for frame in stepping.timeline() {
+            assert_eq!(frame.systems.len(), 3);
+        }
+
+        let mutations = stepping
+            .find_mutations(entity, health)
+            .map(|(frame, record)| (frame, record.name.as_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(mutations.len(), 2);
+        for (_, name) in mutations {
+            assert!(name.ends_with("damage"), "{name}");
+        }
+    }
 }
@@ -6,6 +6,7 @@ mod entity_access;
 mod entity_fetch;
 mod filtered_resource;
 mod identifier;
+mod snapshot;
 mod spawn_batch;
 
 pub mod error;
@@ -27,6 +28,7 @@ pub use entity_access::{
 pub use entity_fetch::{EntityFetcher, WorldEntityFetch};
 pub use filtered_resource::*;
 pub use identifier::WorldId;
+pub use snapshot::*;
 pub use spawn_batch::*;
 
 use crate::{
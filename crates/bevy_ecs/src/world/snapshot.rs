@@ -0,0 +1,693 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::any::{Any, TypeId};
+
+use crate::{
+    archetype::ArchetypeEntity,
+    change_detection::{ComponentTicks, Tick},
+    component::Component,
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    resource::Resource,
+    world::{EntityRef, EntityWorldMut, World},
+};
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    crate::reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    bevy_reflect::{std_traits::ReflectDefault, Reflect, TypeRegistration},
+    log::warn,
+};
+
+/// Marks an [`Entity`] as part of the state captured by a [`WorldSnapshot`].
+///
+/// Only entities with this component are captured, and restoring a snapshot despawns any entity with this
+/// component that was not part of the snapshot.
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Component, Default, Debug, Clone)
+)]
+#[derive(Component, Clone, Copy, Default, Debug)]
+pub struct Rollback;
+
+type SnapshotValue = Box<dyn Any + Send + Sync>;
+
+#[derive(Clone)]
+enum SnapshotComponent {
+    Clone {
+        capture: fn(EntityRef) -> Option<SnapshotValue>,
+        restore: fn(
+            &mut EntityWorldMut,
+            Option<&SnapshotValue>,
+            &mut EntityHashMap<Entity>,
+            &RestoreTicks,
+        ),
+    },
+    #[cfg(feature = "bevy_reflect")]
+    Reflect {
+        reflect_component: ReflectComponent,
+        type_id: TypeId,
+    },
+}
+
+#[derive(Clone)]
+enum SnapshotResource {
+    Clone {
+        capture: fn(&World) -> Option<SnapshotValue>,
+        restore: fn(&mut World, Option<&SnapshotValue>, &mut EntityHashMap<Entity>, &RestoreTicks),
+    },
+    #[cfg(feature = "bevy_reflect")]
+    Reflect {
+        reflect_component: ReflectComponent,
+        type_id: TypeId,
+    },
+}
+
+/// Decides which captured values can be left in place when restoring a [`WorldSnapshot`].
+struct RestoreTicks {
+    /// The change tick of the world when the snapshot was captured.
+    captured_at: Tick,
+    this_run: Tick,
+    /// Whether any captured entity was respawned, in which case entity references in unchanged values may be stale.
+    respawned: bool,
+}
+
+impl RestoreTicks {
+    /// Returns `true` if a value with the given ticks has not changed since the snapshot was captured.
+    ///
+    /// Values changed during the tick of the capture are treated as changed, since they may have changed after it.
+    fn unchanged(&self, ticks: Option<ComponentTicks>) -> bool {
+        !self.respawned
+            && ticks
+                .is_some_and(|ticks| self.captured_at.is_newer_than(ticks.changed, self.this_run))
+    }
+}
+
+/// Selects the components and resources that are copied into a [`WorldSnapshot`], for example to implement
+/// rollback networking.
+///
+/// Components are captured for every entity with the [`Rollback`] component. Entity references inside captured
+/// values are remapped through [`MapEntities`](crate::entity::MapEntities) when restoring, so they keep pointing
+/// at the right entities even if those entities had to be respawned.
+///
+/// Both components and resources can be captured by cloning them or through reflection.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::{Rollback, SnapshotConfig};
+/// #[derive(Component, Clone, PartialEq, Debug)]
+/// struct Position(i32);
+///
+/// let config = SnapshotConfig::new().with_component::<Position>();
+///
+/// let mut world = World::new();
+/// let entity = world.spawn((Rollback, Position(0))).id();
+/// let snapshot = config.capture(&world);
+///
+/// world.entity_mut(entity).insert(Position(10));
+/// config.restore(&mut world, &snapshot);
+/// assert_eq!(world.get::<Position>(entity), Some(&Position(0)));
+/// ```
+#[derive(Clone, Default)]
+pub struct SnapshotConfig {
+    type_ids: Vec<TypeId>,
+    components: Vec<SnapshotComponent>,
+    resources: Vec<SnapshotResource>,
+}
+
+impl SnapshotConfig {
+    /// Creates a configuration that does not capture any components or resources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captures the component `C` by cloning it.
+    pub fn with_component<C: Component + Clone>(mut self) -> Self {
+        if self.register_type::<C>() {
+            self.components.push(SnapshotComponent::Clone {
+                capture: |entity| {
+                    entity
+                        .get::<C>()
+                        .map(|component| Box::new(component.clone()) as SnapshotValue)
+                },
+                restore: |entity, value, entity_map, ticks| match value
+                    .and_then(|value| value.downcast_ref::<C>())
+                {
+                    Some(_) if ticks.unchanged(entity.get_change_ticks::<C>()) => {}
+                    Some(component) => {
+                        let mut component = component.clone();
+                        C::map_entities(&mut component, entity_map);
+                        entity.insert(component);
+                    }
+                    None => {
+                        entity.remove::<C>();
+                    }
+                },
+            });
+        }
+        self
+    }
+
+    /// Captures the component described by `registration` through its [`ReflectComponent`] type data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not registered with [`ReflectComponent`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn with_reflect_component(mut self, registration: &TypeRegistration) -> Self {
+        let reflect_component = registration.data::<ReflectComponent>().unwrap_or_else(|| {
+            panic!(
+                "`{}` cannot be captured by reflection because it is not registered with `ReflectComponent`",
+                registration.type_info().type_path()
+            )
+        });
+        if !self.type_ids.contains(&registration.type_id()) {
+            self.type_ids.push(registration.type_id());
+            self.components.push(SnapshotComponent::Reflect {
+                reflect_component: reflect_component.clone(),
+                type_id: registration.type_id(),
+            });
+        }
+        self
+    }
+
+    /// Captures the resource `R` by cloning it.
+    pub fn with_resource<R: Resource + Clone>(mut self) -> Self {
+        if self.register_type::<R>() {
+            self.resources.push(SnapshotResource::Clone {
+                capture: |world| {
+                    world
+                        .get_resource::<R>()
+                        .map(|resource| Box::new(resource.clone()) as SnapshotValue)
+                },
+                restore: |world, value, entity_map, ticks| match value
+                    .and_then(|value| value.downcast_ref::<R>())
+                {
+                    Some(_) if ticks.unchanged(world.get_resource_change_ticks::<R>()) => {}
+                    Some(resource) => {
+                        let mut resource = resource.clone();
+                        R::map_entities(&mut resource, entity_map);
+                        world.insert_resource(resource);
+                    }
+                    None => {
+                        world.remove_resource::<R>();
+                    }
+                },
+            });
+        }
+        self
+    }
+
+    /// Captures the resource described by `registration` through its [`ReflectResource`] type data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not registered with [`ReflectResource`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn with_reflect_resource(mut self, registration: &TypeRegistration) -> Self {
+        // `ReflectResource` registers the `ReflectComponent` of the resource's component
+        let reflect_component = registration
+            .data::<ReflectResource>()
+            .and(registration.data::<ReflectComponent>())
+            .unwrap_or_else(|| {
+                panic!(
+                    "`{}` cannot be captured by reflection because it is not registered with `ReflectResource`",
+                    registration.type_info().type_path()
+                )
+            });
+        if !self.type_ids.contains(&registration.type_id()) {
+            self.type_ids.push(registration.type_id());
+            self.resources.push(SnapshotResource::Reflect {
+                reflect_component: reflect_component.clone(),
+                type_id: registration.type_id(),
+            });
+        }
+        self
+    }
+
+    fn register_type<T: 'static>(&mut self) -> bool {
+        if self.type_ids.contains(&TypeId::of::<T>()) {
+            return false;
+        }
+        self.type_ids.push(TypeId::of::<T>());
+        true
+    }
+
+    /// Copies the configured components of every [`Rollback`] entity, along with the configured resources,
+    /// into a new [`WorldSnapshot`].
+    pub fn capture(&self, world: &World) -> WorldSnapshot {
+        let entities = rollback_entities(world)
+            .into_iter()
+            .map(|entity| {
+                let entity_ref = world.entity(entity);
+                let components = self
+                    .components
+                    .iter()
+                    .map(|component| match component {
+                        SnapshotComponent::Clone { capture, .. } => capture(entity_ref),
+                        #[cfg(feature = "bevy_reflect")]
+                        SnapshotComponent::Reflect {
+                            reflect_component, ..
+                        } => reflect_component
+                            .reflect(entity_ref)
+                            .and_then(|value| clone_reflect(value))
+                            .map(|value| Box::new(value) as SnapshotValue),
+                    })
+                    .collect();
+                SnapshotEntity { entity, components }
+            })
+            .collect();
+        let resources = self
+            .resources
+            .iter()
+            .map(|resource| match resource {
+                SnapshotResource::Clone { capture, .. } => capture(world),
+                #[cfg(feature = "bevy_reflect")]
+                SnapshotResource::Reflect {
+                    reflect_component,
+                    type_id,
+                } => world
+                    .components()
+                    .get_id(*type_id)
+                    .and_then(|component_id| world.resource_entities().get(component_id))
+                    .and_then(|entity| reflect_component.reflect(world.entity(entity)))
+                    .and_then(|value| clone_reflect(value))
+                    .map(|value| Box::new(value) as SnapshotValue),
+            })
+            .collect();
+        WorldSnapshot {
+            entities,
+            resources,
+            captured_at: world.read_change_tick(),
+        }
+    }
+
+    /// Restores the state captured in `snapshot`.
+    ///
+    /// - Captured entities that no longer exist are respawned.
+    /// - [`Rollback`] entities that were not captured are despawned.
+    /// - Configured components and resources are set to their captured values, or removed if they were
+    ///   absent when the snapshot was captured.
+    ///
+    /// Components and resources that have not changed since the snapshot was captured, according to their
+    /// change ticks, are left in place: they are not re-inserted, so they do not trigger change detection, hooks
+    /// or observers. Everything else is re-inserted, even if it was changed back to its captured value. Values
+    /// mutated while bypassing change detection are not detected, and all values are re-inserted if a captured
+    /// entity had to be respawned, so that their entity references are remapped.
+    ///
+    /// Returns the mapping from the entities in `snapshot` to the entities they were restored to. Entities
+    /// that still existed map to themselves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `snapshot` was not captured with this configuration, or if a reflected component or resource is
+    /// restored without an [`AppTypeRegistry`](crate::reflect::AppTypeRegistry) resource in the world.
+    pub fn restore(&self, world: &mut World, snapshot: &WorldSnapshot) -> EntityHashMap<Entity> {
+        assert_eq!(
+            snapshot.resources.len(),
+            self.resources.len(),
+            "The snapshot was captured with a different `SnapshotConfig`"
+        );
+
+        let mut entity_map = EntityHashMap::default();
+        for snapshot_entity in &snapshot.entities {
+            let entity = snapshot_entity.entity;
+            let target = if world.get_entity(entity).is_ok() {
+                entity
+            } else {
+                world.spawn_empty().id()
+            };
+            entity_map.insert(entity, target);
+        }
+
+        let ticks = RestoreTicks {
+            captured_at: snapshot.captured_at,
+            this_run: world.change_tick(),
+            respawned: entity_map.iter().any(|(entity, target)| entity != target),
+        };
+
+        let restored = entity_map.values().copied().collect::<EntityHashSet>();
+        for entity in rollback_entities(world) {
+            if !restored.contains(&entity) {
+                world.despawn(entity);
+            }
+        }
+
+        #[cfg(feature = "bevy_reflect")]
+        let uses_reflection = self
+            .components
+            .iter()
+            .any(|component| matches!(component, SnapshotComponent::Reflect { .. }))
+            || self
+                .resources
+                .iter()
+                .any(|resource| matches!(resource, SnapshotResource::Reflect { .. }));
+        #[cfg(feature = "bevy_reflect")]
+        let type_registry = uses_reflection.then(|| {
+                world
+                    .get_resource::<AppTypeRegistry>()
+                    .expect(
+                        "Restoring reflected components and resources requires the `AppTypeRegistry` resource",
+                    )
+                    .clone()
+            });
+
+        for snapshot_entity in &snapshot.entities {
+            assert_eq!(
+                snapshot_entity.components.len(),
+                self.components.len(),
+                "The snapshot was captured with a different `SnapshotConfig`"
+            );
+            // A hook may have despawned the entity while restoring an earlier one
+            let Ok(mut entity) = world.get_entity_mut(entity_map[&snapshot_entity.entity]) else {
+                continue;
+            };
+            if !entity.contains::<Rollback>() {
+                entity.insert(Rollback);
+            }
+            for (component, value) in self.components.iter().zip(&snapshot_entity.components) {
+                match component {
+                    SnapshotComponent::Clone { restore, .. } => {
+                        restore(&mut entity, value.as_ref(), &mut entity_map, &ticks);
+                    }
+                    #[cfg(feature = "bevy_reflect")]
+                    SnapshotComponent::Reflect {
+                        reflect_component,
+                        type_id,
+                    } => {
+                        let current_ticks = entity
+                            .world()
+                            .components()
+                            .get_id(*type_id)
+                            .and_then(|component_id| entity.get_change_ticks_by_id(component_id));
+                        if value.is_some() && ticks.unchanged(current_ticks) {
+                            continue;
+                        }
+                        match value
+                            .as_ref()
+                            .and_then(|value| value.downcast_ref::<Box<dyn Reflect>>())
+                            .and_then(|value| clone_reflect(value.as_ref()))
+                        {
+                            Some(mut value) => {
+                                reflect_component.map_entities(value.as_mut(), &mut entity_map);
+                                let type_registry = type_registry.as_ref().unwrap().read();
+                                reflect_component.insert(
+                                    &mut entity,
+                                    value.as_partial_reflect(),
+                                    &type_registry,
+                                );
+                            }
+                            None => reflect_component.remove(&mut entity),
+                        }
+                    }
+                }
+            }
+        }
+
+        for (resource, value) in self.resources.iter().zip(&snapshot.resources) {
+            match resource {
+                SnapshotResource::Clone { restore, .. } => {
+                    restore(world, value.as_ref(), &mut entity_map, &ticks);
+                }
+                #[cfg(feature = "bevy_reflect")]
+                SnapshotResource::Reflect {
+                    reflect_component,
+                    type_id,
+                } => {
+                    let component_id = world.components().get_id(*type_id);
+                    let current_ticks = component_id.and_then(|component_id| {
+                        world.get_resource_change_ticks_by_id(component_id)
+                    });
+                    if value.is_some() && ticks.unchanged(current_ticks) {
+                        continue;
+                    }
+                    match value
+                        .as_ref()
+                        .and_then(|value| value.downcast_ref::<Box<dyn Reflect>>())
+                        .and_then(|value| clone_reflect(value.as_ref()))
+                    {
+                        Some(mut value) => {
+                            reflect_component.map_entities(value.as_mut(), &mut entity_map);
+                            // Like `World::insert_resource_by_id`, inserting the resource's component on an empty entity
+                            // creates the resource
+                            let resource_entity = component_id.and_then(|component_id| {
+                                world.resource_entities().get(component_id)
+                            });
+                            let mut resource_entity = match resource_entity {
+                                Some(entity) => world.entity_mut(entity),
+                                None => world.spawn_empty(),
+                            };
+                            let type_registry = type_registry.as_ref().unwrap().read();
+                            reflect_component.insert(
+                                &mut resource_entity,
+                                value.as_partial_reflect(),
+                                &type_registry,
+                            );
+                        }
+                        None => {
+                            if let Some(component_id) = component_id {
+                                world.remove_resource_by_id(component_id);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        world.flush();
+        entity_map
+    }
+}
+
+struct SnapshotEntity {
+    entity: Entity,
+    components: Vec<Option<SnapshotValue>>,
+}
+
+/// A copy of the components and resources selected by a [`SnapshotConfig`], captured with
+/// [`SnapshotConfig::capture`] and restored with [`SnapshotConfig::restore`].
+pub struct WorldSnapshot {
+    entities: Vec<SnapshotEntity>,
+    resources: Vec<Option<SnapshotValue>>,
+    captured_at: Tick,
+}
+
+impl WorldSnapshot {
+    /// Iterates the [`Rollback`] entities captured in this snapshot.
+    pub fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + '_ {
+        self.entities.iter().map(|entity| entity.entity)
+    }
+
+    /// Returns `true` if this snapshot contains the given entity.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities
+            .iter()
+            .any(|captured| captured.entity == entity)
+    }
+}
+
+/// Returns every entity with the [`Rollback`] component, including disabled entities.
+fn rollback_entities(world: &World) -> Vec<Entity> {
+    let Some(rollback) = world.component_id::<Rollback>() else {
+        return Vec::new();
+    };
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| archetype.contains(rollback))
+        .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+        .collect()
+}
+
+#[cfg(feature = "bevy_reflect")]
+fn clone_reflect(value: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+    match value.reflect_clone() {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!(
+                "Could not capture `{}` in a snapshot: {error}",
+                value.reflect_type_path()
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::Component,
+        entity::Entity,
+        resource::Resource,
+        world::{Rollback, SnapshotConfig, World},
+    };
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Target(#[entities] Entity);
+
+    #[derive(Resource, Clone, PartialEq, Debug)]
+    struct Frame(u32);
+
+    fn config() -> SnapshotConfig {
+        SnapshotConfig::new()
+            .with_component::<Health>()
+            .with_component::<Target>()
+            .with_resource::<Frame>()
+    }
+
+    #[test]
+    fn restore_components_and_resources() {
+        let config = config();
+        let mut world = World::new();
+        world.insert_resource(Frame(1));
+        let a = world.spawn((Rollback, Health(10))).id();
+        let untracked = world.spawn(Health(5)).id();
+        let snapshot = config.capture(&world);
+        assert_eq!(snapshot.entities().collect::<alloc::vec::Vec<_>>(), [a]);
+
+        world.insert_resource(Frame(2));
+        world.entity_mut(a).insert((Health(3), Target(untracked)));
+        world.entity_mut(untracked).insert(Health(1));
+
+        config.restore(&mut world, &snapshot);
+        assert_eq!(world.resource::<Frame>(), &Frame(1));
+        assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+        assert!(world.get::<Target>(a).is_none());
+        // Entities without `Rollback` are left alone
+        assert_eq!(world.get::<Health>(untracked), Some(&Health(1)));
+
+        world.remove_resource::<Frame>();
+        let empty = config.capture(&world);
+        world.insert_resource(Frame(3));
+        config.restore(&mut world, &empty);
+        assert!(!world.contains_resource::<Frame>());
+    }
+
+    #[test]
+    fn restore_spawns_and_despawns_entities() {
+        let config = config();
+        let mut world = World::new();
+        let a = world.spawn((Rollback, Health(10))).id();
+        let b = world.spawn((Rollback, Target(a))).id();
+        let snapshot = config.capture(&world);
+
+        world.despawn(a);
+        let c = world.spawn((Rollback, Health(1))).id();
+
+        let entity_map = config.restore(&mut world, &snapshot);
+        assert!(world.get_entity(c).is_err());
+        assert_eq!(entity_map[&b], b);
+
+        let new_a = entity_map[&a];
+        assert_ne!(new_a, a);
+        assert_eq!(world.get::<Health>(new_a), Some(&Health(10)));
+        assert!(world.entity(new_a).contains::<Rollback>());
+        // Entity references are mapped to the respawned entity
+        assert_eq!(world.get::<Target>(b), Some(&Target(new_a)));
+    }
+
+    #[test]
+    fn restore_skips_unchanged_values() {
+        let config = config();
+        let mut world = World::new();
+        world.insert_resource(Frame(1));
+        let a = world.spawn((Rollback, Health(10))).id();
+        let b = world.spawn((Rollback, Health(20))).id();
+        world.increment_change_tick();
+        let snapshot = config.capture(&world);
+        let captured_at = world.change_tick();
+
+        world.increment_change_tick();
+        world.entity_mut(a).insert(Health(3));
+        config.restore(&mut world, &snapshot);
+
+        assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+        let changed = |world: &World, entity: Entity| {
+            world
+                .entity(entity)
+                .get_change_ticks::<Health>()
+                .unwrap()
+                .changed
+        };
+        assert!(changed(&world, a).is_newer_than(captured_at, world.read_change_tick()));
+        // `b` and the resource did not change, so they were left in place
+        assert!(!changed(&world, b).is_newer_than(captured_at, world.read_change_tick()));
+        assert!(!world
+            .get_resource_change_ticks::<Frame>()
+            .unwrap()
+            .changed
+            .is_newer_than(captured_at, world.read_change_tick()));
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn restore_reflected_resources() {
+        use crate::reflect::{AppTypeRegistry, ReflectResource};
+        use bevy_reflect::Reflect;
+
+        #[derive(Resource, Reflect, PartialEq, Debug)]
+        #[reflect(Resource)]
+        struct Score(u32);
+
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        type_registry.write().register::<Score>();
+        world.insert_resource(type_registry.clone());
+
+        let config = {
+            let type_registry = type_registry.read();
+            SnapshotConfig::new()
+                .with_reflect_resource(type_registry.get(core::any::TypeId::of::<Score>()).unwrap())
+        };
+
+        world.insert_resource(Score(4));
+        let snapshot = config.capture(&world);
+        world.insert_resource(Score(7));
+        config.restore(&mut world, &snapshot);
+        assert_eq!(world.resource::<Score>(), &Score(4));
+
+        // Restoring a snapshot captured with the resource recreates it
+        world.remove_resource::<Score>();
+        config.restore(&mut world, &snapshot);
+        assert_eq!(world.resource::<Score>(), &Score(4));
+
+        world.remove_resource::<Score>();
+        let empty = config.capture(&world);
+        world.insert_resource(Score(1));
+        config.restore(&mut world, &empty);
+        assert!(!world.contains_resource::<Score>());
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn restore_reflected_components() {
+        use crate::reflect::{AppTypeRegistry, ReflectComponent};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Velocity(i32);
+
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        type_registry.write().register::<Velocity>();
+        world.insert_resource(type_registry.clone());
+
+        let config = {
+            let type_registry = type_registry.read();
+            SnapshotConfig::new().with_reflect_component(
+                type_registry
+                    .get(core::any::TypeId::of::<Velocity>())
+                    .unwrap(),
+            )
+        };
+
+        let entity = world.spawn((Rollback, Velocity(4))).id();
+        let snapshot = config.capture(&world);
+        world.entity_mut(entity).insert(Velocity(-1));
+        config.restore(&mut world, &snapshot);
+        assert_eq!(world.get::<Velocity>(entity), Some(&Velocity(4)));
+    }
+}
@@ -5,9 +5,12 @@ use crate::{
 use bevy_ecs::message::MessageWriter;
 use bevy_ecs::prelude::Commands;
 use bevy_ecs::system::ResMut;
-use bevy_input::gamepad::{
-    GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
-    RawGamepadButtonChangedEvent, RawGamepadEvent,
+use bevy_input::{
+    gamepad::{
+        GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+        RawGamepadButtonChangedEvent, RawGamepadEvent,
+    },
+    gamepad_mapping::NoGamepadMappingDatabase,
 };
 use gilrs::{ev::filter::axis_dpad_to_button, EventType, Filter};
 
@@ -19,8 +22,9 @@ pub fn gilrs_event_startup_system(
 ) {
     gilrs.with(|gilrs| {
         for (id, gamepad) in gilrs.gamepads() {
-            // Create entity and add to mapping
-            let entity = commands.spawn_empty().id();
+            // Create entity and add to mapping. Gilrs applies SDL mappings itself and reports
+            // unknown inputs by their platform event code rather than their SDL index.
+            let entity = commands.spawn(NoGamepadMappingDatabase).id();
             gamepads.id_to_entity.insert(id, entity);
             gamepads.entity_to_id.insert(entity, id);
             events.write(GamepadConnectionEvent {
@@ -51,7 +55,7 @@ pub fn gilrs_event_system(
                 EventType::Connected => {
                     let pad = gilrs.gamepad(gilrs_event.id);
                     let entity = gamepads.get_entity(gilrs_event.id).unwrap_or_else(|| {
-                        let entity = commands.spawn_empty().id();
+                        let entity = commands.spawn(NoGamepadMappingDatabase).id();
                         gamepads.id_to_entity.insert(gilrs_event.id, entity);
                         gamepads.entity_to_id.insert(entity, gilrs_event.id);
                         entity
@@ -9,6 +9,9 @@
 //!
 //! This crate is built on top of [GilRs](gilrs), a library
 //! that handles abstracting over platform-specific gamepad APIs.
+//!
+//! Gilrs applies SDL gamepad mappings itself, so the gamepads it reports don't use the
+//! [`GamepadMappingDatabase`](bevy_input::gamepad_mapping::GamepadMappingDatabase).
 
 mod converter;
 mod gilrs_system;
@@ -0,0 +1,110 @@
+use crate::GamepadHaptics;
+use alloc::vec::Vec;
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    message::{MessageCursor, MessageId, Messages},
+    query::With,
+    system::{Local, Query, Res, ResMut},
+};
+use bevy_input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy_time::{Real, Time};
+use core::time::Duration;
+
+/// How the output of each [`GamepadHaptics`] reaches the gamepad.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HapticsBackend {
+    /// Sends the output as [`GamepadRumbleRequest`]s, which are played by the gamepad backend,
+    /// such as `bevy_gilrs`.
+    ///
+    /// Every change of output stops the previous output before starting the new one. Since
+    /// [`GamepadRumbleRequest::Stop`] stops all rumble on the gamepad, the previous output isn't
+    /// stopped while rumble requested by others is playing, and lingers until its request
+    /// expires instead.
+    #[default]
+    RumbleRequests,
+    /// Doesn't send the output anywhere.
+    ///
+    /// Effects are still sequenced and [`GamepadHaptics::output`] is still updated, which makes
+    /// this useful for tests and for platforms without rumble support.
+    Null,
+}
+
+/// How often a constant output is sent again, so that the gamepad keeps rumbling.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How much a motor's intensity has to change before the output is sent again.
+///
+/// Interpolated patterns change a little every frame, and restarting the rumble that often would
+/// flood the gamepad backend.
+const INTENSITY_STEP: f32 = 1.0 / 64.0;
+
+/// Sends the output of every [`GamepadHaptics`] as [`GamepadRumbleRequest`]s.
+///
+/// Used by [`HapticsBackend::RumbleRequests`].
+pub fn send_rumble_requests(
+    time: Res<Time<Real>>,
+    gamepads: Query<(Entity, &GamepadHaptics), With<Gamepad>>,
+    mut sent: Local<EntityHashMap<(GamepadRumbleIntensity, Duration)>>,
+    mut other_rumble: Local<EntityHashMap<Duration>>,
+    mut own_requests: Local<Vec<MessageId<GamepadRumbleRequest>>>,
+    mut cursor: Local<MessageCursor<GamepadRumbleRequest>>,
+    mut requests: ResMut<Messages<GamepadRumbleRequest>>,
+) {
+    let now = time.elapsed();
+
+    // Track until when each gamepad plays rumble requested by others, which mustn't be stopped.
+    for (request, id) in cursor.read_with_id(&requests) {
+        if own_requests.contains(&id) {
+            continue;
+        }
+        match *request {
+            GamepadRumbleRequest::Add {
+                duration, gamepad, ..
+            } => {
+                let until = other_rumble.entry(gamepad).or_default();
+                *until = (*until).max(now.saturating_add(duration));
+            }
+            GamepadRumbleRequest::Stop { gamepad } => {
+                // This also stopped the output, which has to be sent again.
+                other_rumble.remove(&gamepad);
+                sent.remove(&gamepad);
+            }
+        }
+    }
+    own_requests.clear();
+    other_rumble.retain(|gamepad, until| *until > now && gamepads.contains(*gamepad));
+    sent.retain(|gamepad, _| gamepads.contains(*gamepad));
+
+    for (gamepad, haptics) in &gamepads {
+        let output = haptics.output();
+        let last = sent.get(&gamepad).copied();
+        let can_stop = last.is_some() && !other_rumble.contains_key(&gamepad);
+
+        if output == GamepadRumbleIntensity::ZERO {
+            if can_stop {
+                own_requests.push(requests.write(GamepadRumbleRequest::Stop { gamepad }));
+            }
+            sent.remove(&gamepad);
+            continue;
+        }
+
+        if let Some((last_output, sent_at)) = last
+            && (last_output.strong_motor - output.strong_motor).abs() < INTENSITY_STEP
+            && (last_output.weak_motor - output.weak_motor).abs() < INTENSITY_STEP
+            && now.saturating_sub(sent_at) < REFRESH_INTERVAL
+        {
+            continue;
+        }
+
+        if can_stop {
+            own_requests.push(requests.write(GamepadRumbleRequest::Stop { gamepad }));
+        }
+        own_requests.push(requests.write(GamepadRumbleRequest::Add {
+            // Outlive the next refresh, so that the rumble doesn't stutter between requests.
+            duration: REFRESH_INTERVAL * 2,
+            intensity: output,
+            gamepad,
+        }));
+        sent.insert(gamepad, (output, now));
+    }
+}
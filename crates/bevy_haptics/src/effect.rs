@@ -0,0 +1,207 @@
+use crate::{HapticsSettings, RumblePattern};
+use alloc::vec::Vec;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_input::gamepad::GamepadRumbleIntensity;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::{Real, Time};
+use core::time::Duration;
+
+/// A [`RumblePattern`] to play on a gamepad with [`GamepadHaptics::play`].
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(Debug, Clone, PartialEq)]
+pub struct RumbleEffect {
+    /// The pattern to play.
+    pub pattern: Handle<RumblePattern>,
+    /// The priority of this effect.
+    ///
+    /// Only the effects with the highest priority among those playing on a gamepad are felt,
+    /// layered on top of each other. Lower priority effects keep running silently, and are felt
+    /// again once the higher priority effects finish.
+    pub priority: u32,
+    /// How strongly to play the pattern, from `0.0` to `1.0`.
+    pub intensity: f32,
+    /// Whether the pattern restarts when it ends, instead of finishing.
+    pub looping: bool,
+}
+
+impl RumbleEffect {
+    /// Creates an effect that plays `pattern` once, at full intensity and the lowest priority.
+    pub fn new(pattern: Handle<RumblePattern>) -> Self {
+        Self {
+            pattern,
+            priority: 0,
+            intensity: 1.0,
+            looping: false,
+        }
+    }
+
+    /// Sets the [`priority`](Self::priority) of this effect.
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the [`intensity`](Self::intensity) of this effect.
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    /// Makes this effect loop until it is stopped.
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+}
+
+impl From<Handle<RumblePattern>> for RumbleEffect {
+    fn from(pattern: Handle<RumblePattern>) -> Self {
+        Self::new(pattern)
+    }
+}
+
+/// Identifies an effect playing on a [`GamepadHaptics`].
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Debug, Clone, PartialEq, Hash)]
+pub struct RumbleEffectId(u32);
+
+#[derive(Reflect, Debug, Clone)]
+struct PlayingRumble {
+    id: RumbleEffectId,
+    effect: RumbleEffect,
+    /// How far into the pattern the effect is. Only advances once the pattern has loaded.
+    elapsed: Duration,
+}
+
+/// The rumble effects playing on a gamepad.
+///
+/// This component is added to every [`Gamepad`](bevy_input::gamepad::Gamepad) by the
+/// [`HapticsPlugin`](crate::HapticsPlugin), which combines its effects into the
+/// [`output`](Self::output) sent to the gamepad every frame.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Debug, Default, Clone)]
+pub struct GamepadHaptics {
+    effects: Vec<PlayingRumble>,
+    next_id: u32,
+    output: GamepadRumbleIntensity,
+}
+
+impl Default for GamepadHaptics {
+    fn default() -> Self {
+        Self {
+            effects: Vec::new(),
+            next_id: 0,
+            output: GamepadRumbleIntensity::ZERO,
+        }
+    }
+}
+
+impl GamepadHaptics {
+    /// Starts playing an effect, returning an id that can be used to stop it.
+    ///
+    /// The effect starts once its [`RumblePattern`] has loaded.
+    pub fn play(&mut self, effect: impl Into<RumbleEffect>) -> RumbleEffectId {
+        let id = RumbleEffectId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.effects.push(PlayingRumble {
+            id,
+            effect: effect.into(),
+            elapsed: Duration::ZERO,
+        });
+        id
+    }
+
+    /// Stops an effect. Returns `false` if it had already finished or been stopped.
+    pub fn stop(&mut self, id: RumbleEffectId) -> bool {
+        let len = self.effects.len();
+        self.effects.retain(|playing| playing.id != id);
+        self.effects.len() != len
+    }
+
+    /// Stops every effect.
+    pub fn stop_all(&mut self) {
+        self.effects.clear();
+    }
+
+    /// Returns `true` if the effect is still playing.
+    pub fn is_playing(&self, id: RumbleEffectId) -> bool {
+        self.effects.iter().any(|playing| playing.id == id)
+    }
+
+    /// Returns the effects that are still playing.
+    pub fn effects(&self) -> impl Iterator<Item = (RumbleEffectId, &RumbleEffect)> {
+        self.effects
+            .iter()
+            .map(|playing| (playing.id, &playing.effect))
+    }
+
+    /// Returns the intensity the gamepad's motors were set to on the last update, after applying
+    /// [`HapticsSettings::intensity`].
+    pub fn output(&self) -> GamepadRumbleIntensity {
+        self.output
+    }
+
+    /// Advances every effect by `delta` and combines them into the output intensity.
+    fn update(&mut self, delta: Duration, patterns: &Assets<RumblePattern>, scale: f32) {
+        let mut layers = Vec::new();
+        self.effects.retain_mut(|playing| {
+            let Some(pattern) = patterns.get(&playing.effect.pattern) else {
+                return true;
+            };
+            let duration = pattern.duration();
+            let time = if playing.effect.looping {
+                if duration.is_zero() {
+                    Duration::ZERO
+                } else {
+                    Duration::from_nanos((playing.elapsed.as_nanos() % duration.as_nanos()) as u64)
+                }
+            } else if playing.elapsed > duration {
+                return false;
+            } else {
+                playing.elapsed
+            };
+            layers.push((
+                playing.effect.priority,
+                pattern.sample(time),
+                playing.effect.intensity,
+            ));
+            playing.elapsed += delta;
+            true
+        });
+
+        let top_priority = layers.iter().map(|(priority, ..)| *priority).max();
+        let mut output = GamepadRumbleIntensity::ZERO;
+        for (_, sample, intensity) in layers
+            .into_iter()
+            .filter(|(priority, ..)| Some(*priority) == top_priority)
+        {
+            output.strong_motor += sample.strong_motor * intensity;
+            output.weak_motor += sample.weak_motor * intensity;
+        }
+        let scale = scale.clamp(0.0, 1.0);
+        self.output = GamepadRumbleIntensity {
+            strong_motor: output.strong_motor.clamp(0.0, 1.0) * scale,
+            weak_motor: output.weak_motor.clamp(0.0, 1.0) * scale,
+        };
+    }
+}
+
+/// Advances the effects of every [`GamepadHaptics`] and updates their output.
+pub fn update_gamepad_haptics(
+    time: Res<Time<Real>>,
+    settings: Res<HapticsSettings>,
+    patterns: Res<Assets<RumblePattern>>,
+    mut gamepads: Query<&mut GamepadHaptics>,
+) {
+    for mut haptics in &mut gamepads {
+        if haptics.effects.is_empty() && haptics.output == GamepadRumbleIntensity::ZERO {
+            continue;
+        }
+        haptics.update(time.delta(), &patterns, settings.intensity);
+    }
+}
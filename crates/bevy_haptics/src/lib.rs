@@ -0,0 +1,356 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevy.org/assets/icon.png",
+    html_favicon_url = "https://bevy.org/assets/icon.png"
+)]
+
+//! Gamepad haptics for the [Bevy game engine](https://bevy.org/).
+//!
+//! Rumble is described by [`RumblePattern`] assets, which keyframe the intensity of a gamepad's
+//! strong (low-frequency) and weak (high-frequency) motors over time. Patterns are played on a
+//! gamepad through its [`GamepadHaptics`] component, which layers and prioritizes every effect
+//! playing on it, and the combined output is scaled by the global [`HapticsSettings`].
+//!
+//! ```
+//! # use bevy_asset::prelude::*;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_haptics::{GamepadHaptics, RumbleEffect, RumblePattern};
+//! fn rumble_on_hit(asset_server: Res<AssetServer>, mut gamepads: Query<&mut GamepadHaptics>) {
+//!     let hit: Handle<RumblePattern> = asset_server.load("haptics/hit.rumble.ron");
+//!     for mut haptics in &mut gamepads {
+//!         haptics.play(RumbleEffect::new(hit.clone()).with_priority(1));
+//!     }
+//! }
+//! ```
+//!
+//! The output reaches the gamepad through a [`HapticsBackend`]. The default one sends
+//! [`GamepadRumbleRequest`](bevy_input::gamepad::GamepadRumbleRequest)s, which `bevy_gilrs`
+//! plays, while [`HapticsBackend::Null`] sends nothing so effects can be tested without hardware.
+
+extern crate alloc;
+
+mod backend;
+mod effect;
+mod pattern;
+
+pub use backend::*;
+pub use effect::*;
+pub use pattern::*;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::AssetApp;
+use bevy_ecs::{
+    reflect::ReflectResource,
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, SystemSet},
+};
+use bevy_input::gamepad::Gamepad;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// The haptics prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{GamepadHaptics, HapticsSettings, RumbleEffect, RumblePattern};
+}
+
+/// Adds gamepad haptics to an App.
+///
+/// Requires the `AssetPlugin`, the `TimePlugin` and the `InputPlugin`.
+#[derive(Default)]
+pub struct HapticsPlugin {
+    /// How the output of each [`GamepadHaptics`] reaches the gamepad.
+    pub backend: HapticsBackend,
+}
+
+impl Plugin for HapticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<RumblePattern>()
+            .init_asset_loader::<RumblePatternLoader>()
+            .register_asset_reflect::<RumblePattern>()
+            .register_required_components::<Gamepad, GamepadHaptics>()
+            .init_resource::<HapticsSettings>()
+            .add_systems(PostUpdate, update_gamepad_haptics.in_set(HapticsSystems));
+
+        match self.backend {
+            HapticsBackend::RumbleRequests => {
+                app.add_systems(
+                    PostUpdate,
+                    send_rumble_requests
+                        .in_set(HapticsSystems)
+                        .after(update_gamepad_haptics),
+                );
+            }
+            HapticsBackend::Null => {}
+        }
+    }
+}
+
+/// The system set in which [`GamepadHaptics`] are updated and their output is sent.
+#[derive(SystemSet, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct HapticsSystems;
+
+/// Global haptics settings.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Debug, Default, PartialEq, Clone)]
+pub struct HapticsSettings {
+    /// Scales the rumble of every gamepad, from `0.0` (disabled) to `1.0` (full strength).
+    pub intensity: f32,
+}
+
+impl Default for HapticsSettings {
+    fn default() -> Self {
+        Self { intensity: 1.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use bevy_app::TaskPoolPlugin;
+    use bevy_asset::{AssetPlugin, Assets, Handle};
+    use bevy_ecs::{entity::Entity, message::Messages};
+    use bevy_input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+    use bevy_time::{TimePlugin, TimeUpdateStrategy};
+    use core::time::Duration;
+
+    const FRAME: Duration = Duration::from_millis(100);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            TimePlugin,
+            HapticsPlugin {
+                backend: HapticsBackend::Null,
+            },
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        // Let the first frame through, which always has a delta of zero.
+        app.update();
+        app
+    }
+
+    fn add_pattern(app: &mut App, pattern: RumblePattern) -> Handle<RumblePattern> {
+        app.world_mut()
+            .resource_mut::<Assets<RumblePattern>>()
+            .add(pattern)
+    }
+
+    fn spawn_gamepad(app: &mut App) -> Entity {
+        app.world_mut().spawn(Gamepad::default()).id()
+    }
+
+    fn haptics(app: &mut App, gamepad: Entity) -> bevy_ecs::world::Mut<'_, GamepadHaptics> {
+        app.world_mut().get_mut::<GamepadHaptics>(gamepad).unwrap()
+    }
+
+    fn output(app: &App, gamepad: Entity) -> GamepadRumbleIntensity {
+        app.world().get::<GamepadHaptics>(gamepad).unwrap().output()
+    }
+
+    #[test]
+    fn patterns_interpolate_between_keyframes() {
+        let pattern = RumblePattern::default()
+            .with_keyframe(1.0, GamepadRumbleIntensity::ZERO)
+            .with_keyframe(0.0, GamepadRumbleIntensity::strong_motor(1.0));
+
+        assert_eq!(pattern.duration(), Duration::from_secs(1));
+        assert_eq!(
+            pattern
+                .clone()
+                .with_keyframe(f32::INFINITY, GamepadRumbleIntensity::ZERO)
+                .duration(),
+            Duration::MAX
+        );
+        assert_eq!(
+            pattern.sample(Duration::from_millis(250)),
+            GamepadRumbleIntensity::strong_motor(0.75)
+        );
+        assert_eq!(
+            pattern.sample(Duration::from_secs(2)),
+            GamepadRumbleIntensity::ZERO
+        );
+
+        let ron = ron::to_string(&pattern).unwrap();
+        assert_eq!(ron::from_str::<RumblePattern>(&ron).unwrap(), pattern);
+    }
+
+    #[test]
+    fn effects_play_to_the_end_of_their_pattern() {
+        let mut app = app();
+        let gamepad = spawn_gamepad(&mut app);
+        let pattern = add_pattern(
+            &mut app,
+            RumblePattern::constant(GamepadRumbleIntensity::MAX, Duration::from_millis(200)),
+        );
+        let id = haptics(&mut app, gamepad).play(pattern);
+
+        for _ in 0..3 {
+            app.update();
+            assert_eq!(output(&app, gamepad), GamepadRumbleIntensity::MAX);
+        }
+        app.update();
+        assert_eq!(output(&app, gamepad), GamepadRumbleIntensity::ZERO);
+        assert!(!haptics(&mut app, gamepad).is_playing(id));
+    }
+
+    #[test]
+    fn higher_priority_effects_mask_lower_ones() {
+        let mut app = app();
+        let gamepad = spawn_gamepad(&mut app);
+        let weak = add_pattern(
+            &mut app,
+            RumblePattern::constant(
+                GamepadRumbleIntensity::weak_motor(0.5),
+                Duration::from_secs(10),
+            ),
+        );
+        let strong = add_pattern(
+            &mut app,
+            RumblePattern::constant(
+                GamepadRumbleIntensity::strong_motor(0.5),
+                Duration::from_secs(10),
+            ),
+        );
+
+        // Effects with the same priority are layered.
+        haptics(&mut app, gamepad).play(weak.clone());
+        haptics(&mut app, gamepad).play(RumbleEffect::new(weak).with_intensity(0.5));
+        app.update();
+        assert_eq!(
+            output(&app, gamepad),
+            GamepadRumbleIntensity::weak_motor(0.75)
+        );
+
+        let explosion = haptics(&mut app, gamepad).play(RumbleEffect::new(strong).with_priority(1));
+        app.update();
+        assert_eq!(
+            output(&app, gamepad),
+            GamepadRumbleIntensity::strong_motor(0.5)
+        );
+
+        haptics(&mut app, gamepad).stop(explosion);
+        app.update();
+        assert_eq!(
+            output(&app, gamepad),
+            GamepadRumbleIntensity::weak_motor(0.75)
+        );
+    }
+
+    #[test]
+    fn global_intensity_scales_output() {
+        let mut app = app();
+        let gamepad = spawn_gamepad(&mut app);
+        let pattern = add_pattern(
+            &mut app,
+            RumblePattern::constant(GamepadRumbleIntensity::MAX, Duration::from_millis(100)),
+        );
+        haptics(&mut app, gamepad).play(RumbleEffect::new(pattern).looping());
+        app.world_mut().resource_mut::<HapticsSettings>().intensity = 0.25;
+
+        for _ in 0..5 {
+            app.update();
+            assert_eq!(
+                output(&app, gamepad),
+                GamepadRumbleIntensity {
+                    strong_motor: 0.25,
+                    weak_motor: 0.25,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn rumble_requests_follow_output() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            TimePlugin,
+            HapticsPlugin::default(),
+        ))
+        .add_message::<GamepadRumbleRequest>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        app.update();
+
+        let gamepad = spawn_gamepad(&mut app);
+        let pattern = add_pattern(
+            &mut app,
+            RumblePattern::constant(GamepadRumbleIntensity::MAX, Duration::from_millis(100)),
+        );
+        haptics(&mut app, gamepad).play(pattern);
+
+        let mut requests = || {
+            app.update();
+            app.world_mut()
+                .resource_mut::<Messages<GamepadRumbleRequest>>()
+                .drain()
+                .map(|request| match request {
+                    GamepadRumbleRequest::Add { intensity, .. } => Some(intensity),
+                    GamepadRumbleRequest::Stop { .. } => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        // There is nothing to stop before the first output.
+        assert_eq!(requests(), [Some(GamepadRumbleIntensity::MAX)]);
+        // The output didn't change, so nothing is sent.
+        assert_eq!(requests(), []);
+        assert_eq!(requests(), [None]);
+        assert_eq!(requests(), []);
+    }
+
+    #[test]
+    fn rumble_requests_keep_other_rumble() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            TimePlugin,
+            HapticsPlugin::default(),
+        ))
+        .add_message::<GamepadRumbleRequest>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        app.update();
+
+        let gamepad = spawn_gamepad(&mut app);
+        let pattern = add_pattern(
+            &mut app,
+            RumblePattern::constant(GamepadRumbleIntensity::MAX, Duration::from_millis(100)),
+        );
+        app.world_mut()
+            .resource_mut::<Messages<GamepadRumbleRequest>>()
+            .write(GamepadRumbleRequest::Add {
+                duration: Duration::from_secs(1),
+                intensity: GamepadRumbleIntensity::WEAK_MAX,
+                gamepad,
+            });
+        haptics(&mut app, gamepad).play(pattern);
+
+        let mut requests = || {
+            app.update();
+            app.world_mut()
+                .resource_mut::<Messages<GamepadRumbleRequest>>()
+                .drain()
+                .map(|request| match request {
+                    GamepadRumbleRequest::Add { intensity, .. } => Some(intensity),
+                    GamepadRumbleRequest::Stop { .. } => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            requests(),
+            [
+                Some(GamepadRumbleIntensity::WEAK_MAX),
+                Some(GamepadRumbleIntensity::MAX)
+            ]
+        );
+        // The output stops, but the rumble requested by others is still playing.
+        assert_eq!(requests(), []);
+        assert_eq!(requests(), []);
+    }
+}
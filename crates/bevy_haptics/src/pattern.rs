@@ -0,0 +1,180 @@
+use alloc::vec::Vec;
+use bevy_asset::{io::Reader, Asset, AssetLoader, LoadContext};
+use bevy_input::gamepad::GamepadRumbleIntensity;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
+use core::time::Duration;
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use std::io;
+use thiserror::Error;
+
+/// A rumble pattern: the intensity of a gamepad's motors over time.
+///
+/// The intensity is linearly interpolated between [`RumbleKeyframe`]s, held at the value of the
+/// first keyframe before it, and the pattern ends at its last keyframe.
+///
+/// Patterns can be built in code or loaded from `.rumble.ron` files:
+///
+/// ```ron
+/// (
+///     keyframes: [
+///         (time: 0.0, intensity: (strong_motor: 1.0, weak_motor: 0.0)),
+///         (time: 0.1, intensity: (strong_motor: 0.2, weak_motor: 0.6)),
+///         (time: 0.4, intensity: (strong_motor: 0.0, weak_motor: 0.0)),
+///     ],
+/// )
+/// ```
+///
+/// Play them on a gamepad with [`GamepadHaptics::play`](crate::GamepadHaptics::play).
+#[derive(Asset, Reflect, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[reflect(Debug, Clone, Default, PartialEq)]
+#[serde(from = "SerializedRumblePattern", into = "SerializedRumblePattern")]
+pub struct RumblePattern {
+    keyframes: Vec<RumbleKeyframe>,
+}
+
+/// The intensity of a gamepad's motors at a point in a [`RumblePattern`].
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[reflect(Debug, Clone, PartialEq)]
+pub struct RumbleKeyframe {
+    /// The time of this keyframe from the start of the pattern, in seconds.
+    pub time: f32,
+    /// The intensity of the motors at this keyframe.
+    pub intensity: GamepadRumbleIntensity,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedRumblePattern {
+    keyframes: Vec<RumbleKeyframe>,
+}
+
+impl From<SerializedRumblePattern> for RumblePattern {
+    fn from(pattern: SerializedRumblePattern) -> Self {
+        Self::new(pattern.keyframes)
+    }
+}
+
+impl From<RumblePattern> for SerializedRumblePattern {
+    fn from(pattern: RumblePattern) -> Self {
+        Self {
+            keyframes: pattern.keyframes,
+        }
+    }
+}
+
+impl RumblePattern {
+    /// Creates a pattern from keyframes, in any order.
+    pub fn new(keyframes: impl IntoIterator<Item = RumbleKeyframe>) -> Self {
+        let mut keyframes: Vec<_> = keyframes.into_iter().collect();
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self { keyframes }
+    }
+
+    /// Creates a pattern that rumbles at a constant `intensity` for `duration`.
+    pub fn constant(intensity: GamepadRumbleIntensity, duration: Duration) -> Self {
+        Self::new([
+            RumbleKeyframe {
+                time: 0.0,
+                intensity,
+            },
+            RumbleKeyframe {
+                time: duration.as_secs_f32(),
+                intensity,
+            },
+        ])
+    }
+
+    /// Adds a keyframe at `time` seconds from the start of the pattern.
+    pub fn with_keyframe(mut self, time: f32, intensity: GamepadRumbleIntensity) -> Self {
+        let index = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= time);
+        self.keyframes
+            .insert(index, RumbleKeyframe { time, intensity });
+        self
+    }
+
+    /// Returns the keyframes of this pattern, ordered by time.
+    pub fn keyframes(&self) -> &[RumbleKeyframe] {
+        &self.keyframes
+    }
+
+    /// Returns how long this pattern lasts: the time of its last keyframe.
+    ///
+    /// Patterns whose last keyframe is too late to be represented by a [`Duration`] last for
+    /// [`Duration::MAX`].
+    pub fn duration(&self) -> Duration {
+        self.keyframes.last().map_or(Duration::ZERO, |keyframe| {
+            Duration::try_from_secs_f32(keyframe.time.max(0.0)).unwrap_or(Duration::MAX)
+        })
+    }
+
+    /// Returns the intensity of the motors `time` into the pattern.
+    pub fn sample(&self, time: Duration) -> GamepadRumbleIntensity {
+        let time = time.as_secs_f32();
+        let next = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= time);
+        match (
+            self.keyframes.get(next.wrapping_sub(1)),
+            self.keyframes.get(next),
+        ) {
+            (Some(previous), Some(next)) => {
+                let t = (time - previous.time) / (next.time - previous.time);
+                GamepadRumbleIntensity {
+                    strong_motor: previous.intensity.strong_motor
+                        + (next.intensity.strong_motor - previous.intensity.strong_motor) * t,
+                    weak_motor: previous.intensity.weak_motor
+                        + (next.intensity.weak_motor - previous.intensity.weak_motor) * t,
+                }
+            }
+            (Some(keyframe), None) | (None, Some(keyframe)) => keyframe.intensity,
+            (None, None) => GamepadRumbleIntensity::ZERO,
+        }
+    }
+}
+
+/// Loads [`RumblePattern`]s from `.rumble.ron` files.
+#[derive(Default, TypePath)]
+pub struct RumblePatternLoader;
+
+/// Errors that can occur when loading a [`RumblePattern`].
+#[derive(Error, Debug)]
+pub enum RumblePatternLoadError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An error occurred in RON deserialization.
+    #[error(transparent)]
+    SpannedRon(#[from] SpannedError),
+    /// A keyframe's time isn't a number, or is too large to be represented by a [`Duration`].
+    #[error("invalid keyframe time: {0}")]
+    InvalidKeyframeTime(f32),
+}
+
+impl AssetLoader for RumblePatternLoader {
+    type Asset = RumblePattern;
+    type Settings = ();
+    type Error = RumblePatternLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let pattern: RumblePattern = ron::de::from_bytes(&bytes)?;
+        if let Some(keyframe) = pattern.keyframes.iter().find(|keyframe| {
+            keyframe.time.is_nan() || Duration::try_from_secs_f32(keyframe.time.max(0.0)).is_err()
+        }) {
+            return Err(RumblePatternLoadError::InvalidKeyframeTime(keyframe.time));
+        }
+        Ok(pattern)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rumble.ron"]
+    }
+}
@@ -0,0 +1,1120 @@
+//! Maps raw input from devices to game-specific actions.
+//!
+//! Actions are defined by an [`Actionlike`] type, usually an enum. An [`InputMap`] binds each
+//! action to keys, mouse buttons, gamepad buttons and axes, chords of these, or virtual axes made
+//! of them, and [`update_action_state`] writes the resulting state of every action to the
+//! [`ActionState`] next to it. Each player gets their own [`InputMap`] and [`ActionState`] on
+//! their own entity, which can be restricted to a single gamepad with `InputMap::gamepad`.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::action::{ActionState, Actionlike, DualAxisBinding, InputBinding, InputMap};
+//! # #[cfg(feature = "keyboard")]
+//! # {
+//! use bevy_input::keyboard::KeyCode;
+//!
+//! #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+//! enum Action {
+//!     Jump,
+//!     Save,
+//!     Move,
+//! }
+//!
+//! impl Actionlike for Action {}
+//!
+//! fn spawn_player(mut commands: Commands) {
+//!     commands.spawn(
+//!         InputMap::default()
+//!             .with(Action::Jump, KeyCode::Space)
+//!             .with(
+//!                 Action::Save,
+//!                 InputBinding::chord([KeyCode::ControlLeft, KeyCode::KeyS]),
+//!             )
+//!             .with_dual_axis(Action::Move, DualAxisBinding::wasd()),
+//!     );
+//! }
+//!
+//! fn move_player(players: Query<&ActionState<Action>>) {
+//!     for actions in &players {
+//!         if actions.just_pressed(&Action::Jump) {
+//!             // Jump!
+//!         }
+//!         let direction = actions.axis_pair(&Action::Move);
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! Add an [`ActionPlugin`] for every [`Actionlike`] type to update their [`ActionState`]s.
+
+use core::{fmt::Debug, hash::Hash, marker::PhantomData};
+
+use crate::{ButtonInput, InputSystems};
+use alloc::vec::Vec;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    component::Component,
+    query::Without,
+    schedule::{IntoScheduleConfigs, SystemSet},
+    system::Query,
+};
+use bevy_math::Vec2;
+use bevy_platform::collections::{HashMap, HashSet};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+#[cfg(feature = "keyboard")]
+use crate::keyboard::KeyCode;
+#[cfg(feature = "mouse")]
+use crate::mouse::{AccumulatedMouseMotion, MouseButton};
+#[cfg(any(feature = "keyboard", feature = "mouse"))]
+use bevy_ecs::system::Res;
+#[cfg(feature = "gamepad")]
+use {
+    crate::gamepad::{Gamepad, GamepadAxis, GamepadButton},
+    bevy_ecs::entity::Entity,
+};
+
+/// A type whose values are the actions of a game, such as jumping or moving.
+///
+/// This is usually implemented for a fieldless enum:
+///
+/// ```
+/// # use bevy_input::action::Actionlike;
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// enum Action {
+///     Jump,
+///     Move,
+/// }
+///
+/// impl Actionlike for Action {}
+/// ```
+pub trait Actionlike: Clone + Eq + Hash + Debug + Send + Sync + 'static {}
+
+/// The system set in which [`update_action_state`] runs, after [`InputSystems`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct ActionSystems;
+
+/// Updates the [`ActionState<A>`] of every entity from its [`InputMap<A>`].
+pub struct ActionPlugin<A: Actionlike>(PhantomData<A>);
+
+impl<A: Actionlike> Default for ActionPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Actionlike> Plugin for ActionPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(PreUpdate, ActionSystems.after(InputSystems))
+            .add_systems(PreUpdate, update_action_state::<A>.in_set(ActionSystems));
+    }
+}
+
+/// The direction of a gamepad axis that counts as pressing a `ButtonBinding::GamepadAxis`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AxisDirection {
+    /// Towards [`Axis::MAX`](crate::Axis::MAX).
+    Positive,
+    /// Towards [`Axis::MIN`](crate::Axis::MIN).
+    Negative,
+}
+
+impl AxisDirection {
+    /// Returns `1.0` for [`AxisDirection::Positive`] and `-1.0` for [`AxisDirection::Negative`].
+    pub fn sign(self) -> f32 {
+        match self {
+            AxisDirection::Positive => 1.0,
+            AxisDirection::Negative => -1.0,
+        }
+    }
+}
+
+/// A single input that is either pressed or released.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ButtonBinding {
+    /// A key of the keyboard.
+    #[cfg(feature = "keyboard")]
+    Key(KeyCode),
+    /// A button of the mouse.
+    #[cfg(feature = "mouse")]
+    Mouse(MouseButton),
+    /// A button of a gamepad.
+    #[cfg(feature = "gamepad")]
+    GamepadButton(GamepadButton),
+    /// An axis of a gamepad, which is pressed while it is past `threshold` in `direction`.
+    #[cfg(feature = "gamepad")]
+    GamepadAxis {
+        /// The axis of the gamepad.
+        axis: GamepadAxis,
+        /// The direction the axis must be moved in.
+        direction: AxisDirection,
+        /// How far the axis must be moved, between `0.0` and `1.0`.
+        threshold: f32,
+    },
+}
+
+impl ButtonBinding {
+    /// Returns `true` if this input is pressed.
+    #[expect(clippy::allow_attributes, reason = "this is only sometimes unused")]
+    #[allow(unused, reason = "all features could be disabled")]
+    pub fn pressed(&self, inputs: &ActionInputs) -> bool {
+        match *self {
+            #[cfg(feature = "keyboard")]
+            ButtonBinding::Key(key) => inputs.keys.is_some_and(|keys| keys.pressed(key)),
+            #[cfg(feature = "mouse")]
+            ButtonBinding::Mouse(button) => inputs
+                .mouse_buttons
+                .is_some_and(|buttons| buttons.pressed(button)),
+            #[cfg(feature = "gamepad")]
+            ButtonBinding::GamepadButton(button) => {
+                inputs.gamepads().any(|gamepad| gamepad.pressed(button))
+            }
+            #[cfg(feature = "gamepad")]
+            ButtonBinding::GamepadAxis {
+                axis,
+                direction,
+                threshold,
+            } => inputs.gamepads().any(|gamepad| {
+                gamepad.get(axis).unwrap_or_default() * direction.sign() > threshold
+            }),
+        }
+    }
+
+    /// Returns `1.0` if this input is pressed, or `0.0` otherwise.
+    pub fn value(&self, inputs: &ActionInputs) -> f32 {
+        if self.pressed(inputs) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(feature = "keyboard")]
+impl From<KeyCode> for ButtonBinding {
+    fn from(key: KeyCode) -> Self {
+        ButtonBinding::Key(key)
+    }
+}
+
+#[cfg(feature = "mouse")]
+impl From<MouseButton> for ButtonBinding {
+    fn from(button: MouseButton) -> Self {
+        ButtonBinding::Mouse(button)
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl From<GamepadButton> for ButtonBinding {
+    fn from(button: GamepadButton) -> Self {
+        ButtonBinding::GamepadButton(button)
+    }
+}
+
+/// An input that presses an action: a single [`ButtonBinding`], or a chord of them that must
+/// all be pressed at once.
+///
+/// When a chord is pressed, the bindings of other actions made of a subset of its inputs are
+/// ignored, so that pressing Ctrl+S doesn't also press the action bound to S. See
+/// [`ClashStrategy`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputBinding {
+    /// A single input.
+    Button(ButtonBinding),
+    /// Inputs that must all be pressed at once.
+    Chord(Vec<ButtonBinding>),
+}
+
+impl InputBinding {
+    /// Creates a chord of `buttons`, which must all be pressed at once.
+    pub fn chord(buttons: impl IntoIterator<Item = impl Into<ButtonBinding>>) -> Self {
+        InputBinding::Chord(buttons.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns the inputs that must be pressed for this binding to be pressed.
+    pub fn buttons(&self) -> &[ButtonBinding] {
+        match self {
+            InputBinding::Button(button) => core::slice::from_ref(button),
+            InputBinding::Chord(buttons) => buttons,
+        }
+    }
+
+    /// Returns `true` if every input of this binding is pressed.
+    pub fn pressed(&self, inputs: &ActionInputs) -> bool {
+        let buttons = self.buttons();
+        !buttons.is_empty() && buttons.iter().all(|button| button.pressed(inputs))
+    }
+
+    /// Returns `true` if `other` is made of more inputs than this binding, including all of its
+    /// inputs, in which case it shadows this binding when they are both pressed.
+    pub fn is_shadowed_by(&self, other: &InputBinding) -> bool {
+        let (buttons, other_buttons) = (self.buttons(), other.buttons());
+        other_buttons.len() > buttons.len()
+            && buttons.iter().all(|button| other_buttons.contains(button))
+    }
+}
+
+impl From<ButtonBinding> for InputBinding {
+    fn from(button: ButtonBinding) -> Self {
+        InputBinding::Button(button)
+    }
+}
+
+#[cfg(feature = "keyboard")]
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        InputBinding::Button(key.into())
+    }
+}
+
+#[cfg(feature = "mouse")]
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        InputBinding::Button(button.into())
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl From<GamepadButton> for InputBinding {
+    fn from(button: GamepadButton) -> Self {
+        InputBinding::Button(button.into())
+    }
+}
+
+/// An input that gives an action a value between `-1.0` and `1.0`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AxisBinding {
+    /// An axis of a gamepad.
+    #[cfg(feature = "gamepad")]
+    Gamepad(GamepadAxis),
+    /// A virtual axis made of two inputs, which is `-1.0` while `negative` is pressed and `1.0`
+    /// while `positive` is pressed.
+    Virtual {
+        /// The input that moves the axis towards `-1.0`.
+        negative: ButtonBinding,
+        /// The input that moves the axis towards `1.0`.
+        positive: ButtonBinding,
+    },
+}
+
+impl AxisBinding {
+    /// Returns the current value of this axis.
+    pub fn value(&self, inputs: &ActionInputs) -> f32 {
+        match self {
+            #[cfg(feature = "gamepad")]
+            AxisBinding::Gamepad(axis) => inputs
+                .gamepads()
+                .filter_map(|gamepad| gamepad.get(*axis))
+                .fold(0.0, |value: f32, axis| {
+                    if axis.abs() > value.abs() {
+                        axis
+                    } else {
+                        value
+                    }
+                }),
+            AxisBinding::Virtual { negative, positive } => {
+                positive.value(inputs) - negative.value(inputs)
+            }
+        }
+    }
+}
+
+/// An input that gives an action a two-dimensional value, such as a direction to move in.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum DualAxisBinding {
+    /// Two axes of a gamepad, such as those of a stick.
+    #[cfg(feature = "gamepad")]
+    Gamepad {
+        /// The horizontal axis.
+        x: GamepadAxis,
+        /// The vertical axis.
+        y: GamepadAxis,
+    },
+    /// A virtual pair of axes made of four inputs, such as WASD or a D-pad. The value is not
+    /// normalized, so it is `(1.0, 1.0)` when `up` and `right` are pressed at once.
+    Virtual {
+        /// The input that moves the vertical axis towards `1.0`.
+        up: ButtonBinding,
+        /// The input that moves the vertical axis towards `-1.0`.
+        down: ButtonBinding,
+        /// The input that moves the horizontal axis towards `-1.0`.
+        left: ButtonBinding,
+        /// The input that moves the horizontal axis towards `1.0`.
+        right: ButtonBinding,
+    },
+    /// The motion of the mouse this frame, as given by [`AccumulatedMouseMotion`].
+    #[cfg(feature = "mouse")]
+    MouseMotion,
+}
+
+impl DualAxisBinding {
+    /// The left stick of a gamepad.
+    #[cfg(feature = "gamepad")]
+    pub const LEFT_STICK: Self = DualAxisBinding::Gamepad {
+        x: GamepadAxis::LeftStickX,
+        y: GamepadAxis::LeftStickY,
+    };
+
+    /// The right stick of a gamepad.
+    #[cfg(feature = "gamepad")]
+    pub const RIGHT_STICK: Self = DualAxisBinding::Gamepad {
+        x: GamepadAxis::RightStickX,
+        y: GamepadAxis::RightStickY,
+    };
+
+    /// The W, A, S and D keys.
+    #[cfg(feature = "keyboard")]
+    pub fn wasd() -> Self {
+        DualAxisBinding::Virtual {
+            up: KeyCode::KeyW.into(),
+            down: KeyCode::KeyS.into(),
+            left: KeyCode::KeyA.into(),
+            right: KeyCode::KeyD.into(),
+        }
+    }
+
+    /// The arrow keys.
+    #[cfg(feature = "keyboard")]
+    pub fn arrow_keys() -> Self {
+        DualAxisBinding::Virtual {
+            up: KeyCode::ArrowUp.into(),
+            down: KeyCode::ArrowDown.into(),
+            left: KeyCode::ArrowLeft.into(),
+            right: KeyCode::ArrowRight.into(),
+        }
+    }
+
+    /// The D-pad of a gamepad.
+    #[cfg(feature = "gamepad")]
+    pub fn dpad() -> Self {
+        DualAxisBinding::Virtual {
+            up: GamepadButton::DPadUp.into(),
+            down: GamepadButton::DPadDown.into(),
+            left: GamepadButton::DPadLeft.into(),
+            right: GamepadButton::DPadRight.into(),
+        }
+    }
+
+    /// Returns the current value of these axes.
+    pub fn axis_pair(&self, inputs: &ActionInputs) -> Vec2 {
+        match self {
+            #[cfg(feature = "gamepad")]
+            DualAxisBinding::Gamepad { x, y } => Vec2::new(
+                AxisBinding::Gamepad(*x).value(inputs),
+                AxisBinding::Gamepad(*y).value(inputs),
+            ),
+            DualAxisBinding::Virtual {
+                up,
+                down,
+                left,
+                right,
+            } => Vec2::new(
+                right.value(inputs) - left.value(inputs),
+                up.value(inputs) - down.value(inputs),
+            ),
+            #[cfg(feature = "mouse")]
+            DualAxisBinding::MouseMotion => inputs
+                .mouse_motion
+                .map(|motion| motion.delta)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// How [`update_action_state`] resolves bindings of different actions that are pressed at once
+/// and share inputs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone, Default)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ClashStrategy {
+    /// Only the bindings made of the most inputs are pressed: if Ctrl+S is pressed, the actions
+    /// bound to S or Ctrl alone are not. See [`InputBinding::is_shadowed_by`].
+    #[default]
+    PrioritizeLongest,
+    /// Every binding whose inputs are pressed is pressed.
+    PressAll,
+}
+
+/// The bindings of a single action in an [`InputMap`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionBindings {
+    /// The inputs that press the action.
+    pub buttons: Vec<InputBinding>,
+    /// The axes that give the action its [`ActionState::value`].
+    pub axes: Vec<AxisBinding>,
+    /// The pairs of axes that give the action its [`ActionState::axis_pair`].
+    pub dual_axes: Vec<DualAxisBinding>,
+}
+
+impl ActionBindings {
+    /// Returns `true` if the action has no bindings.
+    pub fn is_empty(&self) -> bool {
+        self.buttons.is_empty() && self.axes.is_empty() && self.dual_axes.is_empty()
+    }
+}
+
+/// Binds the actions of type `A` to inputs, for the player of the entity it is on.
+///
+/// The bindings can be changed at any time, for example to let players rebind their controls.
+/// Changes are applied the next time [`update_action_state`] runs.
+///
+/// Requires an [`ActionState<A>`], which holds the resulting state of the actions.
+#[derive(Component, Debug, Clone, PartialEq)]
+#[require(ActionState<A>)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    serde(bound(
+        serialize = "A: serde::Serialize",
+        deserialize = "A: serde::Deserialize<'de>"
+    ))
+)]
+pub struct InputMap<A: Actionlike> {
+    bindings: HashMap<A, ActionBindings>,
+    /// The gamepad whose inputs are read, or `None` to read the inputs of every gamepad.
+    ///
+    /// Use this to give each local player their own gamepad.
+    #[cfg(feature = "gamepad")]
+    pub gamepad: Option<Entity>,
+    /// How bindings of different actions that share inputs are resolved.
+    pub clash_strategy: ClashStrategy,
+}
+
+impl<A: Actionlike> Default for InputMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::default(),
+            #[cfg(feature = "gamepad")]
+            gamepad: None,
+            clash_strategy: ClashStrategy::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> InputMap<A> {
+    /// Binds `action` to `binding`, in addition to its other bindings.
+    pub fn with(mut self, action: A, binding: impl Into<InputBinding>) -> Self {
+        self.insert(action, binding);
+        self
+    }
+
+    /// Binds the value of `action` to `binding`, in addition to its other bindings.
+    pub fn with_axis(mut self, action: A, binding: AxisBinding) -> Self {
+        self.insert_axis(action, binding);
+        self
+    }
+
+    /// Binds the pair of axes of `action` to `binding`, in addition to its other bindings.
+    pub fn with_dual_axis(mut self, action: A, binding: DualAxisBinding) -> Self {
+        self.insert_dual_axis(action, binding);
+        self
+    }
+
+    /// Only reads the inputs of `gamepad`. See [`InputMap::gamepad`].
+    #[cfg(feature = "gamepad")]
+    pub fn with_gamepad(mut self, gamepad: Entity) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Sets how bindings of different actions that share inputs are resolved.
+    pub fn with_clash_strategy(mut self, clash_strategy: ClashStrategy) -> Self {
+        self.clash_strategy = clash_strategy;
+        self
+    }
+
+    /// Binds `action` to `binding`, in addition to its other bindings.
+    pub fn insert(&mut self, action: A, binding: impl Into<InputBinding>) {
+        self.bindings
+            .entry(action)
+            .or_default()
+            .buttons
+            .push(binding.into());
+    }
+
+    /// Binds the value of `action` to `binding`, in addition to its other bindings.
+    pub fn insert_axis(&mut self, action: A, binding: AxisBinding) {
+        self.bindings.entry(action).or_default().axes.push(binding);
+    }
+
+    /// Binds the pair of axes of `action` to `binding`, in addition to its other bindings.
+    pub fn insert_dual_axis(&mut self, action: A, binding: DualAxisBinding) {
+        self.bindings
+            .entry(action)
+            .or_default()
+            .dual_axes
+            .push(binding);
+    }
+
+    /// Replaces the binding `old` of `action` with `new`, keeping its place among the other
+    /// bindings of the action. If `action` isn't bound to `old`, `new` is added to its bindings.
+    pub fn rebind(&mut self, action: A, old: &InputBinding, new: impl Into<InputBinding>) {
+        let buttons = &mut self.bindings.entry(action).or_default().buttons;
+        let new = new.into();
+        match buttons.iter_mut().find(|binding| *binding == old) {
+            Some(binding) => *binding = new,
+            None => buttons.push(new),
+        }
+    }
+
+    /// Removes the binding of `action` to `binding`, returning `true` if it was bound to it.
+    pub fn remove(&mut self, action: &A, binding: &InputBinding) -> bool {
+        let Some(bindings) = self.bindings.get_mut(action) else {
+            return false;
+        };
+        let len = bindings.buttons.len();
+        bindings.buttons.retain(|other| other != binding);
+        let removed = bindings.buttons.len() != len;
+        if bindings.is_empty() {
+            self.bindings.remove(action);
+        }
+        removed
+    }
+
+    /// Removes every binding of `action`, returning them.
+    pub fn clear_action(&mut self, action: &A) -> Option<ActionBindings> {
+        self.bindings.remove(action)
+    }
+
+    /// Returns the bindings of `action`.
+    pub fn get(&self, action: &A) -> Option<&ActionBindings> {
+        self.bindings.get(action)
+    }
+
+    /// Returns the bindings of `action`, to modify them.
+    pub fn get_mut(&mut self, action: &A) -> Option<&mut ActionBindings> {
+        self.bindings.get_mut(action)
+    }
+
+    /// Returns the actions bound to `binding`.
+    pub fn actions_bound_to<'a>(
+        &'a self,
+        binding: &'a InputBinding,
+    ) -> impl Iterator<Item = &'a A> + 'a {
+        self.bindings
+            .iter()
+            .filter(|(_, bindings)| bindings.buttons.contains(binding))
+            .map(|(action, _)| action)
+    }
+
+    /// Iterates over every action and its bindings, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&A, &ActionBindings)> {
+        self.bindings.iter()
+    }
+
+    /// Returns the actions pressed by `inputs`, resolving clashes with the
+    /// [`ClashStrategy`] of this map.
+    pub fn pressed_actions(&self, inputs: &ActionInputs) -> HashSet<A> {
+        let pressed: Vec<(&A, &InputBinding)> = self
+            .bindings
+            .iter()
+            .flat_map(|(action, bindings)| {
+                bindings
+                    .buttons
+                    .iter()
+                    .filter(|binding| binding.pressed(inputs))
+                    .map(move |binding| (action, binding))
+            })
+            .collect();
+        pressed
+            .iter()
+            .filter(|(action, binding)| match self.clash_strategy {
+                ClashStrategy::PressAll => true,
+                ClashStrategy::PrioritizeLongest => {
+                    !pressed.iter().any(|(other_action, other_binding)| {
+                        other_action != action && binding.is_shadowed_by(other_binding)
+                    })
+                }
+            })
+            .map(|(action, _)| (*action).clone())
+            .collect()
+    }
+}
+
+/// The current state of the actions of type `A` of the player of the entity it is on, updated
+/// from its [`InputMap<A>`] by [`update_action_state`].
+///
+/// An action is pressed while any of its inputs is pressed, or while any of its axes is moved.
+/// The state can also be changed directly, for example to drive the actions of a player from
+/// the network or an AI, in which case the entity should have no [`InputMap<A>`]. Its
+/// `just pressed` and `just released` state is still cleared every frame by
+/// [`update_action_state`].
+#[derive(Component, Debug, Clone)]
+pub struct ActionState<A: Actionlike> {
+    buttons: ButtonInput<A>,
+    values: HashMap<A, f32>,
+    axis_pairs: HashMap<A, Vec2>,
+}
+
+impl<A: Actionlike> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            buttons: ButtonInput::default(),
+            values: HashMap::default(),
+            axis_pairs: HashMap::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> ActionState<A> {
+    /// Returns `true` if `action` is pressed.
+    pub fn pressed(&self, action: &A) -> bool {
+        self.buttons.pressed(action.clone())
+    }
+
+    /// Returns `true` if `action` was pressed since the last update.
+    pub fn just_pressed(&self, action: &A) -> bool {
+        self.buttons.just_pressed(action.clone())
+    }
+
+    /// Returns `true` if `action` was released since the last update.
+    pub fn just_released(&self, action: &A) -> bool {
+        self.buttons.just_released(action.clone())
+    }
+
+    /// Returns the value of `action`: the value of its axes if they are moved, or else `1.0` if
+    /// it is pressed and `0.0` if it isn't.
+    pub fn value(&self, action: &A) -> f32 {
+        self.values.get(action).copied().unwrap_or_default()
+    }
+
+    /// Returns the value of the pairs of axes of `action`.
+    pub fn axis_pair(&self, action: &A) -> Vec2 {
+        self.axis_pairs.get(action).copied().unwrap_or_default()
+    }
+
+    /// Returns the pressed state of every action as a [`ButtonInput`].
+    pub fn buttons(&self) -> &ButtonInput<A> {
+        &self.buttons
+    }
+
+    /// Iterates over every pressed action, in arbitrary order.
+    pub fn get_pressed(&self) -> impl ExactSizeIterator<Item = &A> {
+        self.buttons.get_pressed()
+    }
+
+    /// Presses `action`, setting its value to `1.0`.
+    pub fn press(&mut self, action: A) {
+        self.values.insert(action.clone(), 1.0);
+        self.buttons.press(action);
+    }
+
+    /// Releases `action`, setting its value and pair of axes to zero.
+    pub fn release(&mut self, action: A) {
+        self.values.remove(&action);
+        self.axis_pairs.remove(&action);
+        self.buttons.release(action);
+    }
+
+    /// Sets the value of `action`, pressing it unless `value` is zero.
+    pub fn set_value(&mut self, action: A, value: f32) {
+        self.values.insert(action.clone(), value);
+        self.set_pressed(action, value != 0.0);
+    }
+
+    /// Sets the pair of axes of `action`, pressing it unless `axis_pair` is zero.
+    pub fn set_axis_pair(&mut self, action: A, axis_pair: Vec2) {
+        self.axis_pairs.insert(action.clone(), axis_pair);
+        self.set_pressed(action, axis_pair != Vec2::ZERO);
+    }
+
+    /// Clears the `just pressed` and `just released` state of every action.
+    pub fn clear(&mut self) {
+        self.buttons.clear();
+    }
+
+    /// Releases every action, and clears their `just pressed` and `just released` state.
+    pub fn reset_all(&mut self) {
+        self.buttons.reset_all();
+        self.values.clear();
+        self.axis_pairs.clear();
+    }
+
+    fn set_pressed(&mut self, action: A, pressed: bool) {
+        if pressed {
+            self.buttons.press(action);
+        } else {
+            self.buttons.release(action);
+        }
+    }
+
+    /// Updates the state of every action from `input_map` and the current `inputs`.
+    pub fn update(&mut self, input_map: &InputMap<A>, inputs: &ActionInputs) {
+        self.buttons.clear();
+        let pressed = input_map.pressed_actions(inputs);
+        // Release the actions whose bindings have been removed.
+        let unbound: Vec<A> = self
+            .buttons
+            .get_pressed()
+            .filter(|action| input_map.get(action).is_none())
+            .cloned()
+            .collect();
+        for action in unbound {
+            self.release(action);
+        }
+        for (action, bindings) in input_map.iter() {
+            let value = bindings
+                .axes
+                .iter()
+                .map(|binding| binding.value(inputs))
+                .sum::<f32>()
+                .clamp(-1.0, 1.0);
+            let axis_pair = bindings
+                .dual_axes
+                .iter()
+                .map(|binding| binding.axis_pair(inputs))
+                .sum::<Vec2>();
+            let button_pressed = pressed.contains(action);
+            let value = match (value != 0.0, button_pressed) {
+                (true, _) => value,
+                (false, true) => 1.0,
+                (false, false) => 0.0,
+            };
+            self.values.insert(action.clone(), value);
+            self.axis_pairs.insert(action.clone(), axis_pair);
+            self.set_pressed(
+                action.clone(),
+                button_pressed || value != 0.0 || axis_pair != Vec2::ZERO,
+            );
+        }
+    }
+}
+
+/// The input devices read by [`InputMap`] bindings, for a single player.
+///
+/// Devices that are `None` are treated as if none of their inputs were pressed.
+pub struct ActionInputs<'a> {
+    /// The keys of the keyboard.
+    #[cfg(feature = "keyboard")]
+    pub keys: Option<&'a ButtonInput<KeyCode>>,
+    /// The buttons of the mouse.
+    #[cfg(feature = "mouse")]
+    pub mouse_buttons: Option<&'a ButtonInput<MouseButton>>,
+    /// The motion of the mouse this frame.
+    #[cfg(feature = "mouse")]
+    pub mouse_motion: Option<&'a AccumulatedMouseMotion>,
+    /// Every connected gamepad.
+    #[cfg(feature = "gamepad")]
+    pub gamepads: &'a [(Entity, &'a Gamepad)],
+    /// The gamepad of the player, or `None` to read every gamepad.
+    #[cfg(feature = "gamepad")]
+    pub gamepad: Option<Entity>,
+    marker: PhantomData<&'a ()>,
+}
+
+impl Default for ActionInputs<'_> {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "keyboard")]
+            keys: None,
+            #[cfg(feature = "mouse")]
+            mouse_buttons: None,
+            #[cfg(feature = "mouse")]
+            mouse_motion: None,
+            #[cfg(feature = "gamepad")]
+            gamepads: &[],
+            #[cfg(feature = "gamepad")]
+            gamepad: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl ActionInputs<'_> {
+    /// Iterates over the gamepads of the player.
+    #[cfg(feature = "gamepad")]
+    pub fn gamepads(&self) -> impl Iterator<Item = &Gamepad> {
+        self.gamepads
+            .iter()
+            .filter(|(entity, _)| self.gamepad.is_none_or(|gamepad| gamepad == *entity))
+            .map(|(_, gamepad)| *gamepad)
+    }
+}
+
+/// Updates the [`ActionState<A>`] of every entity from its [`InputMap<A>`] and the current input.
+///
+/// The [`ActionState<A>`]s of entities without an [`InputMap<A>`] are only
+/// [cleared](ActionState::clear), so that the actions pressed and released directly are only
+/// `just pressed` and `just released` for a frame.
+pub fn update_action_state<A: Actionlike>(
+    #[cfg(feature = "keyboard")] keys: Option<Res<ButtonInput<KeyCode>>>,
+    #[cfg(feature = "mouse")] mouse_buttons: Option<Res<ButtonInput<MouseButton>>>,
+    #[cfg(feature = "mouse")] mouse_motion: Option<Res<AccumulatedMouseMotion>>,
+    #[cfg(feature = "gamepad")] gamepads: Query<(Entity, &Gamepad)>,
+    mut players: Query<(&InputMap<A>, &mut ActionState<A>)>,
+    mut unmapped: Query<&mut ActionState<A>, Without<InputMap<A>>>,
+) {
+    for mut action_state in &mut unmapped {
+        action_state.clear();
+    }
+
+    #[cfg(feature = "gamepad")]
+    let gamepads: Vec<_> = gamepads.iter().collect();
+    for (input_map, mut action_state) in &mut players {
+        let inputs = ActionInputs {
+            #[cfg(feature = "keyboard")]
+            keys: keys.as_deref(),
+            #[cfg(feature = "mouse")]
+            mouse_buttons: mouse_buttons.as_deref(),
+            #[cfg(feature = "mouse")]
+            mouse_motion: mouse_motion.as_deref(),
+            #[cfg(feature = "gamepad")]
+            gamepads: &gamepads,
+            #[cfg(feature = "gamepad")]
+            gamepad: input_map.gamepad,
+            marker: PhantomData,
+        };
+        action_state.update(input_map, &inputs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::entity::Entity;
+    #[cfg(feature = "keyboard")]
+    use bevy_ecs::world::World;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    enum Action {
+        Jump,
+        Save,
+        Down,
+        Move,
+        Throttle,
+    }
+
+    impl Actionlike for Action {}
+
+    #[cfg(feature = "keyboard")]
+    fn setup(input_map: InputMap<Action>) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(ActionPlugin::<Action>::default())
+            .init_resource::<ButtonInput<KeyCode>>();
+        let player = app.world_mut().spawn(input_map).id();
+        (app, player)
+    }
+
+    #[cfg(feature = "keyboard")]
+    fn keys(world: &mut World) -> bevy_ecs::world::Mut<'_, ButtonInput<KeyCode>> {
+        world.resource_mut::<ButtonInput<KeyCode>>()
+    }
+
+    fn actions(app: &App, player: Entity) -> &ActionState<Action> {
+        app.world().get::<ActionState<Action>>(player).unwrap()
+    }
+
+    #[cfg(feature = "keyboard")]
+    #[test]
+    fn press_and_release_actions() {
+        let (mut app, player) = setup(InputMap::default().with(Action::Jump, KeyCode::Space));
+
+        keys(app.world_mut()).press(KeyCode::Space);
+        app.update();
+        assert!(actions(&app, player).just_pressed(&Action::Jump));
+        assert_eq!(actions(&app, player).value(&Action::Jump), 1.0);
+
+        app.update();
+        assert!(actions(&app, player).pressed(&Action::Jump));
+        assert!(!actions(&app, player).just_pressed(&Action::Jump));
+
+        keys(app.world_mut()).release(KeyCode::Space);
+        app.update();
+        assert!(actions(&app, player).just_released(&Action::Jump));
+        assert_eq!(actions(&app, player).value(&Action::Jump), 0.0);
+    }
+
+    #[cfg(feature = "keyboard")]
+    #[test]
+    fn chords_shadow_their_inputs() {
+        let input_map = InputMap::default()
+            .with(
+                Action::Save,
+                InputBinding::chord([KeyCode::ControlLeft, KeyCode::KeyS]),
+            )
+            .with(Action::Down, KeyCode::KeyS);
+        let (mut app, player) = setup(input_map);
+
+        keys(app.world_mut()).press(KeyCode::KeyS);
+        app.update();
+        assert!(actions(&app, player).pressed(&Action::Down));
+
+        keys(app.world_mut()).press(KeyCode::ControlLeft);
+        app.update();
+        assert!(actions(&app, player).just_pressed(&Action::Save));
+        assert!(actions(&app, player).just_released(&Action::Down));
+
+        app.world_mut()
+            .get_mut::<InputMap<Action>>(player)
+            .unwrap()
+            .clash_strategy = ClashStrategy::PressAll;
+        app.update();
+        assert!(actions(&app, player).pressed(&Action::Save));
+        assert!(actions(&app, player).pressed(&Action::Down));
+    }
+
+    #[cfg(feature = "keyboard")]
+    #[test]
+    fn virtual_axes() {
+        let input_map = InputMap::default()
+            .with_dual_axis(Action::Move, DualAxisBinding::wasd())
+            .with_axis(
+                Action::Throttle,
+                AxisBinding::Virtual {
+                    negative: KeyCode::KeyQ.into(),
+                    positive: KeyCode::KeyE.into(),
+                },
+            );
+        let (mut app, player) = setup(input_map);
+
+        keys(app.world_mut()).press(KeyCode::KeyW);
+        keys(app.world_mut()).press(KeyCode::KeyA);
+        keys(app.world_mut()).press(KeyCode::KeyQ);
+        app.update();
+        assert_eq!(
+            actions(&app, player).axis_pair(&Action::Move),
+            Vec2::new(-1.0, 1.0)
+        );
+        assert!(actions(&app, player).pressed(&Action::Move));
+        assert_eq!(actions(&app, player).value(&Action::Throttle), -1.0);
+    }
+
+    #[cfg(feature = "gamepad")]
+    #[test]
+    fn players_only_read_their_gamepad() {
+        let mut app = App::new();
+        app.add_plugins(ActionPlugin::<Action>::default());
+        let first_gamepad = app.world_mut().spawn(Gamepad::default()).id();
+        let second_gamepad = app.world_mut().spawn(Gamepad::default()).id();
+        let first_player = app
+            .world_mut()
+            .spawn(
+                InputMap::default()
+                    .with(Action::Jump, GamepadButton::South)
+                    .with_dual_axis(Action::Move, DualAxisBinding::LEFT_STICK)
+                    .with_gamepad(first_gamepad),
+            )
+            .id();
+        let second_player = app
+            .world_mut()
+            .spawn(
+                InputMap::default()
+                    .with(Action::Jump, GamepadButton::South)
+                    .with_gamepad(second_gamepad),
+            )
+            .id();
+
+        let mut gamepad = app.world_mut().get_mut::<Gamepad>(first_gamepad).unwrap();
+        gamepad.digital_mut().press(GamepadButton::South);
+        gamepad.analog_mut().set(GamepadAxis::LeftStickX, 0.5);
+        app.update();
+        assert!(actions(&app, first_player).pressed(&Action::Jump));
+        assert_eq!(
+            actions(&app, first_player).axis_pair(&Action::Move),
+            Vec2::new(0.5, 0.0)
+        );
+        assert!(!actions(&app, second_player).pressed(&Action::Jump));
+    }
+
+    #[cfg(feature = "keyboard")]
+    #[test]
+    fn rebind_actions() {
+        let (mut app, player) = setup(InputMap::default().with(Action::Jump, KeyCode::Space));
+
+        app.world_mut()
+            .get_mut::<InputMap<Action>>(player)
+            .unwrap()
+            .rebind(Action::Jump, &KeyCode::Space.into(), KeyCode::KeyJ);
+        keys(app.world_mut()).press(KeyCode::Space);
+        app.update();
+        assert!(!actions(&app, player).pressed(&Action::Jump));
+
+        keys(app.world_mut()).press(KeyCode::KeyJ);
+        app.update();
+        assert!(actions(&app, player).pressed(&Action::Jump));
+
+        // Removing the last binding of a pressed action releases it.
+        let mut input_map = app.world_mut().get_mut::<InputMap<Action>>(player).unwrap();
+        assert!(input_map.remove(&Action::Jump, &KeyCode::KeyJ.into()));
+        app.update();
+        assert!(actions(&app, player).just_released(&Action::Jump));
+    }
+
+    #[test]
+    fn actions_without_input_map_are_cleared() {
+        let mut app = App::new();
+        app.add_plugins(ActionPlugin::<Action>::default());
+        let player = app.world_mut().spawn(ActionState::<Action>::default()).id();
+
+        app.world_mut()
+            .get_mut::<ActionState<Action>>(player)
+            .unwrap()
+            .press(Action::Jump);
+        assert!(actions(&app, player).just_pressed(&Action::Jump));
+        app.update();
+        assert!(actions(&app, player).pressed(&Action::Jump));
+        assert!(!actions(&app, player).just_pressed(&Action::Jump));
+    }
+}
@@ -2,8 +2,11 @@
 
 use core::{ops::RangeInclusive, time::Duration};
 
-use crate::{Axis, ButtonInput, ButtonState};
-use alloc::string::String;
+use crate::{
+    gamepad_mapping::{GamepadMapping, GamepadMappingDatabase, NoGamepadMappingDatabase},
+    Axis, ButtonInput, ButtonState,
+};
+use alloc::{string::String, vec::Vec};
 #[cfg(feature = "bevy_reflect")]
 use bevy_ecs::prelude::ReflectComponent;
 use bevy_ecs::{
@@ -12,7 +15,8 @@ use bevy_ecs::{
     entity::Entity,
     message::{Message, MessageReader, MessageWriter},
     name::Name,
-    system::{Commands, Query},
+    query::With,
+    system::{Commands, Local, Query, Res},
 };
 use bevy_math::ops;
 use bevy_math::Vec2;
@@ -80,6 +84,11 @@ pub enum RawGamepadEvent {
 }
 
 /// [`GamepadButton`] changed event unfiltered by [`GamepadSettings`].
+///
+/// Gamepad backends report the buttons they can't identify as [`GamepadButton::Other`] with the
+/// index SDL gives to the button on the device, which is `N` in the `bN` fields of SDL mapping
+/// strings. The [`GamepadMappingDatabase`] relies on this index, so backends that can't report it
+/// must insert [`NoGamepadMappingDatabase`] on their gamepads.
 #[derive(Message, Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(
     feature = "bevy_reflect",
@@ -112,6 +121,10 @@ impl RawGamepadButtonChangedEvent {
 }
 
 /// [`GamepadAxis`] changed event unfiltered by [`GamepadSettings`].
+///
+/// Like buttons in [`RawGamepadButtonChangedEvent`], the axes gamepad backends can't identify are
+/// reported as [`GamepadAxis::Other`] with the index SDL gives to the axis, which is `N` in the
+/// `aN` fields of SDL mapping strings.
 #[derive(Message, Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(
     feature = "bevy_reflect",
@@ -623,7 +636,8 @@ pub enum GamepadButton {
 
     /// Miscellaneous buttons, considered non-standard (i.e. Extra buttons on a flight stick that do not have a gamepad equivalent).
     ///
-    /// Note that the value may not be stable across different platforms.
+    /// Note that the value may not be stable across different platforms. See
+    /// [`RawGamepadButtonChangedEvent`] for what gamepad backends should report.
     Other(u32),
 }
 
@@ -688,7 +702,8 @@ pub enum GamepadAxis {
     RightZ,
     /// Non-standard support for other axis types (i.e. HOTAS sliders, potentiometers, etc).
     ///
-    /// Note that the value may not be stable across different platforms.
+    /// Note that the value may not be stable across different platforms. See
+    /// [`RawGamepadAxisChangedEvent`] for what gamepad backends should report.
     Other(u32),
 }
 
@@ -1583,15 +1598,23 @@ pub enum GamepadConnection {
     Disconnected,
 }
 
-/// Consumes [`RawGamepadEvent`] events, filters them using their [`GamepadSettings`] and if successful,
+/// Consumes [`RawGamepadEvent`] events, remaps them using their [`GamepadMapping`], filters them using their [`GamepadSettings`] and if successful,
 /// updates the [`Gamepad`] and sends [`GamepadAxisChangedEvent`], [`GamepadButtonStateChangedEvent`], [`GamepadButtonChangedEvent`] events.
+///
+/// Gamepads without a [`GamepadMapping`] component use the mapping of their vendor and product IDs in the [`GamepadMappingDatabase`], if any,
+/// unless they have a [`NoGamepadMappingDatabase`] component.
 pub fn gamepad_event_processing_system(
     mut gamepads: Query<(&mut Gamepad, &GamepadSettings)>,
+    mappings: Query<&GamepadMapping>,
+    no_database: Query<(), With<NoGamepadMappingDatabase>>,
+    database: Option<Res<GamepadMappingDatabase>>,
     mut raw_events: MessageReader<RawGamepadEvent>,
     mut processed_events: MessageWriter<GamepadEvent>,
     mut processed_axis_events: MessageWriter<GamepadAxisChangedEvent>,
     mut processed_digital_events: MessageWriter<GamepadButtonStateChangedEvent>,
     mut processed_analog_events: MessageWriter<GamepadButtonChangedEvent>,
+    mut mapped_inputs: Local<Vec<(GamepadInput, f32)>>,
+    mut raw_values: Local<HashMap<(Entity, GamepadInput), f32>>,
 ) {
     // Clear digital buttons state
     for (mut gamepad, _) in gamepads.iter_mut() {
@@ -1599,87 +1622,119 @@ pub fn gamepad_event_processing_system(
     }
 
     for event in raw_events.read() {
-        match event {
+        let (gamepad, input, value) = match event {
             // Connections require inserting/removing components so they are done in a separate system
             RawGamepadEvent::Connection(send_event) => {
+                if send_event.disconnected() {
+                    raw_values.retain(|(gamepad, _), _| *gamepad != send_event.gamepad);
+                }
                 processed_events.write(GamepadEvent::from(send_event.clone()));
+                continue;
             }
             RawGamepadEvent::Axis(RawGamepadAxisChangedEvent {
                 gamepad,
                 axis,
                 value,
-            }) => {
-                let (gamepad, axis, value) = (*gamepad, *axis, *value);
-                let Ok((mut gamepad_axis, gamepad_settings)) = gamepads.get_mut(gamepad) else {
-                    continue;
-                };
-                let Some(filtered_value) = gamepad_settings
-                    .get_axis_settings(axis)
-                    .filter(value, gamepad_axis.get(axis))
-                else {
-                    continue;
-                };
-                gamepad_axis.analog.set(axis, filtered_value.raw);
-                let send_event =
-                    GamepadAxisChangedEvent::new(gamepad, axis, filtered_value.scaled.to_f32());
-                processed_axis_events.write(send_event);
-                processed_events.write(GamepadEvent::from(send_event));
-            }
+            }) => (*gamepad, GamepadInput::Axis(*axis), *value),
             RawGamepadEvent::Button(RawGamepadButtonChangedEvent {
                 gamepad,
                 button,
                 value,
-            }) => {
-                let (gamepad, button, value) = (*gamepad, *button, *value);
-                let Ok((mut gamepad_buttons, settings)) = gamepads.get_mut(gamepad) else {
-                    continue;
-                };
-                let Some(filtered_value) = settings
-                    .get_button_axis_settings(button)
-                    .filter(value, gamepad_buttons.get(button))
-                else {
-                    continue;
-                };
-                let button_settings = settings.get_button_settings(button);
-                gamepad_buttons.analog.set(button, filtered_value.raw);
-
-                if button_settings.is_released(filtered_value.raw) {
-                    // Check if button was previously pressed
-                    if gamepad_buttons.pressed(button) {
-                        processed_digital_events.write(GamepadButtonStateChangedEvent::new(
-                            gamepad,
-                            button,
-                            ButtonState::Released,
-                        ));
-                    }
-                    // We don't have to check if the button was previously pressed here
-                    // because that check is performed within Input<T>::release()
-                    gamepad_buttons.digital.release(button);
-                } else if button_settings.is_pressed(filtered_value.raw) {
-                    // Check if button was previously not pressed
-                    if !gamepad_buttons.pressed(button) {
-                        processed_digital_events.write(GamepadButtonStateChangedEvent::new(
-                            gamepad,
-                            button,
-                            ButtonState::Pressed,
-                        ));
-                    }
-                    gamepad_buttons.digital.press(button);
-                };
-
-                let button_state = if gamepad_buttons.digital.pressed(button) {
-                    ButtonState::Pressed
-                } else {
-                    ButtonState::Released
-                };
-                let send_event = GamepadButtonChangedEvent::new(
-                    gamepad,
-                    button,
-                    button_state,
-                    filtered_value.scaled.to_f32(),
-                );
-                processed_analog_events.write(send_event);
-                processed_events.write(GamepadEvent::from(send_event));
+            }) => (*gamepad, GamepadInput::Button(*button), *value),
+        };
+
+        let Ok((gamepad_state, _)) = gamepads.get(gamepad) else {
+            continue;
+        };
+        let mapping = mappings.get(gamepad).ok().or_else(|| {
+            if no_database.contains(gamepad) {
+                return None;
+            }
+            database
+                .as_ref()?
+                .get(gamepad_state.vendor_id?, gamepad_state.product_id?)
+        });
+        mapped_inputs.clear();
+        match mapping {
+            Some(mapping) => mapping.map(
+                input,
+                value,
+                |input| raw_values.get(&(gamepad, input)).copied().unwrap_or(0.0),
+                |input, value| mapped_inputs.push((input, value)),
+            ),
+            None => mapped_inputs.push((input, value)),
+        }
+        raw_values.insert((gamepad, input), value);
+
+        for &(input, value) in mapped_inputs.iter() {
+            match input {
+                GamepadInput::Axis(axis) => {
+                    let Ok((mut gamepad_axis, gamepad_settings)) = gamepads.get_mut(gamepad) else {
+                        continue;
+                    };
+                    let Some(filtered_value) = gamepad_settings
+                        .get_axis_settings(axis)
+                        .filter(value, gamepad_axis.get(axis))
+                    else {
+                        continue;
+                    };
+                    gamepad_axis.analog.set(axis, filtered_value.raw);
+                    let send_event =
+                        GamepadAxisChangedEvent::new(gamepad, axis, filtered_value.scaled.to_f32());
+                    processed_axis_events.write(send_event);
+                    processed_events.write(GamepadEvent::from(send_event));
+                }
+                GamepadInput::Button(button) => {
+                    let Ok((mut gamepad_buttons, settings)) = gamepads.get_mut(gamepad) else {
+                        continue;
+                    };
+                    let Some(filtered_value) = settings
+                        .get_button_axis_settings(button)
+                        .filter(value, gamepad_buttons.get(button))
+                    else {
+                        continue;
+                    };
+                    let button_settings = settings.get_button_settings(button);
+                    gamepad_buttons.analog.set(button, filtered_value.raw);
+
+                    if button_settings.is_released(filtered_value.raw) {
+                        // Check if button was previously pressed
+                        if gamepad_buttons.pressed(button) {
+                            processed_digital_events.write(GamepadButtonStateChangedEvent::new(
+                                gamepad,
+                                button,
+                                ButtonState::Released,
+                            ));
+                        }
+                        // We don't have to check if the button was previously pressed here
+                        // because that check is performed within Input<T>::release()
+                        gamepad_buttons.digital.release(button);
+                    } else if button_settings.is_pressed(filtered_value.raw) {
+                        // Check if button was previously not pressed
+                        if !gamepad_buttons.pressed(button) {
+                            processed_digital_events.write(GamepadButtonStateChangedEvent::new(
+                                gamepad,
+                                button,
+                                ButtonState::Pressed,
+                            ));
+                        }
+                        gamepad_buttons.digital.press(button);
+                    };
+
+                    let button_state = if gamepad_buttons.digital.pressed(button) {
+                        ButtonState::Pressed
+                    } else {
+                        ButtonState::Released
+                    };
+                    let send_event = GamepadButtonChangedEvent::new(
+                        gamepad,
+                        button,
+                        button_state,
+                        filtered_value.scaled.to_f32(),
+                    );
+                    processed_analog_events.write(send_event);
+                    processed_events.write(GamepadEvent::from(send_event));
+                }
             }
         }
     }
@@ -1692,6 +1747,11 @@ pub fn gamepad_event_processing_system(
     derive(Reflect),
     reflect(Debug, PartialEq, Clone)
 )]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
 pub struct GamepadRumbleIntensity {
     /// The rumble intensity of the strong gamepad motor.
     ///
@@ -1710,6 +1770,12 @@ pub struct GamepadRumbleIntensity {
 }
 
 impl GamepadRumbleIntensity {
+    /// Both gamepad motors stopped.
+    pub const ZERO: Self = GamepadRumbleIntensity {
+        strong_motor: 0.0,
+        weak_motor: 0.0,
+    };
+
     /// Rumble both gamepad motors at maximum intensity.
     pub const MAX: Self = GamepadRumbleIntensity {
         strong_motor: 1.0,
@@ -1826,7 +1892,10 @@ mod tests {
         GamepadConnectionEvent, GamepadEvent, GamepadSettings, RawGamepadAxisChangedEvent,
         RawGamepadButtonChangedEvent, RawGamepadEvent,
     };
-    use crate::ButtonState;
+    use crate::{
+        gamepad_mapping::{GamepadMapping, GamepadMappingDatabase, NoGamepadMappingDatabase},
+        ButtonState,
+    };
     use alloc::string::ToString;
     use bevy_app::{App, PreUpdate};
     use bevy_ecs::entity::Entity;
@@ -2979,4 +3048,111 @@ mod tests {
             4
         );
     }
+
+    #[test]
+    fn gamepad_mapping_remaps_raw_events() {
+        let mut ctx = TestContext::new();
+        let entity = ctx.send_gamepad_connection_event(None);
+        ctx.update();
+
+        ctx.app.world_mut().entity_mut(entity).insert(
+            GamepadMapping::new("Test gamepad")
+                .with_binding(GamepadButton::Other(5), GamepadButton::South)
+                .with_binding(GamepadAxis::Other(0), GamepadAxis::LeftStickX),
+        );
+        ctx.send_raw_gamepad_event_batch([
+            RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+                entity,
+                GamepadButton::Other(5),
+                1.0,
+            )),
+            RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+                entity,
+                GamepadAxis::Other(0),
+                1.0,
+            )),
+            // Unmapped inputs are passed through.
+            RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+                entity,
+                GamepadButton::Other(6),
+                1.0,
+            )),
+        ]);
+        ctx.update();
+
+        let gamepad = ctx.app.world().get::<Gamepad>(entity).unwrap();
+        assert!(gamepad.pressed(GamepadButton::South));
+        assert!(!gamepad.pressed(GamepadButton::Other(5)));
+        assert!(gamepad.pressed(GamepadButton::Other(6)));
+        assert_eq!(gamepad.get(GamepadAxis::LeftStickX), Some(1.0));
+        assert_eq!(gamepad.get(GamepadAxis::Other(0)), None);
+    }
+
+    #[test]
+    fn gamepad_mapping_database_remaps_raw_events() {
+        let mut ctx = TestContext::new();
+        let mut database = GamepadMappingDatabase::default();
+        database
+            .add_sdl_mappings(
+                "03000000ad1b000016f0000090040000,Arcade Stick,a:b1,-leftx:b4,+leftx:b5,",
+            )
+            .unwrap();
+        ctx.app.insert_resource(database);
+
+        let connect = |ctx: &mut TestContext| {
+            let entity = ctx.app.world_mut().spawn_empty().id();
+            ctx.app
+                .world_mut()
+                .resource_mut::<Messages<GamepadConnectionEvent>>()
+                .write(GamepadConnectionEvent::new(
+                    entity,
+                    Connected {
+                        name: "Arcade Stick".to_string(),
+                        vendor_id: Some(0x1bad),
+                        product_id: Some(0xf016),
+                    },
+                ));
+            ctx.update();
+            entity
+        };
+        let entity = connect(&mut ctx);
+        let opted_out = connect(&mut ctx);
+        ctx.app
+            .world_mut()
+            .entity_mut(opted_out)
+            .insert(NoGamepadMappingDatabase);
+
+        let button = |gamepad, index, value| {
+            RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+                gamepad,
+                GamepadButton::Other(index),
+                value,
+            ))
+        };
+        ctx.send_raw_gamepad_event_batch([
+            button(entity, 1, 1.0),
+            button(entity, 4, 1.0),
+            button(entity, 5, 1.0),
+            button(opted_out, 1, 1.0),
+        ]);
+        ctx.update();
+
+        let gamepad = ctx.app.world().get::<Gamepad>(entity).unwrap();
+        assert!(gamepad.pressed(GamepadButton::South));
+        assert!(!gamepad.pressed(GamepadButton::Other(1)));
+        let gamepad = ctx.app.world().get::<Gamepad>(opted_out).unwrap();
+        assert!(!gamepad.pressed(GamepadButton::South));
+        assert!(gamepad.pressed(GamepadButton::Other(1)));
+
+        // Releasing one half of the axis keeps the other half held.
+        ctx.send_raw_gamepad_event_batch([button(entity, 5, 0.0)]);
+        ctx.update();
+        let gamepad = ctx.app.world().get::<Gamepad>(entity).unwrap();
+        assert_eq!(gamepad.get(GamepadAxis::LeftStickX), Some(-1.0));
+
+        ctx.send_raw_gamepad_event_batch([button(entity, 4, 0.0)]);
+        ctx.update();
+        let gamepad = ctx.app.world().get::<Gamepad>(entity).unwrap();
+        assert_eq!(gamepad.get(GamepadAxis::LeftStickX), Some(0.0));
+    }
 }
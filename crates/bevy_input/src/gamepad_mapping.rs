@@ -0,0 +1,887 @@
+//! Remapping of raw gamepad inputs, from SDL-style mapping strings or per-gamepad overrides.
+//!
+//! Gamepad backends report the buttons and axes they can't identify as [`GamepadButton::Other`]
+//! and [`GamepadAxis::Other`], numbered the way SDL numbers them. A [`GamepadMapping`] binds those
+//! raw inputs (or any other) to the standard [`GamepadButton`]s and [`GamepadAxis`]es before they
+//! reach the [`Gamepad`] and the processed gamepad events.
+//!
+//! Mappings come from two places:
+//! - The [`GamepadMappingDatabase`] resource, keyed by the [vendor](crate::gamepad::Gamepad::vendor_id) and
+//!   [product](crate::gamepad::Gamepad::product_id) IDs of the gamepad and filled from SDL `gamecontrollerdb`
+//!   mapping strings. Gamepads with a [`NoGamepadMappingDatabase`] component don't use it.
+//! - A [`GamepadMapping`] component on the gamepad entity, which replaces the database mapping for
+//!   that gamepad. This is the data model for an in-game remapping menu.
+//!
+//! [`Gamepad`]: crate::gamepad::Gamepad
+
+use crate::gamepad::{GamepadAxis, GamepadButton, GamepadInput};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_ecs::{component::Component, resource::Resource};
+use bevy_platform::collections::HashMap;
+use thiserror::Error;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::prelude::{ReflectComponent, ReflectResource};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The part of an axis' range used by a [`GamepadMappingBinding`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AxisRange {
+    /// The whole axis, from `-1.0` to `1.0`.
+    #[default]
+    Full,
+    /// The positive half of the axis, from `0.0` to `1.0`.
+    Positive,
+    /// The negative half of the axis, from `0.0` to `-1.0`.
+    Negative,
+}
+
+/// The raw input read by a [`GamepadMappingBinding`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum GamepadMappingSource {
+    /// A button, as reported by the gamepad backend.
+    Button(GamepadButton),
+    /// An axis, or half of it, as reported by the gamepad backend.
+    Axis {
+        /// The axis.
+        axis: GamepadAxis,
+        /// The part of the axis that is read.
+        range: AxisRange,
+        /// Whether the value of the axis is negated before it is read.
+        inverted: bool,
+    },
+}
+
+impl GamepadMappingSource {
+    /// Returns the raw [`GamepadInput`] this source reads.
+    pub fn input(&self) -> GamepadInput {
+        match *self {
+            GamepadMappingSource::Button(button) => GamepadInput::Button(button),
+            GamepadMappingSource::Axis { axis, .. } => GamepadInput::Axis(axis),
+        }
+    }
+}
+
+impl From<GamepadButton> for GamepadMappingSource {
+    fn from(button: GamepadButton) -> Self {
+        GamepadMappingSource::Button(button)
+    }
+}
+
+impl From<GamepadAxis> for GamepadMappingSource {
+    fn from(axis: GamepadAxis) -> Self {
+        GamepadMappingSource::Axis {
+            axis,
+            range: AxisRange::Full,
+            inverted: false,
+        }
+    }
+}
+
+/// The standard input written by a [`GamepadMappingBinding`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum GamepadMappingTarget {
+    /// A button.
+    Button(GamepadButton),
+    /// An axis, or half of it.
+    Axis {
+        /// The axis.
+        axis: GamepadAxis,
+        /// The part of the axis that is written.
+        range: AxisRange,
+    },
+}
+
+impl GamepadMappingTarget {
+    /// Returns the [`GamepadInput`] this target writes.
+    pub fn input(&self) -> GamepadInput {
+        match *self {
+            GamepadMappingTarget::Button(button) => GamepadInput::Button(button),
+            GamepadMappingTarget::Axis { axis, .. } => GamepadInput::Axis(axis),
+        }
+    }
+}
+
+impl From<GamepadButton> for GamepadMappingTarget {
+    fn from(button: GamepadButton) -> Self {
+        GamepadMappingTarget::Button(button)
+    }
+}
+
+impl From<GamepadAxis> for GamepadMappingTarget {
+    fn from(axis: GamepadAxis) -> Self {
+        GamepadMappingTarget::Axis {
+            axis,
+            range: AxisRange::Full,
+        }
+    }
+}
+
+/// Binds a raw [`GamepadMappingSource`] to a standard [`GamepadMappingTarget`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GamepadMappingBinding {
+    /// The raw input that is read.
+    pub source: GamepadMappingSource,
+    /// The standard input that is written.
+    pub target: GamepadMappingTarget,
+}
+
+impl GamepadMappingBinding {
+    /// Converts a raw `value` of the [`source`](Self::source) into a value of the
+    /// [`target`](Self::target).
+    ///
+    /// Buttons range from `0.0` to `1.0` and axes from `-1.0` to `1.0`. Reading a full axis into a
+    /// button or a half axis remaps its whole range, while reading a button or a half axis into a
+    /// full axis goes from `-1.0` when released to `1.0` when fully pressed.
+    pub fn map_value(&self, value: f32) -> f32 {
+        // Whether the value covers -1.0..=1.0, rather than 0.0..=1.0.
+        let (value, full) = match self.source {
+            GamepadMappingSource::Button(_) => (value, false),
+            GamepadMappingSource::Axis {
+                range, inverted, ..
+            } => {
+                let value = if inverted { -value } else { value };
+                match range {
+                    AxisRange::Full => (value, true),
+                    AxisRange::Positive => (value.max(0.0), false),
+                    AxisRange::Negative => ((-value).max(0.0), false),
+                }
+            }
+        };
+        let half = if full { (value + 1.0) / 2.0 } else { value };
+        match self.target {
+            GamepadMappingTarget::Button(_) => half,
+            GamepadMappingTarget::Axis { range, .. } => match range {
+                AxisRange::Full if full => value,
+                AxisRange::Full => half * 2.0 - 1.0,
+                AxisRange::Positive => half,
+                AxisRange::Negative => -half,
+            },
+        }
+    }
+}
+
+/// Remaps the raw inputs of a gamepad to standard [`GamepadButton`]s and [`GamepadAxis`]es.
+///
+/// Raw inputs that aren't the [source](GamepadMappingBinding::source) of any binding are passed
+/// through unchanged, while the others are replaced by the [targets](GamepadMappingBinding::target)
+/// of all of their bindings.
+///
+/// Mappings are looked up in the [`GamepadMappingDatabase`] by the IDs of each gamepad. Insert this
+/// component on a [`Gamepad`](crate::gamepad::Gamepad) to replace its database mapping, for
+/// example to let players fix an unknown controller from a remapping menu:
+///
+/// ```
+/// # use bevy_input::gamepad::{GamepadAxis, GamepadButton};
+/// # use bevy_input::gamepad_mapping::GamepadMapping;
+/// let mut mapping = GamepadMapping::new("Arcade stick");
+/// // The player pressed the raw button 5 when asked for the south button.
+/// mapping.bind(GamepadButton::Other(5), GamepadButton::South);
+/// mapping.bind(GamepadAxis::Other(0), GamepadAxis::LeftStickX);
+/// assert_eq!(
+///     mapping.source_of(GamepadButton::South),
+///     Some(GamepadButton::Other(5).into())
+/// );
+/// ```
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Component, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GamepadMapping {
+    /// The name of the gamepad this mapping is for.
+    pub name: String,
+    /// The bindings of this mapping.
+    pub bindings: Vec<GamepadMappingBinding>,
+}
+
+impl GamepadMapping {
+    /// Creates a mapping without bindings.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Binds `source` to `target`, replacing the previous binding of `target`.
+    pub fn bind(
+        &mut self,
+        source: impl Into<GamepadMappingSource>,
+        target: impl Into<GamepadMappingTarget>,
+    ) {
+        let binding = GamepadMappingBinding {
+            source: source.into(),
+            target: target.into(),
+        };
+        self.unbind(binding.target);
+        self.bindings.push(binding);
+    }
+
+    /// Binds `source` to `target`, replacing the previous binding of `target`.
+    pub fn with_binding(
+        mut self,
+        source: impl Into<GamepadMappingSource>,
+        target: impl Into<GamepadMappingTarget>,
+    ) -> Self {
+        self.bind(source, target);
+        self
+    }
+
+    /// Removes the binding of `target`, returning its source.
+    pub fn unbind(
+        &mut self,
+        target: impl Into<GamepadMappingTarget>,
+    ) -> Option<GamepadMappingSource> {
+        let target = target.into();
+        let index = self
+            .bindings
+            .iter()
+            .position(|binding| binding.target == target)?;
+        Some(self.bindings.remove(index).source)
+    }
+
+    /// Returns the source bound to `target`.
+    pub fn source_of(
+        &self,
+        target: impl Into<GamepadMappingTarget>,
+    ) -> Option<GamepadMappingSource> {
+        let target = target.into();
+        self.bindings
+            .iter()
+            .find(|binding| binding.target == target)
+            .map(|binding| binding.source)
+    }
+
+    /// Returns the bindings that read the raw `input`.
+    pub fn bindings_of(
+        &self,
+        input: impl Into<GamepadInput>,
+    ) -> impl Iterator<Item = &GamepadMappingBinding> {
+        let input = input.into();
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.source.input() == input)
+    }
+
+    /// Remaps a raw `value` of `input`, calling `f` with each resulting input and value.
+    ///
+    /// An input written by several bindings, such as an axis bound to a button for each of its
+    /// halves, takes the value furthest from zero among them. `raw_value` returns the current
+    /// value of the other raw inputs these bindings read.
+    pub(crate) fn map(
+        &self,
+        input: GamepadInput,
+        value: f32,
+        raw_value: impl Fn(GamepadInput) -> f32,
+        mut f: impl FnMut(GamepadInput, f32),
+    ) {
+        let mut mapped = false;
+        for (index, binding) in self.bindings.iter().enumerate() {
+            let target = binding.target.input();
+            if binding.source.input() != input
+                || self.bindings[..index]
+                    .iter()
+                    .any(|other| other.source.input() == input && other.target.input() == target)
+            {
+                continue;
+            }
+            let value = self
+                .bindings
+                .iter()
+                .filter(|other| other.target.input() == target)
+                .map(|other| {
+                    let source = other.source.input();
+                    other.map_value(if source == input {
+                        value
+                    } else {
+                        raw_value(source)
+                    })
+                })
+                .fold(
+                    0.0,
+                    |max: f32, value| {
+                        if value.abs() > max.abs() {
+                            value
+                        } else {
+                            max
+                        }
+                    },
+                );
+            f(target, value);
+            mapped = true;
+        }
+        if !mapped {
+            f(input, value);
+        }
+    }
+
+    /// Parses an SDL `gamecontrollerdb` mapping string, returning the USB vendor and product IDs
+    /// encoded in its GUID along with the mapping.
+    ///
+    /// Mapping strings look like `03000000ad1b000016f0000090040000,Arcade Stick,a:b1,b:b2,leftx:a0,`.
+    /// Raw buttons and axes (`b5`, `a0`) are read as [`GamepadButton::Other`] and
+    /// [`GamepadAxis::Other`] with the same index, as described on
+    /// [`RawGamepadButtonChangedEvent`](crate::gamepad::RawGamepadButtonChangedEvent), and SDL's
+    /// downward Y axes are flipped to match [`GamepadAxis`].
+    ///
+    /// Bindings that can't be represented are skipped: hats (`h0.1`), which gamepad backends
+    /// report as buttons or axes already, and targets without a [`GamepadButton`] or
+    /// [`GamepadAxis`] equivalent, such as `paddle1` or `touchpad`.
+    pub fn from_sdl(mapping: &str) -> Result<(u16, u16, Self), GamepadMappingError> {
+        let mut fields = mapping.trim().split(',');
+        let guid = fields.next().unwrap_or_default();
+        let (vendor_id, product_id) = parse_guid(guid)?;
+        let name = fields
+            .next()
+            .filter(|name| !name.is_empty())
+            .ok_or(GamepadMappingError::MissingName)?;
+
+        let mut result = Self::new(name);
+        for field in fields.filter(|field| !field.is_empty()) {
+            let (target, source) = field
+                .split_once(':')
+                .ok_or_else(|| GamepadMappingError::InvalidField(field.to_string()))?;
+            if target == "platform" || target == "crc" || target == "hint" || target == "sdk>=" {
+                continue;
+            }
+            if let Some(binding) = parse_binding(target, source)
+                .map_err(|()| GamepadMappingError::InvalidField(field.to_string()))?
+            {
+                result.bindings.push(binding);
+            }
+        }
+        Ok((vendor_id, product_id, result))
+    }
+
+    /// Formats this mapping as an SDL `gamecontrollerdb` mapping string for a USB gamepad with
+    /// the given vendor and product IDs.
+    ///
+    /// This can be used to save the mapping a player made in a remapping menu. Bindings from or to
+    /// standard inputs without an SDL equivalent are skipped.
+    pub fn to_sdl(&self, vendor_id: u16, product_id: u16) -> String {
+        let [vendor_low, vendor_high] = vendor_id.to_le_bytes();
+        let [product_low, product_high] = product_id.to_le_bytes();
+        let mut result = format!(
+            "03000000{vendor_low:02x}{vendor_high:02x}0000{product_low:02x}{product_high:02x}000000000000,{},",
+            self.name.replace(',', " ")
+        );
+        for binding in &self.bindings {
+            if let Some(field) = format_binding(binding) {
+                result.push_str(&field);
+                result.push(',');
+            }
+        }
+        result
+    }
+}
+
+/// The SDL names of the standard buttons.
+const SDL_BUTTONS: [(&str, GamepadButton); 17] = [
+    ("a", GamepadButton::South),
+    ("b", GamepadButton::East),
+    ("x", GamepadButton::West),
+    ("y", GamepadButton::North),
+    ("back", GamepadButton::Select),
+    ("guide", GamepadButton::Mode),
+    ("start", GamepadButton::Start),
+    ("leftstick", GamepadButton::LeftThumb),
+    ("rightstick", GamepadButton::RightThumb),
+    ("leftshoulder", GamepadButton::LeftTrigger),
+    ("rightshoulder", GamepadButton::RightTrigger),
+    ("lefttrigger", GamepadButton::LeftTrigger2),
+    ("righttrigger", GamepadButton::RightTrigger2),
+    ("dpup", GamepadButton::DPadUp),
+    ("dpdown", GamepadButton::DPadDown),
+    ("dpleft", GamepadButton::DPadLeft),
+    ("dpright", GamepadButton::DPadRight),
+];
+
+/// The SDL names of the standard axes, and whether they point down in SDL.
+const SDL_AXES: [(&str, GamepadAxis, bool); 4] = [
+    ("leftx", GamepadAxis::LeftStickX, false),
+    ("lefty", GamepadAxis::LeftStickY, true),
+    ("rightx", GamepadAxis::RightStickX, false),
+    ("righty", GamepadAxis::RightStickY, true),
+];
+
+/// Reads the vendor and product IDs from an SDL GUID, which stores them as little-endian 16-bit
+/// values in its third and fifth pairs of bytes.
+fn parse_guid(guid: &str) -> Result<(u16, u16), GamepadMappingError> {
+    let invalid = || GamepadMappingError::InvalidGuid(guid.to_string());
+    if guid.len() != 32 || !guid.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let word = |start: usize| {
+        let low = u8::from_str_radix(&guid[start..start + 2], 16).map_err(|_| invalid())?;
+        let high = u8::from_str_radix(&guid[start + 2..start + 4], 16).map_err(|_| invalid())?;
+        Ok(u16::from_le_bytes([low, high]))
+    };
+    Ok((word(8)?, word(16)?))
+}
+
+/// Splits the `+`/`-` prefix from an SDL input name.
+fn split_range(name: &str) -> (AxisRange, &str) {
+    if let Some(name) = name.strip_prefix('+') {
+        (AxisRange::Positive, name)
+    } else if let Some(name) = name.strip_prefix('-') {
+        (AxisRange::Negative, name)
+    } else {
+        (AxisRange::Full, name)
+    }
+}
+
+/// Flips the direction of a half-axis range.
+fn flip(range: AxisRange) -> AxisRange {
+    match range {
+        AxisRange::Full => AxisRange::Full,
+        AxisRange::Positive => AxisRange::Negative,
+        AxisRange::Negative => AxisRange::Positive,
+    }
+}
+
+/// Parses a `target:source` field of an SDL mapping string, or returns `None` if it can't be
+/// represented.
+fn parse_binding(target: &str, source: &str) -> Result<Option<GamepadMappingBinding>, ()> {
+    let (source_range, source) = split_range(source);
+    let (source, inverted) = match source.strip_suffix('~') {
+        Some(source) => (source, true),
+        None => (source, false),
+    };
+    let mut source = if let Some(index) = source.strip_prefix('b') {
+        if source_range != AxisRange::Full || inverted {
+            return Err(());
+        }
+        GamepadMappingSource::Button(GamepadButton::Other(index.parse().map_err(|_| ())?))
+    } else if let Some(index) = source.strip_prefix('a') {
+        GamepadMappingSource::Axis {
+            axis: GamepadAxis::Other(index.parse().map_err(|_| ())?),
+            range: source_range,
+            inverted,
+        }
+    } else if source.starts_with('h') {
+        return Ok(None);
+    } else {
+        return Err(());
+    };
+
+    let (target_range, target) = split_range(target);
+    let target = if let Some(&(_, button)) = SDL_BUTTONS.iter().find(|(name, _)| *name == target) {
+        if target_range != AxisRange::Full {
+            return Err(());
+        }
+        GamepadMappingTarget::Button(button)
+    } else if let Some(&(_, axis, down)) = SDL_AXES.iter().find(|(name, ..)| *name == target) {
+        let mut range = target_range;
+        if down {
+            flip_source_or_target(&mut source, &mut range);
+        }
+        GamepadMappingTarget::Axis { axis, range }
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(GamepadMappingBinding { source, target }))
+}
+
+/// Flips the direction of a binding to or from one of SDL's downward Y axes.
+fn flip_source_or_target(source: &mut GamepadMappingSource, range: &mut AxisRange) {
+    match (source, *range) {
+        (GamepadMappingSource::Axis { inverted, .. }, AxisRange::Full) => *inverted = !*inverted,
+        // A button can't be inverted, and SDL reads it as the top of the axis anyway.
+        (GamepadMappingSource::Button(_), AxisRange::Full) => {}
+        (_, half) => *range = flip(half),
+    }
+}
+
+/// Formats a binding as a `target:source` field of an SDL mapping string, or returns `None` if it
+/// has no SDL equivalent.
+fn format_binding(binding: &GamepadMappingBinding) -> Option<String> {
+    let mut source = binding.source;
+    let target = match binding.target {
+        GamepadMappingTarget::Button(button) => SDL_BUTTONS
+            .iter()
+            .find(|(_, standard)| *standard == button)?
+            .0
+            .to_string(),
+        GamepadMappingTarget::Axis { axis, range } => {
+            let &(name, _, down) = SDL_AXES.iter().find(|(_, standard, _)| *standard == axis)?;
+            let mut range = range;
+            if down {
+                flip_source_or_target(&mut source, &mut range);
+            }
+            format!("{}{name}", range_prefix(range))
+        }
+    };
+    let source = match source {
+        GamepadMappingSource::Button(GamepadButton::Other(index)) => format!("b{index}"),
+        GamepadMappingSource::Axis {
+            axis: GamepadAxis::Other(index),
+            range,
+            inverted,
+        } => format!(
+            "{}a{index}{}",
+            range_prefix(range),
+            if inverted { "~" } else { "" }
+        ),
+        _ => return None,
+    };
+    Some(format!("{target}:{source}"))
+}
+
+fn range_prefix(range: AxisRange) -> &'static str {
+    match range {
+        AxisRange::Full => "",
+        AxisRange::Positive => "+",
+        AxisRange::Negative => "-",
+    }
+}
+
+/// The name SDL gives to the current platform in the `platform` field of mapping strings.
+const SDL_PLATFORM: Option<&str> = if cfg!(target_os = "windows") {
+    Some("Windows")
+} else if cfg!(target_os = "macos") {
+    Some("Mac OS X")
+} else if cfg!(target_os = "linux") {
+    Some("Linux")
+} else if cfg!(target_os = "android") {
+    Some("Android")
+} else if cfg!(target_os = "ios") {
+    Some("iOS")
+} else {
+    None
+};
+
+/// Returns `false` if an SDL mapping string is restricted to another platform.
+fn is_for_current_platform(mapping: &str) -> bool {
+    mapping
+        .split(',')
+        .filter_map(|field| field.strip_prefix("platform:"))
+        .all(|platform| Some(platform) == SDL_PLATFORM)
+}
+
+/// The [`GamepadMapping`]s of known gamepads, keyed by their USB vendor and product IDs.
+///
+/// Fill it with SDL `gamecontrollerdb` mapping strings, such as the community database at
+/// <https://github.com/mdqinc/SDL_GameControllerDB>:
+///
+/// ```
+/// # use bevy_input::gamepad::GamepadButton;
+/// # use bevy_input::gamepad_mapping::GamepadMappingDatabase;
+/// let mut database = GamepadMappingDatabase::default();
+/// database
+///     .add_sdl_mappings("03000000ad1b000016f0000090040000,Arcade Stick,a:b1,b:b2,")
+///     .unwrap();
+/// let mapping = database.get(0x1bad, 0xf016).unwrap();
+/// assert_eq!(
+///     mapping.source_of(GamepadButton::South),
+///     Some(GamepadButton::Other(1).into())
+/// );
+/// ```
+///
+/// A [`GamepadMapping`] component on a gamepad takes precedence over its database mapping, and
+/// gamepads with a [`NoGamepadMappingDatabase`] component don't use it.
+#[derive(Resource, Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Resource, Clone)
+)]
+pub struct GamepadMappingDatabase {
+    mappings: HashMap<(u16, u16), GamepadMapping>,
+}
+
+impl GamepadMappingDatabase {
+    /// Returns the mapping of the gamepad with the given USB vendor and product IDs.
+    pub fn get(&self, vendor_id: u16, product_id: u16) -> Option<&GamepadMapping> {
+        self.mappings.get(&(vendor_id, product_id))
+    }
+
+    /// Sets the mapping of the gamepad with the given USB vendor and product IDs, returning its
+    /// previous mapping.
+    pub fn insert(
+        &mut self,
+        vendor_id: u16,
+        product_id: u16,
+        mapping: GamepadMapping,
+    ) -> Option<GamepadMapping> {
+        self.mappings.insert((vendor_id, product_id), mapping)
+    }
+
+    /// Removes the mapping of the gamepad with the given USB vendor and product IDs.
+    pub fn remove(&mut self, vendor_id: u16, product_id: u16) -> Option<GamepadMapping> {
+        self.mappings.remove(&(vendor_id, product_id))
+    }
+
+    /// Returns the number of mappings in the database.
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    /// Returns `true` if the database has no mappings.
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// Adds SDL mapping strings, one per line, as found in `gamecontrollerdb.txt` files.
+    ///
+    /// Empty lines, `#` comments and mappings for other platforms are ignored, and mappings
+    /// replace earlier ones for the same gamepad. Returns the number of mappings added.
+    pub fn add_sdl_mappings(
+        &mut self,
+        mappings: &str,
+    ) -> Result<usize, GamepadMappingDatabaseError> {
+        let mut added = 0;
+        for (index, line) in mappings.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || !is_for_current_platform(line) {
+                continue;
+            }
+            let (vendor_id, product_id, mapping) =
+                GamepadMapping::from_sdl(line).map_err(|error| GamepadMappingDatabaseError {
+                    line: index + 1,
+                    error,
+                })?;
+            self.insert(vendor_id, product_id, mapping);
+            added += 1;
+        }
+        Ok(added)
+    }
+}
+
+/// Prevents the [`GamepadMappingDatabase`] from remapping the inputs of a gamepad.
+///
+/// Gamepad backends insert this on gamepads whose [`GamepadButton::Other`] and
+/// [`GamepadAxis::Other`] indices don't match SDL's, or that already had an SDL mapping applied by
+/// the backend. A [`GamepadMapping`] component still remaps their inputs.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Component, PartialEq, Clone)
+)]
+pub struct NoGamepadMappingDatabase;
+
+/// An error that occurs when parsing an SDL mapping string.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GamepadMappingError {
+    /// The GUID isn't made of 32 hexadecimal digits.
+    #[error("invalid gamepad GUID `{0}`")]
+    InvalidGuid(String),
+    /// The name of the gamepad is missing.
+    #[error("missing gamepad name")]
+    MissingName,
+    /// A field isn't a valid `target:source` binding.
+    #[error("invalid mapping field `{0}`")]
+    InvalidField(String),
+}
+
+/// An error that occurs when adding SDL mapping strings to a [`GamepadMappingDatabase`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid gamepad mapping on line {line}: {error}")]
+pub struct GamepadMappingDatabaseError {
+    /// The line of the invalid mapping, starting from 1.
+    pub line: usize,
+    /// Why the mapping is invalid.
+    pub error: GamepadMappingError,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARCADE_STICK: &str = "03000000ad1b000016f0000090040000,Arcade Stick,a:b1,b:b2,dpup:-a1,dpdown:+a1,leftx:a0,lefty:a1,righty:a3~,lefttrigger:a2,+rightx:b5,misc1:b9,dpleft:h0.8,";
+
+    #[test]
+    fn parse_sdl_mapping() {
+        let (vendor_id, product_id, mapping) = GamepadMapping::from_sdl(ARCADE_STICK).unwrap();
+        assert_eq!((vendor_id, product_id), (0x1bad, 0xf016));
+        assert_eq!(mapping.name, "Arcade Stick");
+        // `misc1` and the hat are skipped.
+        assert_eq!(mapping.bindings.len(), 9);
+
+        assert_eq!(
+            mapping.source_of(GamepadButton::East),
+            Some(GamepadButton::Other(2).into())
+        );
+        // SDL's Y axes point down.
+        assert_eq!(
+            mapping.source_of(GamepadAxis::LeftStickY),
+            Some(GamepadMappingSource::Axis {
+                axis: GamepadAxis::Other(1),
+                range: AxisRange::Full,
+                inverted: true,
+            })
+        );
+        assert_eq!(
+            mapping.source_of(GamepadAxis::RightStickY),
+            Some(GamepadAxis::Other(3).into())
+        );
+
+        assert_eq!(
+            GamepadMapping::from_sdl(&mapping.to_sdl(vendor_id, product_id)),
+            Ok((vendor_id, product_id, mapping))
+        );
+
+        assert_eq!(
+            GamepadMapping::from_sdl("0300,Pad,a:b0"),
+            Err(GamepadMappingError::InvalidGuid("0300".into()))
+        );
+        assert_eq!(
+            GamepadMapping::from_sdl("03000000ad1b000016f0000090040000,Pad,a:x0"),
+            Err(GamepadMappingError::InvalidField("a:x0".into()))
+        );
+    }
+
+    #[test]
+    fn map_values() {
+        let (_, _, mapping) = GamepadMapping::from_sdl(ARCADE_STICK).unwrap();
+        let map = |input: GamepadInput, value| {
+            let mut mapped = Vec::new();
+            mapping.map(
+                input,
+                value,
+                |_| 0.0,
+                |input, value| mapped.push((input, value)),
+            );
+            mapped
+        };
+
+        assert_eq!(
+            map(GamepadAxis::Other(1).into(), -0.5),
+            [
+                (GamepadButton::DPadUp.into(), 0.5),
+                (GamepadButton::DPadDown.into(), 0.0),
+                (GamepadAxis::LeftStickY.into(), 0.5),
+            ]
+        );
+        assert_eq!(
+            map(GamepadAxis::Other(2).into(), 0.0),
+            [(GamepadButton::LeftTrigger2.into(), 0.5)]
+        );
+        assert_eq!(
+            map(GamepadButton::Other(5).into(), 1.0),
+            [(GamepadAxis::RightStickX.into(), 1.0)]
+        );
+        // Unmapped inputs are passed through.
+        assert_eq!(
+            map(GamepadButton::Other(7).into(), 1.0),
+            [(GamepadButton::Other(7).into(), 1.0)]
+        );
+    }
+
+    #[test]
+    fn map_split_axis() {
+        let (_, _, mapping) = GamepadMapping::from_sdl(
+            "03000000ad1b000016f0000090040000,Arcade Stick,-leftx:b4,+leftx:b5,",
+        )
+        .unwrap();
+        assert_eq!(mapping.bindings.len(), 2);
+        let map = |input: GamepadButton, value, held: Option<GamepadButton>| {
+            let mut mapped = Vec::new();
+            mapping.map(
+                input.into(),
+                value,
+                |other| {
+                    if Some(other) == held.map(Into::into) {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                },
+                |input, value| mapped.push((input, value)),
+            );
+            mapped
+        };
+
+        assert_eq!(
+            map(GamepadButton::Other(4), 1.0, None),
+            [(GamepadAxis::LeftStickX.into(), -1.0)]
+        );
+        // Releasing one half keeps the other one held.
+        assert_eq!(
+            map(GamepadButton::Other(5), 0.0, Some(GamepadButton::Other(4))),
+            [(GamepadAxis::LeftStickX.into(), -1.0)]
+        );
+        assert_eq!(
+            map(GamepadButton::Other(4), 0.0, None),
+            [(GamepadAxis::LeftStickX.into(), 0.0)]
+        );
+    }
+
+    #[test]
+    fn database_filters_lines() {
+        let other_platform = if SDL_PLATFORM == Some("Windows") {
+            "Linux"
+        } else {
+            "Windows"
+        };
+        let mut database = GamepadMappingDatabase::default();
+        let mappings = format!(
+            "# Game controller mappings\n\n{ARCADE_STICK}\n03000000ad1b000017f0000090040000,Other,a:b0,platform:{other_platform},\n"
+        );
+        assert_eq!(database.add_sdl_mappings(&mappings), Ok(1));
+        assert!(database.get(0x1bad, 0xf016).is_some());
+        assert!(database.get(0x1bad, 0xf017).is_none());
+
+        assert_eq!(
+            database.add_sdl_mappings("\nnot a mapping"),
+            Err(GamepadMappingDatabaseError {
+                line: 2,
+                error: GamepadMappingError::InvalidGuid("not a mapping".into()),
+            })
+        );
+    }
+}
@@ -17,8 +17,9 @@ use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 ///
 /// ## Platform-specific
 ///
-/// - Only available on **`macOS`** and **`iOS`**.
+/// - Only delivered by the OS on **`macOS`** and **`iOS`**.
 /// - On **`iOS`**, must be enabled first
+/// - Also written on all platforms by `TouchGesturePlugin`, when it is added
 #[derive(Message, Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(
     feature = "bevy_reflect",
@@ -39,8 +40,9 @@ pub struct PinchGesture(pub f32);
 ///
 /// ## Platform-specific
 ///
-/// - Only available on **`macOS`** and **`iOS`**.
+/// - Only delivered by the OS on **`macOS`** and **`iOS`**.
 /// - On **`iOS`**, must be enabled first
+/// - Also written on all platforms by `TouchGesturePlugin`, when it is added
 #[derive(Message, Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(
     feature = "bevy_reflect",
@@ -58,8 +60,9 @@ pub struct RotationGesture(pub f32);
 ///
 /// ## Platform-specific
 ///
-/// - Only available on **`macOS`** and **`iOS`**.
+/// - Only delivered by the OS on **`macOS`** and **`iOS`**.
 /// - On **`iOS`**, must be enabled first
+/// - Also written on all platforms by `TouchGesturePlugin`, when it is added
 #[derive(Message, Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(
     feature = "bevy_reflect",
@@ -78,6 +81,7 @@ pub struct DoubleTapGesture;
 /// ## Platform-specific
 ///
 /// - On **`iOS`**, must be enabled first
+/// - Also written on all platforms by `TouchGesturePlugin`, when it is added
 #[derive(Message, Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(
     feature = "bevy_reflect",
@@ -90,3 +94,83 @@ pub struct DoubleTapGesture;
     reflect(Serialize, Deserialize)
 )]
 pub struct PanGesture(pub Vec2);
+
+/// Single tap gesture, recognized from a short touch that doesn't move.
+///
+/// The position is in logical pixels, like [`TouchInput::position`](crate::touch::TouchInput::position).
+///
+/// ## Platform-specific
+///
+/// - Only written when `TouchGesturePlugin` is added, on all platforms.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone, Message)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct TapGesture {
+    /// Where the touch was released.
+    pub position: Vec2,
+}
+
+/// Long press gesture, recognized from a touch that is held in place.
+///
+/// Written once, as soon as the touch has been held long enough, rather than when it is released.
+///
+/// ## Platform-specific
+///
+/// - Only written when `TouchGesturePlugin` is added, on all platforms.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone, Message)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct LongPressGesture {
+    /// Where the touch is being held.
+    pub position: Vec2,
+}
+
+/// Swipe gesture, recognized from a single touch that moves quickly and is then released.
+///
+/// Positions are in logical pixels, with the y axis pointing down.
+///
+/// ## Platform-specific
+///
+/// - Only written when `TouchGesturePlugin` is added, on all platforms.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone, Message)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SwipeGesture {
+    /// Where the touch started.
+    pub start: Vec2,
+    /// Where the touch was released.
+    pub end: Vec2,
+    /// The average velocity of the touch, in logical pixels per second.
+    pub velocity: Vec2,
+}
+
+impl SwipeGesture {
+    /// The direction of the swipe, as a unit vector.
+    pub fn direction(&self) -> Vec2 {
+        (self.end - self.start).normalize_or_zero()
+    }
+}
@@ -17,6 +17,7 @@ extern crate std;
 
 extern crate alloc;
 
+pub mod action;
 mod axis;
 mod button_input;
 /// Common run conditions
@@ -25,6 +26,9 @@ pub mod common_conditions;
 #[cfg(feature = "gamepad")]
 pub mod gamepad;
 
+#[cfg(feature = "gamepad")]
+pub mod gamepad_mapping;
+
 #[cfg(feature = "gestures")]
 pub mod gestures;
 
@@ -38,6 +42,9 @@ pub mod mouse;
 #[cfg(any(feature = "touch", feature = "mouse"))]
 pub mod touch;
 
+#[cfg(feature = "touch_gestures")]
+pub mod touch_gestures;
+
 pub use axis::*;
 pub use button_input::*;
 
@@ -46,7 +53,10 @@ pub use button_input::*;
 /// This includes the most common types in this crate, re-exported for your convenience.
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Axis, ButtonInput};
+    pub use crate::{
+        action::{ActionPlugin, ActionState, Actionlike, InputMap},
+        Axis, ButtonInput,
+    };
 
     #[doc(hidden)]
     #[cfg(feature = "gamepad")]
@@ -94,6 +104,9 @@ use gamepad::{
     RawGamepadEvent,
 };
 
+#[cfg(feature = "gamepad")]
+use gamepad_mapping::GamepadMappingDatabase;
+
 #[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
@@ -138,7 +151,10 @@ impl Plugin for InputPlugin {
         app.add_message::<PinchGesture>()
             .add_message::<RotationGesture>()
             .add_message::<DoubleTapGesture>()
-            .add_message::<PanGesture>();
+            .add_message::<PanGesture>()
+            .add_message::<TapGesture>()
+            .add_message::<LongPressGesture>()
+            .add_message::<SwipeGesture>();
 
         #[cfg(feature = "gamepad")]
         app.add_message::<GamepadEvent>()
@@ -150,6 +166,7 @@ impl Plugin for InputPlugin {
             .add_message::<RawGamepadAxisChangedEvent>()
             .add_message::<RawGamepadButtonChangedEvent>()
             .add_message::<GamepadRumbleRequest>()
+            .init_resource::<GamepadMappingDatabase>()
             .add_systems(
                 PreUpdate,
                 (
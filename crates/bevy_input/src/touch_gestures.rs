@@ -0,0 +1,448 @@
+//! Platform-independent gesture recognition from touch input.
+//!
+//! [`TouchGesturePlugin`] derives gestures from [`Touches`] every frame and writes them as
+//! messages, so touchscreens on every platform produce the same gestures:
+//!
+//! - [`TapGesture`] and [`DoubleTapGesture`] for short touches that don't move.
+//! - [`LongPressGesture`] for touches held in place.
+//! - [`SwipeGesture`] for quick single-touch flicks.
+//! - [`PanGesture`] for touches being dragged.
+//! - [`PinchGesture`] and [`RotationGesture`] for two touches moving relative to each other.
+//!
+//! The thresholds used to tell these apart are configured with [`TouchGestureSettings`].
+//!
+//! On **`macOS`** and **`iOS`**, the OS can deliver some of these gestures itself. Avoid enabling
+//! both the OS gestures and this plugin, or the same gesture will be written twice.
+
+use core::time::Duration;
+
+use crate::{
+    gestures::{
+        DoubleTapGesture, LongPressGesture, PanGesture, PinchGesture, RotationGesture,
+        SwipeGesture, TapGesture,
+    },
+    touch::Touches,
+    InputSystems,
+};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    message::MessageWriter,
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, SystemSet},
+    system::{Res, ResMut},
+};
+use bevy_math::Vec2;
+use bevy_platform::collections::HashMap;
+use bevy_time::{Real, Time};
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::reflect::ReflectResource,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+/// Adds platform-independent gesture recognition from touch input.
+///
+/// Requires the `InputPlugin` and the `TimePlugin`. This plugin is included in `DefaultPlugins`
+/// when the `touch_gestures` feature is enabled.
+#[derive(Default)]
+pub struct TouchGesturePlugin;
+
+impl Plugin for TouchGesturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchGestureSettings>()
+            .init_resource::<TouchGestureState>()
+            .add_systems(
+                PreUpdate,
+                recognize_touch_gestures
+                    .in_set(TouchGestureSystems)
+                    .after(InputSystems),
+            );
+    }
+}
+
+/// The system set in which [`recognize_touch_gestures`] runs, after [`InputSystems`].
+#[derive(SystemSet, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct TouchGestureSystems;
+
+/// Thresholds used by [`recognize_touch_gestures`] to tell gestures apart.
+///
+/// Distances are in logical pixels.
+#[derive(Resource, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, Default, PartialEq, Clone)
+)]
+pub struct TouchGestureSettings {
+    /// How far a touch can move from where it started before it is considered to be dragged.
+    ///
+    /// Dragged touches can no longer produce taps or long presses, and start producing pans.
+    pub drag_threshold: f32,
+    /// The longest a touch can be held and still count as a tap.
+    pub tap_max_duration: Duration,
+    /// The longest time between two taps for them to count as a double tap.
+    pub double_tap_max_interval: Duration,
+    /// The furthest apart two taps can be for them to count as a double tap.
+    pub double_tap_max_distance: f32,
+    /// How long a touch has to be held in place to count as a long press.
+    pub long_press_duration: Duration,
+    /// The shortest distance a touch has to travel to count as a swipe.
+    pub swipe_min_distance: f32,
+    /// The lowest average speed, in logical pixels per second, for a touch to count as a swipe.
+    pub swipe_min_speed: f32,
+}
+
+impl Default for TouchGestureSettings {
+    fn default() -> Self {
+        Self {
+            drag_threshold: 10.0,
+            tap_max_duration: Duration::from_millis(300),
+            double_tap_max_interval: Duration::from_millis(300),
+            double_tap_max_distance: 40.0,
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_min_speed: 300.0,
+        }
+    }
+}
+
+/// The touches and taps tracked by [`recognize_touch_gestures`] between frames.
+#[derive(Resource, Debug, Default)]
+pub struct TouchGestureState {
+    touches: HashMap<u64, TrackedTouch>,
+    /// The most touches that were held at once since the last time no touch was held.
+    max_touches: usize,
+    /// When and where the last tap that could start a double tap happened.
+    last_tap: Option<(Duration, Vec2)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TrackedTouch {
+    started: Duration,
+    start_position: Vec2,
+    position: Vec2,
+    dragged: bool,
+    long_pressed: bool,
+}
+
+/// Recognizes gestures from [`Touches`] and writes them as messages.
+///
+/// Taps, double taps, long presses and swipes are only recognized from a single touch. Pans follow
+/// the average movement of every held touch, and pinches and rotations are recognized while
+/// exactly two touches are held.
+pub fn recognize_touch_gestures(
+    settings: Res<TouchGestureSettings>,
+    time: Res<Time<Real>>,
+    touches: Res<Touches>,
+    mut state: ResMut<TouchGestureState>,
+    mut taps: MessageWriter<TapGesture>,
+    mut double_taps: MessageWriter<DoubleTapGesture>,
+    mut long_presses: MessageWriter<LongPressGesture>,
+    mut swipes: MessageWriter<SwipeGesture>,
+    mut pans: MessageWriter<PanGesture>,
+    mut pinches: MessageWriter<PinchGesture>,
+    mut rotations: MessageWriter<RotationGesture>,
+) {
+    let now = time.elapsed();
+    let state = &mut *state;
+
+    for touch in touches.iter_just_pressed() {
+        state.touches.insert(
+            touch.id(),
+            TrackedTouch {
+                started: now,
+                start_position: touch.start_position(),
+                position: touch.start_position(),
+                dragged: false,
+                long_pressed: false,
+            },
+        );
+    }
+    state.max_touches = state.max_touches.max(touches.iter().count());
+
+    // Pinches and rotations compare the two touches against where they were last frame.
+    let mut pair = touches.iter().filter_map(|touch| {
+        let tracked = state.touches.get(&touch.id())?;
+        Some((tracked.position, touch.position()))
+    });
+    if touches.iter().count() == 2
+        && let (Some((previous_a, a)), Some((previous_b, b))) = (pair.next(), pair.next())
+    {
+        let previous = previous_b - previous_a;
+        let current = b - a;
+        let previous_length = previous.length();
+        if previous_length > 0.0 && current != previous {
+            let pinch = current.length() / previous_length - 1.0;
+            if pinch != 0.0 {
+                pinches.write(PinchGesture(pinch));
+            }
+            // Touch positions have the y axis pointing down, so a positive angle is clockwise.
+            let rotation = -previous.angle_to(current);
+            if rotation != 0.0 {
+                rotations.write(RotationGesture(rotation));
+            }
+        }
+    }
+
+    let mut pan = Vec2::ZERO;
+    let mut panning_touches = 0;
+    let mut dragging = false;
+    for touch in touches.iter() {
+        let Some(tracked) = state.touches.get_mut(&touch.id()) else {
+            continue;
+        };
+        pan += touch.position() - tracked.position;
+        panning_touches += 1;
+        tracked.position = touch.position();
+
+        if touch.position().distance(tracked.start_position) > settings.drag_threshold {
+            tracked.dragged = true;
+        }
+        dragging |= tracked.dragged;
+
+        if state.max_touches == 1
+            && !tracked.dragged
+            && !tracked.long_pressed
+            && now.saturating_sub(tracked.started) >= settings.long_press_duration
+        {
+            tracked.long_pressed = true;
+            long_presses.write(LongPressGesture {
+                position: touch.position(),
+            });
+        }
+    }
+    if dragging && panning_touches > 0 && pan != Vec2::ZERO {
+        pans.write(PanGesture(pan / panning_touches as f32));
+    }
+
+    for touch in touches.iter_just_released() {
+        let tracked = state.touches.remove(&touch.id()).unwrap_or(TrackedTouch {
+            started: now,
+            start_position: touch.start_position(),
+            position: touch.position(),
+            dragged: false,
+            long_pressed: false,
+        });
+        if state.max_touches > 1 || tracked.long_pressed {
+            continue;
+        }
+
+        let end = touch.position();
+        let held = now.saturating_sub(tracked.started);
+        let dragged =
+            tracked.dragged || end.distance(tracked.start_position) > settings.drag_threshold;
+
+        if dragged {
+            let distance = end - tracked.start_position;
+            // Touches that start and end within the same frame are given the duration of a frame.
+            let seconds = held.as_secs_f32().max(time.delta_secs()).max(f32::EPSILON);
+            let velocity = distance / seconds;
+            if distance.length() >= settings.swipe_min_distance
+                && velocity.length() >= settings.swipe_min_speed
+            {
+                swipes.write(SwipeGesture {
+                    start: tracked.start_position,
+                    end,
+                    velocity,
+                });
+            }
+        } else if held <= settings.tap_max_duration {
+            taps.write(TapGesture { position: end });
+            match state.last_tap {
+                Some((at, position))
+                    if now.saturating_sub(at) <= settings.double_tap_max_interval
+                        && position.distance(end) <= settings.double_tap_max_distance =>
+                {
+                    double_taps.write(DoubleTapGesture);
+                    // A third tap starts a new double tap instead of completing another one.
+                    state.last_tap = None;
+                }
+                _ => state.last_tap = Some((now, end)),
+            }
+        }
+    }
+
+    for touch in touches.iter_just_canceled() {
+        state.touches.remove(&touch.id());
+    }
+
+    if state.touches.is_empty() {
+        state.max_touches = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gestures::*,
+        touch::{TouchInput, TouchPhase},
+        InputPlugin,
+    };
+    use alloc::vec::Vec;
+    use bevy_app::App;
+    use bevy_ecs::{entity::Entity, message::Messages};
+    use bevy_time::TimeUpdateStrategy;
+
+    const FRAME: Duration = Duration::from_millis(16);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((bevy_time::TimePlugin, InputPlugin, TouchGesturePlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        app.update();
+        app
+    }
+
+    fn touch(app: &mut App, id: u64, phase: TouchPhase, position: Vec2) {
+        app.world_mut().write_message(TouchInput {
+            phase,
+            position,
+            window: Entity::PLACEHOLDER,
+            force: None,
+            id,
+        });
+    }
+
+    /// Updates the app for `duration`, returning the `M` messages written in the meantime.
+    fn wait<M: bevy_ecs::message::Message>(app: &mut App, duration: Duration) -> Vec<M> {
+        let mut messages = Vec::new();
+        for _ in 0..duration.div_duration_f32(FRAME).ceil() as u32 {
+            app.update();
+            messages.extend(drain::<M>(app));
+        }
+        messages
+    }
+
+    fn count<M: bevy_ecs::message::Message>(app: &App) -> usize {
+        app.world().resource::<Messages<M>>().len()
+    }
+
+    fn drain<M: bevy_ecs::message::Message>(app: &mut App) -> Vec<M> {
+        app.world_mut()
+            .resource_mut::<Messages<M>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn taps_and_double_taps() {
+        let mut app = app();
+
+        touch(&mut app, 0, TouchPhase::Started, Vec2::new(100.0, 100.0));
+        app.update();
+        touch(&mut app, 0, TouchPhase::Ended, Vec2::new(100.0, 100.0));
+        app.update();
+        assert_eq!(
+            drain::<TapGesture>(&mut app),
+            [TapGesture {
+                position: Vec2::new(100.0, 100.0)
+            }]
+        );
+        assert_eq!(count::<DoubleTapGesture>(&app), 0);
+
+        touch(&mut app, 1, TouchPhase::Started, Vec2::new(105.0, 100.0));
+        app.update();
+        touch(&mut app, 1, TouchPhase::Ended, Vec2::new(105.0, 100.0));
+        app.update();
+        assert_eq!(drain::<TapGesture>(&mut app).len(), 1);
+        assert_eq!(drain::<DoubleTapGesture>(&mut app).len(), 1);
+
+        // Too slow to be a tap.
+        touch(&mut app, 2, TouchPhase::Started, Vec2::new(100.0, 100.0));
+        wait::<TapGesture>(&mut app, Duration::from_millis(400));
+        touch(&mut app, 2, TouchPhase::Ended, Vec2::new(100.0, 100.0));
+        app.update();
+        assert_eq!(drain::<TapGesture>(&mut app).len(), 0);
+    }
+
+    #[test]
+    fn long_press_is_written_while_held() {
+        let mut app = app();
+
+        touch(&mut app, 0, TouchPhase::Started, Vec2::new(50.0, 50.0));
+        app.update();
+        assert!(wait::<LongPressGesture>(&mut app, Duration::from_millis(450)).is_empty());
+        assert_eq!(
+            wait::<LongPressGesture>(&mut app, Duration::from_millis(100)),
+            [LongPressGesture {
+                position: Vec2::new(50.0, 50.0)
+            }]
+        );
+        assert!(wait::<LongPressGesture>(&mut app, Duration::from_millis(500)).is_empty());
+        touch(&mut app, 0, TouchPhase::Ended, Vec2::new(50.0, 50.0));
+        app.update();
+        assert_eq!(drain::<LongPressGesture>(&mut app).len(), 0);
+        assert_eq!(drain::<TapGesture>(&mut app).len(), 0);
+    }
+
+    #[test]
+    fn swipes_and_pans() {
+        let mut app = app();
+
+        touch(&mut app, 0, TouchPhase::Started, Vec2::new(0.0, 0.0));
+        app.update();
+        let mut pan = Vec2::ZERO;
+        for x in [40.0, 80.0, 120.0] {
+            touch(&mut app, 0, TouchPhase::Moved, Vec2::new(x, 0.0));
+            app.update();
+            pan += drain::<PanGesture>(&mut app)
+                .iter()
+                .map(|pan| pan.0)
+                .sum::<Vec2>();
+        }
+        assert_eq!(pan, Vec2::new(120.0, 0.0));
+
+        touch(&mut app, 0, TouchPhase::Ended, Vec2::new(120.0, 0.0));
+        app.update();
+        let swipes = drain::<SwipeGesture>(&mut app);
+        assert_eq!(swipes.len(), 1);
+        assert_eq!(swipes[0].direction(), Vec2::X);
+        assert_eq!(drain::<TapGesture>(&mut app).len(), 0);
+
+        // Dragging slowly pans without swiping.
+        touch(&mut app, 1, TouchPhase::Started, Vec2::new(0.0, 0.0));
+        app.update();
+        for y in 1..=60 {
+            touch(&mut app, 1, TouchPhase::Moved, Vec2::new(0.0, y as f32));
+            app.update();
+        }
+        touch(&mut app, 1, TouchPhase::Ended, Vec2::new(0.0, 60.0));
+        app.update();
+        assert!(!drain::<PanGesture>(&mut app).is_empty());
+        assert_eq!(drain::<SwipeGesture>(&mut app).len(), 0);
+    }
+
+    #[test]
+    fn pinch_and_rotate_with_two_touches() {
+        let mut app = app();
+
+        touch(&mut app, 0, TouchPhase::Started, Vec2::new(-50.0, 0.0));
+        touch(&mut app, 1, TouchPhase::Started, Vec2::new(50.0, 0.0));
+        app.update();
+
+        // Spread the touches apart.
+        touch(&mut app, 0, TouchPhase::Moved, Vec2::new(-100.0, 0.0));
+        touch(&mut app, 1, TouchPhase::Moved, Vec2::new(100.0, 0.0));
+        app.update();
+        let pinches = drain::<PinchGesture>(&mut app);
+        assert_eq!(pinches.len(), 1);
+        assert!((pinches[0].0 - 1.0).abs() < 1e-5);
+        assert_eq!(drain::<RotationGesture>(&mut app).len(), 0);
+
+        // Turn them a quarter turn counterclockwise on screen, where y points down.
+        touch(&mut app, 0, TouchPhase::Moved, Vec2::new(0.0, 100.0));
+        touch(&mut app, 1, TouchPhase::Moved, Vec2::new(0.0, -100.0));
+        app.update();
+        let rotations = drain::<RotationGesture>(&mut app);
+        assert_eq!(rotations.len(), 1);
+        assert!((rotations[0].0 - core::f32::consts::FRAC_PI_2).abs() < 1e-5);
+
+        touch(&mut app, 0, TouchPhase::Ended, Vec2::new(0.0, 100.0));
+        touch(&mut app, 1, TouchPhase::Ended, Vec2::new(0.0, -100.0));
+        app.update();
+        assert_eq!(count::<TapGesture>(&app), 0);
+        assert_eq!(count::<SwipeGesture>(&app), 0);
+    }
+}
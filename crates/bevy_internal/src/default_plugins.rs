@@ -12,6 +12,8 @@ plugin_group! {
         bevy_transform:::TransformPlugin,
         bevy_diagnostic:::DiagnosticsPlugin,
         bevy_input:::InputPlugin,
+        #[cfg(feature = "touch_gestures")]
+        bevy_input::touch_gestures:::TouchGesturePlugin,
         #[cfg(feature = "bevy_input_focus")]
         bevy_input_focus:::InputFocusPlugin,
         #[cfg(feature = "bevy_input_focus")]
@@ -81,6 +83,8 @@ plugin_group! {
         bevy_audio:::AudioPlugin,
         #[cfg(feature = "bevy_gilrs")]
         bevy_gilrs:::GilrsPlugin,
+        #[cfg(feature = "bevy_haptics")]
+        bevy_haptics:::HapticsPlugin,
         #[cfg(feature = "bevy_animation")]
         bevy_animation:::AnimationPlugin,
         #[cfg(feature = "bevy_gizmos")]
@@ -51,6 +51,8 @@ pub use bevy_gizmos as gizmos;
 pub use bevy_gizmos_render as gizmos_render;
 #[cfg(feature = "bevy_gltf")]
 pub use bevy_gltf as gltf;
+#[cfg(feature = "bevy_haptics")]
+pub use bevy_haptics as haptics;
 #[cfg(feature = "bevy_image")]
 pub use bevy_image as image;
 pub use bevy_input as input;
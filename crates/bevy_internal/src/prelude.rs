@@ -107,6 +107,10 @@ pub use crate::state::prelude::*;
 #[cfg(feature = "bevy_gltf")]
 pub use crate::gltf::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_haptics")]
+pub use crate::haptics::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_picking")]
 pub use crate::picking::prelude::*;
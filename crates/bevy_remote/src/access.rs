@@ -0,0 +1,423 @@
+//! Authentication of BRP clients, and the methods that they are allowed to call.
+//!
+//! By default, every client that can reach a transport may call every method. To restrict
+//! this, give the [`RemotePlugin`](crate::RemotePlugin) a [`RemoteAuthenticator`] such as
+//! [`BearerTokenAuth`], which the network transports use to decide how much
+//! [`RemoteAccess`] a client gets, and [`RemoteMethodPermissions`] to allow or deny
+//! individual methods.
+//!
+//! ```ignore
+//! App::new()
+//!     .add_plugins(DefaultPlugins)
+//!     .add_plugins(
+//!         RemotePlugin::default()
+//!             // Testers get the full token, dashboards the read-only one.
+//!             .with_auth(
+//!                 BearerTokenAuth::new("playtest-secret")
+//!                     .with_read_only_token("dashboard-secret"),
+//!             )
+//!             .with_permissions(
+//!                 RemoteMethodPermissions::default().deny(BRP_DESPAWN_COMPONENTS_METHOD),
+//!             ),
+//!     )
+//!     .add_plugins(RemoteHttpPlugin::default())
+//!     .run();
+//! ```
+
+use crate::{builtin_methods, error_codes, BrpError};
+use alloc::sync::Arc;
+use bevy_ecs::resource::Resource;
+use bevy_platform::collections::HashSet;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// The access that a client has been granted by the [`RemoteAuth`] of the app.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RemoteAccess {
+    /// The client may only call methods that don't modify the world.
+    ///
+    /// See [`RemoteMethodPermissions::with_read_only_method`].
+    ReadOnly,
+    /// The client may call every method that it is allowed to by the
+    /// [`RemoteMethodPermissions`].
+    #[default]
+    Full,
+}
+
+/// Decides whether a client may make requests, based on the credentials it presents.
+///
+/// The network transports pass the bearer token sent by a client, if any. Transports that
+/// can only be reached from the local machine, such as the stdio transport, don't
+/// authenticate their clients and grant them [`RemoteAccess::Full`].
+///
+/// A [`RemoteAccess`] can itself be used as an authenticator that grants every client that
+/// access.
+pub trait RemoteAuthenticator: Send + Sync + 'static {
+    /// Returns the access to grant to a client that presented `token`, or an
+    /// [`UNAUTHORIZED`](error_codes::UNAUTHORIZED) error to reject its requests.
+    fn authenticate(&self, token: Option<&str>) -> Result<RemoteAccess, BrpError>;
+}
+
+impl RemoteAuthenticator for RemoteAccess {
+    fn authenticate(&self, _token: Option<&str>) -> Result<RemoteAccess, BrpError> {
+        Ok(*self)
+    }
+}
+
+/// A [`RemoteAuthenticator`] that requires clients to present a secret bearer token.
+///
+/// Over HTTP, the token is sent in an `Authorization: Bearer <token>` header.
+#[derive(Clone)]
+pub struct BearerTokenAuth {
+    token: String,
+    read_only_token: Option<String>,
+}
+
+impl BearerTokenAuth {
+    /// Creates an authenticator that grants [`RemoteAccess::Full`] to clients presenting
+    /// `token`, and rejects all others.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            read_only_token: None,
+        }
+    }
+
+    /// Also accept `token`, granting [`RemoteAccess::ReadOnly`] to the clients presenting it.
+    #[must_use]
+    pub fn with_read_only_token(mut self, token: impl Into<String>) -> Self {
+        self.read_only_token = Some(token.into());
+        self
+    }
+}
+
+impl fmt::Debug for BearerTokenAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Keep the secrets out of logs.
+        f.debug_struct("BearerTokenAuth")
+            .field("read_only_token", &self.read_only_token.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RemoteAuthenticator for BearerTokenAuth {
+    fn authenticate(&self, token: Option<&str>) -> Result<RemoteAccess, BrpError> {
+        let Some(token) = token else {
+            return Err(BrpError::unauthorized("Missing bearer token"));
+        };
+
+        if constant_time_eq(token, &self.token) {
+            Ok(RemoteAccess::Full)
+        } else if self
+            .read_only_token
+            .as_deref()
+            .is_some_and(|read_only_token| constant_time_eq(token, read_only_token))
+        {
+            Ok(RemoteAccess::ReadOnly)
+        } else {
+            Err(BrpError::unauthorized("Invalid bearer token"))
+        }
+    }
+}
+
+/// Extracts the token from the value of an HTTP `Authorization` header using the `Bearer`
+/// scheme.
+#[cfg_attr(
+    not(any(feature = "http", feature = "websocket")),
+    expect(dead_code, reason = "only used by the network transports")
+)]
+pub(crate) fn bearer_token(authorization: &str) -> Option<&str> {
+    let (scheme, token) = authorization.trim().split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then_some(token.trim_start())
+}
+
+/// Extracts the token from the `access_token` parameter of a URL `query` string, decoding any
+/// percent-encoded characters.
+///
+/// Returns `None` if the parameter is missing, or isn't valid percent-encoded UTF-8.
+#[cfg_attr(
+    not(any(test, feature = "websocket")),
+    expect(dead_code, reason = "only used by the WebSocket transport")
+)]
+pub(crate) fn access_token_param(query: &str) -> Option<String> {
+    let value = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("access_token="))?;
+
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.bytes();
+    while let Some(byte) = rest.next() {
+        if byte == b'%' {
+            let high = char::from(rest.next()?).to_digit(16)?;
+            let low = char::from(rest.next()?).to_digit(16)?;
+            bytes.push((high * 16 + low) as u8);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Compares two secrets in a time that doesn't depend on where they differ.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// A resource holding the [`RemoteAuthenticator`] used by the transports of the app.
+///
+/// This is set with [`RemotePlugin::with_auth`](crate::RemotePlugin::with_auth), and lets
+/// every client in with [`RemoteAccess::Full`] by default.
+#[derive(Resource, Clone)]
+pub struct RemoteAuth(Arc<dyn RemoteAuthenticator>);
+
+impl RemoteAuth {
+    /// Creates a [`RemoteAuth`] that uses `authenticator`.
+    pub fn new(authenticator: impl RemoteAuthenticator) -> Self {
+        Self(Arc::new(authenticator))
+    }
+
+    /// Returns the access granted to a client that presented `token`.
+    pub fn authenticate(&self, token: Option<&str>) -> Result<RemoteAccess, BrpError> {
+        self.0.authenticate(token)
+    }
+}
+
+impl Default for RemoteAuth {
+    fn default() -> Self {
+        Self::new(RemoteAccess::Full)
+    }
+}
+
+impl fmt::Debug for RemoteAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RemoteAuth").finish_non_exhaustive()
+    }
+}
+
+/// A resource controlling which methods clients may call.
+///
+/// Every request is checked against these permissions before its handler runs, whatever
+/// transport it came from. Rejected requests are answered with a
+/// [`METHOD_NOT_ALLOWED`](error_codes::METHOD_NOT_ALLOWED) error.
+///
+/// This is set with [`RemotePlugin::with_permissions`](crate::RemotePlugin::with_permissions).
+/// By default, every method is allowed, and the built-in methods that only read from the
+/// world are marked as read-only.
+#[derive(Debug, Clone, Resource)]
+pub struct RemoteMethodPermissions {
+    /// If set, only these methods may be called.
+    allowed: Option<HashSet<String>>,
+    /// Methods that may never be called.
+    denied: HashSet<String>,
+    /// Methods that clients with [`RemoteAccess::ReadOnly`] may call.
+    read_only_methods: HashSet<String>,
+    /// Whether every client is treated as having [`RemoteAccess::ReadOnly`].
+    read_only: bool,
+}
+
+impl Default for RemoteMethodPermissions {
+    fn default() -> Self {
+        #[cfg_attr(
+            not(feature = "bevy_asset"),
+            expect(unused_mut, reason = "only the asset methods are added below")
+        )]
+        let mut read_only_methods: HashSet<String> = [
+            builtin_methods::BRP_GET_COMPONENTS_METHOD,
+            builtin_methods::BRP_QUERY_METHOD,
+            builtin_methods::BRP_LIST_COMPONENTS_METHOD,
+            builtin_methods::BRP_GET_COMPONENTS_AND_WATCH_METHOD,
+            builtin_methods::BRP_LIST_COMPONENTS_AND_WATCH_METHOD,
+            builtin_methods::BRP_GET_RESOURCE_METHOD,
+            builtin_methods::BRP_LIST_RESOURCES_METHOD,
+            builtin_methods::BRP_OBSERVE_METHOD,
+            builtin_methods::BRP_REGISTRY_SCHEMA_METHOD,
+            builtin_methods::BRP_SCHEDULE_LIST,
+            builtin_methods::BRP_SCHEDULE_GRAPH,
+            builtin_methods::RPC_DISCOVER_METHOD,
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        #[cfg(feature = "bevy_asset")]
+        read_only_methods.extend(
+            [
+                builtin_methods::BRP_LIST_ASSETS_METHOD,
+                builtin_methods::BRP_GET_ASSET_METHOD,
+            ]
+            .map(String::from),
+        );
+
+        Self {
+            allowed: None,
+            denied: HashSet::default(),
+            read_only_methods,
+            read_only: false,
+        }
+    }
+}
+
+impl RemoteMethodPermissions {
+    /// Allow `method` to be called.
+    ///
+    /// Once any method has been allowed, all methods that haven't been are denied.
+    #[must_use]
+    pub fn allow(mut self, method: impl Into<String>) -> Self {
+        self.allowed
+            .get_or_insert_with(HashSet::default)
+            .insert(method.into());
+        self
+    }
+
+    /// Deny `method` from being called, even if it has been allowed.
+    #[must_use]
+    pub fn deny(mut self, method: impl Into<String>) -> Self {
+        self.denied.insert(method.into());
+        self
+    }
+
+    /// Mark `method` as not modifying the world, so that clients with
+    /// [`RemoteAccess::ReadOnly`] may call it.
+    ///
+    /// Custom methods are assumed to modify the world unless they are marked with this.
+    #[must_use]
+    pub fn with_read_only_method(mut self, method: impl Into<String>) -> Self {
+        self.read_only_methods.insert(method.into());
+        self
+    }
+
+    /// Treat every client as having [`RemoteAccess::ReadOnly`], whatever it was granted.
+    #[must_use]
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Returns `true` if `method` is allowed to be called by a client with
+    /// [`RemoteAccess::Full`].
+    pub fn is_allowed(&self, method: &str) -> bool {
+        !self.denied.contains(method)
+            && self
+                .allowed
+                .as_ref()
+                .is_none_or(|allowed| allowed.contains(method))
+    }
+
+    /// Returns `true` if `method` has been marked as not modifying the world.
+    pub fn is_read_only_method(&self, method: &str) -> bool {
+        self.read_only_methods.contains(method)
+    }
+
+    /// Checks whether a client with the given `access` may call `method`.
+    pub fn check(&self, method: &str, access: RemoteAccess) -> Result<(), BrpError> {
+        if !self.is_allowed(method) {
+            return Err(BrpError::method_not_allowed(method));
+        }
+
+        if (self.read_only || access == RemoteAccess::ReadOnly) && !self.is_read_only_method(method)
+        {
+            return Err(BrpError {
+                code: error_codes::METHOD_NOT_ALLOWED,
+                message: format!("Method `{method}` is not allowed with read-only access"),
+                data: None,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtin_methods::{
+        BRP_DESPAWN_COMPONENTS_METHOD, BRP_GET_COMPONENTS_METHOD, BRP_INSERT_RESOURCE_METHOD,
+        BRP_QUERY_METHOD,
+    };
+
+    #[test]
+    fn bearer_token_auth() {
+        let auth = BearerTokenAuth::new("secret").with_read_only_token("viewer");
+
+        assert_eq!(auth.authenticate(Some("secret")), Ok(RemoteAccess::Full));
+        assert_eq!(
+            auth.authenticate(Some("viewer")),
+            Ok(RemoteAccess::ReadOnly)
+        );
+        for token in [None, Some(""), Some("secre"), Some("secret2")] {
+            assert_eq!(
+                auth.authenticate(token).unwrap_err().code,
+                error_codes::UNAUTHORIZED
+            );
+        }
+    }
+
+    #[test]
+    fn access_token_query_param() {
+        assert_eq!(
+            access_token_param("access_token=secret").as_deref(),
+            Some("secret")
+        );
+        assert_eq!(
+            access_token_param("v=1&access_token=a%2Bb%2fc%3D%3D&x=2").as_deref(),
+            Some("a+b/c==")
+        );
+        assert_eq!(
+            access_token_param("access_token=caf%C3%A9").as_deref(),
+            Some("café")
+        );
+        assert_eq!(access_token_param("token=secret"), None);
+        for invalid in [
+            "access_token=%",
+            "access_token=%2",
+            "access_token=%+1",
+            "access_token=%zz",
+            "access_token=%FF",
+        ] {
+            assert_eq!(access_token_param(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn method_permissions() {
+        let permissions = RemoteMethodPermissions::default();
+        assert!(permissions
+            .check(BRP_DESPAWN_COMPONENTS_METHOD, RemoteAccess::Full)
+            .is_ok());
+        assert!(permissions
+            .check(BRP_GET_COMPONENTS_METHOD, RemoteAccess::ReadOnly)
+            .is_ok());
+        assert_eq!(
+            permissions
+                .check(BRP_DESPAWN_COMPONENTS_METHOD, RemoteAccess::ReadOnly)
+                .unwrap_err()
+                .code,
+            error_codes::METHOD_NOT_ALLOWED
+        );
+
+        // Denying takes precedence over allowing, and allowing anything denies the rest.
+        let permissions = RemoteMethodPermissions::default()
+            .allow(BRP_QUERY_METHOD)
+            .allow(BRP_INSERT_RESOURCE_METHOD)
+            .deny(BRP_INSERT_RESOURCE_METHOD);
+        assert!(permissions.is_allowed(BRP_QUERY_METHOD));
+        assert!(!permissions.is_allowed(BRP_INSERT_RESOURCE_METHOD));
+        assert!(!permissions.is_allowed(BRP_GET_COMPONENTS_METHOD));
+
+        // Read-only mode applies to every client, and custom methods can opt in.
+        let permissions = RemoteMethodPermissions::default()
+            .with_read_only_method("custom.inspect")
+            .read_only();
+        assert!(permissions
+            .check(BRP_INSERT_RESOURCE_METHOD, RemoteAccess::Full)
+            .is_err());
+        assert!(permissions
+            .check("custom.inspect", RemoteAccess::Full)
+            .is_ok());
+    }
+}
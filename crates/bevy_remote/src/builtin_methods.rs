@@ -30,10 +30,11 @@ use serde::{de::DeserializeSeed as _, de::IntoDeserializer, Deserialize, Seriali
 use serde_json::{Map, Value};
 
 use crate::{
+    access::RemoteMethodPermissions,
     error_codes,
     schemas::{
         json_schema::{export_type, JsonSchemaBevyType},
-        open_rpc::OpenRpcDocument,
+        open_rpc::{MethodObject, OpenRpcDocument},
     },
     BrpError, BrpResult, PreviousScheduleBuildMetadata,
 };
@@ -41,6 +42,20 @@ use crate::{
 #[cfg(all(feature = "http", not(target_family = "wasm")))]
 use {crate::schemas::open_rpc::ServerObject, bevy_utils::default};
 
+#[cfg(feature = "bevy_asset")]
+use bevy_asset::{
+    uuid::Uuid, AssetIndex, AssetPath, AssetServer, DependencyLoadState, LoadState,
+    RecursiveDependencyLoadState, ReflectAsset, UntypedAssetId,
+};
+
+#[cfg(feature = "bevy_scene")]
+use {
+    bevy_asset::{Assets, Handle},
+    bevy_scene::{
+        apply_scene_patch_instance, AppliedScenePatch, BsnScene, ScenePatch, ScenePatchInstance,
+    },
+};
+
 /// The method path for a `world.get_components` request.
 pub const BRP_GET_COMPONENTS_METHOD: &str = "world.get_components";
 
@@ -50,6 +65,14 @@ pub const BRP_QUERY_METHOD: &str = "world.query";
 /// The method path for a `world.spawn_entity` request.
 pub const BRP_SPAWN_ENTITY_METHOD: &str = "world.spawn_entity";
 
+/// The method path for a `world.spawn_scene` request.
+#[cfg(feature = "bevy_scene")]
+pub const BRP_SPAWN_SCENE_METHOD: &str = "world.spawn_scene";
+
+/// The method path for a `world.apply_scene` request.
+#[cfg(feature = "bevy_scene")]
+pub const BRP_APPLY_SCENE_METHOD: &str = "world.apply_scene";
+
 /// The method path for a `world.insert_components` request.
 pub const BRP_INSERT_COMPONENTS_METHOD: &str = "world.insert_components";
 
@@ -107,6 +130,22 @@ pub const BRP_OBSERVE_METHOD: &str = "world.observe+watch";
 /// The method path for a `schedule.graph` request.
 pub const BRP_SCHEDULE_GRAPH: &str = "schedule.graph";
 
+/// The method path for an `assets.list` request.
+#[cfg(feature = "bevy_asset")]
+pub const BRP_LIST_ASSETS_METHOD: &str = "assets.list";
+
+/// The method path for an `assets.get` request.
+#[cfg(feature = "bevy_asset")]
+pub const BRP_GET_ASSET_METHOD: &str = "assets.get";
+
+/// The method path for an `assets.mutate` request.
+#[cfg(feature = "bevy_asset")]
+pub const BRP_MUTATE_ASSET_METHOD: &str = "assets.mutate";
+
+/// The method path for an `assets.reload` request.
+#[cfg(feature = "bevy_asset")]
+pub const BRP_RELOAD_ASSET_METHOD: &str = "assets.reload";
+
 /// The method path for a `rpc.discover` request.
 pub const RPC_DISCOVER_METHOD: &str = "rpc.discover";
 
@@ -182,6 +221,49 @@ pub struct BrpSpawnEntityParams {
     pub components: HashMap<String, Value>,
 }
 
+/// A scene sent by the client, either as BSN text or as the path of a scene asset.
+#[cfg(feature = "bevy_scene")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpScene {
+    /// The scene, written in the same BSN format as a `.bsn` asset.
+    Bsn(String),
+    /// The asset path of a [`ScenePatch`] asset, such as a `.bsn` file.
+    Path(String),
+}
+
+/// `world.spawn_scene`: Spawns a scene as a new entity, optionally as a child
+/// of an existing entity, and responds with the IDs of the entities created.
+///
+/// The server responds with a [`BrpSpawnSceneResponse`].
+#[cfg(feature = "bevy_scene")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSpawnSceneParams {
+    /// The scene to spawn, given as either a `bsn` or a `path` field.
+    #[serde(flatten)]
+    pub scene: BrpScene,
+
+    /// The ID of the entity that the root entity of the scene will become a
+    /// child of.
+    #[serde(default)]
+    pub parent: Option<Entity>,
+}
+
+/// `world.apply_scene`: Applies a scene to an existing entity and responds with
+/// the IDs of the entities created.
+///
+/// The server responds with a [`BrpSpawnSceneResponse`].
+#[cfg(feature = "bevy_scene")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpApplySceneParams {
+    /// The ID of the entity that the scene is applied to.
+    pub entity: Entity,
+
+    /// The scene to apply, given as either a `bsn` or a `path` field.
+    #[serde(flatten)]
+    pub scene: BrpScene,
+}
+
 /// `world.despawn_entity`: Given an ID, despawns the entity with that ID.
 ///
 /// The server responds with an okay.
@@ -366,6 +448,92 @@ pub struct BrpObserveParams {
     pub entity: Option<Entity>,
 }
 
+/// `assets.list`: Lists the assets of a given type, along with their load
+/// states.
+///
+/// The server responds with a [`BrpListAssetsResponse`].
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpListAssetsParams {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset_type: String,
+}
+
+/// The ID of an asset, as reported by `assets.list`.
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpAssetId {
+    /// The runtime index of the asset. This is only valid while the app is running.
+    Index(AssetIndex),
+    /// The UUID that the asset was explicitly registered with.
+    Uuid(Uuid),
+}
+
+/// An asset sent by the client, either as its ID or as its asset path.
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpAsset {
+    /// The ID of the asset, as reported by `assets.list`.
+    Id(BrpAssetId),
+    /// The asset path that the asset was loaded from.
+    Path(String),
+}
+
+/// `assets.get`: Retrieves the value of a given asset.
+///
+/// The server responds with a [`BrpGetAssetResponse`].
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpGetAssetParams {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset_type: String,
+
+    /// The asset to get, given as either an `id` or a `path` field.
+    #[serde(flatten)]
+    pub asset: BrpAsset,
+}
+
+/// `assets.mutate`:
+///
+/// The server responds with a null.
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpMutateAssetParams {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset_type: String,
+
+    /// The asset to mutate, given as either an `id` or a `path` field.
+    #[serde(flatten)]
+    pub asset: BrpAsset,
+
+    /// The [path] of the field within the asset.
+    ///
+    /// [path]: bevy_reflect::GetPath
+    #[serde(default)]
+    pub field: String,
+
+    /// The value to insert at `field`.
+    pub value: Value,
+}
+
+/// `assets.reload`: Reloads the assets at a given asset path.
+///
+/// The server responds with a null.
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpReloadAssetParams {
+    /// The asset path to reload.
+    pub path: String,
+}
+
 /// `schedule.graph`:
 ///
 /// The server responds with [`BrpScheduleGraphResponse`] if the schedule is found,
@@ -463,6 +631,114 @@ pub struct BrpSpawnEntityResponse {
     pub entity: Entity,
 }
 
+/// The response to a `world.spawn_scene` or `world.apply_scene` request.
+#[cfg(feature = "bevy_scene")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSpawnSceneResponse {
+    /// The ID of the root entity of the scene.
+    pub entity: Entity,
+
+    /// The IDs of the root entity and every related entity the scene spawned.
+    ///
+    /// This only contains the root entity while the scene is `pending`.
+    pub entities: Vec<Entity>,
+
+    /// Whether the scene is still waiting for its assets to load. It will be
+    /// applied to `entity` once they have loaded.
+    pub pending: bool,
+}
+
+/// The load state of an asset or of its dependencies, as reported by the
+/// [`AssetServer`](bevy_asset::AssetServer).
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpLoadState {
+    /// Loading has not started yet.
+    NotLoaded,
+    /// Loading is in progress.
+    Loading,
+    /// Loading has finished.
+    Loaded,
+    /// Loading failed with the given error.
+    Failed(String),
+}
+
+#[cfg(feature = "bevy_asset")]
+impl From<LoadState> for BrpLoadState {
+    fn from(state: LoadState) -> Self {
+        match state {
+            LoadState::NotLoaded => Self::NotLoaded,
+            LoadState::Loading => Self::Loading,
+            LoadState::Loaded => Self::Loaded,
+            LoadState::Failed(error) => Self::Failed(error.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "bevy_asset")]
+impl From<DependencyLoadState> for BrpLoadState {
+    fn from(state: DependencyLoadState) -> Self {
+        match state {
+            DependencyLoadState::NotLoaded => Self::NotLoaded,
+            DependencyLoadState::Loading => Self::Loading,
+            DependencyLoadState::Loaded => Self::Loaded,
+            DependencyLoadState::Failed(error) => Self::Failed(error.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "bevy_asset")]
+impl From<RecursiveDependencyLoadState> for BrpLoadState {
+    fn from(state: RecursiveDependencyLoadState) -> Self {
+        match state {
+            RecursiveDependencyLoadState::NotLoaded => Self::NotLoaded,
+            RecursiveDependencyLoadState::Loading => Self::Loading,
+            RecursiveDependencyLoadState::Loaded => Self::Loaded,
+            RecursiveDependencyLoadState::Failed(error) => Self::Failed(error.to_string()),
+        }
+    }
+}
+
+/// A single asset in the response to an `assets.list` request.
+///
+/// The load states are `None` for assets that weren't loaded by the
+/// [`AssetServer`](bevy_asset::AssetServer), such as assets added directly to
+/// [`Assets`](bevy_asset::Assets).
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetInfo {
+    /// The ID of the asset.
+    pub id: BrpAssetId,
+
+    /// The asset path that the asset was loaded from, if any.
+    pub path: Option<String>,
+
+    /// The load state of the asset itself.
+    pub load_state: Option<BrpLoadState>,
+
+    /// The load state of the direct dependencies of the asset.
+    pub dependency_state: Option<BrpLoadState>,
+
+    /// The load state of all the dependencies of the asset, recursively.
+    pub recursive_dependency_state: Option<BrpLoadState>,
+}
+
+/// The response to an `assets.list` request.
+#[cfg(feature = "bevy_asset")]
+pub type BrpListAssetsResponse = Vec<BrpAssetInfo>;
+
+/// The response to an `assets.get` request.
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpGetAssetResponse {
+    /// The ID of the asset.
+    pub id: BrpAssetId,
+
+    /// The value of the requested asset.
+    pub value: Value,
+}
+
 /// The response to a `world.get_components` request.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
@@ -1075,6 +1351,265 @@ pub fn process_remote_spawn_entity_request(
     serde_json::to_value(response).map_err(BrpError::internal)
 }
 
+/// Handles a `world.spawn_scene` request coming from a client.
+#[cfg(feature = "bevy_scene")]
+pub fn process_remote_spawn_scene_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSpawnSceneParams { scene, parent } = parse_some(params)?;
+
+    if let Some(parent) = parent {
+        get_entity(world, parent)?;
+    }
+    let handle = load_brp_scene(world, scene)?;
+
+    let mut entity = world.spawn_empty();
+    if let Some(parent) = parent {
+        entity.insert(ChildOf(parent));
+    }
+    let entity_id = entity.id();
+
+    apply_brp_scene(world, entity_id, handle)
+}
+
+/// Handles a `world.apply_scene` request coming from a client.
+#[cfg(feature = "bevy_scene")]
+pub fn process_remote_apply_scene_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpApplySceneParams { entity, scene } = parse_some(params)?;
+
+    get_entity(world, entity)?;
+    let handle = load_brp_scene(world, scene)?;
+
+    apply_brp_scene(world, entity, handle)
+}
+
+/// Loads the [`ScenePatch`] for a [`BrpScene`].
+///
+/// BSN text is resolved immediately if its dependencies have already loaded,
+/// so that errors in the scene can be reported to the client.
+#[cfg(feature = "bevy_scene")]
+fn load_brp_scene(world: &mut World, scene: BrpScene) -> Result<Handle<ScenePatch>, BrpError> {
+    let mut asset_server = world
+        .get_resource::<AssetServer>()
+        .ok_or_else(|| BrpError::resource_not_present(core::any::type_name::<AssetServer>()))?;
+
+    let text = match scene {
+        BrpScene::Path(path) => return Ok(asset_server.load(path)),
+        BrpScene::Bsn(text) => text,
+    };
+
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let scene =
+        BsnScene::parse(&text, &type_registry, &mut asset_server).map_err(BrpError::scene_error)?;
+    let mut patch = ScenePatch::load(asset_server, scene);
+    if !patch
+        .dependencies
+        .iter()
+        .all(|dependency| asset_server.is_loaded_with_dependencies(dependency))
+    {
+        // The asset server resolves the scene once its dependencies have loaded
+        return Ok(asset_server.add(patch));
+    }
+
+    patch
+        .resolve(asset_server, world.resource::<Assets<ScenePatch>>())
+        .map_err(BrpError::scene_error)?;
+    drop(type_registry);
+    // Add the resolved scene directly, so that it can be applied right away
+    Ok(world.resource_mut::<Assets<ScenePatch>>().add(patch))
+}
+
+/// Applies the [`ScenePatch`] to `entity` as a [`ScenePatchInstance`], so that
+/// it is updated in place when the asset is reloaded.
+///
+/// If the scene is ready, it is applied immediately, so that the entities it
+/// creates can be returned to the client. Otherwise the response is pending,
+/// and the scene is applied once its assets have loaded.
+#[cfg(feature = "bevy_scene")]
+fn apply_brp_scene(world: &mut World, entity: Entity, handle: Handle<ScenePatch>) -> BrpResult {
+    world
+        .entity_mut(entity)
+        .remove::<(ScenePatchInstance, AppliedScenePatch)>()
+        .insert(ScenePatchInstance(handle));
+
+    let applied = apply_scene_patch_instance(world, entity).map_err(BrpError::scene_error)?;
+    let response = if applied {
+        BrpSpawnSceneResponse {
+            entity,
+            entities: world
+                .get::<AppliedScenePatch>(entity)
+                .map(|applied| applied.applied().entities())
+                .unwrap_or_else(|| vec![entity]),
+            pending: false,
+        }
+    } else {
+        BrpSpawnSceneResponse {
+            entity,
+            entities: vec![entity],
+            pending: true,
+        }
+    };
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles an `assets.list` request coming from a client.
+#[cfg(feature = "bevy_asset")]
+pub fn process_remote_list_assets_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpListAssetsParams { asset_type } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let (_, reflect_asset) = get_reflect_asset(&type_registry, &asset_type, world)?;
+    let asset_server = world.get_resource::<AssetServer>();
+
+    let response: BrpListAssetsResponse = reflect_asset
+        .ids(world)
+        .map(|id| {
+            let (load_state, dependency_state, recursive_dependency_state) = asset_server
+                .and_then(|asset_server| asset_server.get_load_states(id))
+                .map_or((None, None, None), |(load, dependency, recursive)| {
+                    (
+                        Some(load.into()),
+                        Some(dependency.into()),
+                        Some(recursive.into()),
+                    )
+                });
+            BrpAssetInfo {
+                id: id.into(),
+                path: asset_server
+                    .and_then(|asset_server| asset_server.get_path(id))
+                    .map(|path| path.to_string()),
+                load_state,
+                dependency_state,
+                recursive_dependency_state,
+            }
+        })
+        .collect();
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles an `assets.get` request coming from a client.
+#[cfg(feature = "bevy_asset")]
+pub fn process_remote_get_asset_request(In(params): In<Option<Value>>, world: &World) -> BrpResult {
+    let BrpGetAssetParams { asset_type, asset } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let (type_id, reflect_asset) = get_reflect_asset(&type_registry, &asset_type, world)?;
+    let id = get_asset_id(world, type_id, &asset)?;
+
+    let Some(reflected) = reflect_asset.get(world, id) else {
+        return Err(BrpError::asset_not_present(&asset_type));
+    };
+
+    // Use the `ReflectSerializer` to serialize the value of the asset;
+    // this produces a map with a single item.
+    let reflect_serializer = ReflectSerializer::new(reflected.as_partial_reflect(), &type_registry);
+    let Value::Object(serialized_object) =
+        serde_json::to_value(&reflect_serializer).map_err(BrpError::asset_error)?
+    else {
+        return Err(BrpError::asset_error(format!(
+            "Asset of type `{asset_type}` could not be serialized"
+        )));
+    };
+
+    // Get the single value out of the map.
+    let value = serialized_object.into_values().next().ok_or_else(|| {
+        BrpError::internal(anyhow!("Unexpected format of serialized asset value"))
+    })?;
+    let response = BrpGetAssetResponse {
+        id: id.into(),
+        value,
+    };
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles an `assets.mutate` request coming from a client.
+#[cfg(feature = "bevy_asset")]
+pub fn process_remote_mutate_asset_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpMutateAssetParams {
+        asset_type,
+        asset,
+        field,
+        value,
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+    let (type_id, reflect_asset) = get_reflect_asset(&type_registry, &asset_type, world)?;
+    let id = get_asset_id(world, type_id, &asset)?;
+
+    // Get the actual asset value from the world as a `dyn Reflect`. This marks
+    // the asset as modified, so that systems depending on it pick up the change.
+    let reflected_asset = reflect_asset
+        .get_mut(world, id)
+        .ok_or_else(|| BrpError::asset_not_present(&asset_type))?;
+
+    // Get the type registration for the field with the given path.
+    let value_registration = type_registry
+        .get_with_type_path(
+            reflected_asset
+                .reflect_path(field.as_str())
+                .map_err(BrpError::asset_error)?
+                .reflect_type_path(),
+        )
+        .ok_or_else(|| BrpError::asset_error(anyhow!("Unknown asset field type: `{}`", field)))?;
+
+    // Use the field's type registration to deserialize the given value.
+    let deserialized_value: Box<dyn PartialReflect> =
+        TypedReflectDeserializer::new(value_registration, &type_registry)
+            .deserialize(&value)
+            .map_err(BrpError::asset_error)?;
+
+    // Apply the value to the asset.
+    reflected_asset
+        .reflect_path_mut(field.as_str())
+        .map_err(BrpError::asset_error)?
+        .try_apply(&*deserialized_value)
+        .map_err(BrpError::asset_error)?;
+
+    Ok(Value::Null)
+}
+
+/// Handles an `assets.reload` request coming from a client.
+#[cfg(feature = "bevy_asset")]
+pub fn process_remote_reload_asset_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpReloadAssetParams { path } = parse_some(params)?;
+
+    let asset_server = world
+        .get_resource::<AssetServer>()
+        .ok_or_else(|| BrpError::resource_not_present(core::any::type_name::<AssetServer>()))?;
+    let asset_path = AssetPath::try_parse(&path).map_err(|err| BrpError {
+        code: error_codes::INVALID_PARAMS,
+        message: err.to_string(),
+        data: None,
+    })?;
+
+    // The asset server only reloads assets that are currently loaded, and
+    // would otherwise fail silently.
+    if asset_server.get_path_id(&asset_path).is_none() {
+        return Err(BrpError::asset_error(format!(
+            "No asset is loaded from `{path}`"
+        )));
+    }
+    asset_server.reload(asset_path);
+
+    Ok(Value::Null)
+}
+
 /// Handles a `rpc.discover` request coming from a client.
 pub fn process_remote_list_methods_request(
     In(_params): In<Option<Value>>,
@@ -1103,9 +1638,14 @@ pub fn process_remote_list_methods_request(
     #[cfg(any(not(feature = "http"), target_family = "wasm"))]
     let servers = None;
 
+    let mut methods: Vec<MethodObject> = remote_methods.into();
+    if let Some(permissions) = world.get_resource::<RemoteMethodPermissions>() {
+        methods.retain(|method| permissions.is_allowed(&method.name));
+    }
+
     let doc = OpenRpcDocument {
         info: Default::default(),
-        methods: remote_methods.into(),
+        methods,
         openrpc: "1.3.2".to_owned(),
         servers,
     };
@@ -2021,6 +2561,73 @@ fn get_resource_entity_pair(
     Ok((entity, component_id))
 }
 
+/// Given an asset's type path, return its [`TypeId`] and the associated
+/// [`ReflectAsset`] from the given `type_registry` if possible.
+///
+/// This also checks that the [`Assets`](bevy_asset::Assets) resource of the
+/// asset type exists in the `world`.
+#[cfg(feature = "bevy_asset")]
+fn get_reflect_asset<'r>(
+    type_registry: &'r TypeRegistry,
+    asset_path: &str,
+    world: &World,
+) -> Result<(TypeId, &'r ReflectAsset), BrpError> {
+    let asset_registration = type_registry
+        .get_with_type_path(asset_path)
+        .ok_or_else(|| BrpError::asset_error(format!("Unknown asset type: `{asset_path}`")))?;
+    let reflect_asset = asset_registration
+        .data::<ReflectAsset>()
+        .ok_or_else(|| BrpError::asset_error(format!("Asset `{asset_path}` isn't reflectable")))?;
+
+    if !world
+        .components()
+        .get_id(reflect_asset.assets_resource_type_id())
+        .is_some_and(|component_id| world.contains_resource_by_id(component_id))
+    {
+        return Err(BrpError::resource_not_present(&format!(
+            "bevy_asset::assets::Assets<{asset_path}>"
+        )));
+    }
+
+    Ok((asset_registration.type_id(), reflect_asset))
+}
+
+/// Finds the ID of the asset of type `type_id` that the client refers to with
+/// `asset`.
+#[cfg(feature = "bevy_asset")]
+fn get_asset_id(world: &World, type_id: TypeId, asset: &BrpAsset) -> BrpResult<UntypedAssetId> {
+    match asset {
+        BrpAsset::Id(BrpAssetId::Index(index)) => Ok(UntypedAssetId::Index {
+            type_id,
+            index: *index,
+        }),
+        BrpAsset::Id(BrpAssetId::Uuid(uuid)) => Ok(UntypedAssetId::Uuid {
+            type_id,
+            uuid: *uuid,
+        }),
+        BrpAsset::Path(path) => {
+            let asset_server = world.get_resource::<AssetServer>().ok_or_else(|| {
+                BrpError::resource_not_present(core::any::type_name::<AssetServer>())
+            })?;
+            let asset_path = AssetPath::try_parse(path).map_err(BrpError::asset_error)?;
+            asset_server
+                .get_path_and_type_id_handle(&asset_path, type_id)
+                .map(|handle| handle.id())
+                .ok_or_else(|| BrpError::asset_error(format!("No asset is loaded from `{path}`")))
+        }
+    }
+}
+
+#[cfg(feature = "bevy_asset")]
+impl From<UntypedAssetId> for BrpAssetId {
+    fn from(id: UntypedAssetId) -> Self {
+        match id {
+            UntypedAssetId::Index { index, .. } => Self::Index(index),
+            UntypedAssetId::Uuid { uuid, .. } => Self::Uuid(uuid),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     /// A generic function that tests serialization and deserialization of any type
@@ -2287,6 +2894,93 @@ mod tests {
         });
     }
 
+    #[cfg(feature = "bevy_scene")]
+    #[test]
+    fn scene_params_serialization() {
+        let parent = Entity::from_raw_u32(1).unwrap();
+        let params: BrpSpawnSceneParams = parse(serde_json::json!({
+            "bsn": "#Player",
+            "parent": parent,
+        }))
+        .unwrap();
+        assert_eq!(params.scene, BrpScene::Bsn("#Player".to_owned()));
+        assert_eq!(params.parent, Some(parent));
+
+        let params: BrpApplySceneParams = parse(serde_json::json!({
+            "entity": parent,
+            "path": "scenes/player.bsn",
+        }))
+        .unwrap();
+        assert_eq!(params.scene, BrpScene::Path("scenes/player.bsn".to_owned()));
+
+        test_serialize_deserialize(params);
+        test_serialize_deserialize(BrpSpawnSceneResponse {
+            entity: parent,
+            entities: vec![parent],
+            pending: true,
+        });
+    }
+
+    #[cfg(feature = "bevy_asset")]
+    #[test]
+    fn get_and_mutate_asset() {
+        use bevy_asset::{Asset, Assets, ReflectAsset};
+
+        #[derive(Asset, Reflect)]
+        #[reflect(Asset)]
+        struct Material {
+            color: f32,
+        }
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<Material>();
+        let mut world = World::new();
+        world.insert_resource(atr);
+        world.init_resource::<Assets<Material>>();
+        let handle = world
+            .resource_mut::<Assets<Material>>()
+            .add(Material { color: 0.5 });
+
+        let asset_type = "bevy_remote::builtin_methods::tests::Material";
+        let list: BrpListAssetsResponse = parse(
+            process_remote_list_assets_request(
+                In(Some(serde_json::json!({ "asset_type": asset_type }))),
+                &world,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].id, BrpAssetId::from(handle.id().untyped()));
+        // The asset wasn't loaded by an `AssetServer`.
+        assert_eq!(list[0].load_state, None);
+
+        let params = serde_json::to_value(&BrpMutateAssetParams {
+            asset_type: asset_type.to_owned(),
+            asset: BrpAsset::Id(list[0].id),
+            field: ".color".to_owned(),
+            value: serde_json::json!(2.0),
+        })
+        .unwrap();
+        assert_eq!(
+            process_remote_mutate_asset_request(In(Some(params)), &mut world),
+            Ok(Null)
+        );
+
+        let response: BrpGetAssetResponse = parse(
+            process_remote_get_asset_request(
+                In(Some(serde_json::json!({
+                    "asset_type": asset_type,
+                    "id": list[0].id,
+                }))),
+                &world,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response.value, serde_json::json!({ "color": 2.0 }));
+    }
+
     #[test]
     fn test_schedule_list() {
         let mut world = World::default();
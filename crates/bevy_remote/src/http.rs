@@ -7,12 +7,16 @@
 //!
 //! Clients are expected to `POST` JSON requests to the root URL; see the `client`
 //! example for a trivial example of use.
+//!
+//! Requests are authenticated with the [`RemoteAuth`] of the app, using the bearer token
+//! sent in their `Authorization` header. Rejected requests get a `401 Unauthorized` response.
 
 #![cfg(not(target_family = "wasm"))]
 
 #[cfg(feature = "bevy_render")]
 use crate::setup_mailbox_channel;
 use crate::{
+    access::{bearer_token, RemoteAccess, RemoteAuth},
     error_codes, BrpBatch, BrpError, BrpMessage, BrpRequest, BrpResponse, BrpResult, BrpSender,
 };
 use anyhow::Result as AnyhowResult;
@@ -37,7 +41,7 @@ use hyper::{
     body::{Body, Bytes, Frame, Incoming},
     header::{HeaderName, HeaderValue},
     server::conn::http1,
-    service, Request, Response,
+    service, Request, Response, StatusCode,
 };
 use serde_json::Value;
 use smol_hyper::rt::{FuturesIo, SmolTimer};
@@ -237,6 +241,7 @@ fn start_http_server(
     address: Res<HostAddress>,
     remote_port: Res<HostPort>,
     headers: Res<HostHeaders>,
+    auth: Res<RemoteAuth>,
 ) {
     IoTaskPool::get()
         .spawn(server_main(
@@ -244,6 +249,7 @@ fn start_http_server(
             remote_port.0,
             request_sender.clone(),
             headers.0.clone(),
+            auth.clone(),
         ))
         .detach();
 }
@@ -254,11 +260,13 @@ async fn server_main(
     port: u16,
     request_sender: Sender<BrpMessage>,
     headers: Headers,
+    auth: RemoteAuth,
 ) -> AnyhowResult<()> {
     listen(
         Async::<TcpListener>::bind((address, port))?,
         &request_sender,
         &headers,
+        &auth,
     )
     .await
 }
@@ -267,15 +275,17 @@ async fn listen(
     listener: Async<TcpListener>,
     request_sender: &Sender<BrpMessage>,
     headers: &Headers,
+    auth: &RemoteAuth,
 ) -> AnyhowResult<()> {
     loop {
         let (client, _) = listener.accept().await?;
 
         let request_sender = request_sender.clone();
         let headers = headers.clone();
+        let auth = auth.clone();
         IoTaskPool::get()
             .spawn(async move {
-                let _ = handle_client(client, request_sender, headers).await;
+                let _ = handle_client(client, request_sender, headers, auth).await;
             })
             .detach();
     }
@@ -285,13 +295,14 @@ async fn handle_client(
     client: Async<TcpStream>,
     request_sender: Sender<BrpMessage>,
     headers: Headers,
+    auth: RemoteAuth,
 ) -> AnyhowResult<()> {
     http1::Builder::new()
         .timer(SmolTimer::new())
         .serve_connection(
             FuturesIo::new(client),
             service::service_fn(|request| {
-                process_request_batch(request, &request_sender, &headers)
+                process_request_batch(request, &request_sender, &headers, &auth)
             }),
         )
         .await?;
@@ -305,13 +316,36 @@ async fn process_request_batch(
     request: Request<Incoming>,
     request_sender: &Sender<BrpMessage>,
     headers: &Headers,
+    auth: &RemoteAuth,
 ) -> AnyhowResult<Response<BrpHttpBody>> {
+    let token = request
+        .headers()
+        .get(hyper::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(bearer_token);
+    let access = match auth.authenticate(token) {
+        Ok(access) => access,
+        Err(err) => {
+            let serialized = serde_json::to_string(&BrpResponse::new(None, Err(err)))?;
+            let mut response = json_response(serialized);
+            *response.status_mut() = StatusCode::UNAUTHORIZED;
+            response.headers_mut().insert(
+                hyper::header::WWW_AUTHENTICATE,
+                HeaderValue::from_static("Bearer"),
+            );
+            for (key, value) in &headers.headers {
+                response.headers_mut().insert(key, value.clone());
+            }
+            return Ok(response);
+        }
+    };
+
     let batch_bytes = request.into_body().collect().await?.to_bytes();
     let batch: Result<BrpBatch, _> = serde_json::from_slice(&batch_bytes);
 
     let result = match batch {
         Ok(BrpBatch::Single(request)) => {
-            let response = process_single_request(request, request_sender).await?;
+            let response = process_single_request(request, request_sender, access).await?;
             match response {
                 BrpHttpResponse::Complete(res) => {
                     BrpHttpResponse::Complete(serde_json::to_string(&res)?)
@@ -323,7 +357,7 @@ async fn process_request_batch(
             let mut responses = Vec::new();
 
             for request in requests {
-                let response = process_single_request(request, request_sender).await?;
+                let response = process_single_request(request, request_sender, access).await?;
                 match response {
                     BrpHttpResponse::Complete(res) => responses.push(res),
                     BrpHttpResponse::Stream(BrpStream { id, .. }) => {
@@ -356,16 +390,7 @@ async fn process_request_batch(
     };
 
     let mut response = match result {
-        BrpHttpResponse::Complete(serialized) => {
-            let mut response = Response::new(BrpHttpBody::Complete(Full::new(Bytes::from(
-                serialized.as_bytes().to_owned(),
-            ))));
-            response.headers_mut().insert(
-                hyper::header::CONTENT_TYPE,
-                HeaderValue::from_static("application/json"),
-            );
-            response
-        }
+        BrpHttpResponse::Complete(serialized) => json_response(serialized),
         BrpHttpResponse::Stream(stream) => {
             let mut response = Response::new(BrpHttpBody::Stream(stream));
             response.headers_mut().insert(
@@ -381,11 +406,22 @@ async fn process_request_batch(
     Ok(response)
 }
 
+/// Creates a response with a serialized JSON body.
+fn json_response(serialized: String) -> Response<BrpHttpBody> {
+    let mut response = Response::new(BrpHttpBody::Complete(Full::new(Bytes::from(serialized))));
+    response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
 /// A helper function for the Bevy Remote Protocol server that processes a single
 /// request coming from a client.
 async fn process_single_request(
     request: Value,
     request_sender: &Sender<BrpMessage>,
+    access: RemoteAccess,
 ) -> AnyhowResult<BrpHttpResponse<BrpResponse, BrpStream>> {
     // Reach in and get the request ID early so that we can report it even when parsing fails.
     let id = request.as_object().and_then(|map| map.get("id")).cloned();
@@ -412,6 +448,7 @@ async fn process_single_request(
         .send(BrpMessage {
             method: request.method,
             params: request.params,
+            access,
             sender: result_sender,
         })
         .await;
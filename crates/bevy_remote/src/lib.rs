@@ -297,6 +297,37 @@
 //! `result`:
 //! - `entity`: The ID of the newly spawned entity.
 //!
+//! ### `world.spawn_scene`
+//!
+//! Spawn a scene as a new entity and return the IDs of the entities it created. Requires the
+//! `bevy_scene` feature.
+//!
+//! `params`:
+//! - `bsn`: The scene, written in the BSN format used by `.bsn` assets. Either this or `path`
+//!   must be given.
+//! - `path`: The asset path of a scene asset, such as a `.bsn` file.
+//! - `parent` (optional): The ID of the entity that the scene's root entity will become a child of.
+//!
+//! `result`:
+//! - `entity`: The ID of the scene's root entity.
+//! - `entities`: The IDs of the root entity and every entity spawned by the scene.
+//! - `pending`: Whether the scene is still waiting for its assets to load. In that case, `entities`
+//!   only contains the root entity, and the scene will be applied to it once the assets have loaded.
+//!
+//! ### `world.apply_scene`
+//!
+//! Apply a scene to an existing entity and return the IDs of the entities it created.
+//! The scene's children replace the entity's children: previous children are removed from the
+//! entity, but not despawned. Requires the `bevy_scene` feature.
+//!
+//! `params`:
+//! - `entity`: The ID of the entity to apply the scene to.
+//! - `bsn`: The scene, written in the BSN format used by `.bsn` assets. Either this or `path`
+//!   must be given.
+//! - `path`: The asset path of a scene asset, such as a `.bsn` file.
+//!
+//! `result`: The same as `world.spawn_scene`.
+//!
 //! ### `world.despawn_entity`
 //!
 //! Despawn the entity with the given ID.
@@ -463,6 +494,63 @@
 //!
 //! `result`: null.
 //!
+//! ### `assets.list`
+//!
+//! List the assets of a given type, along with their load states. Requires the `bevy_asset`
+//! feature.
+//!
+//! `params`:
+//! - `asset_type`: The [fully-qualified type name] of the asset type. The type must be registered
+//!   with `register_asset_reflect`.
+//!
+//! `result`: An array of objects, one for each asset, with the following fields:
+//! - `id`: The ID of the asset, either as `{ "index": { "generation": 0, "index": 3 } }` or as
+//!   `{ "uuid": "..." }`. Indices are only valid while the app is running.
+//! - `path`: The asset path that the asset was loaded from, or null.
+//! - `load_state`: The load state of the asset: `"not_loaded"`, `"loading"`, `"loaded"`, or
+//!   `{ "failed": "<error>" }`. This is null for assets that weren't loaded by the `AssetServer`.
+//! - `dependency_state`: The load state of the direct dependencies of the asset, in the same format.
+//! - `recursive_dependency_state`: The load state of all the dependencies of the asset, in the
+//!   same format.
+//!
+//! ### `assets.get`
+//!
+//! Retrieve the value of an asset. Requires the `bevy_asset` feature.
+//!
+//! `params`:
+//! - `asset_type`: The [fully-qualified type name] of the asset type.
+//! - `id`: The ID of the asset, as returned by `assets.list`. Either this or `path` must be given.
+//! - `path`: The asset path that the asset was loaded from.
+//!
+//! `result`:
+//! - `id`: The ID of the asset.
+//! - `value`: The value of the asset.
+//!
+//! ### `assets.mutate`
+//!
+//! Mutate a field in an asset. Requires the `bevy_asset` feature.
+//!
+//! `params`:
+//! - `asset_type`: The [fully-qualified type name] of the asset type.
+//! - `id`: The ID of the asset, as returned by `assets.list`. Either this or `path` must be given.
+//! - `path`: The asset path that the asset was loaded from.
+//! - `field` (optional): The path of the field within the asset. See
+//!   [`GetPath`](bevy_reflect::GetPath#syntax) for more information on formatting this string.
+//!   When omitted, the whole asset is replaced.
+//! - `value`: The value to be inserted at `field`.
+//!
+//! `result`: null.
+//!
+//! ### `assets.reload`
+//!
+//! Reload the assets that were loaded from a given asset path, as if the file had changed.
+//! Requires the `bevy_asset` feature.
+//!
+//! `params`:
+//! - `path`: The asset path to reload.
+//!
+//! `result`: null.
+//!
 //! ### `registry.schema`
 //!
 //! Retrieve schema information about registered types in the Bevy app's type registry.
@@ -489,6 +577,60 @@
 //! - Server connection information (when using HTTP transport)
 //! - `OpenRPC` specification version
 //!
+//! ## Transports
+//!
+//! The [`RemotePlugin`] only processes requests; they reach it through one or more transport
+//! plugins, all of which feed the same [`BrpSender`]:
+//!
+//! - [`RemoteHttpPlugin`](http::RemoteHttpPlugin) (`http` feature, enabled by default):
+//!   each request is `POST`ed on its own, and `+watch` methods are streamed back as
+//!   `text/event-stream`.
+//! - `RemoteWebSocketPlugin` (`websocket` feature): requests and responses are WebSocket
+//!   text messages.
+//! - `RemoteStdioPlugin` (`stdio` feature): requests and responses are lines on the
+//!   standard input and output of the app.
+//! - `RemoteUnixSocketPlugin` (`unix_socket` feature, Unix only): requests and responses
+//!   are lines on a Unix domain socket.
+//!
+//! Unlike HTTP, the last three keep a connection open for any number of requests. Each
+//! response carries the `id` of the request it answers, so several requests, including
+//! `+watch` requests, can be in flight at once. These transports also support one
+//! additional method:
+//!
+//! ### `rpc.cancel`
+//!
+//! Cancel a request that is still outstanding on the same connection. A cancelled `+watch`
+//! request stops sending updates.
+//!
+//! `params`:
+//! - `id`: The `id` of the request to cancel.
+//!
+//! `result`: null. If the request was still outstanding, it is answered with a
+//! `REQUEST_CANCELLED` error.
+//!
+//! All outstanding requests of a connection are cancelled when it closes.
+//!
+//! ## Authentication and permissions
+//!
+//! By default, any client that can reach a transport may call any method. The
+//! [`RemotePlugin`] can be given a [`RemoteAuthenticator`] with
+//! [`RemotePlugin::with_auth`], such as a [`BearerTokenAuth`](access::BearerTokenAuth)
+//! that requires clients of the HTTP and WebSocket transports to present a secret token.
+//! Clients can be granted [`RemoteAccess::ReadOnly`], which only lets them call methods that
+//! don't modify the world.
+//!
+//! Individual methods can be allowed or denied with [`RemotePlugin::with_permissions`].
+//! Requests rejected by these permissions are answered with a `METHOD_NOT_ALLOWED` error,
+//! and denied methods are left out of `rpc.discover`. See the [`access`] module for details.
+//!
+//! ## Recording and replaying sessions
+//!
+//! The [`RemoteRecordingPlugin`](recording::RemoteRecordingPlugin) writes every request
+//! received by the main app, and every response it sends, to a file along with the frame
+//! number. A [`BrpReplay`](recording::BrpReplay) sends the recorded requests to another app,
+//! such as a headless one in a test, on the same frames, and reports the responses that
+//! differ. See the [`recording`] module for details.
+//!
 //! ## Custom methods
 //!
 //! In addition to the provided methods, the Bevy Remote Protocol can be extended to include custom
@@ -534,6 +676,7 @@
 
 extern crate alloc;
 
+use access::{RemoteAccess, RemoteAuth, RemoteAuthenticator, RemoteMethodPermissions};
 use async_channel::{Receiver, Sender};
 use bevy_app::{prelude::*, MainScheduleOrder};
 use bevy_derive::{Deref, DerefMut};
@@ -545,21 +688,32 @@ use bevy_ecs::{
         InternedScheduleLabel, IntoScheduleConfigs, ScheduleBuildMetadata, ScheduleBuilt,
         ScheduleLabel, SystemSet,
     },
-    system::{Commands, In, IntoSystem, ResMut, System, SystemId},
+    system::{Commands, In, IntoSystem, Res, ResMut, System, SystemId},
     world::World,
 };
 use bevy_platform::collections::HashMap;
 #[cfg(feature = "bevy_render")]
 use bevy_render::{Render, RenderApp, RenderScheduleOrder, RenderStartup};
 use bevy_utils::prelude::default;
+use recording::BrpRecorder;
 use serde::{ser::SerializeMap, Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::RwLock;
 
+pub mod access;
 pub mod builtin_methods;
 #[cfg(feature = "http")]
 pub mod http;
+pub mod recording;
 pub mod schemas;
+#[cfg(feature = "stdio")]
+pub mod stdio;
+#[cfg(any(feature = "websocket", feature = "stdio", feature = "unix_socket"))]
+pub mod transport;
+#[cfg(feature = "unix_socket")]
+pub mod unix_socket;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 const CHANNEL_SIZE: usize = 16;
 
@@ -574,6 +728,10 @@ pub struct RemotePlugin {
     methods: RwLock<Vec<(String, RemoteMethodHandler)>>,
     /// The verbs that the server will recognize and respond to for the render subapp.
     render_methods: RwLock<Vec<(String, RemoteMethodHandler)>>,
+    /// How the transports authenticate clients.
+    auth: RemoteAuth,
+    /// Which methods clients are allowed to call.
+    permissions: RemoteMethodPermissions,
 }
 
 impl RemotePlugin {
@@ -583,9 +741,30 @@ impl RemotePlugin {
         Self {
             methods: RwLock::new(vec![]),
             render_methods: RwLock::new(vec![]),
+            auth: default(),
+            permissions: default(),
         }
     }
 
+    /// Set the [`RemoteAuthenticator`] that the transports use to decide whether to accept
+    /// the requests of a client.
+    ///
+    /// By default, every client is accepted with [`RemoteAccess::Full`].
+    #[must_use]
+    pub fn with_auth(mut self, authenticator: impl RemoteAuthenticator) -> Self {
+        self.auth = RemoteAuth::new(authenticator);
+        self
+    }
+
+    /// Set the [`RemoteMethodPermissions`] that control which methods clients may call.
+    ///
+    /// By default, every method may be called by clients with [`RemoteAccess::Full`].
+    #[must_use]
+    pub fn with_permissions(mut self, permissions: RemoteMethodPermissions) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
     /// Add a remote method to the plugin using the given `name` and `handler` to main app.
     #[inline]
     pub fn with_method_main<M>(
@@ -785,6 +964,58 @@ impl RemotePlugin {
             builtin_methods::schedule_graph,
             to_main,
         )
+        .add_asset_methods(to_main)
+        .add_scene_methods(to_main)
+    }
+
+    /// Add the BRP methods for inspecting and reloading assets
+    #[cfg(feature = "bevy_asset")]
+    fn add_asset_methods(self, to_main: bool) -> Self {
+        self.with_method(
+            builtin_methods::BRP_LIST_ASSETS_METHOD,
+            builtin_methods::process_remote_list_assets_request,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_GET_ASSET_METHOD,
+            builtin_methods::process_remote_get_asset_request,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_MUTATE_ASSET_METHOD,
+            builtin_methods::process_remote_mutate_asset_request,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_RELOAD_ASSET_METHOD,
+            builtin_methods::process_remote_reload_asset_request,
+            to_main,
+        )
+    }
+
+    #[cfg(not(feature = "bevy_asset"))]
+    fn add_asset_methods(self, _to_main: bool) -> Self {
+        self
+    }
+
+    /// Add the BRP methods for spawning scenes
+    #[cfg(feature = "bevy_scene")]
+    fn add_scene_methods(self, to_main: bool) -> Self {
+        self.with_method(
+            builtin_methods::BRP_SPAWN_SCENE_METHOD,
+            builtin_methods::process_remote_spawn_scene_request,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_APPLY_SCENE_METHOD,
+            builtin_methods::process_remote_apply_scene_request,
+            to_main,
+        )
+    }
+
+    #[cfg(not(feature = "bevy_scene"))]
+    fn add_scene_methods(self, _to_main: bool) -> Self {
+        self
     }
 }
 
@@ -835,6 +1066,8 @@ impl Plugin for RemotePlugin {
             .insert_after(Last, RemoteLast);
 
         app.insert_resource(remote_methods)
+            .insert_resource(self.auth.clone())
+            .insert_resource(self.permissions.clone())
             .init_resource::<schemas::SchemaTypesMetadata>()
             .init_resource::<RemoteWatchingRequests>()
             .init_resource::<builtin_methods::BrpEventObservers>()
@@ -886,6 +1119,8 @@ impl Plugin for RemotePlugin {
 
             render_app
                 .insert_resource(render_remote_methods)
+                .insert_resource(self.auth.clone())
+                .insert_resource(self.permissions.clone())
                 .init_resource::<schemas::SchemaTypesMetadata>()
                 .init_resource::<RemoteWatchingRequests>()
                 .add_systems(RenderStartup, setup_mailbox_channel.run_if(run_once))
@@ -1281,7 +1516,7 @@ impl BrpResponse {
 }
 
 /// A result/error payload present in every response.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum BrpPayload {
     /// `Ok` variant
@@ -1381,6 +1616,56 @@ impl BrpError {
             data: None,
         }
     }
+
+    /// An arbitrary scene error. Possibly related to parsing or resolving the scene.
+    #[must_use]
+    pub fn scene_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::SCENE_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
+    /// Asset was not present in its `Assets` collection.
+    #[must_use]
+    pub fn asset_not_present(asset_type: &str) -> Self {
+        Self {
+            code: error_codes::ASSET_NOT_PRESENT,
+            message: format!("Asset of type `{asset_type}` not present"),
+            data: None,
+        }
+    }
+
+    /// An arbitrary asset error. Possibly related to reflection or asset paths.
+    #[must_use]
+    pub fn asset_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::ASSET_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
+    /// The client could not be authenticated.
+    #[must_use]
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self {
+            code: error_codes::UNAUTHORIZED,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// The client is not allowed to call a method.
+    #[must_use]
+    pub fn method_not_allowed(method: &str) -> Self {
+        Self {
+            code: error_codes::METHOD_NOT_ALLOWED,
+            message: format!("Method `{method}` is not allowed"),
+            data: None,
+        }
+    }
 }
 
 /// Error codes used by BRP.
@@ -1405,6 +1690,15 @@ pub mod error_codes {
 
     // Bevy errors (i.e. application errors)
 
+    /// The request was cancelled by the client.
+    pub const REQUEST_CANCELLED: i16 = -23101;
+
+    /// The client could not be authenticated.
+    pub const UNAUTHORIZED: i16 = -23102;
+
+    /// The client is not allowed to call the method.
+    pub const METHOD_NOT_ALLOWED: i16 = -23103;
+
     /// Entity not found.
     pub const ENTITY_NOT_FOUND: i16 = -23401;
 
@@ -1422,6 +1716,15 @@ pub mod error_codes {
 
     /// Could not find resource in the world.
     pub const RESOURCE_NOT_PRESENT: i16 = -23502;
+
+    /// Could not parse, resolve or apply a scene.
+    pub const SCENE_ERROR: i16 = -23601;
+
+    /// Could not reflect or find asset.
+    pub const ASSET_ERROR: i16 = -23701;
+
+    /// Could not find asset in its `Assets` collection.
+    pub const ASSET_NOT_PRESENT: i16 = -23702;
 }
 
 /// The result of a request.
@@ -1450,6 +1753,9 @@ pub struct BrpMessage {
     /// The request params.
     pub params: Option<Value>,
 
+    /// The access granted to the client that sent the request.
+    pub access: RemoteAccess,
+
     /// The channel on which the response is to be sent.
     ///
     /// The value sent here is serialized and sent back to the client.
@@ -1467,7 +1773,12 @@ pub struct BrpSender(Sender<BrpMessage>);
 #[derive(Debug, Resource, Deref, DerefMut)]
 pub struct BrpReceiver(Receiver<BrpMessage>);
 
-fn setup_mailbox_channel(mut commands: Commands) {
+fn setup_mailbox_channel(mut commands: Commands, mailbox: Option<Res<BrpSender>>) {
+    // Keep a mailbox that was set up before startup, such as by a `BrpReplay`.
+    if mailbox.is_some() {
+        return;
+    }
+
     // Create the channel and the mailbox.
     let (request_sender, request_receiver) = async_channel::bounded(CHANNEL_SIZE);
     commands.insert_resource(BrpSender(request_sender));
@@ -1484,7 +1795,18 @@ fn process_remote_requests(world: &mut World) {
         return;
     }
 
-    while let Ok(message) = world.resource_mut::<BrpReceiver>().try_recv() {
+    while let Ok(mut message) = world.resource_mut::<BrpReceiver>().try_recv() {
+        if let Some(mut recorder) = world.get_resource_mut::<BrpRecorder>() {
+            recorder.record_request(&mut message);
+        }
+
+        if let Some(permissions) = world.get_resource::<RemoteMethodPermissions>()
+            && let Err(error) = permissions.check(&message.method, message.access)
+        {
+            let _ = message.sender.force_send(Err(error));
+            continue;
+        }
+
         // Fetch the handler for the method. If there's no such handler
         // registered, return an error.
         let Some(&handler) = world.resource::<RemoteMethods>().get(&message.method) else {
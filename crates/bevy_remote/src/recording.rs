@@ -0,0 +1,562 @@
+//! Recording BRP sessions and replaying them as regression tests.
+//!
+//! Adding the [`RemoteRecordingPlugin`] to your [`App`] writes every request received by the
+//! main app, and every response sent back, to a file as [`BrpRecord`]s, along with the frame
+//! on which it happened. The file holds one JSON object per line.
+//!
+//! A [`BrpReplay`] loaded from such a file drives another [`App`], typically a headless one
+//! built the same way as the recorded app, by sending it the same requests on the same
+//! frames. It then diffs the responses of that app against the recorded ones:
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_remote::{recording::BrpReplay, RemotePlugin};
+//! # fn build_game(app: &mut App) {}
+//! let mut app = App::new();
+//! build_game(&mut app);
+//! app.add_plugins(RemotePlugin::default());
+//!
+//! let replay = BrpReplay::load("session.brp.jsonl").unwrap();
+//! let report = replay.run(&mut app);
+//! assert!(report.passed(), "{report}");
+//! ```
+//!
+//! Replaying is only deterministic if the app is: anything that depends on wall-clock time,
+//! randomness or I/O may produce different responses.
+
+use crate::{
+    access::RemoteAccess, BrpMessage, BrpPayload, BrpReceiver, BrpResult, BrpSender, RemoteLast,
+    RemoteSystems,
+};
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, Plugin};
+use bevy_ecs::{resource::Resource, schedule::IntoScheduleConfigs, system::ResMut};
+use bevy_log::{error, warn_once};
+use bevy_platform::collections::HashMap;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Add this plugin to your [`App`] to record the BRP requests and responses of the main app
+/// to a file. It requires the [`RemotePlugin`](crate::RemotePlugin).
+///
+/// The file is created, or truncated, when the plugin is built.
+pub struct RemoteRecordingPlugin {
+    /// The path of the file that the session is recorded to.
+    path: PathBuf,
+}
+
+impl RemoteRecordingPlugin {
+    /// Create a [`RemoteRecordingPlugin`] recording to the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Plugin for RemoteRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        match File::create(&self.path) {
+            Ok(file) => add_recorder(app, BrpRecorder::new(BufWriter::new(file))),
+            Err(err) => error!(
+                "Failed to create the BRP recording at `{}`: {err}",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+/// Inserts `recorder` and the system recording the responses it routes.
+fn add_recorder(app: &mut App, recorder: BrpRecorder) {
+    app.insert_resource(recorder).add_systems(
+        RemoteLast,
+        record_remote_responses
+            .after(RemoteSystems::ProcessRequests)
+            .before(RemoteSystems::Cleanup),
+    );
+}
+
+/// A single entry of a recorded BRP session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BrpRecord {
+    /// A request received from a client.
+    Request {
+        /// The frame on which the request was processed.
+        frame: u64,
+        /// The number of the request, counting from 0 in the order they were received.
+        request: u64,
+        /// The request method.
+        method: String,
+        /// The request params.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        params: Option<Value>,
+        /// The access granted to the client that sent the request.
+        access: RemoteAccess,
+    },
+    /// A response sent back to a client.
+    ///
+    /// Requests to watching methods may have any number of responses.
+    Response {
+        /// The frame on which the response was sent.
+        frame: u64,
+        /// The number of the request that this response answers.
+        request: u64,
+        /// The result or error of the request.
+        #[serde(flatten)]
+        payload: BrpPayload,
+    },
+}
+
+/// A resource that records the BRP requests received by the app and the responses sent back.
+///
+/// This is inserted by the [`RemoteRecordingPlugin`].
+#[derive(Resource)]
+pub struct BrpRecorder {
+    /// Where the [`BrpRecord`]s are written.
+    writer: Box<dyn Write + Send + Sync>,
+    /// The number of frames recorded so far.
+    frame: u64,
+    /// The number of requests recorded so far.
+    next_request: u64,
+    /// The requests that may still be answered, and the channels their responses are
+    /// forwarded through.
+    pending: Vec<PendingRequest>,
+}
+
+/// A request that was received by the [`BrpRecorder`] and may still be answered.
+struct PendingRequest {
+    request: u64,
+    /// Receives the responses of the handler.
+    responses: Receiver<BrpResult>,
+    /// The channel that the client is waiting on.
+    client: Sender<BrpResult>,
+}
+
+impl BrpRecorder {
+    /// Create a [`BrpRecorder`] writing [`BrpRecord`]s to `writer`, one per line.
+    pub fn new(writer: impl Write + Send + Sync + 'static) -> Self {
+        Self {
+            writer: Box::new(writer),
+            frame: 0,
+            next_request: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// The current frame, counting from 0 on the first frame that was recorded.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Record `message`, and route its responses through the recorder so that they are
+    /// recorded too.
+    pub(crate) fn record_request(&mut self, message: &mut BrpMessage) {
+        let request = self.next_request;
+        self.next_request += 1;
+        self.write(&BrpRecord::Request {
+            frame: self.frame,
+            request,
+            method: message.method.clone(),
+            params: message.params.clone(),
+            access: message.access,
+        });
+
+        let (sender, responses) = async_channel::unbounded();
+        let client = core::mem::replace(&mut message.sender, sender);
+        self.pending.push(PendingRequest {
+            request,
+            responses,
+            client,
+        });
+    }
+
+    fn write(&mut self, record: &BrpRecord) {
+        let result = serde_json::to_writer(&mut self.writer, record)
+            .map_err(io::Error::from)
+            .and_then(|()| self.writer.write_all(b"\n"));
+        if let Err(err) = result {
+            warn_once!("Failed to write to the BRP recording: {err}");
+        }
+    }
+}
+
+/// A system that records the responses sent during this frame, and forwards them to the
+/// clients.
+fn record_remote_responses(mut recorder: ResMut<BrpRecorder>) {
+    let recorder = &mut *recorder;
+    let frame = recorder.frame;
+    let mut records = Vec::new();
+
+    recorder.pending.retain(|pending| {
+        while let Ok(result) = pending.responses.try_recv() {
+            records.push(BrpRecord::Response {
+                frame,
+                request: pending.request,
+                payload: BrpPayload::from(result.clone()),
+            });
+            // The client may have gone away, in which case the response is dropped.
+            let _ = pending.client.force_send(result);
+        }
+
+        // Closing the channel lets the `RemotePlugin` clean up watching requests whose
+        // client has gone away.
+        if pending.client.is_closed() {
+            pending.responses.close();
+        }
+        // The channel is closed once the handler has dropped its sender.
+        !pending.responses.is_closed()
+    });
+
+    for record in &records {
+        recorder.write(record);
+    }
+    if !records.is_empty()
+        && let Err(err) = recorder.writer.flush()
+    {
+        warn_once!("Failed to write to the BRP recording: {err}");
+    }
+    recorder.frame += 1;
+}
+
+/// A recorded BRP session, which can be replayed against an [`App`] to check that it still
+/// responds the same way.
+#[derive(Debug, Clone, Default)]
+pub struct BrpReplay {
+    records: Vec<BrpRecord>,
+}
+
+impl BrpReplay {
+    /// Load the session recorded by the [`RemoteRecordingPlugin`] in the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BrpReplayError> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Read a recorded session from `reader`, which holds one [`BrpRecord`] per line.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, BrpReplayError> {
+        let mut records = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str(&line).map_err(|source| BrpReplayError::Parse {
+                line: index + 1,
+                source,
+            })?;
+            records.push(record);
+        }
+        Ok(Self { records })
+    }
+
+    /// Create a [`BrpReplay`] from `records`.
+    pub fn from_records(records: Vec<BrpRecord>) -> Self {
+        Self { records }
+    }
+
+    /// The records of the session.
+    pub fn records(&self) -> &[BrpRecord] {
+        &self.records
+    }
+
+    /// Replay the session against `app`, which must have the [`RemotePlugin`](crate::RemotePlugin)
+    /// and should not have run any frames yet.
+    ///
+    /// Every recorded request is sent to `app` before the update of the frame it was recorded
+    /// on, and the app is updated until the last recorded frame. The responses of `app` are
+    /// then compared against the recorded ones.
+    pub fn run(&self, app: &mut App) -> BrpReplayReport {
+        // Set up the mailbox before the first update, so that requests recorded on the first
+        // frame can be sent. The `RemotePlugin` keeps a mailbox that already exists.
+        if !app.world().contains_resource::<BrpSender>() {
+            let (request_sender, request_receiver) = async_channel::unbounded();
+            app.insert_resource(BrpSender(request_sender))
+                .insert_resource(BrpReceiver(request_receiver));
+        }
+
+        // The requests to send on each frame, and the channels their responses arrive on.
+        let mut requests: HashMap<u64, Vec<BrpMessage>> = HashMap::default();
+        let mut receivers: Vec<(u64, Receiver<BrpResult>)> = Vec::new();
+        let mut expected: HashMap<(u64, u64), Vec<BrpPayload>> = HashMap::default();
+        let mut methods: HashMap<u64, String> = HashMap::default();
+        let mut last_frame = None;
+        for record in &self.records {
+            match record {
+                BrpRecord::Request {
+                    frame,
+                    request,
+                    method,
+                    params,
+                    access,
+                } => {
+                    let (sender, receiver) = async_channel::unbounded();
+                    requests.entry(*frame).or_default().push(BrpMessage {
+                        method: method.clone(),
+                        params: params.clone(),
+                        access: *access,
+                        sender,
+                    });
+                    receivers.push((*request, receiver));
+                    methods.insert(*request, method.clone());
+                    last_frame = last_frame.max(Some(*frame));
+                }
+                BrpRecord::Response {
+                    frame,
+                    request,
+                    payload,
+                } => {
+                    expected
+                        .entry((*frame, *request))
+                        .or_default()
+                        .push(payload.clone());
+                    last_frame = last_frame.max(Some(*frame));
+                }
+            }
+        }
+
+        let mut actual: HashMap<(u64, u64), Vec<BrpPayload>> = HashMap::default();
+        let frames = last_frame.map_or(0, |frame| frame + 1);
+        for frame in 0..frames {
+            let request_sender = app.world().resource::<BrpSender>().0.clone();
+            for message in requests.remove(&frame).unwrap_or_default() {
+                // If the mailbox is gone, the missing responses are reported as mismatches.
+                let _ = request_sender.force_send(message);
+            }
+
+            app.update();
+
+            for (request, receiver) in &receivers {
+                while let Ok(result) = receiver.try_recv() {
+                    actual
+                        .entry((frame, *request))
+                        .or_default()
+                        .push(BrpPayload::from(result));
+                }
+            }
+        }
+
+        let mut keys: Vec<_> = expected.keys().chain(actual.keys()).copied().collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let mismatches = keys
+            .into_iter()
+            .filter_map(|(frame, request)| {
+                let expected = expected.remove(&(frame, request)).unwrap_or_default();
+                let actual = actual.remove(&(frame, request)).unwrap_or_default();
+                (expected != actual).then(|| BrpReplayMismatch {
+                    frame,
+                    request,
+                    method: methods.get(&request).cloned().unwrap_or_default(),
+                    expected,
+                    actual,
+                })
+            })
+            .collect();
+
+        BrpReplayReport { frames, mismatches }
+    }
+}
+
+/// The outcome of replaying a [`BrpReplay`].
+#[derive(Debug, Clone)]
+pub struct BrpReplayReport {
+    /// The number of frames that were replayed.
+    pub frames: u64,
+    /// The responses that differ from the recorded ones, ordered by frame.
+    pub mismatches: Vec<BrpReplayMismatch>,
+}
+
+impl BrpReplayReport {
+    /// Returns `true` if every response matched the recorded one.
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl fmt::Display for BrpReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} mismatched responses over {} frames",
+            self.mismatches.len(),
+            self.frames
+        )?;
+        for mismatch in &self.mismatches {
+            write!(f, "\n{mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The responses to a request on a given frame, when they differ from the recorded ones.
+#[derive(Debug, Clone)]
+pub struct BrpReplayMismatch {
+    /// The frame on which the responses were sent.
+    pub frame: u64,
+    /// The number of the request.
+    pub request: u64,
+    /// The method of the request.
+    pub method: String,
+    /// The recorded responses.
+    pub expected: Vec<BrpPayload>,
+    /// The responses sent while replaying.
+    pub actual: Vec<BrpPayload>,
+}
+
+impl fmt::Display for BrpReplayMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = |payloads: &Vec<BrpPayload>| {
+            serde_json::to_string(payloads).unwrap_or_else(|err| err.to_string())
+        };
+        write!(
+            f,
+            "frame {}, request {} (`{}`): expected {}, got {}",
+            self.frame,
+            self.request,
+            self.method,
+            json(&self.expected),
+            json(&self.actual)
+        )
+    }
+}
+
+/// An error that occurs when loading a recorded BRP session.
+#[derive(Error, Debug)]
+pub enum BrpReplayError {
+    /// The recording couldn't be read.
+    #[error("failed to read the BRP recording: {0}")]
+    Io(#[from] io::Error),
+    /// A line of the recording isn't a valid [`BrpRecord`].
+    #[error("invalid BRP record on line {line}: {source}")]
+    Parse {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The underlying error.
+        source: serde_json::Error,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BrpError, RemotePlugin};
+    use alloc::sync::Arc;
+    use bevy_ecs::system::In;
+    use std::sync::Mutex;
+
+    /// A writer whose contents can still be read after it was moved into a [`BrpRecorder`].
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Counter(u64);
+
+    fn increment(In(params): In<Option<Value>>, mut counter: ResMut<Counter>) -> BrpResult {
+        counter.0 += params.and_then(|params| params.as_u64()).unwrap_or(1);
+        Ok(Value::from(counter.0))
+    }
+
+    fn test_app(counter: u64) -> App {
+        let mut app = App::new();
+        app.insert_resource(Counter(counter))
+            .add_plugins(RemotePlugin::empty().with_method_main("test.increment", increment));
+        app
+    }
+
+    fn send(app: &App, method: &str, params: Option<Value>) -> Receiver<BrpResult> {
+        let (sender, receiver) = async_channel::unbounded();
+        app.world()
+            .resource::<BrpSender>()
+            .force_send(BrpMessage {
+                method: method.to_owned(),
+                params,
+                access: RemoteAccess::Full,
+                sender,
+            })
+            .unwrap();
+        receiver
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let buffer = SharedBuffer::default();
+        let mut app = test_app(0);
+        add_recorder(&mut app, BrpRecorder::new(buffer.clone()));
+        app.update();
+
+        let increment = send(&app, "test.increment", Some(Value::from(2)));
+        let missing = send(&app, "test.missing", None);
+        app.update();
+        app.update();
+        let increment_again = send(&app, "test.increment", None);
+        app.update();
+
+        // The recorder forwards the responses to the clients.
+        assert_eq!(increment.try_recv(), Ok(Ok(Value::from(2))));
+        assert!(matches!(missing.try_recv(), Ok(Err(BrpError { .. }))));
+        assert_eq!(increment_again.try_recv(), Ok(Ok(Value::from(3))));
+        drop(app);
+
+        let replay = BrpReplay::from_reader(&buffer.0.lock().unwrap()[..]).unwrap();
+        assert_eq!(
+            replay.records()[..3],
+            [
+                BrpRecord::Request {
+                    frame: 1,
+                    request: 0,
+                    method: "test.increment".to_owned(),
+                    params: Some(Value::from(2)),
+                    access: RemoteAccess::Full,
+                },
+                BrpRecord::Request {
+                    frame: 1,
+                    request: 1,
+                    method: "test.missing".to_owned(),
+                    params: None,
+                    access: RemoteAccess::Full,
+                },
+                BrpRecord::Response {
+                    frame: 1,
+                    request: 0,
+                    payload: BrpPayload::Result(Value::from(2)),
+                },
+            ]
+        );
+
+        let report = replay.run(&mut test_app(0));
+        assert!(report.passed(), "{report}");
+        assert_eq!(report.frames, 4);
+
+        let report = replay.run(&mut test_app(5));
+        let mismatched: Vec<_> = report
+            .mismatches
+            .iter()
+            .map(|mismatch| (mismatch.frame, mismatch.request))
+            .collect();
+        assert_eq!(mismatched, [(1, 0), (3, 2)]);
+    }
+
+    #[test]
+    fn invalid_recording() {
+        let recording = "\n{\"kind\":\"request\",\"frame\":0}\n";
+        assert!(matches!(
+            BrpReplay::from_reader(recording.as_bytes()),
+            Err(BrpReplayError::Parse { line: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,96 @@
+//! The BRP transport using JSON-RPC over the standard input and output of the process.
+//!
+//! Adding the [`RemoteStdioPlugin`] to your [`App`] lets the process that launched it send
+//! requests without opening any port, which is handy for editors and test harnesses.
+//!
+//! Each line written to the app's standard input holds a JSON-RPC request or a batch of
+//! requests, and every response is written to its standard output as a single line carrying
+//! the `id` of the request it answers. Several requests, including `+watch` requests, can
+//! be in flight at once, and can be cancelled with
+//! [`BRP_CANCEL_METHOD`](crate::transport::BRP_CANCEL_METHOD). Closing the standard input
+//! cancels all of them.
+//!
+//! Clients of this transport aren't authenticated, since only the process that launched the
+//! app can reach it. They are granted [`RemoteAccess::Full`], limited by the
+//! [`RemoteMethodPermissions`](crate::access::RemoteMethodPermissions) of the app.
+//!
+//! Nothing else should be written to the standard output while this transport is in use.
+//! Bevy's own logs go to the standard error, so they don't interfere.
+
+#![cfg(not(target_family = "wasm"))]
+
+use crate::{access::RemoteAccess, transport::BrpConnection, BrpSender};
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::system::Res;
+use bevy_log::error;
+use bevy_tasks::IoTaskPool;
+use std::{
+    io::{self, BufRead, Write},
+    thread,
+};
+
+/// Add this plugin to your [`App`] to accept remote requests over the standard input, and
+/// answer them over the standard output. It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// This BRP transport cannot be used when targeting WASM.
+#[derive(Default)]
+pub struct RemoteStdioPlugin;
+
+impl Plugin for RemoteStdioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, start_stdio_transport);
+    }
+}
+
+/// A system that starts reading requests from the standard input.
+fn start_stdio_transport(request_sender: Res<BrpSender>) {
+    let (connection, responses) = BrpConnection::new(request_sender.clone(), RemoteAccess::Full);
+    let (line_sender, line_receiver) = async_channel::unbounded::<String>();
+
+    // The standard input and output only support blocking calls, so each of them gets a
+    // thread, while the connection itself is handled on the `IoTaskPool` like the other
+    // transports.
+    let reader = thread::Builder::new()
+        .name("BRP stdin".to_string())
+        .spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if line_sender.send_blocking(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+    let writer = thread::Builder::new()
+        .name("BRP stdout".to_string())
+        .spawn(move || {
+            let mut stdout = io::stdout();
+            while let Ok(response) = responses.recv_blocking() {
+                if writeln!(stdout, "{response}")
+                    .and_then(|()| stdout.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+    if let Err(err) = reader.and(writer) {
+        error!("Failed to start the BRP stdio transport: {err}");
+        return;
+    }
+
+    IoTaskPool::get()
+        .spawn(async move {
+            while let Ok(line) = line_receiver.recv().await {
+                if !line.trim().is_empty() {
+                    connection.handle_message(&line).await;
+                }
+            }
+            // Dropping the connection once the standard input closes cancels its
+            // outstanding requests.
+        })
+        .detach();
+}
@@ -0,0 +1,452 @@
+//! Building blocks shared by the connection-oriented BRP transports.
+//!
+//! Unlike HTTP, where every request gets its own response body, the WebSocket, stdio and
+//! Unix socket transports keep a single connection open for any number of requests. Each
+//! incoming message holds one JSON-RPC request (or a batch of them), and every response is
+//! sent back as a separate message carrying the `id` of the request it answers. This lets a
+//! client run several `+watch` requests at once over one connection, and stop them again
+//! with [`BRP_CANCEL_METHOD`].
+//!
+//! [`BrpConnection`] implements this protocol on top of the [`BrpSender`](crate::BrpSender),
+//! so custom transports only need to move text messages in and out of it.
+
+#![cfg(not(target_family = "wasm"))]
+
+use crate::{
+    access::RemoteAccess, error_codes, BrpBatch, BrpError, BrpMessage, BrpRequest, BrpResponse,
+    BrpResult,
+};
+use alloc::sync::Arc;
+use async_channel::{Receiver, Sender};
+use bevy_platform::collections::HashMap;
+use bevy_tasks::IoTaskPool;
+use bevy_utils::prelude::default;
+use core::sync::atomic::{AtomicU64, Ordering};
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Mutex;
+
+/// The method that a client calls to cancel one of its outstanding requests.
+///
+/// `params`:
+/// - `id`: The `id` of the request to cancel.
+///
+/// `result`: null. The cancelled request is answered with a
+/// [`REQUEST_CANCELLED`](error_codes::REQUEST_CANCELLED) error, and no further responses
+/// are sent for it.
+pub const BRP_CANCEL_METHOD: &str = "rpc.cancel";
+
+/// `rpc.cancel`: Cancels an outstanding request made over the same connection.
+#[derive(Debug, Deserialize)]
+struct BrpCancelParams {
+    /// The `id` of the request to cancel.
+    id: Value,
+}
+
+/// The requests of a [`BrpConnection`] that are still waiting on the world, keyed by
+/// [`request_key`].
+type ActiveRequests = Arc<Mutex<HashMap<String, Receiver<BrpResult>>>>;
+
+/// One client connection of a connection-oriented BRP transport.
+///
+/// Messages received from the client are passed to [`BrpConnection::handle_message`], and
+/// the serialized responses come out of the [`Receiver`] returned by [`BrpConnection::new`],
+/// to be written back to the client in order.
+///
+/// Requests with an `id` can be cancelled with [`BRP_CANCEL_METHOD`]. All outstanding
+/// requests, including `+watch` requests, are cancelled when the connection is dropped.
+#[derive(Debug)]
+pub struct BrpConnection {
+    request_sender: Sender<BrpMessage>,
+    access: RemoteAccess,
+    response_sender: Sender<String>,
+    active_requests: ActiveRequests,
+    /// Used to track requests without an `id`, which can't be cancelled by the client.
+    next_anonymous_request: AtomicU64,
+}
+
+impl BrpConnection {
+    /// Creates a connection that forwards its requests to `request_sender`, usually the
+    /// [`BrpSender`](crate::BrpSender) of the app, for a client that has been granted `access`.
+    ///
+    /// Returns the connection along with the receiving end of its responses.
+    pub fn new(
+        request_sender: Sender<BrpMessage>,
+        access: RemoteAccess,
+    ) -> (Self, Receiver<String>) {
+        let (response_sender, response_receiver) = async_channel::unbounded();
+        let connection = Self {
+            request_sender,
+            access,
+            response_sender,
+            active_requests: default(),
+            next_anonymous_request: AtomicU64::new(0),
+        };
+        (connection, response_receiver)
+    }
+
+    /// Handles a message received from the client.
+    ///
+    /// This only waits until the requests in the message have been handed to the world;
+    /// their responses are sent asynchronously as they become available.
+    pub async fn handle_message(&self, message: &str) {
+        let batch = match serde_json::from_str::<BrpBatch>(message) {
+            Ok(batch) => batch,
+            Err(err) => {
+                self.respond(BrpResponse::new(
+                    None,
+                    Err(BrpError {
+                        code: error_codes::PARSE_ERROR,
+                        message: err.to_string(),
+                        data: None,
+                    }),
+                ));
+                return;
+            }
+        };
+
+        match batch {
+            BrpBatch::Single(request) => match self.dispatch(request).await {
+                Dispatch::Complete(response) => self.respond(response),
+                Dispatch::Pending {
+                    id,
+                    key,
+                    watch,
+                    receiver,
+                } => self.forward(id, key, watch, receiver),
+            },
+            BrpBatch::Batch(requests) => {
+                let mut pending = Vec::new();
+                for request in requests {
+                    pending.push(match self.dispatch(request).await {
+                        Dispatch::Complete(response) => Ok(response),
+                        Dispatch::Pending {
+                            id,
+                            key,
+                            watch: true,
+                            receiver,
+                        } => {
+                            self.active_requests.lock().unwrap().remove(&key);
+                            receiver.close();
+                            Ok(BrpResponse::new(
+                                id,
+                                Err(BrpError {
+                                    code: error_codes::INVALID_REQUEST,
+                                    message: "Streaming can not be used in batch requests"
+                                        .to_string(),
+                                    data: None,
+                                }),
+                            ))
+                        }
+                        Dispatch::Pending {
+                            id, key, receiver, ..
+                        } => Err((id, key, receiver)),
+                    });
+                }
+                self.forward_batch(pending);
+            }
+        }
+    }
+
+    /// Cancels every outstanding request of this connection.
+    pub fn cancel_all(&self) {
+        for (_, receiver) in self.active_requests.lock().unwrap().drain() {
+            receiver.close();
+        }
+    }
+
+    /// Parses a single request and hands it to the world, unless it can be answered
+    /// right away.
+    async fn dispatch(&self, request: Value) -> Dispatch {
+        // Reach in and get the request ID early so that we can report it even when parsing fails.
+        let id = request.as_object().and_then(|map| map.get("id")).cloned();
+
+        let request: BrpRequest = match serde_json::from_value(request) {
+            Ok(request) => request,
+            Err(err) => {
+                return Dispatch::Complete(BrpResponse::new(
+                    id,
+                    Err(BrpError {
+                        code: error_codes::INVALID_REQUEST,
+                        message: err.to_string(),
+                        data: None,
+                    }),
+                ));
+            }
+        };
+
+        if request.method == BRP_CANCEL_METHOD {
+            let result = self.cancel(request.params);
+            return Dispatch::Complete(BrpResponse::new(request.id, result));
+        }
+
+        let watch = request.method.contains("+watch");
+        let size = if watch { 8 } else { 1 };
+        let (result_sender, result_receiver) = async_channel::bounded(size);
+
+        let key = match &request.id {
+            Some(id) => request_key(id),
+            None => format!(
+                "#{}",
+                self.next_anonymous_request.fetch_add(1, Ordering::Relaxed)
+            ),
+        };
+        self.active_requests
+            .lock()
+            .unwrap()
+            .insert(key.clone(), result_receiver.clone());
+
+        let _ = self
+            .request_sender
+            .send(BrpMessage {
+                method: request.method,
+                params: request.params,
+                access: self.access,
+                sender: result_sender,
+            })
+            .await;
+
+        Dispatch::Pending {
+            id: request.id,
+            key,
+            watch,
+            receiver: result_receiver,
+        }
+    }
+
+    /// Handles a [`BRP_CANCEL_METHOD`] request.
+    fn cancel(&self, params: Option<Value>) -> BrpResult {
+        let BrpCancelParams { id } = params
+            .ok_or_else(|| BrpError {
+                code: error_codes::INVALID_PARAMS,
+                message: String::from("Params not provided"),
+                data: None,
+            })
+            .and_then(|params| {
+                serde_json::from_value(params).map_err(|err| BrpError {
+                    code: error_codes::INVALID_PARAMS,
+                    message: err.to_string(),
+                    data: None,
+                })
+            })?;
+
+        // Only requests that have not already been answered in full can be cancelled.
+        // Removing the request here guarantees that its responses stop immediately,
+        // even if the world has already sent another one.
+        if let Some(receiver) = self
+            .active_requests
+            .lock()
+            .unwrap()
+            .remove(&request_key(&id))
+        {
+            receiver.close();
+            self.respond(BrpResponse::new(
+                Some(id),
+                Err(BrpError {
+                    code: error_codes::REQUEST_CANCELLED,
+                    message: String::from("Request cancelled by the client"),
+                    data: None,
+                }),
+            ));
+        }
+
+        Ok(Value::Null)
+    }
+
+    /// Spawns a task that sends the responses for a request back to the client.
+    fn forward(&self, id: Option<Value>, key: String, watch: bool, receiver: Receiver<BrpResult>) {
+        let response_sender = self.response_sender.clone();
+        let active_requests = self.active_requests.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                while let Ok(result) = receiver.recv().await {
+                    let active = if watch {
+                        active_requests.lock().unwrap().contains_key(&key)
+                    } else {
+                        active_requests.lock().unwrap().remove(&key).is_some()
+                    };
+                    if !active
+                        || send_response(&response_sender, BrpResponse::new(id.clone(), result))
+                            .is_err()
+                        || !watch
+                    {
+                        break;
+                    }
+                }
+
+                if let Some(receiver) = active_requests.lock().unwrap().remove(&key) {
+                    receiver.close();
+                }
+            })
+            .detach();
+    }
+
+    /// Spawns a task that waits for every request of a batch and sends the responses back
+    /// to the client as a single array.
+    fn forward_batch(&self, pending: Vec<Result<BrpResponse, PendingRequest>>) {
+        let response_sender = self.response_sender.clone();
+        let active_requests = self.active_requests.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let mut responses = Vec::with_capacity(pending.len());
+                for response in pending {
+                    match response {
+                        Ok(response) => responses.push(response),
+                        Err((id, key, receiver)) => {
+                            let result = receiver.recv().await;
+                            // Requests that were cancelled have already been answered.
+                            if active_requests.lock().unwrap().remove(&key).is_none() {
+                                continue;
+                            }
+                            if let Ok(result) = result {
+                                responses.push(BrpResponse::new(id, result));
+                            }
+                        }
+                    }
+                }
+
+                if let Ok(serialized) = serde_json::to_string(&responses) {
+                    let _ = response_sender.force_send(serialized);
+                }
+            })
+            .detach();
+    }
+
+    fn respond(&self, response: BrpResponse) {
+        let _ = send_response(&self.response_sender, response);
+    }
+}
+
+impl Drop for BrpConnection {
+    fn drop(&mut self) {
+        self.cancel_all();
+    }
+}
+
+/// Something that happened on the connection of a transport.
+pub(crate) enum ConnectionEvent<M, R> {
+    /// The client sent a message.
+    Message(M),
+    /// A response is ready to be sent to the client.
+    Response(R),
+}
+
+/// The outcome of [`BrpConnection::dispatch`].
+enum Dispatch {
+    /// The request was answered without involving the world.
+    Complete(BrpResponse),
+    /// The request was sent to the world, which answers it through `receiver`.
+    Pending {
+        id: Option<Value>,
+        key: String,
+        watch: bool,
+        receiver: Receiver<BrpResult>,
+    },
+}
+
+/// A request of a batch that is waiting on the world, along with its `id` and [`request_key`].
+type PendingRequest = (Option<Value>, String, Receiver<BrpResult>);
+
+/// The key of a request in [`ActiveRequests`].
+///
+/// Requests without an `id` use keys starting with `#`, which can't collide with these.
+fn request_key(id: &Value) -> String {
+    id.to_string()
+}
+
+/// Sends a response to the client, failing if the connection has been closed.
+fn send_response(response_sender: &Sender<String>, response: BrpResponse) -> Result<(), ()> {
+    let serialized = serde_json::to_string(&response).map_err(|_| ())?;
+    response_sender
+        .force_send(serialized)
+        .map(|_| ())
+        .map_err(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_tasks::{block_on, TaskPool};
+
+    /// Waits for the next response sent by a connection.
+    fn next_response(responses: &Receiver<String>) -> Value {
+        loop {
+            // Without the `multi_threaded` feature, tasks only run when their executor is ticked.
+            IoTaskPool::get().with_local_executor(|executor| while executor.try_tick() {});
+            if let Ok(response) = responses.try_recv() {
+                return serde_json::from_str(&response).unwrap();
+            }
+            std::thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn watch_can_be_cancelled() {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let (request_sender, request_receiver) = async_channel::bounded(4);
+        let (connection, responses) = BrpConnection::new(request_sender, RemoteAccess::Full);
+
+        block_on(
+            connection.handle_message(
+                r#"{"jsonrpc":"2.0","id":1,"method":"world.get_components+watch"}"#,
+            ),
+        );
+        block_on(
+            connection
+                .handle_message(r#"{"jsonrpc":"2.0","id":2,"method":"world.list_resources"}"#),
+        );
+        let watch = request_receiver.try_recv().unwrap();
+        let list = request_receiver.try_recv().unwrap();
+        assert_eq!(watch.method, "world.get_components+watch");
+
+        // Responses are matched to their request by id, in whatever order they complete.
+        list.sender.try_send(Ok(Value::from("list"))).unwrap();
+        let response = next_response(&responses);
+        assert_eq!(response["id"], Value::from(2));
+        assert_eq!(response["result"], Value::from("list"));
+
+        watch.sender.try_send(Ok(Value::from("first"))).unwrap();
+        watch.sender.try_send(Ok(Value::from("second"))).unwrap();
+        for expected in ["first", "second"] {
+            let update = next_response(&responses);
+            assert_eq!(update["id"], Value::from(1));
+            assert_eq!(update["result"], Value::from(expected));
+        }
+
+        block_on(
+            connection.handle_message(
+                r#"{"jsonrpc":"2.0","id":3,"method":"rpc.cancel","params":{"id":1}}"#,
+            ),
+        );
+        let cancelled = next_response(&responses);
+        assert_eq!(cancelled["id"], Value::from(1));
+        assert_eq!(
+            cancelled["error"]["code"],
+            Value::from(error_codes::REQUEST_CANCELLED)
+        );
+        let cancel = next_response(&responses);
+        assert_eq!(cancel["id"], Value::from(3));
+        assert_eq!(cancel["result"], Value::Null);
+
+        // The world sees the watch as closed, and stops running it.
+        assert!(watch.sender.is_closed());
+    }
+
+    #[test]
+    fn dropped_connection_cancels_requests() {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let (request_sender, request_receiver) = async_channel::bounded(4);
+        let (connection, _responses) = BrpConnection::new(request_sender, RemoteAccess::Full);
+
+        // Requests without an id can't be cancelled by the client, but still end with the
+        // connection.
+        block_on(
+            connection.handle_message(r#"{"jsonrpc":"2.0","method":"world.get_components+watch"}"#),
+        );
+        let watch = request_receiver.try_recv().unwrap();
+        assert!(!watch.sender.is_closed());
+
+        drop(connection);
+        assert!(watch.sender.is_closed());
+    }
+}
@@ -0,0 +1,215 @@
+//! The BRP transport using JSON-RPC over a Unix domain socket.
+//!
+//! Adding the [`RemoteUnixSocketPlugin`] to your [`App`] causes Bevy to accept connections
+//! on a socket file while your app is running, so that local tools can talk to it without
+//! opening a TCP port.
+//!
+//! Each line sent by a client holds a JSON-RPC request or a batch of requests, and every
+//! response is sent back as a single line carrying the `id` of the request it answers.
+//! Several requests, including `+watch` requests, can be in flight at once on a connection,
+//! and can be cancelled with [`BRP_CANCEL_METHOD`](crate::transport::BRP_CANCEL_METHOD).
+//! Closing the connection cancels all of them.
+//!
+//! Clients of this transport aren't authenticated; who may connect is controlled by the
+//! file permissions of the socket instead, which only let the user running the app connect.
+//! They are granted [`RemoteAccess::Full`], limited by the
+//! [`RemoteMethodPermissions`](crate::access::RemoteMethodPermissions) of the app.
+
+#![cfg(unix)]
+
+use crate::{
+    access::RemoteAccess,
+    transport::{BrpConnection, ConnectionEvent},
+    BrpMessage, BrpSender,
+};
+use anyhow::{bail, Result as AnyhowResult};
+use async_channel::Sender;
+use async_io::Async;
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::{resource::Resource, system::Res};
+use bevy_log::{error, warn};
+use bevy_tasks::{
+    futures_lite::{
+        future,
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        StreamExt,
+    },
+    IoTaskPool,
+};
+use std::{
+    fs, io,
+    os::unix::{
+        fs::{FileTypeExt, PermissionsExt},
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+};
+
+/// Returns the default path of the socket file.
+///
+/// The socket is named after the ID of the process, so that several apps can listen at once,
+/// and is placed in `$XDG_RUNTIME_DIR`, or in [`std::env::temp_dir`] if it isn't set.
+pub fn default_socket_path() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(format!("bevy_remote_{}.sock", std::process::id()))
+}
+
+/// Add this plugin to your [`App`] to allow remote connections over a Unix domain socket to
+/// inspect and modify entities. It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// By default, the socket is created at [`default_socket_path`]. A socket left at the path by
+/// a previous run is replaced, but the server doesn't start if another server is listening on
+/// it or if the path holds any other kind of file. The socket is only accessible to the user
+/// running the app.
+pub struct RemoteUnixSocketPlugin {
+    /// The path of the socket file.
+    path: PathBuf,
+}
+
+impl Default for RemoteUnixSocketPlugin {
+    fn default() -> Self {
+        Self {
+            path: default_socket_path(),
+        }
+    }
+}
+
+impl Plugin for RemoteUnixSocketPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(UnixSocketPath(self.path.clone()))
+            .add_systems(Startup, start_unix_socket_server);
+    }
+}
+
+impl RemoteUnixSocketPlugin {
+    /// Set the path of the socket file that the server will listen on.
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = path.into();
+        self
+    }
+}
+
+/// A resource containing the path of the socket file that Bevy will listen on.
+///
+/// Currently, changing this while the application is running has no effect; this merely
+/// reflects the path that is set during the setup of the [`RemoteUnixSocketPlugin`].
+#[derive(Debug, Resource)]
+pub struct UnixSocketPath(pub PathBuf);
+
+/// A system that starts up the Bevy Remote Protocol Unix socket server.
+fn start_unix_socket_server(request_sender: Res<BrpSender>, path: Res<UnixSocketPath>) {
+    let path = path.0.clone();
+    let request_sender = request_sender.clone();
+    IoTaskPool::get()
+        .spawn(async move {
+            if let Err(err) = server_main(&path, request_sender).await {
+                error!(
+                    "BRP Unix socket server at `{}` stopped: {err}",
+                    path.display()
+                );
+            }
+        })
+        .detach();
+}
+
+/// The Bevy Remote Protocol Unix socket server main loop.
+async fn server_main(path: &Path, request_sender: Sender<BrpMessage>) -> AnyhowResult<()> {
+    remove_stale_socket(path)?;
+
+    let listener = Async::<UnixListener>::bind(path)?;
+    // Clients aren't authenticated, so don't rely on the umask to keep other users out.
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    loop {
+        let (client, _) = listener.accept().await?;
+
+        let request_sender = request_sender.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                if let Err(err) = handle_client(client, request_sender).await {
+                    warn!("BRP Unix socket connection closed with an error: {err}");
+                }
+            })
+            .detach();
+    }
+}
+
+/// Removes a socket left at `path` by a previous run, which would make binding fail.
+///
+/// Fails if another server is still listening on the socket, or if `path` isn't a socket.
+fn remove_stale_socket(path: &Path) -> AnyhowResult<()> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    if !metadata.file_type().is_socket() {
+        bail!("`{}` already exists and isn't a socket", path.display());
+    }
+    if UnixStream::connect(path).is_ok() {
+        bail!("another server is listening on `{}`", path.display());
+    }
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+async fn handle_client(
+    client: Async<UnixStream>,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    let (connection, responses) = BrpConnection::new(request_sender, RemoteAccess::Full);
+    let mut lines = BufReader::new(&client).lines();
+    let mut writer = &client;
+
+    loop {
+        let event = future::or(
+            async { ConnectionEvent::Message(lines.next().await) },
+            async { ConnectionEvent::Response(responses.recv().await) },
+        )
+        .await;
+
+        match event {
+            ConnectionEvent::Message(Some(line)) => {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    connection.handle_message(&line).await;
+                }
+            }
+            ConnectionEvent::Response(Ok(mut response)) => {
+                response.push('\n');
+                writer.write_all(response.as_bytes()).await?;
+            }
+            ConnectionEvent::Message(None) | ConnectionEvent::Response(Err(_)) => break,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_stale_sockets_are_removed() {
+        let path = std::env::temp_dir().join(format!(
+            "bevy_remote_stale_socket_{}.sock",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        remove_stale_socket(&path).unwrap();
+
+        fs::write(&path, "not a socket").unwrap();
+        assert!(remove_stale_socket(&path).is_err());
+        assert!(path.exists());
+        fs::remove_file(&path).unwrap();
+
+        let listener = UnixListener::bind(&path).unwrap();
+        assert!(remove_stale_socket(&path).is_err());
+        drop(listener);
+        remove_stale_socket(&path).unwrap();
+        assert!(!path.exists());
+    }
+}
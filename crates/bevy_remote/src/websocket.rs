@@ -0,0 +1,219 @@
+//! The BRP transport using JSON-RPC over WebSocket.
+//!
+//! Adding the [`RemoteWebSocketPlugin`] to your [`App`] causes Bevy to accept
+//! WebSocket connections (by default, on port 15704) while your app is running.
+//!
+//! Every text message sent by a client holds a JSON-RPC request or a batch of requests.
+//! Unlike the HTTP transport, a single connection can have any number of requests in flight,
+//! including several `+watch` requests, each of which is answered with messages carrying
+//! the `id` of the request. Outstanding requests can be cancelled with
+//! [`BRP_CANCEL_METHOD`](crate::transport::BRP_CANCEL_METHOD), and are all cancelled when
+//! the connection closes.
+//!
+//! Clients are authenticated with the [`RemoteAuth`] of the app when they connect, using the
+//! bearer token sent in the `Authorization` header of the handshake. Since browsers can't
+//! set that header, the token may also be passed as a percent-encoded `access_token` query
+//! parameter.
+
+#![cfg(not(target_family = "wasm"))]
+
+use crate::{
+    access::{access_token_param, bearer_token, RemoteAccess, RemoteAuth},
+    transport::{BrpConnection, ConnectionEvent},
+    BrpError, BrpMessage, BrpResponse, BrpSender,
+};
+use anyhow::Result as AnyhowResult;
+use async_channel::Sender;
+use async_io::Async;
+use async_tungstenite::tungstenite::{
+    handshake::server::{ErrorResponse, Request},
+    http::{header, HeaderValue, StatusCode},
+    Message,
+};
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::{resource::Resource, system::Res};
+use bevy_log::warn;
+use bevy_tasks::{
+    futures_lite::{future, StreamExt},
+    IoTaskPool,
+};
+use core::net::{IpAddr, Ipv4Addr};
+use std::net::{TcpListener, TcpStream};
+
+/// The default port that Bevy will listen on for WebSocket connections.
+///
+/// This is the port after the ones used by the HTTP transport.
+pub const DEFAULT_PORT: u16 = 15704;
+
+/// The default host address that Bevy will use for its WebSocket server.
+pub const DEFAULT_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+
+/// Add this plugin to your [`App`] to allow remote connections over WebSocket to inspect and
+/// modify entities. It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// This BRP transport cannot be used when targeting WASM.
+///
+/// The defaults are:
+/// - [`DEFAULT_ADDR`] : 127.0.0.1.
+/// - [`DEFAULT_PORT`] : 15704.
+pub struct RemoteWebSocketPlugin {
+    /// The address that Bevy will bind to.
+    address: IpAddr,
+    /// The port that Bevy will listen on.
+    port: u16,
+}
+
+impl Default for RemoteWebSocketPlugin {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDR,
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+impl Plugin for RemoteWebSocketPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WebSocketHost {
+            address: self.address,
+            port: self.port,
+        })
+        .add_systems(Startup, start_websocket_server);
+    }
+}
+
+impl RemoteWebSocketPlugin {
+    /// Set the IP address that the server will use.
+    #[must_use]
+    pub fn with_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+    /// Set the remote port that the server will listen on.
+    #[must_use]
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+}
+
+/// A resource containing the address and port that the WebSocket server will listen on.
+///
+/// Currently, changing this while the application is running has no effect; this merely
+/// reflects the configuration of the [`RemoteWebSocketPlugin`].
+#[derive(Debug, Resource)]
+pub struct WebSocketHost {
+    /// The IP address that Bevy will host on.
+    pub address: IpAddr,
+    /// The port number that Bevy will listen on.
+    pub port: u16,
+}
+
+/// A system that starts up the Bevy Remote Protocol WebSocket server.
+fn start_websocket_server(
+    request_sender: Res<BrpSender>,
+    host: Res<WebSocketHost>,
+    auth: Res<RemoteAuth>,
+) {
+    IoTaskPool::get()
+        .spawn(server_main(
+            host.address,
+            host.port,
+            request_sender.clone(),
+            auth.clone(),
+        ))
+        .detach();
+}
+
+/// The Bevy Remote Protocol WebSocket server main loop.
+async fn server_main(
+    address: IpAddr,
+    port: u16,
+    request_sender: Sender<BrpMessage>,
+    auth: RemoteAuth,
+) -> AnyhowResult<()> {
+    let listener = Async::<TcpListener>::bind((address, port))?;
+    loop {
+        let (client, _) = listener.accept().await?;
+
+        let request_sender = request_sender.clone();
+        let auth = auth.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                if let Err(err) = handle_client(client, request_sender, auth).await {
+                    warn!("BRP WebSocket connection closed with an error: {err}");
+                }
+            })
+            .detach();
+    }
+}
+
+async fn handle_client(
+    client: Async<TcpStream>,
+    request_sender: Sender<BrpMessage>,
+    auth: RemoteAuth,
+) -> AnyhowResult<()> {
+    let mut access = RemoteAccess::Full;
+    #[expect(
+        clippy::result_large_err,
+        reason = "the signature of the callback is defined by `tungstenite`"
+    )]
+    let handshake = async_tungstenite::accept_hdr_async(client, |request: &Request, response| {
+        access = authenticate(&auth, request).map_err(unauthorized_response)?;
+        Ok(response)
+    });
+    let mut socket = handshake.await?;
+    let (connection, responses) = BrpConnection::new(request_sender, access);
+
+    loop {
+        let event = future::or(
+            async { ConnectionEvent::Message(socket.next().await) },
+            async { ConnectionEvent::Response(responses.recv().await) },
+        )
+        .await;
+
+        match event {
+            ConnectionEvent::Message(Some(message)) => match message? {
+                Message::Text(text) => connection.handle_message(&text).await,
+                Message::Binary(bytes) => {
+                    if let Ok(text) = core::str::from_utf8(&bytes) {
+                        connection.handle_message(text).await;
+                    }
+                }
+                Message::Close(_) => break,
+                // Pings are answered by `tungstenite` itself.
+                Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {}
+            },
+            ConnectionEvent::Response(Ok(response)) => {
+                socket.send(Message::text(response)).await?;
+            }
+            ConnectionEvent::Message(None) | ConnectionEvent::Response(Err(_)) => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Authenticates the client making a WebSocket handshake `request`.
+fn authenticate(auth: &RemoteAuth, request: &Request) -> Result<RemoteAccess, BrpError> {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(bearer_token)
+        .map(str::to_owned)
+        .or_else(|| request.uri().query().and_then(access_token_param));
+
+    auth.authenticate(token.as_deref())
+}
+
+/// The response rejecting a WebSocket handshake that failed to authenticate.
+fn unauthorized_response(error: BrpError) -> ErrorResponse {
+    let body = serde_json::to_string(&BrpResponse::new(None, Err(error))).ok();
+    let mut response = ErrorResponse::new(body);
+    *response.status_mut() = StatusCode::UNAUTHORIZED;
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    response
+}
@@ -0,0 +1,731 @@
+use crate::{
+    bsn_asset::{
+        parse::{parse_bsn, AstEntry, AstFields, AstPath, AstValue, AstValueKind},
+        BsnLocation, BsnParseError, BsnScene, BsnSceneEntry,
+    },
+    macro_utils::CallCounter,
+    CachedSceneAsset, NameEntityReference, ReflectRelatedScenes, ReflectedComponent,
+    ReflectedComponentScene, ReflectedPatch, ReflectedTemplateError, ScenePatch,
+};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_asset::{io::Reader, AssetLoader, AssetPath, LoadContext, LoadFromPath, ReflectHandle};
+use bevy_ecs::{
+    entity::Entity,
+    name::Name,
+    reflect::AppTypeRegistry,
+    template::SceneEntityReference,
+    world::{FromWorld, World},
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{
+    array::DynamicArray,
+    convert::ReflectConvert,
+    enums::{DynamicEnum, DynamicVariant, VariantInfo},
+    list::DynamicList,
+    structs::DynamicStruct,
+    tuple::DynamicTuple,
+    tuple_struct::DynamicTupleStruct,
+    Access, ParsedPath, PartialReflect, Type, TypeInfo, TypePath, TypeRegistration, TypeRegistry,
+    TypeRegistryArc,
+};
+use core::any::TypeId;
+use thiserror::Error;
+
+/// The "file" used for the [`SceneEntityReference`]s of `.bsn` assets. Each loaded asset uses a unique "runtime" id,
+/// which gives every asset its own name scope.
+const BSN_ASSET_INVOCATION: (&str, usize, usize) = ("<bsn asset>", 0, 0);
+
+/// Differentiates the name scopes of loaded `.bsn` assets.
+static BSN_ASSET_CALL_ID: CallCounter = CallCounter::new();
+
+/// Asset loader for `.bsn` files, which produces a [`ScenePatch`].
+///
+/// `.bsn` assets use the same notation as the [`bsn!`](crate::bsn) macro, but component types are resolved at runtime
+/// using the [`AppTypeRegistry`]. See the [crate-level docs](crate#bsn-asset-format) for more information.
+#[derive(Debug, TypePath)]
+pub struct BsnLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BsnLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BsnLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`BsnLoader`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum BsnLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the .bsn file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file is not valid UTF-8.
+    #[error("The .bsn file is not valid UTF-8: {0}")]
+    Utf8(#[from] core::str::Utf8Error),
+    /// The file could not be parsed.
+    #[error("Could not parse the .bsn file: {0}")]
+    Parse(#[from] BsnParseError),
+    /// The file was parsed, but does not match the reflected types in the [`TypeRegistry`].
+    #[error("{location}: {error}")]
+    Type {
+        /// Where the error occurred.
+        location: BsnLocation,
+        /// The error that occurred.
+        error: BsnTypeError,
+    },
+}
+
+/// An error that occurs when a parsed `.bsn` file does not match the reflected types in the [`TypeRegistry`].
+#[derive(Debug, Error)]
+pub enum BsnTypeError {
+    /// No type was registered with the given path.
+    #[error("The type {0} is not registered in the TypeRegistry")]
+    UnknownType(String),
+    /// Multiple types are registered with the given short type path.
+    #[error("The type {0} is ambiguous. Use its full type path instead")]
+    AmbiguousType(String),
+    /// The type does not have type info.
+    #[error("The type {0} has no type info in the TypeRegistry")]
+    UnregisteredType(&'static str),
+    /// The type cannot be used as a component.
+    #[error(transparent)]
+    Component(#[from] ReflectedTemplateError),
+    /// The type does not have [`ReflectRelatedScenes`] type data.
+    #[error("{0} cannot be used to spawn related scenes. Consider adding #[reflect(RelatedScenes)] to the relationship target")]
+    NotRelatedScenes(&'static str),
+    /// The given field does not exist.
+    #[error("{type_path} does not have a field named {field}")]
+    UnknownField {
+        /// The type path of the type.
+        type_path: &'static str,
+        /// The name of the field.
+        field: String,
+    },
+    /// Too many unnamed fields were given.
+    #[error("{type_path} has {expected} fields, but {found} were given")]
+    TooManyFields {
+        /// The type path of the type.
+        type_path: &'static str,
+        /// The number of fields on the type.
+        expected: usize,
+        /// The number of fields given.
+        found: usize,
+    },
+    /// The given enum variant does not exist.
+    #[error("{type_path} does not have a variant named {variant}")]
+    UnknownVariant {
+        /// The type path of the enum.
+        type_path: &'static str,
+        /// The name of the variant.
+        variant: String,
+    },
+    /// An enum was given without a variant, but the enum does not reflect [`Default`].
+    #[error("Expected a variant of {0}")]
+    MissingVariant(&'static str),
+    /// The given type name does not match the expected type.
+    #[error("Expected {expected}, found {found}")]
+    MismatchedType {
+        /// The type path of the expected type.
+        expected: &'static str,
+        /// The given type name.
+        found: String,
+    },
+    /// The given value cannot be converted to the expected type.
+    #[error("Expected {expected}, found {found}")]
+    MismatchedValue {
+        /// The type path of the expected type.
+        expected: &'static str,
+        /// The kind of value that was given.
+        found: &'static str,
+    },
+    /// The given literal is not valid for the expected type.
+    #[error("{literal} is not a valid {type_path}")]
+    InvalidLiteral {
+        /// The given literal.
+        literal: String,
+        /// The type path of the expected type.
+        type_path: &'static str,
+    },
+}
+
+impl AssetLoader for BsnLoader {
+    type Asset = ScenePatch;
+    type Settings = ();
+    type Error = BsnLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = core::str::from_utf8(&bytes)?;
+        let scene = BsnScene::parse(text, &self.type_registry.read(), load_context)?;
+        Ok(ScenePatch::load_with(load_context, scene))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bsn"]
+    }
+}
+
+impl BsnScene {
+    /// Parses the given `.bsn` text into a [`BsnScene`], resolving component types using the given `type_registry`.
+    /// Asset handles referenced by the scene are loaded using `load_from_path`.
+    pub fn parse(
+        text: &str,
+        type_registry: &TypeRegistry,
+        load_from_path: &mut impl LoadFromPath,
+    ) -> Result<Self, BsnLoaderError> {
+        let entries = parse_bsn(text)?;
+        BsnConverter {
+            registry: type_registry,
+            load_from_path,
+            names: HashMap::default(),
+            call_id: BSN_ASSET_CALL_ID.increment(),
+        }
+        .convert_scene(&entries)
+    }
+}
+
+fn type_error(location: BsnLocation, error: impl Into<BsnTypeError>) -> BsnLoaderError {
+    BsnLoaderError::Type {
+        location,
+        error: error.into(),
+    }
+}
+
+/// Converts parsed entries to a [`BsnScene`] using reflection.
+struct BsnConverter<'a, L: LoadFromPath> {
+    registry: &'a TypeRegistry,
+    load_from_path: &'a mut L,
+    /// Maps entity names to their per-asset index.
+    names: HashMap<String, usize>,
+    call_id: u64,
+}
+
+/// The state used while converting a single component patch.
+struct PatchState<'s> {
+    /// The reflect path of the value currently being converted.
+    path: Vec<Access<'static>>,
+    entities: Vec<(ParsedPath, SceneEntityReference)>,
+    dependencies: &'s mut Vec<(TypeId, AssetPath<'static>)>,
+}
+
+impl<'a, L: LoadFromPath> BsnConverter<'a, L> {
+    fn entity_reference(&mut self, name: &str) -> SceneEntityReference {
+        let next_id = self.names.len();
+        let name_id = *self.names.entry(name.to_string()).or_insert(next_id);
+        SceneEntityReference::new(BSN_ASSET_INVOCATION, name_id, self.call_id)
+    }
+
+    fn convert_scene(&mut self, entries: &[AstEntry]) -> Result<BsnScene, BsnLoaderError> {
+        let mut scene = BsnScene::default();
+        for entry in entries {
+            let entry = match entry {
+                AstEntry::CachedScene(path, _) => {
+                    BsnSceneEntry::CachedScene(CachedSceneAsset(AssetPath::from(path.clone())))
+                }
+                AstEntry::Name(name) => BsnSceneEntry::Name(NameEntityReference {
+                    name: Name::new(name.clone()),
+                    reference: self.entity_reference(name),
+                }),
+                AstEntry::Patch(path, fields) => {
+                    let (registration, variant) = self.resolve_type(path)?;
+                    let component = ReflectedComponent::from_registration(registration)
+                        .map_err(|error| type_error(path.location, error))?;
+                    let mut state = PatchState {
+                        path: Vec::new(),
+                        entities: Vec::new(),
+                        dependencies: &mut scene.dependencies,
+                    };
+                    let value = self.convert_type(
+                        registration.type_info(),
+                        variant,
+                        fields,
+                        path.location,
+                        &mut state,
+                    )?;
+                    BsnSceneEntry::Component(ReflectedComponentScene {
+                        component,
+                        patch: ReflectedPatch {
+                            value,
+                            entities: state.entities,
+                        },
+                    })
+                }
+                AstEntry::Related(path, scenes) => {
+                    let (registration, _) = self.resolve_type(path)?;
+                    let related = registration
+                        .data::<ReflectRelatedScenes>()
+                        .ok_or_else(|| {
+                            type_error(
+                                path.location,
+                                BsnTypeError::NotRelatedScenes(
+                                    registration.type_info().type_path(),
+                                ),
+                            )
+                        })?
+                        .clone();
+                    let scenes = scenes
+                        .iter()
+                        .map(|entries| self.convert_scene(entries))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    BsnSceneEntry::Related { related, scenes }
+                }
+            };
+            scene.entries.push(entry);
+        }
+        Ok(scene)
+    }
+
+    /// Resolves the type of a scene entry. Like the [`bsn!`](crate::bsn) macro, paths whose last two segments are types
+    /// (ex: `Visibility::Hidden`) are treated as enum variants.
+    fn resolve_type<'p>(
+        &self,
+        path: &'p AstPath,
+    ) -> Result<(&'a TypeRegistration, Option<&'p str>), BsnLoaderError> {
+        let segments = &path.segments;
+        let (type_segments, variant) = match segments.len() {
+            2.. if super::parse::is_type_segment(&segments[segments.len() - 2]) => (
+                &segments[..segments.len() - 1],
+                Some(segments[segments.len() - 1].as_str()),
+            ),
+            _ => (&segments[..], None),
+        };
+        let full_path = type_segments.join("::");
+        if let Some(registration) = self.registry.get_with_type_path(&full_path) {
+            return Ok((registration, variant));
+        }
+        let short_path = type_segments.last().map(String::as_str).unwrap_or_default();
+        if self.registry.is_ambiguous(short_path) {
+            return Err(type_error(
+                path.location,
+                BsnTypeError::AmbiguousType(full_path),
+            ));
+        }
+        self.registry
+            .get_with_short_type_path(short_path)
+            .map(|registration| (registration, variant))
+            .ok_or_else(|| type_error(path.location, BsnTypeError::UnknownType(full_path)))
+    }
+
+    /// Returns the [`TypeInfo`] of a field, item, or variant field.
+    fn type_info(
+        &self,
+        info: Option<&'static TypeInfo>,
+        ty: &Type,
+        location: BsnLocation,
+    ) -> Result<&'static TypeInfo, BsnLoaderError> {
+        info.or_else(|| self.registry.get(ty.id()).map(TypeRegistration::type_info))
+            .ok_or_else(|| type_error(location, BsnTypeError::UnregisteredType(ty.path())))
+    }
+
+    fn convert_value(
+        &mut self,
+        value: &AstValue,
+        info: &'static TypeInfo,
+        state: &mut PatchState,
+    ) -> Result<Box<dyn PartialReflect>, BsnLoaderError> {
+        let type_id = info.type_id();
+        let type_path = info.type_path();
+        let location = value.location;
+        let mismatched = || {
+            type_error(
+                location,
+                BsnTypeError::MismatchedValue {
+                    expected: type_path,
+                    found: value.kind.describe(),
+                },
+            )
+        };
+        let invalid_literal = |literal: String| {
+            type_error(
+                location,
+                BsnTypeError::InvalidLiteral { literal, type_path },
+            )
+        };
+
+        if type_id == TypeId::of::<Entity>() {
+            let AstValueKind::EntityName(name) = &value.kind else {
+                return Err(mismatched());
+            };
+            let reference = self.entity_reference(name);
+            state
+                .entities
+                .push((ParsedPath::from(state.path.clone()), reference));
+            return Ok(Box::new(Entity::PLACEHOLDER));
+        }
+
+        if let Some(reflect_handle) = self.registry.get_type_data::<ReflectHandle>(type_id) {
+            let AstValueKind::String(path) = &value.kind else {
+                return Err(mismatched());
+            };
+            let asset_type_id = reflect_handle.asset_type_id();
+            let path = AssetPath::from(path.clone());
+            state.dependencies.push((asset_type_id, path.clone()));
+            let handle = self
+                .load_from_path
+                .load_from_path_erased(asset_type_id, path);
+            return Ok(reflect_handle.typed(handle).into_partial_reflect());
+        }
+
+        match &value.kind {
+            AstValueKind::Int { negative, digits } => {
+                let literal = if *negative {
+                    alloc::format!("-{digits}")
+                } else {
+                    digits.clone()
+                };
+                parse_number(type_id, &literal, true).ok_or_else(|| {
+                    if is_number(type_id) {
+                        invalid_literal(literal)
+                    } else {
+                        mismatched()
+                    }
+                })
+            }
+            AstValueKind::Float(literal) => {
+                parse_number(type_id, literal, false).ok_or_else(|| {
+                    if is_number(type_id) {
+                        invalid_literal(literal.clone())
+                    } else {
+                        mismatched()
+                    }
+                })
+            }
+            AstValueKind::Bool(value) if type_id == TypeId::of::<bool>() => Ok(Box::new(*value)),
+            AstValueKind::Char(value) if type_id == TypeId::of::<char>() => Ok(Box::new(*value)),
+            AstValueKind::String(value) => {
+                if type_id == TypeId::of::<String>() {
+                    Ok(Box::new(value.clone()))
+                } else if type_id == TypeId::of::<Cow<'static, str>>() {
+                    Ok(Box::new(Cow::<'static, str>::Owned(value.clone())))
+                } else if let Some(reflect_convert) =
+                    self.registry.get_type_data::<ReflectConvert>(type_id)
+                {
+                    reflect_convert
+                        .try_convert_from(Box::new(value.clone()))
+                        .map(PartialReflect::into_partial_reflect)
+                        .map_err(|_| invalid_literal(alloc::format!("{value:?}")))
+                } else {
+                    Err(mismatched())
+                }
+            }
+            AstValueKind::Path(path, fields) => {
+                let variant = match info {
+                    TypeInfo::Enum(_) => {
+                        let (variant, type_segments) = path.segments.split_last().unwrap();
+                        if let Some(type_segment) = type_segments.last() {
+                            check_type_name(info, type_segment, location)?;
+                        }
+                        Some(variant.as_str())
+                    }
+                    _ => {
+                        check_type_name(info, path.last(), location)?;
+                        None
+                    }
+                };
+                self.convert_type(info, variant, fields, location, state)
+            }
+            AstValueKind::Tuple(values) => {
+                let TypeInfo::Tuple(tuple_info) = info else {
+                    return Err(mismatched());
+                };
+                if values.len() > tuple_info.field_len() {
+                    return Err(type_error(
+                        location,
+                        BsnTypeError::TooManyFields {
+                            type_path,
+                            expected: tuple_info.field_len(),
+                            found: values.len(),
+                        },
+                    ));
+                }
+                let mut tuple = DynamicTuple::default();
+                tuple.set_represented_type(Some(info));
+                for (index, value) in values.iter().enumerate() {
+                    let field = tuple_info.field_at(index).unwrap();
+                    let field_info = self.type_info(field.type_info(), field.ty(), location)?;
+                    state.path.push(Access::TupleIndex(index));
+                    tuple.insert_boxed(self.convert_value(value, field_info, state)?);
+                    state.path.pop();
+                }
+                Ok(Box::new(tuple))
+            }
+            AstValueKind::List(values) => {
+                let (item_info, item_ty) = match info {
+                    TypeInfo::List(list_info) => (list_info.item_info(), list_info.item_ty()),
+                    TypeInfo::Array(array_info) => (array_info.item_info(), array_info.item_ty()),
+                    _ => return Err(mismatched()),
+                };
+                let item_info = self.type_info(item_info, &item_ty, location)?;
+                let mut items = Vec::with_capacity(values.len());
+                for (index, value) in values.iter().enumerate() {
+                    state.path.push(Access::ListIndex(index));
+                    items.push(self.convert_value(value, item_info, state)?);
+                    state.path.pop();
+                }
+                Ok(if let TypeInfo::Array(_) = info {
+                    let mut array = DynamicArray::new(items.into_boxed_slice());
+                    array.set_represented_type(Some(info));
+                    Box::new(array)
+                } else {
+                    let mut list = DynamicList::default();
+                    list.set_represented_type(Some(info));
+                    for item in items {
+                        list.push_box(item);
+                    }
+                    Box::new(list)
+                })
+            }
+            AstValueKind::Bool(_) | AstValueKind::Char(_) | AstValueKind::EntityName(_) => {
+                Err(mismatched())
+            }
+        }
+    }
+
+    /// Converts a type (or enum `variant`) with the given `fields` into a patch.
+    fn convert_type(
+        &mut self,
+        info: &'static TypeInfo,
+        variant: Option<&str>,
+        fields: &AstFields,
+        location: BsnLocation,
+        state: &mut PatchState,
+    ) -> Result<Box<dyn PartialReflect>, BsnLoaderError> {
+        let type_path = info.type_path();
+        let mismatched = |found: &'static str| {
+            type_error(
+                location,
+                BsnTypeError::MismatchedValue {
+                    expected: type_path,
+                    found,
+                },
+            )
+        };
+        match info {
+            TypeInfo::Struct(struct_info) => {
+                if variant.is_some() {
+                    return Err(mismatched("an enum variant"));
+                }
+                let mut dynamic_struct = DynamicStruct::default();
+                dynamic_struct.set_represented_type(Some(info));
+                match fields {
+                    AstFields::Unit => {}
+                    AstFields::Named(fields) => {
+                        for (name, field_location, value) in fields {
+                            let field = struct_info.field(name).ok_or_else(|| {
+                                type_error(
+                                    *field_location,
+                                    BsnTypeError::UnknownField {
+                                        type_path,
+                                        field: name.clone(),
+                                    },
+                                )
+                            })?;
+                            let field_info =
+                                self.type_info(field.type_info(), field.ty(), *field_location)?;
+                            state.path.push(Access::Field(Cow::Owned(name.clone())));
+                            let value = self.convert_value(value, field_info, state)?;
+                            state.path.pop();
+                            dynamic_struct.insert_boxed(name.clone(), value);
+                        }
+                    }
+                    AstFields::Tuple(_) => return Err(mismatched("unnamed fields")),
+                }
+                Ok(Box::new(dynamic_struct))
+            }
+            TypeInfo::TupleStruct(tuple_struct_info) => {
+                if variant.is_some() {
+                    return Err(mismatched("an enum variant"));
+                }
+                let mut tuple_struct = DynamicTupleStruct::default();
+                tuple_struct.set_represented_type(Some(info));
+                match fields {
+                    AstFields::Unit => {}
+                    AstFields::Tuple(values) => {
+                        if values.len() > tuple_struct_info.field_len() {
+                            return Err(type_error(
+                                location,
+                                BsnTypeError::TooManyFields {
+                                    type_path,
+                                    expected: tuple_struct_info.field_len(),
+                                    found: values.len(),
+                                },
+                            ));
+                        }
+                        for (index, value) in values.iter().enumerate() {
+                            let field = tuple_struct_info.field_at(index).unwrap();
+                            let field_info =
+                                self.type_info(field.type_info(), field.ty(), value.location)?;
+                            state.path.push(Access::TupleIndex(index));
+                            tuple_struct
+                                .insert_boxed(self.convert_value(value, field_info, state)?);
+                            state.path.pop();
+                        }
+                    }
+                    AstFields::Named(_) => return Err(mismatched("named fields")),
+                }
+                Ok(Box::new(tuple_struct))
+            }
+            TypeInfo::Enum(enum_info) => {
+                let Some(variant) = variant else {
+                    // An enum without a variant uses its default value.
+                    let reflect_default = self
+                        .registry
+                        .get_type_data::<bevy_reflect::std_traits::ReflectDefault>(info.type_id())
+                        .filter(|_| matches!(fields, AstFields::Unit))
+                        .ok_or_else(|| {
+                            type_error(location, BsnTypeError::MissingVariant(type_path))
+                        })?;
+                    return Ok(reflect_default.default().into_partial_reflect());
+                };
+                let variant_info = enum_info.variant(variant).ok_or_else(|| {
+                    type_error(
+                        location,
+                        BsnTypeError::UnknownVariant {
+                            type_path,
+                            variant: variant.to_string(),
+                        },
+                    )
+                })?;
+                let dynamic_variant = match (variant_info, fields) {
+                    (VariantInfo::Unit(_), AstFields::Unit) => DynamicVariant::Unit,
+                    (VariantInfo::Tuple(tuple_info), AstFields::Tuple(values)) => {
+                        if values.len() > tuple_info.field_len() {
+                            return Err(type_error(
+                                location,
+                                BsnTypeError::TooManyFields {
+                                    type_path,
+                                    expected: tuple_info.field_len(),
+                                    found: values.len(),
+                                },
+                            ));
+                        }
+                        let mut tuple = DynamicTuple::default();
+                        for (index, value) in values.iter().enumerate() {
+                            let field = tuple_info.field_at(index).unwrap();
+                            let field_info =
+                                self.type_info(field.type_info(), field.ty(), value.location)?;
+                            state.path.push(Access::TupleIndex(index));
+                            tuple.insert_boxed(self.convert_value(value, field_info, state)?);
+                            state.path.pop();
+                        }
+                        DynamicVariant::Tuple(tuple)
+                    }
+                    (VariantInfo::Struct(struct_info), AstFields::Named(fields)) => {
+                        let mut dynamic_struct = DynamicStruct::default();
+                        for (name, field_location, value) in fields {
+                            let field = struct_info.field(name).ok_or_else(|| {
+                                type_error(
+                                    *field_location,
+                                    BsnTypeError::UnknownField {
+                                        type_path,
+                                        field: name.clone(),
+                                    },
+                                )
+                            })?;
+                            let field_info =
+                                self.type_info(field.type_info(), field.ty(), *field_location)?;
+                            state.path.push(Access::Field(Cow::Owned(name.clone())));
+                            let value = self.convert_value(value, field_info, state)?;
+                            state.path.pop();
+                            dynamic_struct.insert_boxed(name.clone(), value);
+                        }
+                        DynamicVariant::Struct(dynamic_struct)
+                    }
+                    (VariantInfo::Tuple(_), AstFields::Unit) => {
+                        DynamicVariant::Tuple(DynamicTuple::default())
+                    }
+                    (VariantInfo::Struct(_), AstFields::Unit) => {
+                        DynamicVariant::Struct(DynamicStruct::default())
+                    }
+                    (_, AstFields::Tuple(_)) => return Err(mismatched("unnamed fields")),
+                    (_, AstFields::Named(_)) => return Err(mismatched("named fields")),
+                };
+                let mut dynamic_enum = DynamicEnum::new(variant, dynamic_variant);
+                dynamic_enum.set_represented_type(Some(info));
+                Ok(Box::new(dynamic_enum))
+            }
+            _ => Err(mismatched("a type")),
+        }
+    }
+}
+
+/// Verifies that the given path segment names the type described by `info`.
+fn check_type_name(
+    info: &'static TypeInfo,
+    segment: &str,
+    location: BsnLocation,
+) -> Result<(), BsnLoaderError> {
+    let name = segment.split('<').next().unwrap_or_default();
+    if info.type_path_table().ident() == Some(name) {
+        Ok(())
+    } else {
+        Err(type_error(
+            location,
+            BsnTypeError::MismatchedType {
+                expected: info.type_path(),
+                found: segment.to_string(),
+            },
+        ))
+    }
+}
+
+macro_rules! number_types {
+    ($callback: ident) => {
+        $callback!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64)
+    };
+}
+
+fn is_number(type_id: TypeId) -> bool {
+    macro_rules! is_number {
+        ($($ty: ty),*) => {
+            $(type_id == TypeId::of::<$ty>())||*
+        };
+    }
+    number_types!(is_number)
+}
+
+/// Parses a numeric literal (with an optional type suffix such as `u32`) as the type with the given `type_id`. Integer
+/// literals can be parsed as floats, but float literals cannot be parsed as integers.
+fn parse_number(
+    type_id: TypeId,
+    literal: &str,
+    is_integer: bool,
+) -> Option<Box<dyn PartialReflect>> {
+    const SUFFIXES: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+        "f32", "f64",
+    ];
+    let literal = SUFFIXES
+        .iter()
+        .find_map(|suffix| literal.strip_suffix(suffix))
+        .unwrap_or(literal);
+    macro_rules! parse {
+        ($($ty: ty),*) => {
+            $(
+                if type_id == TypeId::of::<$ty>() {
+                    if !is_integer && !(TypeId::of::<$ty>() == TypeId::of::<f32>() || TypeId::of::<$ty>() == TypeId::of::<f64>()) {
+                        return None;
+                    }
+                    return literal.parse::<$ty>().ok().map(|value| Box::new(value) as Box<dyn PartialReflect>);
+                }
+            )*
+        };
+    }
+    number_types!(parse);
+    None
+}
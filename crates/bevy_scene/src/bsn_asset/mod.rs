@@ -0,0 +1,9 @@
+mod loader;
+mod parse;
+mod scene;
+mod writer;
+
+pub use loader::*;
+pub use parse::{BsnLocation, BsnParseError, BsnParseErrorKind};
+pub use scene::*;
+pub use writer::*;
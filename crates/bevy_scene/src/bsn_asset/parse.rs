@@ -0,0 +1,873 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, iter::Peekable, str::CharIndices};
+use thiserror::Error;
+
+/// A location in a `.bsn` asset's source text. Lines and columns start at 1.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BsnLocation {
+    /// The line of the location.
+    pub line: usize,
+    /// The column of the location (in characters).
+    pub column: usize,
+}
+
+impl fmt::Display for BsnLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// An error that occurs when parsing the text of a `.bsn` asset.
+#[derive(Error, Debug, PartialEq)]
+#[error("{location}: {kind}")]
+pub struct BsnParseError {
+    /// Where the error occurred.
+    pub location: BsnLocation,
+    /// The kind of error.
+    pub kind: BsnParseErrorKind,
+}
+
+/// The kind of [`BsnParseError`].
+#[derive(Error, Debug, PartialEq)]
+pub enum BsnParseErrorKind {
+    /// An unexpected character was encountered.
+    #[error("Unexpected character '{0}'")]
+    UnexpectedCharacter(char),
+    /// A string or character literal was not terminated.
+    #[error("Unterminated literal")]
+    UnterminatedLiteral,
+    /// A block comment was not terminated.
+    #[error("Unterminated block comment")]
+    UnterminatedComment,
+    /// An invalid escape sequence was found in a string or character literal.
+    #[error("Invalid escape sequence '\\{0}'")]
+    InvalidEscape(char),
+    /// A character literal did not contain exactly one character.
+    #[error("Character literals must contain exactly one character")]
+    InvalidCharLiteral,
+    /// The parser expected something else.
+    #[error("Expected {expected}, found {found}")]
+    Expected {
+        /// What was expected.
+        expected: &'static str,
+        /// What was found instead.
+        found: String,
+    },
+    /// A cached scene was not the first entry of a scene.
+    #[error("Caching entries after the first is not supported, remove the ':' prefix or make this the first entry")]
+    LateCachedScene,
+    /// A feature of the `bsn!` macro that is not supported in `.bsn` assets was used.
+    #[error("{0} are not supported in .bsn assets")]
+    Unsupported(&'static str),
+}
+
+/// A parsed path, such as `bevy_transform::components::Transform` or `Visibility::Hidden`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AstPath {
+    /// Whether the path starts with `::`.
+    pub(crate) leading_colon: bool,
+    /// The segments of the path, including any generic arguments (ex: `Foo<u32>`).
+    pub(crate) segments: Vec<String>,
+    pub(crate) location: BsnLocation,
+}
+
+impl AstPath {
+    /// The full path, without a leading `::`.
+    pub(crate) fn full(&self) -> String {
+        self.segments.join("::")
+    }
+
+    /// The last segment of the path.
+    pub(crate) fn last(&self) -> &str {
+        self.segments.last().map(String::as_str).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for AstPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.leading_colon {
+            f.write_str("::")?;
+        }
+        f.write_str(&self.full())
+    }
+}
+
+/// The fields of a parsed type or enum variant.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum AstFields {
+    /// No fields: `Foo`
+    Unit,
+    /// Named fields: `Foo { a: 1 }`
+    Named(Vec<(String, BsnLocation, AstValue)>),
+    /// Unnamed fields: `Foo(1, 2)`
+    Tuple(Vec<AstValue>),
+}
+
+/// A parsed value.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AstValue {
+    pub(crate) kind: AstValueKind,
+    pub(crate) location: BsnLocation,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum AstValueKind {
+    /// An integer literal, with `_` separators removed.
+    Int {
+        negative: bool,
+        digits: String,
+    },
+    /// A float literal, with `_` separators removed.
+    Float(String),
+    String(String),
+    Char(char),
+    Bool(bool),
+    /// A `#Name` entity reference.
+    EntityName(String),
+    /// A type, enum variant, or unit value: `Foo { a: 1 }`, `Foo::Bar(1)`, `None`.
+    Path(AstPath, AstFields),
+    Tuple(Vec<AstValue>),
+    List(Vec<AstValue>),
+}
+
+impl AstValueKind {
+    /// A short description of this kind of value, for error messages.
+    pub(crate) fn describe(&self) -> &'static str {
+        match self {
+            AstValueKind::Int { .. } => "an integer",
+            AstValueKind::Float(_) => "a float",
+            AstValueKind::String(_) => "a string",
+            AstValueKind::Char(_) => "a char",
+            AstValueKind::Bool(_) => "a bool",
+            AstValueKind::EntityName(_) => "an entity name",
+            AstValueKind::Path(..) => "a type",
+            AstValueKind::Tuple(_) => "a tuple",
+            AstValueKind::List(_) => "a list",
+        }
+    }
+}
+
+/// A parsed scene entry.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum AstEntry {
+    /// `:"path.bsn"`
+    CachedScene(String, BsnLocation),
+    /// `#Name`
+    Name(String),
+    /// `Foo { a: 1 }`
+    Patch(AstPath, AstFields),
+    /// `Children [ A, B ]`
+    Related(AstPath, Vec<Vec<AstEntry>>),
+}
+
+/// Parses the text of a `.bsn` asset into a list of entries for the root entity.
+pub(crate) fn parse_bsn(text: &str) -> Result<Vec<AstEntry>, BsnParseError> {
+    let tokens = tokenize(text)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+    };
+    let entries = parser.parse_entries(false)?;
+    parser.expect_end()?;
+    Ok(entries)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(String),
+    Float(String),
+    String(String),
+    Char(char),
+    Punct(char),
+    /// `::`
+    PathSeparator,
+    End,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(ident) => write!(f, "'{ident}'"),
+            Token::Int(value) | Token::Float(value) => write!(f, "'{value}'"),
+            Token::String(value) => write!(f, "{value:?}"),
+            Token::Char(value) => write!(f, "{value:?}"),
+            Token::Punct(value) => write!(f, "'{value}'"),
+            Token::PathSeparator => f.write_str("'::'"),
+            Token::End => f.write_str("the end of the file"),
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    location: BsnLocation,
+}
+
+impl Lexer<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        let mut chars = self.chars.clone();
+        chars.next();
+        chars.next().map(|(_, c)| c)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let (_, c) = self.chars.next()?;
+        if c == '\n' {
+            self.location.line += 1;
+            self.location.column = 1;
+        } else {
+            self.location.column += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, kind: BsnParseErrorKind) -> BsnParseError {
+        BsnParseError {
+            location: self.location,
+            kind,
+        }
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_trivia(&mut self) -> Result<(), BsnParseError> {
+        loop {
+            match (self.peek(), self.peek_second()) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.next();
+                }
+                (Some('/'), Some('/')) => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.next();
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    let start = self.location;
+                    self.next();
+                    self.next();
+                    let mut depth = 1;
+                    while depth > 0 {
+                        match (self.next(), self.peek()) {
+                            (Some('*'), Some('/')) => {
+                                self.next();
+                                depth -= 1;
+                            }
+                            (Some('/'), Some('*')) => {
+                                self.next();
+                                depth += 1;
+                            }
+                            (Some(_), _) => {}
+                            (None, _) => {
+                                return Err(BsnParseError {
+                                    location: start,
+                                    kind: BsnParseErrorKind::UnterminatedComment,
+                                })
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> String {
+        let mut value = String::new();
+        while let Some(c) = self.peek()
+            && predicate(c)
+        {
+            value.push(c);
+            self.next();
+        }
+        value
+    }
+
+    fn number(&mut self) -> Token {
+        let mut value = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        let mut is_float = false;
+        // `1.5` and `1.` are floats, but `1..` and `1.foo` are not.
+        if !value.starts_with("0x")
+            && self.peek() == Some('.')
+            && self
+                .peek_second()
+                .is_none_or(|c| c != '.' && !c.is_alphabetic() && c != '_')
+        {
+            is_float = true;
+            self.next();
+            value.push('.');
+            value.push_str(&self.take_while(|c| c.is_ascii_alphanumeric() || c == '_'));
+        }
+        if !value.starts_with("0x") && value.contains(['e', 'E']) {
+            is_float = true;
+            // Exponents can be signed: `1e-3`
+            if value.ends_with(['e', 'E']) && matches!(self.peek(), Some('-' | '+')) {
+                value.push(self.next().unwrap());
+                value.push_str(&self.take_while(|c| c.is_ascii_alphanumeric() || c == '_'));
+            }
+        }
+        let value = value.replace('_', "");
+        if is_float {
+            Token::Float(value)
+        } else {
+            Token::Int(value)
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, BsnParseError> {
+        let location = self.location;
+        let invalid = |c| BsnParseError {
+            location,
+            kind: BsnParseErrorKind::InvalidEscape(c),
+        };
+        Ok(match self.next() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('0') => '\0',
+            Some('\\') => '\\',
+            Some('"') => '"',
+            Some('\'') => '\'',
+            Some('u') => {
+                if self.next() != Some('{') {
+                    return Err(invalid('u'));
+                }
+                let digits = self.take_while(|c| c.is_ascii_hexdigit());
+                if self.next() != Some('}') {
+                    return Err(invalid('u'));
+                }
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| invalid('u'))?
+            }
+            Some(c) => return Err(invalid(c)),
+            None => return Err(self.error(BsnParseErrorKind::UnterminatedLiteral)),
+        })
+    }
+
+    fn string(&mut self) -> Result<Token, BsnParseError> {
+        let start = self.location;
+        self.next();
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(Token::String(value)),
+                Some('\\') => value.push(self.escape()?),
+                Some(c) => value.push(c),
+                None => {
+                    return Err(BsnParseError {
+                        location: start,
+                        kind: BsnParseErrorKind::UnterminatedLiteral,
+                    })
+                }
+            }
+        }
+    }
+
+    fn char(&mut self) -> Result<Token, BsnParseError> {
+        let start = self.location;
+        self.next();
+        let value = match self.next() {
+            Some('\\') => self.escape()?,
+            Some('\'') => {
+                return Err(BsnParseError {
+                    location: start,
+                    kind: BsnParseErrorKind::InvalidCharLiteral,
+                })
+            }
+            Some(c) => c,
+            None => {
+                return Err(BsnParseError {
+                    location: start,
+                    kind: BsnParseErrorKind::UnterminatedLiteral,
+                })
+            }
+        };
+        if self.next() != Some('\'') {
+            return Err(BsnParseError {
+                location: start,
+                kind: BsnParseErrorKind::InvalidCharLiteral,
+            });
+        }
+        Ok(Token::Char(value))
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<(Token, BsnLocation)>, BsnParseError> {
+    let mut lexer = Lexer {
+        chars: text.char_indices().peekable(),
+        location: BsnLocation { line: 1, column: 1 },
+    };
+    let mut tokens = Vec::new();
+    loop {
+        lexer.skip_trivia()?;
+        let location = lexer.location;
+        let Some(c) = lexer.peek() else {
+            tokens.push((Token::End, location));
+            return Ok(tokens);
+        };
+        let token = match c {
+            c if c.is_alphabetic() || c == '_' => {
+                Token::Ident(lexer.take_while(|c| c.is_alphanumeric() || c == '_'))
+            }
+            c if c.is_ascii_digit() => lexer.number(),
+            '"' => lexer.string()?,
+            '\'' => lexer.char()?,
+            ':' if lexer.peek_second() == Some(':') => {
+                lexer.next();
+                lexer.next();
+                Token::PathSeparator
+            }
+            ':' | '#' | '{' | '}' | '(' | ')' | '[' | ']' | ',' | '-' | '<' | '>' | '~' | '@'
+            | '|' => {
+                lexer.next();
+                Token::Punct(c)
+            }
+            c => return Err(lexer.error(BsnParseErrorKind::UnexpectedCharacter(c))),
+        };
+        tokens.push((token, location));
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, BsnLocation)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn peek_second(&self) -> &Token {
+        &self.tokens[(self.position + 1).min(self.tokens.len() - 1)].0
+    }
+
+    fn location(&self) -> BsnLocation {
+        self.tokens[self.position].1
+    }
+
+    fn next(&mut self) -> (Token, BsnLocation) {
+        let token = self.tokens[self.position].clone();
+        if self.position < self.tokens.len() - 1 {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn is_punct(&self, punct: char) -> bool {
+        *self.peek() == Token::Punct(punct)
+    }
+
+    fn error(&self, kind: BsnParseErrorKind) -> BsnParseError {
+        BsnParseError {
+            location: self.location(),
+            kind,
+        }
+    }
+
+    fn expected(&self, expected: &'static str) -> BsnParseError {
+        self.error(BsnParseErrorKind::Expected {
+            expected,
+            found: self.peek().to_string(),
+        })
+    }
+
+    fn expect_punct(&mut self, punct: char, expected: &'static str) -> Result<(), BsnParseError> {
+        if self.is_punct(punct) {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.expected(expected))
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<(String, BsnLocation), BsnParseError> {
+        match self.next() {
+            (Token::Ident(ident), location) => Ok((ident, location)),
+            _ => {
+                self.position -= 1;
+                Err(self.expected("an identifier"))
+            }
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), BsnParseError> {
+        if *self.peek() == Token::End {
+            Ok(())
+        } else {
+            Err(self.expected("a scene entry"))
+        }
+    }
+
+    /// Parses scene entries until the end of the current scope. If `in_list` is true, entries also end at `,`.
+    fn parse_entries(&mut self, in_list: bool) -> Result<Vec<AstEntry>, BsnParseError> {
+        let mut entries = Vec::new();
+        loop {
+            match self.peek() {
+                Token::End | Token::Punct(']' | ')') => break,
+                Token::Punct(',') if in_list => break,
+                Token::Punct('(') => {
+                    // Parenthesized entries are "flattened" into the current scene.
+                    self.next();
+                    let location = self.location();
+                    let inner = self.parse_entries(false)?;
+                    self.expect_punct(')', "')'")?;
+                    for entry in inner {
+                        if matches!(entry, AstEntry::CachedScene(..)) && !entries.is_empty() {
+                            return Err(BsnParseError {
+                                location,
+                                kind: BsnParseErrorKind::LateCachedScene,
+                            });
+                        }
+                        entries.push(entry);
+                    }
+                }
+                _ => {
+                    let location = self.location();
+                    let entry = self.parse_entry()?;
+                    if matches!(entry, AstEntry::CachedScene(..)) && !entries.is_empty() {
+                        return Err(BsnParseError {
+                            location,
+                            kind: BsnParseErrorKind::LateCachedScene,
+                        });
+                    }
+                    entries.push(entry);
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    fn parse_entry(&mut self) -> Result<AstEntry, BsnParseError> {
+        match self.peek().clone() {
+            Token::Punct(':') => {
+                self.next();
+                match self.next() {
+                    (Token::String(path), location) => Ok(AstEntry::CachedScene(path, location)),
+                    _ => {
+                        self.position -= 1;
+                        Err(self.error(BsnParseErrorKind::Unsupported(
+                            "Cached entries other than scene assets",
+                        )))
+                    }
+                }
+            }
+            Token::String(_) => Err(self.error(BsnParseErrorKind::Expected {
+                expected: "a ':' prefix (scene assets must be cached)",
+                found: self.peek().to_string(),
+            })),
+            Token::Punct('#') => {
+                self.next();
+                Ok(AstEntry::Name(self.expect_ident()?.0))
+            }
+            Token::Punct('~') => {
+                Err(self.error(BsnParseErrorKind::Unsupported("Template patches")))
+            }
+            Token::Punct('@') => {
+                Err(self.error(BsnParseErrorKind::Unsupported("Scene components")))
+            }
+            Token::Punct('{') => Err(self.error(BsnParseErrorKind::Unsupported("Expressions"))),
+            Token::Ident(_) | Token::PathSeparator => {
+                let path = self.parse_path()?;
+                if !is_type_segment(path.last()) {
+                    return Err(BsnParseError {
+                        location: path.location,
+                        kind: BsnParseErrorKind::Unsupported("Functions and constants"),
+                    });
+                }
+                if self.is_punct('[') {
+                    self.next();
+                    let mut scenes = Vec::new();
+                    loop {
+                        if self.is_punct(']') {
+                            self.next();
+                            break;
+                        }
+                        scenes.push(self.parse_entries(true)?);
+                        if self.is_punct(',') {
+                            self.next();
+                        } else {
+                            self.expect_punct(']', "',' or ']'")?;
+                            break;
+                        }
+                    }
+                    Ok(AstEntry::Related(path, scenes))
+                } else {
+                    let fields = self.parse_fields()?;
+                    Ok(AstEntry::Patch(path, fields))
+                }
+            }
+            _ => Err(self.expected("a scene entry")),
+        }
+    }
+
+    fn parse_path(&mut self) -> Result<AstPath, BsnParseError> {
+        let location = self.location();
+        let leading_colon = *self.peek() == Token::PathSeparator;
+        if leading_colon {
+            self.next();
+        }
+        let mut segments = Vec::new();
+        loop {
+            let (mut segment, _) = self.expect_ident()?;
+            if self.is_punct('<') {
+                segment.push_str(&self.parse_generics()?);
+            }
+            segments.push(segment);
+            if *self.peek() == Token::PathSeparator && matches!(self.peek_second(), Token::Ident(_))
+            {
+                self.next();
+            } else {
+                break;
+            }
+        }
+        Ok(AstPath {
+            leading_colon,
+            segments,
+            location,
+        })
+    }
+
+    /// Parses generic arguments (ex: `<u32, Foo<Bar>>`) into the form used by type paths.
+    fn parse_generics(&mut self) -> Result<String, BsnParseError> {
+        self.expect_punct('<', "'<'")?;
+        let mut args = Vec::new();
+        loop {
+            args.push(self.parse_path()?.to_string());
+            if self.is_punct(',') {
+                self.next();
+            } else {
+                self.expect_punct('>', "',' or '>'")?;
+                break;
+            }
+        }
+        Ok(format!("<{}>", args.join(", ")))
+    }
+
+    fn parse_fields(&mut self) -> Result<AstFields, BsnParseError> {
+        if self.is_punct('{') {
+            self.next();
+            let mut fields = Vec::new();
+            while !self.is_punct('}') {
+                let (name, location) = self.expect_ident()?;
+                self.expect_punct(':', "':' (field shorthand is not supported in .bsn assets)")?;
+                fields.push((name, location, self.parse_value()?));
+                if self.is_punct(',') {
+                    self.next();
+                } else {
+                    break;
+                }
+            }
+            self.expect_punct('}', "',' or '}'")?;
+            Ok(AstFields::Named(fields))
+        } else if self.is_punct('(') {
+            Ok(AstFields::Tuple(self.parse_value_list('(', ')')?))
+        } else {
+            Ok(AstFields::Unit)
+        }
+    }
+
+    fn parse_value_list(
+        &mut self,
+        open: char,
+        close: char,
+    ) -> Result<Vec<AstValue>, BsnParseError> {
+        let expected = if close == ')' {
+            "',' or ')'"
+        } else {
+            "',' or ']'"
+        };
+        self.expect_punct(open, expected)?;
+        let mut values = Vec::new();
+        while !self.is_punct(close) {
+            values.push(self.parse_value()?);
+            if self.is_punct(',') {
+                self.next();
+            } else {
+                break;
+            }
+        }
+        self.expect_punct(close, expected)?;
+        Ok(values)
+    }
+
+    fn parse_value(&mut self) -> Result<AstValue, BsnParseError> {
+        let location = self.location();
+        let kind = match self.peek().clone() {
+            Token::Int(digits) => {
+                self.next();
+                AstValueKind::Int {
+                    negative: false,
+                    digits,
+                }
+            }
+            Token::Float(value) => {
+                self.next();
+                AstValueKind::Float(value)
+            }
+            Token::Punct('-') => {
+                self.next();
+                match self.next().0 {
+                    Token::Int(digits) => AstValueKind::Int {
+                        negative: true,
+                        digits,
+                    },
+                    Token::Float(value) => AstValueKind::Float(format!("-{value}")),
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.expected("a number"));
+                    }
+                }
+            }
+            Token::String(value) => {
+                self.next();
+                AstValueKind::String(value)
+            }
+            Token::Char(value) => {
+                self.next();
+                AstValueKind::Char(value)
+            }
+            Token::Punct('#') => {
+                self.next();
+                AstValueKind::EntityName(self.expect_ident()?.0)
+            }
+            Token::Punct('(') => {
+                let mut values = self.parse_value_list('(', ')')?;
+                // The token before the closing ')'
+                let has_trailing_comma =
+                    matches!(self.tokens[self.position - 2].0, Token::Punct(','));
+                if values.len() == 1 && !has_trailing_comma {
+                    // `(value)` is a parenthesized value, not a tuple.
+                    return Ok(values.pop().unwrap());
+                }
+                AstValueKind::Tuple(values)
+            }
+            Token::Punct('[') => AstValueKind::List(self.parse_value_list('[', ']')?),
+            Token::Ident(ident) if ident == "true" || ident == "false" => {
+                self.next();
+                AstValueKind::Bool(ident == "true")
+            }
+            Token::Ident(_) | Token::PathSeparator => {
+                let path = self.parse_path()?;
+                if !is_type_segment(path.last()) {
+                    return Err(BsnParseError {
+                        location: path.location,
+                        kind: BsnParseErrorKind::Unsupported("Functions and constants"),
+                    });
+                }
+                let fields = self.parse_fields()?;
+                AstValueKind::Path(path, fields)
+            }
+            Token::Punct('{') => {
+                return Err(self.error(BsnParseErrorKind::Unsupported("Expressions")))
+            }
+            Token::Punct('|') => return Err(self.error(BsnParseErrorKind::Unsupported("Closures"))),
+            _ => return Err(self.expected("a value")),
+        };
+        Ok(AstValue { kind, location })
+    }
+}
+
+/// Returns true if the given path segment refers to a type or enum variant (ex: `Foo` or `Foo<u32>`), rather than
+/// a function (`foo`) or constant (`FOO`). This follows the same rules as the `bsn!` macro.
+pub(crate) fn is_type_segment(segment: &str) -> bool {
+    let ident = segment.split('<').next().unwrap_or_default();
+    let mut chars = ident.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    // Single uppercase characters (ex: `A`) are types. Otherwise, any lowercase character distinguishes a type from a constant.
+    first.is_uppercase() && (ident.len() == 1 || ident.chars().any(char::is_lowercase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_entries() {
+        let entries = parse_bsn(
+            r#"
+            // A comment
+            :"base.bsn"
+            #Root
+            a::Foo { x: 1, y: -2.5, name: "hi\n", list: [1, 2,], tuple: (1, 'c'), unit: (1) }
+            Bar(true, #Root, Baz::Qux { a: None })
+            /* block /* nested */ comment */
+            Children [
+                A B,
+                (C),
+                :"child.bsn" D,
+            ]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 5);
+        assert!(matches!(&entries[0], AstEntry::CachedScene(path, _) if path == "base.bsn"));
+        assert_eq!(entries[1], AstEntry::Name("Root".into()));
+        let AstEntry::Patch(path, AstFields::Named(fields)) = &entries[2] else {
+            panic!("expected a patch");
+        };
+        assert_eq!(path.full(), "a::Foo");
+        assert_eq!(fields.len(), 6);
+        assert_eq!(fields[1].2.kind, AstValueKind::Float("-2.5".into()),);
+        assert_eq!(fields[2].2.kind, AstValueKind::String("hi\n".into()));
+        assert!(matches!(&fields[3].2.kind, AstValueKind::List(values) if values.len() == 2));
+        assert!(matches!(&fields[4].2.kind, AstValueKind::Tuple(values) if values.len() == 2));
+        assert!(
+            matches!(&fields[5].2.kind, AstValueKind::Int { negative: false, digits } if digits == "1")
+        );
+        let AstEntry::Related(path, scenes) = &entries[4] else {
+            panic!("expected related scenes");
+        };
+        assert_eq!(path.full(), "Children");
+        assert_eq!(scenes.len(), 3);
+        assert_eq!(scenes[0].len(), 2);
+        assert_eq!(scenes[1].len(), 1);
+        assert_eq!(scenes[2].len(), 2);
+    }
+
+    #[test]
+    fn parses_generics() {
+        let entries = parse_bsn("::a::Foo<u32, b::Bar<c::Baz>>").unwrap();
+        let AstEntry::Patch(path, AstFields::Unit) = &entries[0] else {
+            panic!("expected a patch");
+        };
+        assert!(path.leading_colon);
+        assert_eq!(path.full(), "a::Foo<u32, b::Bar<c::Baz>>");
+    }
+
+    #[test]
+    fn reports_errors() {
+        let error = parse_bsn("Foo\n  #Name\n  Bar { x: {value} }").unwrap_err();
+        assert_eq!(
+            error.location,
+            BsnLocation {
+                line: 3,
+                column: 12
+            }
+        );
+        assert_eq!(error.kind, BsnParseErrorKind::Unsupported("Expressions"));
+
+        let error = parse_bsn("Foo :\"a.bsn\"").unwrap_err();
+        assert_eq!(error.kind, BsnParseErrorKind::LateCachedScene);
+
+        let error = parse_bsn("foo()").unwrap_err();
+        assert_eq!(
+            error.kind,
+            BsnParseErrorKind::Unsupported("Functions and constants")
+        );
+
+        let error = parse_bsn("Foo { x: \"abc }").unwrap_err();
+        assert_eq!(error.kind, BsnParseErrorKind::UnterminatedLiteral);
+    }
+}
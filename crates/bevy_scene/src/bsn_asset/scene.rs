@@ -0,0 +1,75 @@
+use crate::{
+    CachedSceneAsset, NameEntityReference, ReflectRelatedScenes, ReflectedComponentScene,
+    ResolveContext, ResolveSceneError, ResolvedScene, Scene, SceneDependencies,
+};
+use alloc::vec::Vec;
+use bevy_asset::AssetPath;
+use core::any::TypeId;
+
+/// A [`Scene`] loaded from a `.bsn` asset by the [`BsnLoader`](crate::BsnLoader).
+///
+/// Unlike scenes produced by the [`bsn!`](crate::bsn) macro, the component types in a [`BsnScene`] are not known at compile time.
+/// Components are instead patched using reflection (see [`ReflectedComponentScene`]).
+#[derive(Default)]
+pub struct BsnScene {
+    pub(crate) entries: Vec<BsnSceneEntry>,
+    /// Asset dependencies referenced by this entity's components.
+    pub(crate) dependencies: Vec<(TypeId, AssetPath<'static>)>,
+}
+
+/// A single entry in a [`BsnScene`].
+pub(crate) enum BsnSceneEntry {
+    CachedScene(CachedSceneAsset),
+    Name(NameEntityReference),
+    Component(ReflectedComponentScene),
+    Related {
+        related: ReflectRelatedScenes,
+        scenes: Vec<BsnScene>,
+    },
+}
+
+impl Scene for BsnScene {
+    fn resolve(
+        self,
+        context: &mut ResolveContext,
+        scene: &mut ResolvedScene,
+    ) -> Result<(), ResolveSceneError> {
+        for entry in self.entries {
+            match entry {
+                BsnSceneEntry::CachedScene(cached) => cached.resolve(context, scene)?,
+                BsnSceneEntry::Name(name) => name.resolve_inline(context, scene),
+                BsnSceneEntry::Component(component) => component.resolve(context, scene)?,
+                BsnSceneEntry::Related { related, scenes } => {
+                    let related_scenes = related.get_or_insert(scene);
+                    for related_scene in scenes {
+                        let mut resolved_scene = ResolvedScene::default();
+                        // The cached scene of this entity does not apply to related entities
+                        let cached = context.cached.take();
+                        let result = related_scene.resolve(context, &mut resolved_scene);
+                        context.cached = cached;
+                        result?;
+                        related_scenes.scenes.push(resolved_scene);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn register_dependencies(&self, dependencies: &mut SceneDependencies) {
+        for (type_id, path) in &self.dependencies {
+            dependencies.register_erased(*type_id, path.clone());
+        }
+        for entry in &self.entries {
+            match entry {
+                BsnSceneEntry::CachedScene(cached) => cached.register_dependencies(dependencies),
+                BsnSceneEntry::Related { scenes, .. } => {
+                    for scene in scenes {
+                        scene.register_dependencies(dependencies);
+                    }
+                }
+                BsnSceneEntry::Name(_) | BsnSceneEntry::Component(_) => {}
+            }
+        }
+    }
+}
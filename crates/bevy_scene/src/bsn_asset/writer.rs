@@ -0,0 +1,532 @@
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_asset::ReflectHandle;
+use bevy_ecs::{
+    component::{Component, ComponentInfo},
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    name::Name,
+    reflect::ReflectComponent,
+    world::{EntityRef, World},
+};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_reflect::{
+    enums::VariantType, std_traits::ReflectDefault, PartialReflect, Reflect, ReflectRef,
+    TypeRegistry,
+};
+use core::{any::TypeId, fmt::Write};
+use thiserror::Error;
+
+/// The indentation used for each level of the hierarchy.
+const INDENT: &str = "    ";
+
+/// Writes a live entity hierarchy as `.bsn` text, which can be loaded again using the [`BsnLoader`](crate::BsnLoader).
+///
+/// Each entity is written as a list of its reflected components. Only fields that differ from the component's
+/// [`Default`] value are written (this requires the component to reflect [`Default`]). [`Name`] components are written
+/// as `#Name`, which allows [`Entity`] fields to reference other named entities in the hierarchy. [`Children`] are
+/// written recursively.
+///
+/// Components that are not registered in the [`TypeRegistry`], or that do not reflect [`Component`], are skipped.
+///
+/// ```
+/// # use bevy_scene::BsnWriter;
+/// # use bevy_ecs::{prelude::*, reflect::AppTypeRegistry};
+/// # use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component, Default)]
+/// struct Health {
+///     current: u32,
+///     max: u32,
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// let entity = world.spawn(Health { current: 0, max: 100 }).id();
+///
+/// let type_registry = world.resource::<AppTypeRegistry>().read();
+/// let bsn = BsnWriter::new(&world, &type_registry).write(entity).unwrap();
+/// assert_eq!(bsn, "Health { max: 100 }\n");
+/// ```
+pub struct BsnWriter<'w> {
+    world: &'w World,
+    type_registry: &'w TypeRegistry,
+    denied_components: HashSet<TypeId>,
+}
+
+/// An error that occurs when writing an entity hierarchy using the [`BsnWriter`].
+#[derive(Error, Debug)]
+pub enum BsnWriteError {
+    /// The entity does not exist.
+    #[error("The entity {0} does not exist")]
+    EntityNotFound(Entity),
+    /// The [`Name`] of an entity cannot be written as a `#Name` entry.
+    #[error("The name {name:?} of entity {entity} is not a valid identifier")]
+    InvalidName {
+        /// The named entity.
+        entity: Entity,
+        /// The invalid name.
+        name: String,
+    },
+    /// An [`Entity`] field references an entity that is not a named entity in the written hierarchy.
+    #[error(
+        "{component} references the entity {entity}, which is not a named entity in the hierarchy"
+    )]
+    UnnamedEntityReference {
+        /// The type path of the component containing the reference.
+        component: &'static str,
+        /// The referenced entity.
+        entity: Entity,
+    },
+    /// A [`Handle`](bevy_asset::Handle) field references an asset that does not have a path.
+    #[error("{component} references an asset of type {type_path} that does not have a path")]
+    MissingAssetPath {
+        /// The type path of the component containing the handle.
+        component: &'static str,
+        /// The type path of the handle.
+        type_path: &'static str,
+    },
+    /// A value cannot be written as `.bsn`. Consider denying the component using [`BsnWriter::deny_component`].
+    #[error("{component} contains a value of type {type_path} that cannot be written as BSN")]
+    UnsupportedValue {
+        /// The type path of the component containing the value.
+        component: &'static str,
+        /// The type path of the value.
+        type_path: String,
+    },
+}
+
+impl<'w> BsnWriter<'w> {
+    /// Creates a new [`BsnWriter`] that writes entities from `world`, using `type_registry` to reflect their components.
+    pub fn new(world: &'w World, type_registry: &'w TypeRegistry) -> Self {
+        Self {
+            world,
+            type_registry,
+            denied_components: HashSet::default(),
+        }
+    }
+
+    /// Skips the component `T` when writing entities.
+    #[must_use]
+    pub fn deny_component<T: Component>(mut self) -> Self {
+        self.denied_components.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Writes `entity` and its descendants as `.bsn` text.
+    pub fn write(&self, entity: Entity) -> Result<String, BsnWriteError> {
+        let mut names = HashMap::default();
+        self.collect_names(entity, &mut names)?;
+        let entries = self.write_entity(entity, &names, 0)?;
+        let mut output = entries.join("\n");
+        output.push('\n');
+        Ok(output)
+    }
+
+    fn entity(&self, entity: Entity) -> Result<EntityRef<'w>, BsnWriteError> {
+        self.world
+            .get_entity(entity)
+            .map_err(|_| BsnWriteError::EntityNotFound(entity))
+    }
+
+    /// Collects the names of `entity` and its descendants, which are used to write [`Entity`] references.
+    fn collect_names(
+        &self,
+        entity: Entity,
+        names: &mut HashMap<Entity, String>,
+    ) -> Result<(), BsnWriteError> {
+        let entity_ref = self.entity(entity)?;
+        if let Some(name) = entity_ref.get::<Name>() {
+            if !is_identifier(name.as_str()) {
+                return Err(BsnWriteError::InvalidName {
+                    entity,
+                    name: name.to_string(),
+                });
+            }
+            names.insert(entity, name.to_string());
+        }
+        for child in entity_ref.get::<Children>().into_iter().flatten() {
+            self.collect_names(*child, names)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the entries of `entity`. Each entry is indented for the given `depth`, except for its first line.
+    fn write_entity(
+        &self,
+        entity: Entity,
+        names: &HashMap<Entity, String>,
+        depth: usize,
+    ) -> Result<Vec<String>, BsnWriteError> {
+        let entity_ref = self.entity(entity)?;
+        let mut entries = Vec::new();
+        if let Some(name) = names.get(&entity) {
+            entries.push(format!("#{name}"));
+        }
+
+        for &component_id in entity_ref.archetype().components().iter() {
+            let Some(type_id) = self
+                .world
+                .components()
+                .get_info(component_id)
+                .and_then(ComponentInfo::type_id)
+            else {
+                continue;
+            };
+            if self.denied_components.contains(&type_id)
+                || type_id == TypeId::of::<Name>()
+                || type_id == TypeId::of::<Children>()
+                || type_id == TypeId::of::<ChildOf>()
+            {
+                continue;
+            }
+            let Some(registration) = self.type_registry.get(type_id) else {
+                continue;
+            };
+            let Some(component) = registration
+                .data::<ReflectComponent>()
+                .and_then(|reflect_component| reflect_component.reflect(entity_ref))
+            else {
+                continue;
+            };
+            let default = registration
+                .data::<ReflectDefault>()
+                .map(ReflectDefault::default);
+
+            let type_path_table = registration.type_info().type_path_table();
+            let path = if self
+                .type_registry
+                .is_ambiguous(type_path_table.short_path())
+            {
+                type_path_table.path()
+            } else {
+                type_path_table.short_path()
+            };
+            let mut value_writer = ValueWriter {
+                type_registry: self.type_registry,
+                names,
+                component: type_path_table.path(),
+            };
+            let mut entry = String::new();
+            value_writer.write_type(
+                component.as_partial_reflect(),
+                default.as_deref().map(PartialReflect::as_partial_reflect),
+                path,
+                &mut entry,
+            )?;
+            entries.push(entry);
+        }
+
+        if let Some(children) = entity_ref.get::<Children>() {
+            let indent = INDENT.repeat(depth + 1);
+            let mut entry = String::from("Children [");
+            for child in children {
+                entry.push('\n');
+                entry.push_str(&indent);
+                let child_entries = self.write_entity(*child, names, depth + 2)?;
+                match child_entries.as_slice() {
+                    [child_entry] if !child_entry.contains('\n') => entry.push_str(child_entry),
+                    child_entries => {
+                        entry.push('(');
+                        for child_entry in child_entries {
+                            let _ = write!(entry, "\n{indent}{INDENT}{child_entry}");
+                        }
+                        if !child_entries.is_empty() {
+                            entry.push('\n');
+                            entry.push_str(&indent);
+                        }
+                        entry.push(')');
+                    }
+                }
+                entry.push(',');
+            }
+            let _ = write!(entry, "\n{}]", INDENT.repeat(depth));
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+/// Writes reflected values of a single component.
+struct ValueWriter<'a> {
+    type_registry: &'a TypeRegistry,
+    names: &'a HashMap<Entity, String>,
+    /// The type path of the component being written, for errors.
+    component: &'static str,
+}
+
+impl ValueWriter<'_> {
+    fn unsupported(&self, value: &dyn PartialReflect) -> BsnWriteError {
+        BsnWriteError::UnsupportedValue {
+            component: self.component,
+            type_path: value.reflect_type_path().to_string(),
+        }
+    }
+
+    /// Writes a struct, tuple struct, or enum `value` using the given type `path`. Only fields that differ from
+    /// `default` are written.
+    fn write_type(
+        &mut self,
+        value: &dyn PartialReflect,
+        default: Option<&dyn PartialReflect>,
+        path: &str,
+        output: &mut String,
+    ) -> Result<(), BsnWriteError> {
+        match value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                output.push_str(path);
+                let default = default.and_then(|default| default.reflect_ref().as_struct().ok());
+                let fields = (0..value.field_len()).map(|index| {
+                    let name = value.name_at(index).unwrap();
+                    let default = default.and_then(|default| default.field(name));
+                    (name, value.field_at(index).unwrap(), default)
+                });
+                self.write_named_fields(fields, output)
+            }
+            ReflectRef::TupleStruct(value) => {
+                output.push_str(path);
+                let default =
+                    default.and_then(|default| default.reflect_ref().as_tuple_struct().ok());
+                let fields = (0..value.field_len()).map(|index| {
+                    let default = default.and_then(|default| default.field(index));
+                    (value.field(index).unwrap(), default)
+                });
+                self.write_unnamed_fields(fields, output)
+            }
+            ReflectRef::Enum(value) => {
+                let _ = write!(output, "{path}::{}", value.variant_name());
+                // Fields are only patched if the default value uses the same variant
+                let default = default
+                    .and_then(|default| default.reflect_ref().as_enum().ok())
+                    .filter(|default| default.variant_name() == value.variant_name());
+                match value.variant_type() {
+                    VariantType::Struct => {
+                        let fields = (0..value.field_len()).map(|index| {
+                            let name = value.name_at(index).unwrap();
+                            let default = default.and_then(|default| default.field(name));
+                            (name, value.field_at(index).unwrap(), default)
+                        });
+                        self.write_named_fields(fields, output)
+                    }
+                    VariantType::Tuple => {
+                        let fields = (0..value.field_len()).map(|index| {
+                            let default = default.and_then(|default| default.field_at(index));
+                            (value.field_at(index).unwrap(), default)
+                        });
+                        self.write_unnamed_fields(fields, output)
+                    }
+                    VariantType::Unit => Ok(()),
+                }
+            }
+            _ => Err(self.unsupported(value)),
+        }
+    }
+
+    fn write_named_fields<'v>(
+        &mut self,
+        fields: impl Iterator<
+            Item = (
+                &'v str,
+                &'v dyn PartialReflect,
+                Option<&'v dyn PartialReflect>,
+            ),
+        >,
+        output: &mut String,
+    ) -> Result<(), BsnWriteError> {
+        let mut is_first = true;
+        for (name, value, default) in fields {
+            if is_default(value, default) {
+                continue;
+            }
+            output.push_str(if is_first { " { " } else { ", " });
+            is_first = false;
+            let _ = write!(output, "{name}: ");
+            self.write_value(value, default, output)?;
+        }
+        if !is_first {
+            output.push_str(" }");
+        }
+        Ok(())
+    }
+
+    /// Writes unnamed fields. Because unnamed fields are positional, every field up to the last non-default field is written.
+    fn write_unnamed_fields<'v>(
+        &mut self,
+        fields: impl Iterator<Item = (&'v dyn PartialReflect, Option<&'v dyn PartialReflect>)>,
+        output: &mut String,
+    ) -> Result<(), BsnWriteError> {
+        let fields = fields.collect::<Vec<_>>();
+        let Some(len) = fields
+            .iter()
+            .rposition(|(value, default)| !is_default(*value, *default))
+            .map(|index| index + 1)
+        else {
+            return Ok(());
+        };
+        output.push('(');
+        for (index, (value, default)) in fields[..len].iter().enumerate() {
+            if index > 0 {
+                output.push_str(", ");
+            }
+            self.write_value(*value, *default, output)?;
+        }
+        output.push(')');
+        Ok(())
+    }
+
+    fn write_value(
+        &mut self,
+        value: &dyn PartialReflect,
+        default: Option<&dyn PartialReflect>,
+        output: &mut String,
+    ) -> Result<(), BsnWriteError> {
+        let Some(info) = value.get_represented_type_info() else {
+            return Err(self.unsupported(value));
+        };
+        let type_id = info.type_id();
+
+        if let Some(reflect_handle) = self.type_registry.get_type_data::<ReflectHandle>(type_id) {
+            let path = value
+                .try_as_reflect()
+                .and_then(|value| reflect_handle.downcast_handle_untyped(value.as_any()))
+                .and_then(|handle| handle.path().cloned())
+                .ok_or(BsnWriteError::MissingAssetPath {
+                    component: self.component,
+                    type_path: info.type_path(),
+                })?;
+            write_string(&path.to_string(), output);
+            return Ok(());
+        }
+
+        if let ReflectRef::Opaque(_) = value.reflect_ref() {
+            return self.write_opaque(value, output);
+        }
+
+        match value.reflect_ref() {
+            ReflectRef::Struct(_) | ReflectRef::TupleStruct(_) | ReflectRef::Enum(_) => {
+                let Some(ident) = info.type_path_table().ident() else {
+                    return Err(self.unsupported(value));
+                };
+                self.write_type(value, default, ident, output)
+            }
+            ReflectRef::Tuple(tuple) => {
+                output.push('(');
+                for (index, field) in tuple.iter_fields().enumerate() {
+                    if index > 0 {
+                        output.push_str(", ");
+                    }
+                    self.write_value(field, None, output)?;
+                }
+                if tuple.field_len() == 1 {
+                    output.push(',');
+                }
+                output.push(')');
+                Ok(())
+            }
+            ReflectRef::List(list) => self.write_items(list.iter(), output),
+            ReflectRef::Array(array) => self.write_items(array.iter(), output),
+            _ => Err(self.unsupported(value)),
+        }
+    }
+
+    fn write_items<'v>(
+        &mut self,
+        items: impl Iterator<Item = &'v dyn PartialReflect>,
+        output: &mut String,
+    ) -> Result<(), BsnWriteError> {
+        output.push('[');
+        for (index, item) in items.enumerate() {
+            if index > 0 {
+                output.push_str(", ");
+            }
+            self.write_value(item, None, output)?;
+        }
+        output.push(']');
+        Ok(())
+    }
+
+    fn write_opaque(
+        &mut self,
+        value: &dyn PartialReflect,
+        output: &mut String,
+    ) -> Result<(), BsnWriteError> {
+        let Some(any) = value.try_as_reflect().map(Reflect::as_any) else {
+            return Err(self.unsupported(value));
+        };
+
+        if let Some(entity) = any.downcast_ref::<Entity>() {
+            let name = self
+                .names
+                .get(entity)
+                .ok_or(BsnWriteError::UnnamedEntityReference {
+                    component: self.component,
+                    entity: *entity,
+                })?;
+            let _ = write!(output, "#{name}");
+            return Ok(());
+        }
+
+        macro_rules! write_numbers {
+            ($($ty: ty),*) => {
+                $(
+                    if let Some(value) = any.downcast_ref::<$ty>() {
+                        let _ = write!(output, "{value}");
+                        return Ok(());
+                    }
+                )*
+            };
+        }
+        write_numbers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+        macro_rules! write_floats {
+            ($($ty: ty),*) => {
+                $(
+                    if let Some(value) = any.downcast_ref::<$ty>() {
+                        if !value.is_finite() {
+                            return Err(self.unsupported(value));
+                        }
+                        // Debug formatting always includes a decimal point or an exponent
+                        let _ = write!(output, "{value:?}");
+                        return Ok(());
+                    }
+                )*
+            };
+        }
+        write_floats!(f32, f64);
+
+        if let Some(value) = any.downcast_ref::<bool>() {
+            let _ = write!(output, "{value}");
+        } else if let Some(value) = any.downcast_ref::<char>() {
+            let _ = write!(output, "{value:?}");
+        } else if let Some(value) = any.downcast_ref::<String>() {
+            write_string(value, output);
+        } else if let Some(value) = any.downcast_ref::<Cow<'static, str>>() {
+            write_string(value, output);
+        } else {
+            return Err(self.unsupported(value));
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if `value` is equal to `default`.
+fn is_default(value: &dyn PartialReflect, default: Option<&dyn PartialReflect>) -> bool {
+    default.is_some_and(|default| value.reflect_partial_eq(default) == Some(true))
+}
+
+/// Writes a string literal, escaping it using the same rules as Rust's [`Debug`] formatting.
+fn write_string(value: &str, output: &mut String) {
+    let _ = write!(output, "{value:?}");
+}
+
+/// Returns true if `name` can be written as a `#Name` entry.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
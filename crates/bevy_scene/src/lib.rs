@@ -74,8 +74,8 @@
 //!
 //! ## Core Concepts
 //!
-//! - **[`Scene`]**: Describes what a spawned [`Entity`] should look like, created using [`bsn!`] or,
-//!   in the future, `.bsn` asset files. Conceptually, a [`Scene`] contains a list of "entries" to apply to an [`Entity`].
+//! - **[`Scene`]**: Describes what a spawned [`Entity`] should look like, created using [`bsn!`] or
+//!   `.bsn` asset files. Conceptually, a [`Scene`] contains a list of "entries" to apply to an [`Entity`].
 //! - **[`SceneList`]**: A list of scenes, returned by [`bsn_list!`].
 //!   Each [`Scene`] in the list produces one [`Entity`].
 //! - **Scene Composition**: Composition works by including scenes in other scenes. The included scenes "entries" will be
@@ -385,7 +385,7 @@
 //! ```
 //!
 //! Scene assets always need to be cached using the `:` prefix.
-//! Note that the `.bsn` file format is not yet released. (This already works, assuming theres a loader for the asset format)
+//! `.bsn` assets are loaded by the [`BsnLoader`] (see [.bsn Asset Format](#bsn-asset-format)).
 //! ```ignore
 //! bsn! {
 //!    :"enemy.bsn"
@@ -662,9 +662,6 @@
 //!
 //! ### `SceneComponent` Asset Paths
 //!
-//! Note: Currently, Bevy does not include a `.bsn` asset format. These docs exist to help you understand what is planned, and what is currently possible
-//! with third-party asset formats.
-//!
 //! Alternatively, a scene asset path can be specified:
 //!
 //! ```
@@ -862,24 +859,34 @@
 //!
 //! ## .bsn Asset Format
 //!
-//! Bevy does not currently have support for `.bsn` files,
-//! but intends to offer a `.bsn` asset format in future releases.
+//! [`ScenePlugin`] registers the [`BsnLoader`], which loads `.bsn` files as [`ScenePatch`] assets.
+//! This allows you to define your scenes on disk, creating/modifying them in various authoring tools.
 //!
-//! This would allow you to define your scenes on disk,
-//! creating/modifying them in various authoring tools and using asset hot-reloading.
+//! The format uses the same notation as the `bsn!` macro, making it easy to port your content between both the macro and the asset form:
 //!
-//! This format is intended to have broad syntactic compatibility with the `bsn!` macro,
-//! making it easy to port your content between both the macro and the asset form.
+//! ```text
+//! :"enemy.bsn"
+//! Health { max: 200 }
+//! Visibility::Hidden
+//! Children [
+//!     (#Weapon Sword { owner: #Player }),
+//!     Sprite("player.png"),
+//! ]
+//! ```
 //!
-//! When planning your future use of `.bsn` asset files (which are not currently shipped), be aware that
-//! unlike `bsn!` macro calls `.bsn` assets will not support expressions or other dynamic features directly.
+//! Unlike `bsn!` macro calls, `.bsn` assets are resolved at runtime using the [`AppTypeRegistry`]:
+//! - Components must be registered and reflect [`Component`](bevy_ecs::reflect::ReflectComponent), as well as [`Default`] or `FromReflect`.
+//!   They can be referred to by their short type path (`Health`), or by their full type path if the short path is ambiguous.
+//! - Relationship targets (such as [`Children`]) must reflect [`RelatedScenes`](ReflectRelatedScenes) to spawn related entities.
+//! - [`Handle`] fields accept asset paths, as long as the asset type was registered with `register_asset_reflect`.
+//!   These are registered as dependencies of the [`ScenePatch`].
+//! - Expressions, function calls, constants, closures, template patches (`~`), and scene components (`@`) are not supported.
 //!
-//! For now, you should use existing non-Bevy asset formats like glTF,
-//! search for ecosystem implementations or stick to `bsn!` macro calls.
+//! A spawned entity hierarchy can be written back to `.bsn` text using the [`BsnWriter`], which allows tools such as
+//! in-game editors to save scenes in the same format.
 //!
-//! Note that the architecture to support an asset format already exists,
-//! allowing community implementations/experimentation until an official version exists. An example of how to go about this
-//! can be found in the [scene benchmarks](<https://github.com/bevyengine/bevy/blob/v0.19.0/benches/benches/bevy_scene/spawn.rs#L414>)
+//! [`AppTypeRegistry`]: bevy_ecs::reflect::AppTypeRegistry
+//! [`Handle`]: bevy_asset::Handle
 //!
 //! [`Template`]: bevy_ecs::template::Template
 //! [`FromTemplate`]: bevy_ecs::template::FromTemplate
@@ -910,17 +917,23 @@ pub mod macro_utils;
 
 extern crate alloc;
 
+mod bsn_asset;
+mod reflect;
 mod resolved_scene;
 mod scene;
 mod scene_component;
+mod scene_diff;
 mod scene_list;
 mod scene_patch;
 mod spawn;
 mod spawn_system;
 
+pub use bsn_asset::*;
+pub use reflect::*;
 pub use resolved_scene::*;
 pub use scene::*;
 pub use scene_component::*;
+pub use scene_diff::*;
 pub use scene_list::*;
 pub use scene_patch::*;
 pub use spawn::*;
@@ -946,6 +959,9 @@ impl Plugin for ScenePlugin {
             .init_resource::<WaitingScenes>()
             .init_asset::<ScenePatch>()
             .init_asset::<SceneListPatch>()
+            .init_asset_loader::<BsnLoader>()
+            .register_type::<Children>()
+            .register_type_data::<Children, ReflectRelatedScenes>()
             .add_systems(
                 SpawnScene,
                 (resolve_scene_patches, spawn_queued)
@@ -968,7 +984,11 @@ pub struct Ready {
 #[cfg(test)]
 mod tests {
     use crate::{self as bevy_scene, Ready, ScenePlugin};
-    use crate::{prelude::*, ScenePatch};
+    use crate::{
+        apply_scene_patch_instance, prelude::*, AppliedScenePatch, BsnLoaderError, BsnLocation,
+        BsnScene, BsnTypeError, BsnWriteError, BsnWriter, ComponentChange, RelatedSceneChange,
+        ResolvedScene, ResolvedSceneRoot, SceneDependencies, SceneMergeConflict, ScenePatch,
+    };
     use alloc::sync::Arc;
     use bevy_app::{App, TaskPoolPlugin};
     use bevy_asset::io::memory::{Dir, MemoryAssetReader};
@@ -980,8 +1000,9 @@ mod tests {
     use bevy_ecs::relationship::Relationship;
     use bevy_ecs::system::{system_value, SystemHandle};
     use bevy_ecs::world::DeferredWorld;
-    use bevy_reflect::TypePath;
+    use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
     use bevy_scene_macros::SceneComponent;
+    use core::any::TypeId;
     use std::path::Path;
     use std::sync::Mutex;
 
@@ -1099,7 +1120,7 @@ mod tests {
 
         fn b() -> impl Scene {
             bsn! {
-                :"a.bsn"
+                :"a.fake_bsn"
                 Position { x: 1. }
                 Children [ #Y ]
             }
@@ -1113,7 +1134,7 @@ mod tests {
         }
 
         #[derive(SceneComponent, Default, Clone)]
-        #[scene("a.bsn")]
+        #[scene("a.fake_bsn")]
         struct AWidget {
             value: usize,
         }
@@ -1141,9 +1162,9 @@ mod tests {
         app.register_asset_loader(FakeSceneLoader::new(a));
 
         // Insert an asset that the fake loader can fake read.
-        dir.insert_asset_text(Path::new("a.bsn"), "");
+        dir.insert_asset_text(Path::new("a.fake_bsn"), "");
         let asset_server = app.world().resource::<AssetServer>().clone();
-        let handle = asset_server.load("a.bsn");
+        let handle = asset_server.load("a.fake_bsn");
         assert!(app.world().get_resource::<Assets<ScenePatch>>().is_some());
         run_app_until(&mut app, || asset_server.is_loaded(&handle));
         let patch = app
@@ -1173,7 +1194,7 @@ mod tests {
         let name = y.get::<Name>().unwrap();
         assert_eq!(name.as_str(), "Y");
 
-        // "a.bsn" as AWidget's "component scene"
+        // "a.fake_bsn" as AWidget's "component scene"
         let id = world
             .spawn_scene(bsn! {@AWidget { value: 2 }})
             .unwrap()
@@ -2952,7 +2973,7 @@ mod tests {
         fn root() -> impl Scene {
             bsn! {
                 Foo(0)
-                Children [ :"child.bsn" ]
+                Children [ :"child.fake_bsn" ]
             }
         }
 
@@ -2997,9 +3018,9 @@ mod tests {
         app.register_asset_loader(FakeSceneLoader::new(child));
 
         // Insert an asset that the fake loader can fake read.
-        dir.insert_asset_text(Path::new("child.bsn"), "");
+        dir.insert_asset_text(Path::new("child.fake_bsn"), "");
         let asset_server = app.world().resource::<AssetServer>().clone();
-        let handle = asset_server.load("child.bsn");
+        let handle = asset_server.load("child.fake_bsn");
         assert!(app.world().get_resource::<Assets<ScenePatch>>().is_some());
         run_app_until(&mut app, || asset_server.is_loaded(&handle));
         let patch = app
@@ -3035,6 +3056,511 @@ mod tests {
         );
     }
 
+    #[derive(Component, Reflect, Default, Clone, PartialEq, Debug)]
+    #[reflect(Component, Default)]
+    struct BsnPosition {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component, Default)]
+    enum BsnTeam {
+        #[default]
+        Red,
+        Blue {
+            size: u32,
+        },
+    }
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct BsnTarget(Entity);
+
+    fn bsn_asset_app() -> (App, Dir) {
+        let mut app = App::new();
+        let dir = Dir::default();
+        let dir_clone = dir.clone();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || {
+                Box::new(MemoryAssetReader {
+                    root: dir_clone.clone(),
+                })
+            }),
+        );
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            ScenePlugin,
+        ))
+        .register_type::<BsnPosition>()
+        .register_type::<BsnTeam>()
+        .register_type::<BsnTarget>();
+        app.finish();
+        app.cleanup();
+        (app, dir)
+    }
+
+    #[test]
+    fn bsn_asset() {
+        let (mut app, dir) = bsn_asset_app();
+        dir.insert_asset_text(
+            Path::new("a.bsn"),
+            r#"
+            // Components are resolved using the AppTypeRegistry
+            BsnPosition { x: 1.5 }
+            BsnTeam::Blue { size: 3 }
+            Children [
+                (#A BsnTarget(#B)),
+                #B bevy_scene::tests::BsnPosition { y: -2 },
+            ]
+            "#,
+        );
+        dir.insert_asset_text(
+            Path::new("b.bsn"),
+            r#"
+            :"a.bsn"
+            BsnPosition { y: 4 }
+            "#,
+        );
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a = asset_server.load::<ScenePatch>("a.bsn");
+        let b = asset_server.load::<ScenePatch>("b.bsn");
+        run_app_until(&mut app, || {
+            asset_server.is_loaded_with_dependencies(&a)
+                && asset_server.is_loaded_with_dependencies(&b)
+        });
+
+        let world = app.world_mut();
+        let id = world.spawn_scene(bsn! { :"a.bsn" }).unwrap().id();
+        let root = world.entity(id);
+        assert_eq!(
+            root.get::<BsnPosition>(),
+            Some(&BsnPosition { x: 1.5, y: 0. })
+        );
+        assert_eq!(root.get::<BsnTeam>(), Some(&BsnTeam::Blue { size: 3 }));
+
+        let children = root.get::<Children>().unwrap();
+        assert_eq!(children.len(), 2);
+        let a = world.entity(children[0]);
+        let b = world.entity(children[1]);
+        assert_eq!(a.get::<Name>().unwrap().as_str(), "A");
+        assert_eq!(a.get::<BsnTarget>().unwrap().0, b.id());
+        assert_eq!(b.get::<Name>().unwrap().as_str(), "B");
+        assert_eq!(b.get::<BsnPosition>(), Some(&BsnPosition { x: 0., y: -2. }));
+
+        // Reflected patches from a cached .bsn asset are applied before the patches of the inheriting scene
+        let id = world.spawn_scene(bsn! { :"b.bsn" }).unwrap().id();
+        assert_eq!(
+            world.entity(id).get::<BsnPosition>(),
+            Some(&BsnPosition { x: 1.5, y: 4. })
+        );
+
+        // Patches from bsn! can be applied on top of reflected patches
+        let id = world
+            .spawn_scene(bsn! { :"a.bsn" BsnPosition { y: 3. } })
+            .unwrap()
+            .id();
+        assert_eq!(
+            world.entity(id).get::<BsnPosition>(),
+            Some(&BsnPosition { x: 1.5, y: 3. })
+        );
+    }
+
+    #[test]
+    fn bsn_asset_handles() {
+        #[derive(Asset, Reflect)]
+        struct Image;
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component, Default)]
+        struct Sprite(Handle<Image>);
+
+        let (mut app, _dir) = bsn_asset_app();
+        app.init_asset::<Image>()
+            .register_asset_reflect::<Image>()
+            .register_type::<Sprite>();
+
+        let mut asset_server = app.world().resource::<AssetServer>().clone();
+        let scene = {
+            let registry = app.world().resource::<AppTypeRegistry>().read();
+            BsnScene::parse(r#"Sprite("image.png")"#, &registry, &mut asset_server).unwrap()
+        };
+
+        let mut dependencies = SceneDependencies::default();
+        scene.register_dependencies(&mut dependencies);
+        let dependencies = dependencies.iter().collect::<Vec<_>>();
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].path, "image.png".into());
+
+        let handle = asset_server.load::<Image>("image.png");
+        let world = app.world_mut();
+        let id = world.spawn_scene(scene).unwrap().id();
+        assert_eq!(world.entity(id).get::<Sprite>().unwrap().0, handle);
+    }
+
+    #[test]
+    fn apply_scene_patch_instance_immediately() {
+        let (mut app, dir) = bsn_asset_app();
+        dir.insert_asset_text(
+            Path::new("a.bsn"),
+            r#"
+            BsnPosition { x: 1 }
+            Children [ #A, #B ]
+            "#,
+        );
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle = asset_server.load::<ScenePatch>("a.bsn");
+        let pending = app
+            .world_mut()
+            .spawn(ScenePatchInstance(handle.clone()))
+            .id();
+        assert!(!apply_scene_patch_instance(app.world_mut(), pending).unwrap());
+        for _ in 0..10000 {
+            app.update();
+            if app.world().entity(pending).contains::<AppliedScenePatch>() {
+                break;
+            }
+        }
+
+        // The scene is resolved, so it is applied without running `SpawnScene`
+        let world = app.world_mut();
+        let root = world.spawn(ScenePatchInstance(handle)).id();
+        assert!(apply_scene_patch_instance(world, root).unwrap());
+        let children = world.entity(root).get::<Children>().unwrap().to_vec();
+        assert_eq!(children.len(), 2);
+
+        // ... and not applied a second time by `spawn_queued`
+        app.update();
+        let entity = app.world().entity(root);
+        assert!(entity.contains::<AppliedScenePatch>());
+        assert_eq!(entity.get::<Children>().unwrap().to_vec(), children);
+    }
+
+    #[test]
+    fn bsn_asset_reload() {
+        #[derive(Component)]
+        struct RuntimeOnly;
+
+        fn update_until(app: &mut App, predicate: impl Fn(&World) -> bool) {
+            for _ in 0..10000 {
+                app.update();
+                if predicate(app.world()) {
+                    return;
+                }
+            }
+            panic!("Ran out of loops to return `true` from `predicate`");
+        }
+
+        let (mut app, dir) = bsn_asset_app();
+        dir.insert_asset_text(
+            Path::new("a.bsn"),
+            r#"
+            BsnPosition { x: 1 }
+            BsnTeam::Blue { size: 3 }
+            Children [
+                (#A BsnPosition { x: 1 }),
+                #B,
+            ]
+            "#,
+        );
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle = asset_server.load::<ScenePatch>("a.bsn");
+        let root = app.world_mut().spawn(ScenePatchInstance(handle)).id();
+        update_until(&mut app, |world| {
+            world.entity(root).contains::<AppliedScenePatch>()
+        });
+
+        let world = app.world_mut();
+        let children = world.entity(root).get::<Children>().unwrap().to_vec();
+        assert_eq!(children.len(), 2);
+        let (a, b) = (children[0], children[1]);
+        world.entity_mut(root).insert(RuntimeOnly);
+        let runtime_child = world.spawn(ChildOf(root)).id();
+
+        // Matching entities are updated in place, removed components and entities are removed
+        dir.insert_asset_text(
+            Path::new("a.bsn"),
+            r#"
+            BsnPosition { x: 2 }
+            Children [
+                (#A BsnPosition { x: 5 }),
+            ]
+            "#,
+        );
+        asset_server.reload("a.bsn");
+        update_until(&mut app, |world| {
+            world.entity(root).get::<BsnPosition>() == Some(&BsnPosition { x: 2., y: 0. })
+        });
+
+        let world = app.world_mut();
+        let entity = world.entity(root);
+        assert!(!entity.contains::<BsnTeam>());
+        assert!(entity.contains::<RuntimeOnly>());
+        assert_eq!(
+            entity.get::<Children>().unwrap().to_vec(),
+            vec![a, runtime_child]
+        );
+        assert_eq!(
+            world.entity(a).get::<BsnPosition>(),
+            Some(&BsnPosition { x: 5., y: 0. })
+        );
+        assert!(world.get_entity(b).is_err());
+
+        // New related scenes are spawned
+        dir.insert_asset_text(
+            Path::new("a.bsn"),
+            r#"
+            BsnPosition { x: 3 }
+            Children [
+                #A,
+                #C,
+            ]
+            "#,
+        );
+        asset_server.reload("a.bsn");
+        update_until(&mut app, |world| {
+            world.entity(root).get::<BsnPosition>() == Some(&BsnPosition { x: 3., y: 0. })
+        });
+
+        let world = app.world();
+        let children = world.entity(root).get::<Children>().unwrap();
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[0], a);
+        assert!(!world.entity(a).contains::<BsnPosition>());
+        let c = children
+            .iter()
+            .find(|child| *child != a && *child != runtime_child)
+            .unwrap();
+        assert_eq!(world.entity(c).get::<Name>().unwrap().as_str(), "C");
+    }
+
+    #[test]
+    fn bsn_asset_errors() {
+        let (app, _dir) = bsn_asset_app();
+        let mut asset_server = app.world().resource::<AssetServer>().clone();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let mut parse = |text| BsnScene::parse(text, &registry, &mut asset_server).err();
+
+        assert!(matches!(
+            parse("BsnPosition Missing"),
+            Some(BsnLoaderError::Type {
+                location: BsnLocation {
+                    line: 1,
+                    column: 13
+                },
+                error: BsnTypeError::UnknownType(_),
+            })
+        ));
+        assert!(matches!(
+            parse("BsnPosition { z: 1 }"),
+            Some(BsnLoaderError::Type {
+                error: BsnTypeError::UnknownField { .. },
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse("BsnPosition { x: \"1\" }"),
+            Some(BsnLoaderError::Type {
+                error: BsnTypeError::MismatchedValue { .. },
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse("BsnTeam::Green"),
+            Some(BsnLoaderError::Type {
+                error: BsnTypeError::UnknownVariant { .. },
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse("BsnPosition Children [ BsnTarget(1) ]"),
+            Some(BsnLoaderError::Type {
+                error: BsnTypeError::MismatchedValue { .. },
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse("BsnPosition {"),
+            Some(BsnLoaderError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn resolved_scene_diff_and_merge() {
+        #[derive(Component, Reflect, Default, Clone, PartialEq, Debug)]
+        #[reflect(Component, Default)]
+        struct Speed(f32);
+
+        let (mut app, _dir) = bsn_asset_app();
+        app.register_type::<Speed>();
+        let world = app.world_mut();
+        let resolve = |world: &World, scene: Box<dyn Scene>| {
+            let assets = world.resource::<AssetServer>();
+            let patches = world.resource::<Assets<ScenePatch>>();
+            ResolvedSceneRoot::resolve(scene, assets, patches)
+                .unwrap()
+                .scene
+        };
+
+        let base = resolve(
+            world,
+            Box::new(bsn! {
+                BsnPosition { x: 1. }
+                Children [ BsnPosition { x: 1. }, BsnPosition { x: 2. } ]
+            }),
+        );
+        let ours = resolve(
+            world,
+            Box::new(bsn! {
+                BsnPosition { x: 2. }
+                Children [ BsnPosition { x: 1. }, BsnPosition { x: 2. }, BsnPosition { x: 3. } ]
+            }),
+        );
+        let theirs = resolve(
+            world,
+            Box::new(bsn! {
+                BsnPosition { x: 1. }
+                Speed(2.)
+                Children [ BsnPosition { x: 1. }, BsnPosition { x: 2., y: 5. } ]
+            }),
+        );
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        assert!(base.diff(&base, &registry).is_empty());
+
+        let diff = base.diff(&ours, &registry);
+        assert_eq!(diff.components.len(), 1);
+        assert_eq!(diff.components[0].type_id, TypeId::of::<BsnPosition>());
+        assert_eq!(diff.components[0].change, ComponentChange::Changed);
+        assert_eq!(diff.related.len(), 1);
+        assert_eq!(diff.related[0].position.index, 2);
+        assert_eq!(diff.related[0].change, RelatedSceneChange::Added);
+
+        let diff = base.diff(&theirs, &registry);
+        assert_eq!(diff.components.len(), 1);
+        assert_eq!(diff.components[0].type_id, TypeId::of::<Speed>());
+        assert_eq!(diff.components[0].change, ComponentChange::Added);
+        assert_eq!(diff.related.len(), 1);
+        assert_eq!(diff.related[0].position.index, 1);
+        let RelatedSceneChange::Changed(child_diff) = &diff.related[0].change else {
+            panic!("expected the second child to change");
+        };
+        assert_eq!(child_diff.components[0].change, ComponentChange::Changed);
+
+        // Changes from both sides are merged
+        let merge = ResolvedScene::merge(&base, &ours, &theirs, &registry);
+        assert!(merge.conflicts.is_empty());
+        drop(registry);
+        let merged = ResolvedSceneRoot { scene: merge.scene };
+        let root = merged.spawn(world).unwrap().id();
+        let entity = world.entity(root);
+        assert_eq!(
+            entity.get::<BsnPosition>(),
+            Some(&BsnPosition { x: 2., y: 0. })
+        );
+        assert_eq!(entity.get::<Speed>(), Some(&Speed(2.)));
+        let positions = entity
+            .get::<Children>()
+            .unwrap()
+            .iter()
+            .map(|child| world.entity(child).get::<BsnPosition>().unwrap().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                BsnPosition { x: 1., y: 0. },
+                BsnPosition { x: 2., y: 5. },
+                BsnPosition { x: 3., y: 0. },
+            ]
+        );
+
+        // Conflicting changes keep "ours"
+        let conflicting = resolve(
+            world,
+            Box::new(bsn! {
+                BsnPosition { x: 3. }
+                Children [ BsnPosition { x: 1. }, BsnPosition { x: 2. } ]
+            }),
+        );
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let merge = ResolvedScene::merge(&base, &ours, &conflicting, &registry);
+        assert_eq!(
+            merge.conflicts,
+            vec![SceneMergeConflict::Component {
+                path: Vec::new(),
+                type_id: TypeId::of::<BsnPosition>(),
+                type_path: Some(BsnPosition::type_path()),
+            }]
+        );
+        assert!(merge.scene.diff(&ours, &registry).is_empty());
+    }
+
+    #[test]
+    fn bsn_writer() {
+        let (mut app, _dir) = bsn_asset_app();
+        let world = app.world_mut();
+        let root = world
+            .spawn((
+                Name::new("Root"),
+                BsnPosition { x: 1.5, y: 0. },
+                BsnTeam::Blue { size: 3 },
+            ))
+            .id();
+        let b = world
+            .spawn((Name::new("B"), BsnPosition { x: 0., y: -2. }, ChildOf(root)))
+            .id();
+        world.spawn((Name::new("A"), BsnTarget(b), ChildOf(root)));
+        world.spawn((BsnTeam::Red, ChildOf(root)));
+        world.spawn(ChildOf(root));
+
+        let expected = r#"#Root
+BsnPosition { x: 1.5 }
+BsnTeam::Blue { size: 3 }
+Children [
+    (
+        #B
+        BsnPosition { y: -2.0 }
+    ),
+    (
+        #A
+        BsnTarget(#B)
+    ),
+    BsnTeam::Red,
+    (),
+]
+"#;
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let bsn = BsnWriter::new(world, &type_registry).write(root).unwrap();
+        assert_eq!(bsn, expected);
+
+        // The written text can be loaded again
+        let mut asset_server = world.resource::<AssetServer>().clone();
+        let scene = BsnScene::parse(&bsn, &type_registry, &mut asset_server).unwrap();
+        let id = world.spawn_scene(scene).unwrap().id();
+        let bsn = BsnWriter::new(world, &type_registry).write(id).unwrap();
+        assert_eq!(bsn, expected);
+
+        let bsn = BsnWriter::new(world, &type_registry)
+            .deny_component::<BsnPosition>()
+            .deny_component::<BsnTeam>()
+            .deny_component::<BsnTarget>()
+            .write(b)
+            .unwrap();
+        assert_eq!(bsn, "#B\n");
+
+        assert!(matches!(
+            BsnWriter::new(world, &type_registry).write(world.entity(root).get::<Children>().unwrap()[1]),
+            Err(BsnWriteError::UnnamedEntityReference { entity, .. }) if entity == b
+        ));
+    }
+
     #[derive(TypePath)]
     struct FakeSceneLoader(Box<dyn Fn() -> Box<dyn Scene> + Send + Sync + 'static>);
 
@@ -3049,6 +3575,10 @@ mod tests {
         type Error = std::io::Error;
         type Settings = ();
 
+        fn extensions(&self) -> &[&str] {
+            &["fake_bsn"]
+        }
+
         async fn load(
             &self,
             _reader: &mut dyn bevy_asset::io::Reader,
@@ -0,0 +1,422 @@
+use crate::{
+    ErasedComponentTemplate, RelatedResolvedScenes, ResolveContext, ResolveSceneError,
+    ResolvedScene, Scene,
+};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use bevy_ecs::{
+    bundle::BundleWriter,
+    error::BevyError,
+    ptr::{OwningPtr, Ptr},
+    reflect::ReflectComponent,
+    relationship::RelationshipTarget,
+    template::{SceneEntityReference, TemplateContext},
+};
+use bevy_reflect::{
+    std_traits::ReflectDefault, ApplyError, CreateTypeData, GetPath, ParsedPath, PartialReflect,
+    Reflect, ReflectCloneError, ReflectFromPtr, ReflectFromReflect, TypeInfo, TypePath,
+    TypeRegistration,
+};
+use core::{
+    alloc::Layout,
+    any::{Any, TypeId},
+    ptr::NonNull,
+};
+use thiserror::Error;
+
+/// The reflection type data required to build a [`Component`] from reflected patches.
+///
+/// This is used by [`ReflectedComponentTemplate`] and [`ReflectedComponentScene`] to build component values when the
+/// concrete type is not known at compile time (ex: when loading `.bsn` assets).
+///
+/// [`Component`]: bevy_ecs::component::Component
+#[derive(Clone)]
+pub struct ReflectedComponent {
+    type_info: &'static TypeInfo,
+    reflect_component: ReflectComponent,
+    reflect_default: Option<ReflectDefault>,
+    reflect_from_reflect: Option<ReflectFromReflect>,
+    reflect_from_ptr: Option<ReflectFromPtr>,
+}
+
+impl ReflectedComponent {
+    /// Creates a new [`ReflectedComponent`] from the given [`TypeRegistration`].
+    ///
+    /// This will fail if the type does not have [`ReflectComponent`] type data, or if it has neither [`ReflectDefault`] nor
+    /// [`ReflectFromReflect`] type data (which are required to construct the component).
+    pub fn from_registration(
+        registration: &TypeRegistration,
+    ) -> Result<Self, ReflectedTemplateError> {
+        let type_info = registration.type_info();
+        let reflect_component = registration
+            .data::<ReflectComponent>()
+            .ok_or_else(|| ReflectedTemplateError::NotAComponent {
+                type_path: type_info.type_path(),
+            })?
+            .clone();
+        let reflect_default = registration.data::<ReflectDefault>().cloned();
+        let reflect_from_reflect = registration.data::<ReflectFromReflect>().cloned();
+        if reflect_default.is_none() && reflect_from_reflect.is_none() {
+            return Err(ReflectedTemplateError::NotConstructible {
+                type_path: type_info.type_path(),
+            });
+        }
+        Ok(Self {
+            type_info,
+            reflect_component,
+            reflect_default,
+            reflect_from_reflect,
+            reflect_from_ptr: registration.data::<ReflectFromPtr>().cloned(),
+        })
+    }
+
+    /// The [`TypeId`] of the component.
+    pub fn type_id(&self) -> TypeId {
+        self.type_info.type_id()
+    }
+
+    /// The [`TypeInfo`] of the component.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// Returns a dynamic copy of the given `template`, which must be a concrete instance of this component
+    /// (ex: a [`Default`] + [`Clone`] component used as its own template).
+    pub(crate) fn template_value(
+        &self,
+        template: &dyn ErasedComponentTemplate,
+    ) -> Result<Box<dyn PartialReflect>, ReflectedTemplateError> {
+        let type_path = self.type_info.type_path();
+        let Some(reflect_from_ptr) = &self.reflect_from_ptr else {
+            return Err(ReflectedTemplateError::IncompatibleTemplate { type_path });
+        };
+        if (template as &dyn Any).type_id() != self.type_id() {
+            return Err(ReflectedTemplateError::IncompatibleTemplate { type_path });
+        }
+        let ptr = NonNull::from(template).cast::<u8>();
+        // SAFETY: the concrete type of `template` was verified to match the component type above,
+        // and `reflect_from_ptr` was registered for that same type.
+        let value = unsafe { reflect_from_ptr.as_reflect(Ptr::new(ptr)) };
+        value
+            .to_dynamic()
+            .map_err(|error| ReflectedTemplateError::Clone { type_path, error })
+    }
+}
+
+/// A reflected "patch" that can be applied on top of a component value. This is generally a dynamic type, such as
+/// [`DynamicStruct`](bevy_reflect::structs::DynamicStruct), which only contains the fields that should be changed.
+pub struct ReflectedPatch {
+    /// The reflected value to apply.
+    pub value: Box<dyn PartialReflect>,
+    /// Fields that should be set to scene entities when the patch is applied. Each path is relative to the component
+    /// and must point to an [`Entity`](bevy_ecs::entity::Entity) field.
+    pub entities: Vec<(ParsedPath, SceneEntityReference)>,
+}
+
+/// An [`ErasedComponentTemplate`] that builds a [`Component`] using reflection, by applying a list of [`ReflectedPatch`]es
+/// on top of the component's reflected [`Default`] value.
+///
+/// This is used by scenes whose component types are not known at compile time, such as `.bsn` assets.
+///
+/// [`Component`]: bevy_ecs::component::Component
+pub struct ReflectedComponentTemplate {
+    component: ReflectedComponent,
+    patches: Vec<Arc<ReflectedPatch>>,
+}
+
+impl ReflectedComponentTemplate {
+    /// Creates a new [`ReflectedComponentTemplate`] with no patches.
+    pub fn new(component: ReflectedComponent) -> Self {
+        Self {
+            component,
+            patches: Vec::new(),
+        }
+    }
+
+    /// The component this template builds.
+    pub fn component(&self) -> &ReflectedComponent {
+        &self.component
+    }
+
+    /// Adds the given `patch` to the back of this template. It will be applied after all previous patches.
+    pub fn push_patch(&mut self, patch: ReflectedPatch) {
+        self.patches.push(Arc::new(patch));
+    }
+
+    /// Builds the component value. If `context` is [`None`], building will fail if any patch references an entity.
+    pub fn build(
+        &self,
+        context: Option<&mut TemplateContext>,
+    ) -> Result<Box<dyn Reflect>, ReflectedTemplateError> {
+        self.build_with(context, false)
+    }
+
+    /// Builds the component value, leaving entity fields set by patches at their previous values.
+    pub(crate) fn build_ignoring_entities(
+        &self,
+    ) -> Result<Box<dyn Reflect>, ReflectedTemplateError> {
+        self.build_with(None, true)
+    }
+
+    /// The paths of the entity fields set by this template's patches, in the order they are set.
+    pub(crate) fn entity_paths(&self) -> impl Iterator<Item = &ParsedPath> {
+        self.patches
+            .iter()
+            .flat_map(|patch| patch.entities.iter().map(|(path, _)| path))
+    }
+
+    fn build_with(
+        &self,
+        mut context: Option<&mut TemplateContext>,
+        ignore_entities: bool,
+    ) -> Result<Box<dyn Reflect>, ReflectedTemplateError> {
+        let type_path = self.component.type_info.type_path();
+        let mut value = self
+            .component
+            .reflect_default
+            .as_ref()
+            .map(ReflectDefault::default);
+        for patch in &self.patches {
+            let value = match &mut value {
+                Some(value) => {
+                    value
+                        .try_apply(&*patch.value)
+                        .map_err(|error| ReflectedTemplateError::Apply { type_path, error })?;
+                    value
+                }
+                None => value.insert(
+                    self.component
+                        .reflect_from_reflect
+                        .as_ref()
+                        .and_then(|from_reflect| from_reflect.from_reflect(&*patch.value))
+                        .ok_or(ReflectedTemplateError::NotConstructible { type_path })?,
+                ),
+            };
+            if ignore_entities {
+                continue;
+            }
+            for (path, reference) in &patch.entities {
+                let Some(context) = context.as_deref_mut() else {
+                    return Err(ReflectedTemplateError::UnresolvedEntity { type_path });
+                };
+                let entity = context.get_entity(*reference);
+                value
+                    .reflect_path_mut(path)
+                    .map_err(|error| ReflectedTemplateError::EntityPath {
+                        type_path,
+                        path: path.to_string(),
+                        error: error.to_string(),
+                    })?
+                    .try_apply(&entity)
+                    .map_err(|error| ReflectedTemplateError::Apply { type_path, error })?;
+            }
+        }
+        let value = value.ok_or(ReflectedTemplateError::NotConstructible { type_path })?;
+        if value.as_any().type_id() != self.component.type_id() {
+            return Err(ReflectedTemplateError::MismatchedType {
+                expected: type_path,
+                found: value.reflect_type_path().into(),
+            });
+        }
+        Ok(value)
+    }
+
+    /// Builds the component value as the concrete type `T`. This returns [`None`] if `T` is not the component type, or
+    /// if building fails (ex: because a patch references an entity).
+    pub(crate) fn build_concrete<T: 'static>(&self) -> Option<T> {
+        self.build(None)
+            .ok()?
+            .into_any()
+            .downcast::<T>()
+            .ok()
+            .map(|value| *value)
+    }
+}
+
+impl ErasedComponentTemplate for ReflectedComponentTemplate {
+    unsafe fn apply(
+        &self,
+        context: &mut TemplateContext,
+        bundle_writer: &mut BundleWriter,
+    ) -> Result<(), BevyError> {
+        let value = self.build(Some(context))?;
+        let component_id = self
+            .component
+            .reflect_component
+            // SAFETY: World is only used for component registration, which does not affect the entity location
+            .register_component(unsafe { context.entity.world_mut() });
+        let layout = Layout::for_value::<dyn Reflect>(&*value);
+        let ptr = Box::into_raw(value).cast::<u8>();
+        // SAFETY:
+        // - `ptr` comes from a Box, so it is non-null and points to an initialized value with the given layout.
+        // - `build` verified that the value is an instance of the component type registered as `component_id`.
+        // - The caller verifies that `bundle_writer` is always used with the same World.
+        unsafe {
+            bundle_writer.push_component_by_id(
+                component_id,
+                OwningPtr::new(NonNull::new_unchecked(ptr)),
+                layout,
+            );
+        }
+        if layout.size() != 0 {
+            // SAFETY: The value has been moved into `bundle_writer`, so the allocation is freed without dropping the value.
+            // Zero-sized values are not backed by an allocation.
+            unsafe { alloc::alloc::dealloc(ptr, layout) };
+        }
+        Ok(())
+    }
+
+    fn clone_template(&self) -> Box<dyn ErasedComponentTemplate> {
+        Box::new(ReflectedComponentTemplate {
+            component: self.component.clone(),
+            patches: self.patches.clone(),
+        })
+    }
+
+    fn template_type_id(&self) -> TypeId {
+        self.component.type_id()
+    }
+}
+
+/// A [`Scene`] that patches a component using reflection.
+///
+/// If the [`ResolvedScene`] already has a [`ReflectedComponentTemplate`] for the component, the patch will be added to it.
+/// If it has a concrete template for the component (ex: a [`Default`] + [`Clone`] component added by the [`bsn!`] macro),
+/// that template is replaced by a [`ReflectedComponentTemplate`] that starts from its current value.
+///
+/// [`bsn!`]: crate::bsn
+pub struct ReflectedComponentScene {
+    /// The component to patch.
+    pub component: ReflectedComponent,
+    /// The patch to apply.
+    pub patch: ReflectedPatch,
+}
+
+impl Scene for ReflectedComponentScene {
+    fn resolve(
+        self,
+        context: &mut ResolveContext,
+        scene: &mut ResolvedScene,
+    ) -> Result<(), ResolveSceneError> {
+        let type_id = self.component.type_id();
+        let template = scene.get_or_insert_erased_template(context, type_id, || {
+            Box::new(ReflectedComponentTemplate::new(self.component.clone()))
+        });
+        let base = match (template as &mut dyn Any).downcast_mut::<ReflectedComponentTemplate>() {
+            Some(reflected) => {
+                reflected.push_patch(self.patch);
+                return Ok(());
+            }
+            None => self.component.template_value(template)?,
+        };
+        let mut reflected = ReflectedComponentTemplate::new(self.component);
+        reflected.push_patch(ReflectedPatch {
+            value: base,
+            entities: Vec::new(),
+        });
+        reflected.push_patch(self.patch);
+        scene.insert_erased_template(type_id, Box::new(reflected));
+        Ok(())
+    }
+}
+
+/// Type data that allows related scenes to be added for a [`RelationshipTarget`] using reflection (ex: `Children [ ... ]`
+/// in a `.bsn` asset).
+///
+/// [`ScenePlugin`](crate::ScenePlugin) registers this for [`Children`]. Other relationship targets can register it using
+/// `#[reflect(RelatedScenes)]` or [`App::register_type_data`].
+///
+/// [`Children`]: bevy_ecs::hierarchy::Children
+/// [`App::register_type_data`]: bevy_app::App::register_type_data
+#[derive(Clone)]
+pub struct ReflectRelatedScenes {
+    relationship_type_id: TypeId,
+    new: fn() -> RelatedResolvedScenes,
+}
+
+impl ReflectRelatedScenes {
+    /// The [`TypeId`] of the [`Relationship`](bevy_ecs::relationship::Relationship) component.
+    pub fn relationship_type_id(&self) -> TypeId {
+        self.relationship_type_id
+    }
+
+    /// Returns the [`RelatedResolvedScenes`] for the given `scene`, inserting an empty one if it doesn't exist yet.
+    pub fn get_or_insert<'a>(&self, scene: &'a mut ResolvedScene) -> &'a mut RelatedResolvedScenes {
+        scene.get_or_insert_related_resolved_scenes_erased(self.relationship_type_id, self.new)
+    }
+}
+
+impl<T: RelationshipTarget + Reflect + TypePath> CreateTypeData<T> for ReflectRelatedScenes {
+    fn create_type_data(_input: ()) -> Self {
+        Self {
+            relationship_type_id: TypeId::of::<T::Relationship>(),
+            new: RelatedResolvedScenes::new::<T::Relationship>,
+        }
+    }
+}
+
+/// An error that occurs when building or resolving a [`ReflectedComponentTemplate`].
+#[derive(Error, Debug)]
+pub enum ReflectedTemplateError {
+    /// The type is not a reflected [`Component`](bevy_ecs::component::Component).
+    #[error(
+        "{type_path} does not reflect Component. Consider adding #[reflect(Component)] to the type"
+    )]
+    NotAComponent {
+        /// The type path of the type.
+        type_path: &'static str,
+    },
+    /// The component could not be constructed.
+    #[error("{type_path} cannot be constructed: it does not reflect Default, and the first patch does not fully describe the component. Consider adding #[reflect(Default)] to the type")]
+    NotConstructible {
+        /// The type path of the component.
+        type_path: &'static str,
+    },
+    /// Applying a patch to the component failed.
+    #[error("Failed to apply a patch to {type_path}: {error}")]
+    Apply {
+        /// The type path of the component.
+        type_path: &'static str,
+        /// The error that occurred.
+        error: ApplyError,
+    },
+    /// An entity reference pointed to an invalid path.
+    #[error("Failed to set the entity at path {path} on {type_path}: {error}")]
+    EntityPath {
+        /// The type path of the component.
+        type_path: &'static str,
+        /// The path of the entity field.
+        path: String,
+        /// The error that occurred.
+        error: String,
+    },
+    /// The component references an entity, but was built without a [`TemplateContext`].
+    #[error(
+        "{type_path} references a scene entity, which can only be resolved when spawning the scene"
+    )]
+    UnresolvedEntity {
+        /// The type path of the component.
+        type_path: &'static str,
+    },
+    /// The built value did not match the component type.
+    #[error("Expected the built value to be {expected}, but it was {found}")]
+    MismatchedType {
+        /// The type path of the component.
+        expected: &'static str,
+        /// The type path of the built value.
+        found: String,
+    },
+    /// An existing template for the component cannot be patched using reflection.
+    #[error("The existing template for {type_path} cannot be patched using reflection. Templates must either be reflected templates or the component itself")]
+    IncompatibleTemplate {
+        /// The type path of the component.
+        type_path: &'static str,
+    },
+    /// Cloning a reflected value failed.
+    #[error("Failed to clone {type_path}: {error}")]
+    Clone {
+        /// The type path of the component.
+        type_path: &'static str,
+        /// The error that occurred.
+        error: ReflectCloneError,
+    },
+}
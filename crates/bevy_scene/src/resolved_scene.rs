@@ -1,8 +1,12 @@
-use crate::{Ready, ResolveContext, ResolveSceneError, Scene, SceneList, ScenePatch};
+use crate::{
+    Ready, ReflectedComponentTemplate, ResolveContext, ResolveSceneError, Scene, SceneList,
+    ScenePatch,
+};
+use alloc::sync::Arc;
 use bevy_asset::{AssetId, AssetPath, AssetServer, Assets, Handle, UntypedAssetId};
 use bevy_ecs::{
     bundle::{Bundle, BundleScratch, BundleWriter},
-    component::{Component, ComponentsRegistrator},
+    component::{Component, ComponentId, ComponentsRegistrator},
     entity::Entity,
     error::{BevyError, Result},
     relationship::{Relationship, RelationshipTarget},
@@ -66,13 +70,65 @@ impl ResolvedSceneRoot {
         &self,
         entity: &mut EntityWorldMut,
         bundle_scratch: &mut BundleScratch,
+    ) -> Result<(), ApplySceneError> {
+        Self::scoped_apply(entity, bundle_scratch, |context, bundle_scratch| {
+            self.scene.apply(context, bundle_scratch)
+        })
+    }
+
+    /// Same as [`ResolvedSceneRoot::apply`], but also returns an [`AppliedScene`] that records the components and related entities
+    /// written by this scene. This can be passed to [`ResolvedSceneRoot::reapply`] to update the entity in place.
+    pub fn apply_tracked(
+        &self,
+        entity: &mut EntityWorldMut,
+        bundle_scratch: &mut BundleScratch,
+    ) -> Result<AppliedScene, ApplySceneError> {
+        let mut applied = AppliedScene::new(entity.id());
+        Self::scoped_apply(entity, bundle_scratch, |context, bundle_scratch| {
+            self.scene
+                .apply_with(context, bundle_scratch, Some(&mut applied), |_, _| {})
+        })?;
+        Ok(applied)
+    }
+
+    /// Applies this scene to an entity that a different version of the scene was previously applied to (as recorded by `previous`),
+    /// updating the entity and its related entities in place.
+    ///
+    /// Related entities are matched with the `previous` related entities by their position. Matching entities keep their [`Entity`] id
+    /// and are re-applied recursively, related scenes that were added are spawned, and related entities that were removed are despawned.
+    /// Components written by the previous scene that are not written by this scene are removed. Components that were not written by
+    /// either scene (such as components added at runtime) are left untouched.
+    ///
+    /// Bundle templates (such as observers added using [`on`](crate::on)) are only applied to newly spawned entities, and [`Ready`]
+    /// is only triggered for newly spawned entities.
+    pub fn reapply(
+        &self,
+        entity: &mut EntityWorldMut,
+        previous: &AppliedScene,
+        bundle_scratch: &mut BundleScratch,
+    ) -> Result<AppliedScene, ApplySceneError> {
+        let mut applied = AppliedScene::new(entity.id());
+        Self::scoped_apply(entity, bundle_scratch, |context, bundle_scratch| {
+            self.scene
+                .set_previous_entity_references(context, previous)?;
+            self.scene
+                .reapply(context, bundle_scratch, previous, &mut applied)
+        })?;
+        Ok(applied)
+    }
+
+    /// Runs `apply` with a new [`TemplateContext`] for `entity`, dropping any components left in `bundle_scratch` afterwards.
+    fn scoped_apply(
+        entity: &mut EntityWorldMut,
+        bundle_scratch: &mut BundleScratch,
+        apply: impl FnOnce(&mut TemplateContext, &mut BundleScratch) -> Result<(), ApplySceneError>,
     ) -> Result<(), ApplySceneError> {
         let mut entity_references = SceneEntityReferences::default();
         let mut context = TemplateContext::new(entity, &mut entity_references);
 
-        let result = self.scene.apply(&mut context, bundle_scratch);
+        let result = apply(&mut context, bundle_scratch);
         if !bundle_scratch.is_empty() {
-            // SAFETY: Components comes from the same world as the `context` passed in to self.scene.apply above
+            // SAFETY: Components comes from the same world as the `context` passed in to `apply` above
             unsafe {
                 bundle_scratch.manual_drop(entity.world().components());
             }
@@ -81,6 +137,41 @@ impl ResolvedSceneRoot {
     }
 }
 
+/// A record of the components and related entities that were written when a [`ResolvedSceneRoot`] was applied to an entity
+/// (see [`ResolvedSceneRoot::apply_tracked`]). This is used to update the entity in place using [`ResolvedSceneRoot::reapply`].
+#[derive(Clone, Debug)]
+pub struct AppliedScene {
+    entity: Entity,
+    /// The components written by the scene's component templates.
+    components: Vec<ComponentId>,
+    /// The related entities spawned for each [`Relationship`], in order.
+    related: TypeIdHashMap<Vec<AppliedScene>>,
+}
+
+impl AppliedScene {
+    fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            components: Vec::new(),
+            related: TypeIdHashMap::default(),
+        }
+    }
+
+    /// The entity the scene was applied to.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns the entity the scene was applied to, followed by every related entity the scene spawned (recursively).
+    pub fn entities(&self) -> Vec<Entity> {
+        let mut entities = vec![self.entity];
+        for applied in self.related.values().flatten() {
+            entities.extend(applied.entities());
+        }
+        entities
+    }
+}
+
 /// A final "spawnable" root list of [`ResolvedScene`]s.
 pub struct ResolvedSceneListRoot {
     /// The root [`ResolvedScene`] list.
@@ -164,19 +255,19 @@ impl ResolvedSceneListRoot {
 #[derive(Default)]
 pub struct ResolvedScene {
     /// The collection of component [`Template`]s to apply to a spawned [`Entity`]. This can have multiple copies of the same [`Template`].
-    component_templates: Vec<Box<dyn ErasedComponentTemplate>>,
+    pub(crate) component_templates: Vec<Box<dyn ErasedComponentTemplate>>,
     /// The collection of Bundle templates to apply to a spawned [`Entity`].
-    bundle_templates: Vec<Box<dyn ErasedBundleTemplate>>,
+    pub(crate) bundle_templates: Vec<Box<dyn ErasedBundleTemplate>>,
     /// The collection of [`RelatedResolvedScenes`], which will be spawned as "related" entities (ex: [`Children`] entities).
     ///
     /// [`Children`]: bevy_ecs::hierarchy::Children
     // PERF: special casing Children might make sense here to avoid hashing
-    related: TypeIdIndexMap<RelatedResolvedScenes>,
+    pub(crate) related: TypeIdIndexMap<RelatedResolvedScenes>,
     /// The cached [`ScenePatch`] to apply _first_ before applying this [`ResolvedScene`].
-    cached: Option<CachedSceneInfo>,
+    pub(crate) cached: Option<CachedSceneInfo>,
     /// A [`TypeId`] to `templates` index mapping. If a [`Template`] is intended to be shared / patched across scenes, it should be registered
     /// here.
-    template_indices: TypeIdHashMap<usize>,
+    pub(crate) template_indices: TypeIdHashMap<usize>,
     /// A list of all [`SceneEntityReference`] values associated with this entity. There can be more than one if this scene uses
     /// "flattened" caching.
     pub entity_references: Vec<SceneEntityReference>,
@@ -193,6 +284,27 @@ impl core::fmt::Debug for ResolvedScene {
     }
 }
 
+impl Clone for ResolvedScene {
+    fn clone(&self) -> Self {
+        Self {
+            component_templates: self
+                .component_templates
+                .iter()
+                .map(|template| (**template).clone_template())
+                .collect(),
+            bundle_templates: self
+                .bundle_templates
+                .iter()
+                .map(|template| (**template).clone_template())
+                .collect(),
+            related: self.related.clone(),
+            cached: self.cached.clone(),
+            template_indices: self.template_indices.clone(),
+            entity_references: self.entity_references.clone(),
+        }
+    }
+}
+
 impl ResolvedScene {
     /// Applies this scene to the given [`TemplateContext`] (which holds an already-spawned [`EntityWorldMut`]).
     ///
@@ -205,7 +317,7 @@ impl ResolvedScene {
         context: &mut TemplateContext,
         bundle_scratch: &mut BundleScratch,
     ) -> Result<(), ApplySceneError> {
-        self.apply_with(context, bundle_scratch, |_, _| {})
+        self.apply_with(context, bundle_scratch, None, |_, _| {})
     }
 
     /// Applies this scene to the given [`TemplateContext`] (which holds an already-spawned [`EntityWorldMut`]).
@@ -215,6 +327,8 @@ impl ResolvedScene {
     ///
     /// If this [`ResolvedScene`] includes a cached scene, that scene will be applied _first_.
     ///
+    /// If `applied` is set, the components and related entities written by this scene will be recorded in it.
+    ///
     /// This will call `writer_ops` right before calling [`BundleWriter::write`]. This will pass in the `context` value,
     /// which is the same context used to write all of the scene components to the [`BundleWriter`]. This ensures that
     /// writing to [`BundleWriter`] with the [`TemplateContext`] is safe (although those functions, if they are called, are still
@@ -223,32 +337,20 @@ impl ResolvedScene {
         &self,
         context: &mut TemplateContext,
         bundle_scratch: &mut BundleScratch,
+        mut applied: Option<&mut AppliedScene>,
         writer_ops: impl FnOnce(&mut TemplateContext, &mut BundleWriter),
     ) -> Result<(), ApplySceneError> {
-        let mut bundle_writer = bundle_scratch.writer();
         for entity_reference in self.entity_references.iter().copied() {
             context
                 .entity_references
                 .set(entity_reference, context.entity.id());
         }
-        if let Some(cached) = &self.cached {
-            let scene_patches = context.resource::<Assets<ScenePatch>>();
-            let Some(patch) = scene_patches.get(&cached.handle) else {
-                return Err(ApplySceneError::MissingCachedScene {
-                    path: cached.handle.path().cloned(),
-                    id: cached.handle.id(),
-                });
-            };
-            let Some(resolved_cached) = &patch.resolved else {
-                return Err(ApplySceneError::UnresolvedCachedScene {
-                    path: cached.handle.path().cloned(),
-                    id: cached.handle.id(),
-                });
-            };
-            let resolved_cached = resolved_cached.clone();
-            // SAFETY: bundle_writer is used with the same World across all template.apply calls,
-            // and the next bundle_writer.write call
-            unsafe {
+        let resolved_cached = self.resolved_cached(context)?;
+        let mut bundle_writer = bundle_scratch.writer();
+        // SAFETY: bundle_writer is used with the same World across all template.apply calls,
+        // and the next bundle_writer.write call
+        unsafe {
+            if let Some((cached, resolved_cached)) = &resolved_cached {
                 resolved_cached
                     .scene
                     .apply_templates_without_bundle_write(
@@ -258,55 +360,41 @@ impl ResolvedScene {
                         // have local copies in the current scene
                         // (cached templates are copy-on-write)()
                         &cached.duplicate_templates,
+                        true,
                     )
                     .map_err(|e| ApplySceneError::CachedSceneApplyError {
                         cached: cached.handle.path().cloned(),
                         error: Box::new(e),
                     })?;
-                self.apply_templates_without_bundle_write(context, &mut bundle_writer, ())?;
-                // SAFETY: World is only used for component registration, which does not affect
-                // the entity location
-                let components = &mut context.entity.world_mut().components_registrator();
-                // This inserts empty RelationshipTarget collections to avoid archetype moves when then related entities are spawned
-                // It pre-allocates space in the collection to avoid reallocs as related entities are added.
-                for related in self.related.values() {
-                    (related.insert_relationship_target)(
-                        &mut bundle_writer,
-                        components,
-                        related.scenes.len(),
-                    );
-                }
+            }
+            self.apply_templates_without_bundle_write(context, &mut bundle_writer, (), true)?;
+            if let Some(applied) = applied.as_deref_mut() {
+                applied.components = bundle_writer.component_ids().to_vec();
+            }
+            // SAFETY: World is only used for component registration, which does not affect
+            // the entity location
+            let components = &mut context.entity.world_mut().components_registrator();
+            // This inserts empty RelationshipTarget collections to avoid archetype moves when then related entities are spawned
+            // It pre-allocates space in the collection to avoid reallocs as related entities are added.
+            for related in self.related.values() {
+                (related.insert_relationship_target)(
+                    &mut bundle_writer,
+                    components,
+                    related.scenes.len(),
+                );
+            }
 
-                (writer_ops)(context, &mut bundle_writer);
+            (writer_ops)(context, &mut bundle_writer);
 
-                bundle_writer.write(context.entity);
+            bundle_writer.write(context.entity);
+        }
 
-                resolved_cached
-                    .scene
-                    .apply_related(context, bundle_scratch)?;
-                self.apply_related(context, bundle_scratch)?;
-            }
-        } else {
-            // SAFETY: bundle_writer was used with the same World across all cases in this function,
-            unsafe {
-                self.apply_templates_without_bundle_write(context, &mut bundle_writer, ())?;
-                // SAFETY: World is only used for component registration, which does not affect
-                // the entity location
-                let components = &mut context.entity.world_mut().components_registrator();
-                // This inserts empty RelationshipTarget collections to avoid archetype moves when then related entities are spawned
-                // It pre-allocates space in the collection to avoid reallocs as related entities are added.
-                for related in self.related.values() {
-                    (related.insert_relationship_target)(
-                        &mut bundle_writer,
-                        components,
-                        related.scenes.len(),
-                    );
-                }
-                (writer_ops)(context, &mut bundle_writer);
-                bundle_writer.write(context.entity);
-                self.apply_related(context, bundle_scratch)?;
-            }
-        };
+        if let Some((_, resolved_cached)) = &resolved_cached {
+            resolved_cached
+                .scene
+                .apply_related(context, bundle_scratch, applied.as_deref_mut())?;
+        }
+        self.apply_related(context, bundle_scratch, applied)?;
 
         let entity = context.entity.id();
         context.entity.world_scope(|world| {
@@ -316,6 +404,30 @@ impl ResolvedScene {
         Ok(())
     }
 
+    /// Returns the [`CachedSceneInfo`] and [`ResolvedSceneRoot`] of this scene's cached scene, if it has one.
+    fn resolved_cached<'a>(
+        &'a self,
+        context: &TemplateContext,
+    ) -> Result<Option<(&'a CachedSceneInfo, Arc<ResolvedSceneRoot>)>, ApplySceneError> {
+        let Some(cached) = &self.cached else {
+            return Ok(None);
+        };
+        let scene_patches = context.resource::<Assets<ScenePatch>>();
+        let Some(patch) = scene_patches.get(&cached.handle) else {
+            return Err(ApplySceneError::MissingCachedScene {
+                path: cached.handle.path().cloned(),
+                id: cached.handle.id(),
+            });
+        };
+        let Some(resolved_cached) = &patch.resolved else {
+            return Err(ApplySceneError::UnresolvedCachedScene {
+                path: cached.handle.path().cloned(),
+                id: cached.handle.id(),
+            });
+        };
+        Ok(Some((cached, resolved_cached.clone())))
+    }
+
     /// # Safety
     ///
     /// `bundle_writer` must either be empty or only contain components registered with the given
@@ -325,9 +437,10 @@ impl ResolvedScene {
         context: &mut TemplateContext,
         bundle_writer: &mut BundleWriter,
         skip_templates: impl SkipTemplate,
+        apply_bundle_templates: bool,
     ) -> Result<(), ApplySceneError> {
         for template in &self.component_templates {
-            if skip_templates.should_skip((**template).type_id()) {
+            if skip_templates.should_skip(template.template_type_id()) {
                 continue;
             }
             // SAFETY: bundle_writer is used with the same World across all template.apply calls,
@@ -339,6 +452,9 @@ impl ResolvedScene {
             }
         }
 
+        if !apply_bundle_templates {
+            return Ok(());
+        }
         for template in &self.bundle_templates {
             // SAFETY: bundle_writer is used with the same World across all template.apply calls,
             // and the next bundle_writer.write call
@@ -355,8 +471,9 @@ impl ResolvedScene {
         &self,
         context: &mut TemplateContext,
         bundle_scratch: &mut BundleScratch,
+        mut applied: Option<&mut AppliedScene>,
     ) -> Result<(), ApplySceneError> {
-        for related_resolved_scenes in self.related.values() {
+        for (type_id, related_resolved_scenes) in self.related.iter() {
             let target = context.entity.id();
             let TemplateContext {
                 entity,
@@ -364,37 +481,29 @@ impl ResolvedScene {
             } = context;
             entity.world_scope(|world| -> Result<(), ApplySceneError> {
                 for (index, scene) in related_resolved_scenes.scenes.iter().enumerate() {
-                    let mut entity =
-                        if let Some(entity_reference) = scene.entity_references.first().copied() {
-                            let entity = entity_references.get(entity_reference, world);
-                            world.entity_mut(entity)
-                        } else {
-                            world.spawn_empty()
-                        };
-
-                    scene
-                        .apply_with(
-                            &mut TemplateContext::new(&mut entity, entity_references),
+                    let related_applied = scene
+                        .spawn_related(
+                            world,
+                            entity_references,
                             bundle_scratch,
-                            |context, bundle_writer| {
-                                // SAFETY: `context` is used to write all previous `bundle_writer` components
-                                // and is also used to write this relationship component
-                                unsafe {
-                                    (related_resolved_scenes.insert_relationship)(
-                                        bundle_writer,
-                                        // SAFETY: World is only used for component registration, which does not affect
-                                        // the entity location
-                                        &mut context.entity.world_mut().components_registrator(),
-                                        target,
-                                    );
-                                }
-                            },
+                            related_resolved_scenes,
+                            target,
+                            applied.is_some(),
                         )
                         .map_err(|e| ApplySceneError::RelatedSceneError {
                             relationship_type_name: related_resolved_scenes.relationship_name,
                             index,
                             error: Box::new(e),
                         })?;
+                    if let Some(applied) = applied.as_deref_mut()
+                        && let Some(related_applied) = related_applied
+                    {
+                        applied
+                            .related
+                            .entry(*type_id)
+                            .or_default()
+                            .push(related_applied);
+                    }
                 }
                 Ok(())
             })?;
@@ -403,6 +512,213 @@ impl ResolvedScene {
         Ok(())
     }
 
+    /// Spawns this scene as an entity related to `target` (or applies it to the entity it is referenced by). If `track` is true,
+    /// the resulting [`AppliedScene`] is returned.
+    fn spawn_related(
+        &self,
+        world: &mut World,
+        entity_references: &mut SceneEntityReferences,
+        bundle_scratch: &mut BundleScratch,
+        related_resolved_scenes: &RelatedResolvedScenes,
+        target: Entity,
+        track: bool,
+    ) -> Result<Option<AppliedScene>, ApplySceneError> {
+        let mut entity = if let Some(entity_reference) = self.entity_references.first().copied() {
+            let entity = entity_references.get(entity_reference, world);
+            world.entity_mut(entity)
+        } else {
+            world.spawn_empty()
+        };
+
+        let mut applied = track.then(|| AppliedScene::new(entity.id()));
+        self.apply_with(
+            &mut TemplateContext::new(&mut entity, entity_references),
+            bundle_scratch,
+            applied.as_mut(),
+            |context, bundle_writer| {
+                // SAFETY: `context` is used to write all previous `bundle_writer` components
+                // and is also used to write this relationship component
+                unsafe {
+                    (related_resolved_scenes.insert_relationship)(
+                        bundle_writer,
+                        // SAFETY: World is only used for component registration, which does not affect
+                        // the entity location
+                        &mut context.entity.world_mut().components_registrator(),
+                        target,
+                    );
+                }
+            },
+        )?;
+        Ok(applied)
+    }
+
+    /// Returns the related scenes of this scene and its `cached` scene, grouped by their relationship. The related scenes of the
+    /// cached scene come first, which matches the order they are spawned in.
+    fn all_related<'a>(
+        &'a self,
+        cached: Option<&'a ResolvedScene>,
+    ) -> TypeIdIndexMap<(&'a RelatedResolvedScenes, Vec<&'a ResolvedScene>)> {
+        let mut all_related =
+            TypeIdIndexMap::<(&RelatedResolvedScenes, Vec<&ResolvedScene>)>::default();
+        for (type_id, related) in cached
+            .into_iter()
+            .flat_map(|cached| cached.related.iter())
+            .chain(self.related.iter())
+        {
+            all_related
+                .entry(*type_id)
+                .or_insert_with(|| (related, Vec::new()))
+                .1
+                .extend(related.scenes.iter());
+        }
+        all_related
+    }
+
+    /// Sets the entity references of this scene (and its related scenes) to the entities of the matching `previous`
+    /// [`AppliedScene`], if they still exist. This ensures references resolve to existing entities when re-applying a scene.
+    fn set_previous_entity_references(
+        &self,
+        context: &mut TemplateContext,
+        previous: &AppliedScene,
+    ) -> Result<(), ApplySceneError> {
+        if context.entity.world().get_entity(previous.entity).is_err() {
+            return Ok(());
+        }
+        for entity_reference in self.entity_references.iter().copied() {
+            context
+                .entity_references
+                .set(entity_reference, previous.entity);
+        }
+        let resolved_cached = self.resolved_cached(context)?;
+        let all_related =
+            self.all_related(resolved_cached.as_ref().map(|(_, cached)| &cached.scene));
+        for (type_id, (_, scenes)) in &all_related {
+            for (scene, previous) in scenes
+                .iter()
+                .zip(previous.related.get(type_id).into_iter().flatten())
+            {
+                scene.set_previous_entity_references(context, previous)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-applies this scene to the entity in `context`, which `previous` was applied to. See [`ResolvedSceneRoot::reapply`].
+    fn reapply(
+        &self,
+        context: &mut TemplateContext,
+        bundle_scratch: &mut BundleScratch,
+        previous: &AppliedScene,
+        applied: &mut AppliedScene,
+    ) -> Result<(), ApplySceneError> {
+        for entity_reference in self.entity_references.iter().copied() {
+            context
+                .entity_references
+                .set(entity_reference, context.entity.id());
+        }
+        let resolved_cached = self.resolved_cached(context)?;
+        let mut bundle_writer = bundle_scratch.writer();
+        // SAFETY: bundle_writer is used with the same World across all template.apply calls,
+        // and the next bundle_writer.write call
+        unsafe {
+            if let Some((cached, resolved_cached)) = &resolved_cached {
+                resolved_cached
+                    .scene
+                    .apply_templates_without_bundle_write(
+                        context,
+                        &mut bundle_writer,
+                        &cached.duplicate_templates,
+                        false,
+                    )
+                    .map_err(|e| ApplySceneError::CachedSceneApplyError {
+                        cached: cached.handle.path().cloned(),
+                        error: Box::new(e),
+                    })?;
+            }
+            self.apply_templates_without_bundle_write(context, &mut bundle_writer, (), false)?;
+            applied.components = bundle_writer.component_ids().to_vec();
+            bundle_writer.write(context.entity);
+        }
+
+        // Components that are no longer in the scene are removed. Components that were never written by the scene are untouched.
+        let removed = previous
+            .components
+            .iter()
+            .filter(|id| !applied.components.contains(id))
+            .copied()
+            .collect::<Vec<_>>();
+        if !removed.is_empty() {
+            context.entity.remove_by_ids(&removed);
+        }
+
+        let all_related =
+            self.all_related(resolved_cached.as_ref().map(|(_, cached)| &cached.scene));
+        let target = context.entity.id();
+        let TemplateContext {
+            entity,
+            entity_references,
+        } = context;
+        entity.world_scope(|world| -> Result<(), ApplySceneError> {
+            for (type_id, (related_resolved_scenes, scenes)) in &all_related {
+                let previous_related = previous
+                    .related
+                    .get(type_id)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let mut applied_related = Vec::with_capacity(scenes.len());
+                for (index, scene) in scenes.iter().enumerate() {
+                    let map_err = |e| ApplySceneError::RelatedSceneError {
+                        relationship_type_name: related_resolved_scenes.relationship_name,
+                        index,
+                        error: Box::new(e),
+                    };
+                    // Related entities are matched by their position
+                    if let Some(previous) = previous_related.get(index)
+                        && let Ok(mut entity) = world.get_entity_mut(previous.entity)
+                    {
+                        let mut related_applied = AppliedScene::new(previous.entity);
+                        scene
+                            .reapply(
+                                &mut TemplateContext::new(&mut entity, entity_references),
+                                bundle_scratch,
+                                previous,
+                                &mut related_applied,
+                            )
+                            .map_err(map_err)?;
+                        applied_related.push(related_applied);
+                    } else {
+                        let related_applied = scene
+                            .spawn_related(
+                                world,
+                                entity_references,
+                                bundle_scratch,
+                                related_resolved_scenes,
+                                target,
+                                true,
+                            )
+                            .map_err(map_err)?;
+                        applied_related.extend(related_applied);
+                    }
+                }
+                applied.related.insert(*type_id, applied_related);
+            }
+
+            // Related entities that are no longer in the scene are despawned
+            for (type_id, previous_related) in &previous.related {
+                let len = all_related
+                    .get(type_id)
+                    .map(|(_, scenes)| scenes.len())
+                    .unwrap_or_default();
+                for previous in previous_related.iter().skip(len) {
+                    if let Ok(entity) = world.get_entity_mut(previous.entity) {
+                        entity.despawn();
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// This will get the [`Template`], if it already exists in this [`ResolvedScene`]. If it doesn't exist,
     /// it will use [`Default`] to create a new [`Template`].
     ///
@@ -418,8 +734,35 @@ impl ResolvedScene {
         &'a mut self,
         context: &mut ResolveContext,
     ) -> &'a mut T {
-        (self.get_or_insert_erased_template(context, TypeId::of::<T>(), || Box::new(T::default()))
-            as &mut dyn Any)
+        let output_type_id = TypeId::of::<T::Output>();
+        if output_type_id != TypeId::of::<T>()
+            && let Some(cached) = &mut self.cached
+            && let Some(cached_patch) = context.cached
+            && let Some(resolved_cached) = &cached_patch.resolved
+            && resolved_cached
+                .scene
+                .get_direct_erased_template(output_type_id)
+                .is_some()
+        {
+            // The cached scene has a reflected template that writes the same component. This template
+            // takes precedence, so the cached template is skipped to avoid writing the component twice.
+            cached.duplicate_templates.insert(output_type_id);
+        }
+
+        let index = self.get_or_insert_erased_template_index(context, TypeId::of::<T>(), || {
+            Box::new(T::default())
+        });
+        let template = &mut self.component_templates[index];
+        if !(&**template as &dyn Any).is::<T>() {
+            // Templates that were not added by typed scenes (such as reflected templates from `.bsn` assets) are converted
+            // to `T` if possible. If not, `T` starts from its default value.
+            let value = (&**template as &dyn Any)
+                .downcast_ref::<ReflectedComponentTemplate>()
+                .and_then(ReflectedComponentTemplate::build_concrete::<T>)
+                .unwrap_or_default();
+            *template = Box::new(value);
+        }
+        (&mut **template as &mut dyn Any)
             // PERF: this could be unchecked, given that we control what is stored here
             // The method isn't stable yet, and it would require making get_or_insert_erased_template unsafe
             .downcast_mut()
@@ -454,6 +797,20 @@ impl ResolvedScene {
         }
     }
 
+    /// Removes the [`Template`] stored with the given `type_id` (see [`ResolvedScene::insert_erased_template`]), if it exists.
+    pub fn remove_erased_template(
+        &mut self,
+        type_id: TypeId,
+    ) -> Option<Box<dyn ErasedComponentTemplate>> {
+        let index = self.template_indices.remove(&type_id)?;
+        for other_index in self.template_indices.values_mut() {
+            if *other_index > index {
+                *other_index -= 1;
+            }
+        }
+        Some(self.component_templates.remove(index))
+    }
+
     /// This will get the [`ErasedComponentTemplate`] for the given [`TypeId`], if it already exists in this [`ResolvedScene`]. If it doesn't exist,
     /// it will use the `default` function to create a new [`ErasedComponentTemplate`]. _For correctness, the [`TypeId`] of the [`Template`] returned
     /// by `default` should match the passed in `type_id`_.
@@ -467,8 +824,19 @@ impl ResolvedScene {
         &'a mut self,
         context: &mut ResolveContext,
         type_id: TypeId,
-        default: fn() -> Box<dyn ErasedComponentTemplate>,
+        default: impl FnOnce() -> Box<dyn ErasedComponentTemplate>,
     ) -> &'a mut dyn ErasedComponentTemplate {
+        let index = self.get_or_insert_erased_template_index(context, type_id, default);
+        &mut *self.component_templates[index]
+    }
+
+    /// Same as [`ResolvedScene::get_or_insert_erased_template`], but returns the index of the template in `component_templates`.
+    fn get_or_insert_erased_template_index(
+        &mut self,
+        context: &mut ResolveContext,
+        type_id: TypeId,
+        default: impl FnOnce() -> Box<dyn ErasedComponentTemplate>,
+    ) -> usize {
         let mut is_cached = false;
         let index = self.template_indices.entry(type_id).or_insert_with(|| {
             let index = self.component_templates.len();
@@ -485,11 +853,6 @@ impl ResolvedScene {
             self.component_templates.push(value);
             index
         });
-        let template = self
-            .component_templates
-            .get_mut(*index)
-            .map(|value| &mut **value)
-            .unwrap();
 
         if is_cached {
             self.cached
@@ -499,7 +862,7 @@ impl ResolvedScene {
                 .insert(type_id);
         }
 
-        template
+        *index
     }
 
     /// Returns the [`ErasedComponentTemplate`] for the given `type_id`, if it exists in this [`ResolvedScene`]. This ignores cached scenes.
@@ -547,6 +910,16 @@ impl ResolvedScene {
             .or_insert_with(RelatedResolvedScenes::new::<R>)
     }
 
+    /// Same as [`ResolvedScene::get_or_insert_related_resolved_scenes`], but for a [`Relationship`] with the given `type_id`.
+    /// If the [`RelatedResolvedScenes`] does not exist yet, it will be created using `new`.
+    pub fn get_or_insert_related_resolved_scenes_erased(
+        &mut self,
+        type_id: TypeId,
+        new: impl FnOnce() -> RelatedResolvedScenes,
+    ) -> &mut RelatedResolvedScenes {
+        self.related.entry(type_id).or_insert_with(new)
+    }
+
     /// Configures this [`ResolvedScene`] to include the given [`ScenePatch`] cached.
     ///
     /// If this [`ResolvedScene`] already includes a cached scene, it will return [`CachedSceneError::MultipleCached`].
@@ -573,7 +946,7 @@ impl ResolvedScene {
 }
 
 /// Information about a [`ResolvedScene`]'s cached scene.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct CachedSceneInfo {
     /// The handle of the cached scene.
     pub(crate) handle: Handle<ScenePatch>,
@@ -652,6 +1025,7 @@ pub enum ApplySceneError {
 
 /// A collection of [`ResolvedScene`]s that are related to a given [`ResolvedScene`] by a [`Relationship`].
 /// Each [`ResolvedScene`] added here will be spawned as a new [`Entity`] when the "parent" [`ResolvedScene`] is spawned.
+#[derive(Clone)]
 pub struct RelatedResolvedScenes {
     /// The related resolved scenes. Each entry in the list corresponds to a new related entity that will be spawned with the given scene.
     pub scenes: Vec<ResolvedScene>,
@@ -714,6 +1088,11 @@ pub trait ErasedComponentTemplate: Any + Send + Sync {
 
     /// Clones this template. See [`Clone`].
     fn clone_template(&self) -> Box<dyn ErasedComponentTemplate>;
+
+    /// The [`TypeId`] this template is stored under in a [`ResolvedScene`]. This defaults to the type of the template itself.
+    fn template_type_id(&self) -> TypeId {
+        <Self as Any>::type_id(self)
+    }
 }
 
 impl<T: Template<Output: Component> + Send + Sync + 'static> ErasedComponentTemplate for T {
@@ -1,4 +1,7 @@
-use crate::{CachedSceneError, ErasedComponentTemplate, ResolvedScene, SceneList, ScenePatch};
+use crate::{
+    CachedSceneError, ErasedComponentTemplate, ReflectedTemplateError, ResolvedScene, SceneList,
+    ScenePatch,
+};
 use bevy_asset::{Asset, AssetPath, AssetServer, Assets};
 use bevy_ecs::{
     component::Component,
@@ -160,6 +163,9 @@ pub enum ResolveSceneError {
     /// Caused when including a cached scene during [`Scene::resolve`] fails.
     #[error(transparent)]
     CachedSceneError(#[from] CachedSceneError),
+    /// Caused when patching a component using reflection fails during [`Scene::resolve`].
+    #[error(transparent)]
+    ReflectedTemplateError(#[from] ReflectedTemplateError),
     /// Caused when a [`Scene`]/[`SceneList`] is not present on the scene asset.
     #[error("The Scene/SceneList is not present on the scene asset. This is likely because the scene has already been resolved, which consumed the source scene")]
     MissingScene,
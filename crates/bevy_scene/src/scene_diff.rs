@@ -0,0 +1,430 @@
+use crate::{
+    ErasedComponentTemplate, ReflectedComponent, ReflectedComponentTemplate, RelatedResolvedScenes,
+    ResolvedScene, ResolvedSceneRoot, ScenePatch,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bevy_asset::AssetId;
+use bevy_reflect::{ParsedPath, PartialReflect, TypeRegistry};
+use bevy_utils::TypeIdIndexMap;
+use core::any::{Any, TypeId};
+
+/// The structural differences between two [`ResolvedScene`]s, as computed by [`ResolvedScene::diff`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResolvedSceneDiff {
+    /// The components that were added, removed, or changed.
+    pub components: Vec<ComponentDiff>,
+    /// The related scenes (ex: children) that were added, removed, or changed.
+    pub related: Vec<RelatedSceneDiff>,
+    /// Whether the scenes include different cached scenes.
+    pub cached_changed: bool,
+}
+
+impl ResolvedSceneDiff {
+    /// Returns true if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty() && self.related.is_empty() && !self.cached_changed
+    }
+}
+
+/// A change to a component [`Template`](bevy_ecs::template::Template) in a [`ResolvedSceneDiff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentDiff {
+    /// The [`TypeId`] the template is stored under (see [`ErasedComponentTemplate::template_type_id`]).
+    pub type_id: TypeId,
+    /// The type path of the template, if it is registered in the [`TypeRegistry`].
+    pub type_path: Option<&'static str>,
+    /// How the template changed.
+    pub change: ComponentChange,
+}
+
+/// The kind of change in a [`ComponentDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentChange {
+    /// The component only exists in the new scene.
+    Added,
+    /// The component only exists in the old scene.
+    Removed,
+    /// The component exists in both scenes, but with different values.
+    Changed,
+}
+
+/// The position of a related scene within its parent [`ResolvedScene`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelatedSceneIndex {
+    /// The [`TypeId`] of the [`Relationship`](bevy_ecs::relationship::Relationship).
+    pub relationship_type_id: TypeId,
+    /// The type name of the [`Relationship`](bevy_ecs::relationship::Relationship).
+    pub relationship_name: &'static str,
+    /// The index of the scene in [`RelatedResolvedScenes::scenes`].
+    pub index: usize,
+}
+
+/// A change to a related scene in a [`ResolvedSceneDiff`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelatedSceneDiff {
+    /// The position of the related scene.
+    pub position: RelatedSceneIndex,
+    /// How the related scene changed.
+    pub change: RelatedSceneChange,
+}
+
+/// The kind of change in a [`RelatedSceneDiff`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelatedSceneChange {
+    /// The related scene only exists in the new scene.
+    Added,
+    /// The related scene only exists in the old scene.
+    Removed,
+    /// The related scene exists in both scenes, with the given differences.
+    Changed(ResolvedSceneDiff),
+}
+
+/// The result of a three-way merge, as computed by [`ResolvedScene::merge`].
+#[derive(Debug)]
+pub struct ResolvedSceneMerge {
+    /// The merged scene. Conflicts are resolved in favor of "ours".
+    pub scene: ResolvedScene,
+    /// The conflicts that were encountered while merging.
+    pub conflicts: Vec<SceneMergeConflict>,
+}
+
+/// A conflict encountered by [`ResolvedScene::merge`]. Each conflict is resolved in favor of "ours".
+///
+/// The `path` of each conflict is the list of related scene positions leading from the merged root scene to the conflicting scene.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SceneMergeConflict {
+    /// Both sides changed the cached scene. The scene from "ours" (including its templates and related scenes) is kept as-is.
+    Cached {
+        /// The path to the conflicting scene.
+        path: Vec<RelatedSceneIndex>,
+    },
+    /// Both sides changed the same component in different ways.
+    Component {
+        /// The path to the scene containing the component.
+        path: Vec<RelatedSceneIndex>,
+        /// The [`TypeId`] the template is stored under (see [`ErasedComponentTemplate::template_type_id`]).
+        type_id: TypeId,
+        /// The type path of the template, if it is registered in the [`TypeRegistry`].
+        type_path: Option<&'static str>,
+    },
+    /// One side removed or added a related scene that the other side changed or added differently.
+    RelatedScene {
+        /// The path to the conflicting related scene.
+        path: Vec<RelatedSceneIndex>,
+    },
+}
+
+impl ResolvedScene {
+    /// Computes the structural differences between this (old) scene and the `other` (new) scene.
+    ///
+    /// Components are compared using reflection, which requires the component type to be registered in the `registry` with
+    /// [`ReflectComponent`](bevy_ecs::reflect::ReflectComponent) and either [`ReflectDefault`](bevy_reflect::std_traits::ReflectDefault)
+    /// or [`ReflectFromReflect`](bevy_reflect::ReflectFromReflect) type data. Templates that cannot be compared (ex: custom
+    /// [`Template`](bevy_ecs::template::Template) types) are always considered changed. Entity fields are compared by their paths,
+    /// as entity references are local to the scene they were resolved in.
+    ///
+    /// Related scenes are matched by their position (the same way as [`ResolvedSceneRoot::reapply`]). Only the templates stored by
+    /// [`TypeId`] are compared: templates added with [`ResolvedScene::push_template`], bundle templates, and the contents of cached
+    /// scenes are not compared (cached scenes are compared by their asset id).
+    pub fn diff(&self, other: &ResolvedScene, registry: &TypeRegistry) -> ResolvedSceneDiff {
+        let mut diff = ResolvedSceneDiff {
+            cached_changed: self.cached_id() != other.cached_id(),
+            ..Default::default()
+        };
+
+        for (type_id, template) in self.canonical_templates() {
+            let change = match other.get_direct_erased_template(type_id) {
+                None => ComponentChange::Removed,
+                Some(other) if !templates_eq(template, other, registry) => ComponentChange::Changed,
+                Some(_) => continue,
+            };
+            diff.components.push(ComponentDiff {
+                type_id,
+                type_path: type_path(registry, type_id),
+                change,
+            });
+        }
+        for (type_id, _) in other.canonical_templates() {
+            if self.get_direct_erased_template(type_id).is_none() {
+                diff.components.push(ComponentDiff {
+                    type_id,
+                    type_path: type_path(registry, type_id),
+                    change: ComponentChange::Added,
+                });
+            }
+        }
+
+        for (relationship_type_id, related) in related_types(&[self, other]) {
+            let scenes = self.related_scenes(relationship_type_id);
+            let other_scenes = other.related_scenes(relationship_type_id);
+            for index in 0..scenes.len().max(other_scenes.len()) {
+                let change = match (scenes.get(index), other_scenes.get(index)) {
+                    (Some(scene), Some(other)) => {
+                        let scene_diff = scene.diff(other, registry);
+                        if scene_diff.is_empty() {
+                            continue;
+                        }
+                        RelatedSceneChange::Changed(scene_diff)
+                    }
+                    (Some(_), None) => RelatedSceneChange::Removed,
+                    (None, _) => RelatedSceneChange::Added,
+                };
+                diff.related.push(RelatedSceneDiff {
+                    position: RelatedSceneIndex {
+                        relationship_type_id,
+                        relationship_name: related.relationship_name,
+                        index,
+                    },
+                    change,
+                });
+            }
+        }
+
+        diff
+    }
+
+    /// Performs a three-way merge of the changes made to `base` in `ours` and `theirs`.
+    ///
+    /// For each component and related scene, a change made on only one side is taken from that side. If both sides made the same
+    /// change, it is taken once. If both sides made different changes, a [`SceneMergeConflict`] is recorded and "ours" is kept.
+    /// Related scenes that were changed on both sides are merged recursively.
+    ///
+    /// Changes are detected using [`ResolvedScene::diff`], so the same limitations apply. Templates that cannot be compared are
+    /// considered changed on both sides, and therefore always conflict (unless they only exist on one side). Templates added with
+    /// [`ResolvedScene::push_template`], bundle templates, and entity references are always taken from "ours".
+    pub fn merge(
+        base: &ResolvedScene,
+        ours: &ResolvedScene,
+        theirs: &ResolvedScene,
+        registry: &TypeRegistry,
+    ) -> ResolvedSceneMerge {
+        let mut conflicts = Vec::new();
+        let scene = merge_scene(
+            base,
+            ours,
+            theirs,
+            registry,
+            &mut Vec::new(),
+            &mut conflicts,
+        );
+        ResolvedSceneMerge { scene, conflicts }
+    }
+
+    /// Returns the templates stored by [`TypeId`], in the order they will be applied.
+    fn canonical_templates(&self) -> impl Iterator<Item = (TypeId, &dyn ErasedComponentTemplate)> {
+        let mut indices = self
+            .template_indices
+            .iter()
+            .map(|(type_id, index)| (*type_id, *index))
+            .collect::<Vec<_>>();
+        indices.sort_unstable_by_key(|(_, index)| *index);
+        indices
+            .into_iter()
+            .map(|(type_id, index)| (type_id, &*self.component_templates[index]))
+    }
+
+    fn related_scenes(&self, relationship_type_id: TypeId) -> &[ResolvedScene] {
+        self.related
+            .get(&relationship_type_id)
+            .map(|related| &related.scenes[..])
+            .unwrap_or_default()
+    }
+
+    fn cached_id(&self) -> Option<AssetId<ScenePatch>> {
+        self.cached.as_ref().map(|cached| cached.handle.id())
+    }
+}
+
+impl ResolvedSceneRoot {
+    /// Computes the structural differences between this (old) scene and the `other` (new) scene. See [`ResolvedScene::diff`].
+    pub fn diff(&self, other: &ResolvedSceneRoot, registry: &TypeRegistry) -> ResolvedSceneDiff {
+        self.scene.diff(&other.scene, registry)
+    }
+}
+
+fn merge_scene(
+    base: &ResolvedScene,
+    ours: &ResolvedScene,
+    theirs: &ResolvedScene,
+    registry: &TypeRegistry,
+    path: &mut Vec<RelatedSceneIndex>,
+    conflicts: &mut Vec<SceneMergeConflict>,
+) -> ResolvedScene {
+    let mut merged = ours.clone();
+    if ours.cached_id() != theirs.cached_id() {
+        // Templates are only valid relative to the cached scene they were resolved with, so they cannot be mixed across cached scenes
+        if theirs.cached_id() != base.cached_id() {
+            conflicts.push(SceneMergeConflict::Cached { path: path.clone() });
+        }
+        return merged;
+    }
+
+    let mut type_ids = Vec::new();
+    for scene in [ours, theirs, base] {
+        for (type_id, _) in scene.canonical_templates() {
+            if !type_ids.contains(&type_id) {
+                type_ids.push(type_id);
+            }
+        }
+    }
+    for type_id in type_ids {
+        let base_template = base.get_direct_erased_template(type_id);
+        let our_template = ours.get_direct_erased_template(type_id);
+        let their_template = theirs.get_direct_erased_template(type_id);
+        if optional_templates_eq(our_template, their_template, registry)
+            || optional_templates_eq(their_template, base_template, registry)
+        {
+            continue;
+        }
+        if !optional_templates_eq(our_template, base_template, registry) {
+            conflicts.push(SceneMergeConflict::Component {
+                path: path.clone(),
+                type_id,
+                type_path: type_path(registry, type_id),
+            });
+            continue;
+        }
+
+        match their_template {
+            Some(template) => merged.insert_erased_template(type_id, template.clone_template()),
+            None => {
+                merged.remove_erased_template(type_id);
+            }
+        }
+        if let Some(cached) = &mut merged.cached {
+            let their_duplicates = &theirs.cached.as_ref().unwrap().duplicate_templates;
+            if their_duplicates.contains(&type_id) {
+                cached.duplicate_templates.insert(type_id);
+            } else {
+                cached.duplicate_templates.remove(&type_id);
+            }
+        }
+    }
+
+    let mut related = TypeIdIndexMap::default();
+    for (relationship_type_id, info) in related_types(&[ours, theirs, base]) {
+        let base_scenes = base.related_scenes(relationship_type_id);
+        let our_scenes = ours.related_scenes(relationship_type_id);
+        let their_scenes = theirs.related_scenes(relationship_type_id);
+        let len = base_scenes
+            .len()
+            .max(our_scenes.len())
+            .max(their_scenes.len());
+        let mut scenes = Vec::new();
+        for index in 0..len {
+            path.push(RelatedSceneIndex {
+                relationship_type_id,
+                relationship_name: info.relationship_name,
+                index,
+            });
+            let (base, ours, theirs) = (
+                base_scenes.get(index),
+                our_scenes.get(index),
+                their_scenes.get(index),
+            );
+            let scene = if optional_scenes_eq(ours, theirs, registry)
+                || optional_scenes_eq(theirs, base, registry)
+            {
+                ours.cloned()
+            } else if optional_scenes_eq(ours, base, registry) {
+                theirs.cloned()
+            } else if let (Some(base), Some(ours), Some(theirs)) = (base, ours, theirs) {
+                Some(merge_scene(base, ours, theirs, registry, path, conflicts))
+            } else {
+                conflicts.push(SceneMergeConflict::RelatedScene { path: path.clone() });
+                ours.cloned()
+            };
+            scenes.extend(scene);
+            path.pop();
+        }
+
+        if !scenes.is_empty() || merged.related.contains_key(&relationship_type_id) {
+            related.insert(
+                relationship_type_id,
+                RelatedResolvedScenes {
+                    scenes,
+                    insert_relationship: info.insert_relationship,
+                    insert_relationship_target: info.insert_relationship_target,
+                    relationship_name: info.relationship_name,
+                },
+            );
+        }
+    }
+    merged.related = related;
+
+    merged
+}
+
+/// Returns the related scene types of all `scenes`, in order of first appearance.
+fn related_types<'a>(scenes: &[&'a ResolvedScene]) -> Vec<(TypeId, &'a RelatedResolvedScenes)> {
+    let mut types: Vec<(TypeId, &RelatedResolvedScenes)> = Vec::new();
+    for scene in scenes {
+        for (type_id, related) in scene.related.iter() {
+            if !types.iter().any(|(existing, _)| existing == type_id) {
+                types.push((*type_id, related));
+            }
+        }
+    }
+    types
+}
+
+fn optional_scenes_eq(
+    a: Option<&ResolvedScene>,
+    b: Option<&ResolvedScene>,
+    registry: &TypeRegistry,
+) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.diff(b, registry).is_empty(),
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+fn optional_templates_eq(
+    a: Option<&dyn ErasedComponentTemplate>,
+    b: Option<&dyn ErasedComponentTemplate>,
+    registry: &TypeRegistry,
+) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => templates_eq(a, b, registry),
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+fn templates_eq(
+    a: &dyn ErasedComponentTemplate,
+    b: &dyn ErasedComponentTemplate,
+    registry: &TypeRegistry,
+) -> bool {
+    let (Some((a, a_entities)), Some((b, b_entities))) =
+        (template_value(a, registry), template_value(b, registry))
+    else {
+        return false;
+    };
+    a_entities == b_entities && a.reflect_partial_eq(&*b).unwrap_or(false)
+}
+
+/// Returns the reflected value of `template` and the paths of its entity fields, if the template can be reflected.
+fn template_value<'a>(
+    template: &'a dyn ErasedComponentTemplate,
+    registry: &TypeRegistry,
+) -> Option<(Box<dyn PartialReflect>, Vec<&'a ParsedPath>)> {
+    if let Some(reflected) = (template as &dyn Any).downcast_ref::<ReflectedComponentTemplate>() {
+        let value = reflected.build_ignoring_entities().ok()?;
+        return Some((
+            value.into_partial_reflect(),
+            reflected.entity_paths().collect(),
+        ));
+    }
+    let registration = registry.get(template.template_type_id())?;
+    let value = ReflectedComponent::from_registration(registration)
+        .ok()?
+        .template_value(template)
+        .ok()?;
+    Some((value, Vec::new()))
+}
+
+fn type_path(registry: &TypeRegistry, type_id: TypeId) -> Option<&'static str> {
+    registry
+        .get(type_id)
+        .map(|registration| registration.type_info().type_path())
+}
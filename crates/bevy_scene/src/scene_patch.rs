@@ -106,6 +106,9 @@ pub enum SpawnSceneError {
 }
 
 /// A component that, when added, will queue applying the given [`ScenePatch`] after the scene and its dependencies have been loaded and resolved.
+///
+/// When the [`ScenePatch`] is reloaded (for example, when a `.bsn` file is changed with asset hot-reloading enabled), the entity will be
+/// updated in place using [`ResolvedSceneRoot::reapply`](crate::ResolvedSceneRoot::reapply).
 #[derive(Component, FromTemplate, Deref, DerefMut)]
 pub struct ScenePatchInstance(pub Handle<ScenePatch>);
 
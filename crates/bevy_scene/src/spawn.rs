@@ -1,9 +1,9 @@
 use crate::{
-    ResolvedSceneRoot, Scene, SceneList, SceneListPatch, ScenePatch, ScenePatchInstance,
-    SpawnSceneError,
+    AppliedScene, ApplySceneError, ResolvedSceneRoot, Scene, SceneList, SceneListPatch, ScenePatch,
+    ScenePatchInstance, SpawnSceneError,
 };
 use alloc::sync::Arc;
-use bevy_asset::{AssetEvent, AssetServer, Assets, Handle};
+use bevy_asset::{AssetEvent, AssetId, AssetServer, Assets, Handle};
 use bevy_ecs::{
     bundle::BundleScratch, message::MessageCursor, prelude::*, relationship::Relationship,
 };
@@ -20,7 +20,6 @@ pub trait WorldSceneExt {
     /// See [`Scene`] for the features of the scene system (and how to use it).
     ///
     /// If your scene has a dependency that might not be loaded yet (for example, it includes a `.bsn` asset file), consider using [`World::queue_spawn_scene`].
-    /// Note that the `.bsn` file format is not yet released.
     ///
     /// ```
     /// # use bevy_app::App;
@@ -84,7 +83,7 @@ pub trait WorldSceneExt {
     /// #[derive(Component, Default, Clone)]
     /// struct Shield;
     ///
-    /// // This scene includes the "player.bsn" asset (note that the `.bsn` file format is not yet released). It will be spawned on the frame that "player.bsn"
+    /// // This scene includes the "player.bsn" asset. It will be spawned on the frame that "player.bsn"
     /// // is fully loaded.
     /// world.queue_spawn_scene(bsn! {
     ///     :"player.bsn"
@@ -106,7 +105,6 @@ pub trait WorldSceneExt {
     /// See [`Scene`] for the features of the scene system (and how to use it).
     ///
     /// If your scene list has a dependency that might not be loaded yet (for example, it includes a `.bsn` asset file), consider using [`World::queue_spawn_scene_list`].
-    /// Note that the `.bsn` file format is not yet released.
     ///
     /// ```
     /// # use bevy_app::App;
@@ -166,7 +164,7 @@ pub trait WorldSceneExt {
     ///     Red,
     ///     Blue,
     /// }
-    /// // This scene list includes the "player.bsn" asset (note that the `.bsn` file format is not yet released). It will be spawned on the frame that "player.bsn"
+    /// // This scene list includes the "player.bsn" asset. It will be spawned on the frame that "player.bsn"
     /// // is loaded.
     /// world.queue_spawn_scene_list(bsn_list! [
     ///     (
@@ -235,7 +233,6 @@ pub trait CommandsSceneExt {
     /// See [`Scene`] for the features of the scene system (and how to use it).
     ///
     /// If your scene has a dependency that might not be loaded yet (for example, it includes a `.bsn` asset file), consider using [`Commands::queue_spawn_scene`].
-    /// Note that the `.bsn` file format is not yet released.
     ///
     /// ```
     /// # use bevy_scene::prelude::*;
@@ -283,7 +280,7 @@ pub trait CommandsSceneExt {
     /// #[derive(Component, Default, Clone)]
     /// struct Shield;
     ///
-    /// // This scene includes the "player.bsn" asset (note that the `.bsn` file format is not yet released). It will be spawned on the frame that "player.bsn"
+    /// // This scene includes the "player.bsn" asset. It will be spawned on the frame that "player.bsn"
     /// // is fully loaded.
     /// commands.queue_spawn_scene(bsn! {
     ///     :"player.bsn"
@@ -318,7 +315,6 @@ pub trait CommandsSceneExt {
     ///     Blue,
     /// }
     ///
-    /// // Note that the .bsn file format is not yet released.
     /// commands.spawn_scene_list(bsn_list! {
     ///     (
     ///         :"player.bsn"
@@ -351,7 +347,7 @@ pub trait CommandsSceneExt {
     ///     Blue,
     /// }
     ///
-    /// // This scene list includes the "player.bsn" asset (note that the `.bsn` file format is not yet released). It will be spawned on the frame that "player.bsn"
+    /// // This scene list includes the "player.bsn" asset. It will be spawned on the frame that "player.bsn"
     /// // is loaded.
     /// commands.queue_spawn_scene_list(bsn_list! [
     ///     (
@@ -463,7 +459,6 @@ pub trait EntityWorldMutSceneExt {
     /// See [`Scene`] for the features of the scene system (and how to use it).
     ///
     /// If your scene has a dependency that might not be loaded yet (for example, it includes a `.bsn` asset file), consider using [`World::queue_spawn_scene`].
-    /// Note that the .bsn file format is not yet released.
     fn apply_scene<S: Scene>(&mut self, scene: S) -> Result<(), SpawnSceneError>;
 
     /// Queues the `scene` to be applied. This will evaluate the `scene`'s dependencies (via [`Scene::register_dependencies`]) and queue it to be resolved and spawned
@@ -568,7 +563,6 @@ pub trait EntityCommandsSceneExt {
     /// See [`Scene`] for the features of the scene system (and how to use it).
     ///
     /// If your scene has a dependency that might not be loaded yet (for example, it includes a `.bsn` asset file), consider using [`Commands::spawn_scene`].
-    /// Note that the .bsn file format is not yet released.
     fn apply_scene<S: Scene>(&mut self, scene: S) -> &mut Self;
 
     /// Queues the `scene` to be applied. This will evaluate the `scene`'s dependencies (via [`Scene::register_dependencies`]) and queue it to be resolved and spawned
@@ -704,10 +698,31 @@ pub fn on_add_scene_patch_instance(
     }
 }
 
-/// A system that spawns queued scenes when they are loaded.
+/// A [`Component`] inserted on [`ScenePatchInstance`] entities after their [`ScenePatch`] has been applied. This records what the scene
+/// wrote to the entity, which enables updating the entity in place (using [`ResolvedSceneRoot::reapply`]) when the [`ScenePatch`] is
+/// reloaded.
+#[derive(Component)]
+pub struct AppliedScenePatch {
+    resolved: Arc<ResolvedSceneRoot>,
+    applied: AppliedScene,
+}
+
+impl AppliedScenePatch {
+    /// The components and related entities written by the most recently applied version of the [`ScenePatch`].
+    pub fn applied(&self) -> &AppliedScene {
+        &self.applied
+    }
+}
+
+/// A system that spawns queued scenes when they are loaded. When the [`ScenePatch`] of a [`ScenePatchInstance`] is reloaded,
+/// this will update the instance in place using [`ResolvedSceneRoot::reapply`].
+///
+/// Note that only changes to the [`ScenePatch`] itself will be reapplied. If the [`ScenePatch`] inherits from another scene asset,
+/// changes to that asset will not be picked up until the [`ScenePatch`] is reloaded.
 pub fn spawn_queued(
     world: &mut World,
     scene_patch_instances: &mut QueryState<&ScenePatchInstance>,
+    applied_scene_patches: &mut QueryState<(Entity, &ScenePatchInstance, &AppliedScenePatch)>,
     mut queued: Local<QueuedScenes>,
     mut bundle_scratch: Local<BundleScratch>,
     mut reader: Local<MessageCursor<AssetEvent<ScenePatch>>>,
@@ -717,15 +732,27 @@ pub fn spawn_queued(
         world.resource_scope(|world, mut waiting: Mut<WaitingScenes>| {
             world.resource_scope(|world, events: Mut<Messages<AssetEvent<ScenePatch>>>| {
                 for event in reader.read(&events) {
+                    let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) =
+                        *event
+                    else {
+                        continue;
+                    };
                     let patches = world.resource::<Assets<ScenePatch>>();
-                    if let AssetEvent::LoadedWithDependencies { id } = event
-                        && let Some(resolved) = patches.get(*id).and_then(|p| p.resolved.clone())
-                        && let Some(entities) = waiting.scene_entities.remove(id)
+                    let Some(resolved) = patches.get(id).and_then(|p| p.resolved.clone()) else {
+                        continue;
+                    };
+
+                    if let AssetEvent::LoadedWithDependencies { .. } = event
+                        && let Some(entities) = waiting.scene_entities.remove(&id)
                     {
                         for entity in entities {
                             if let Ok(mut entity_mut) = world.get_entity_mut(entity)
-                                && let Err(err) =
-                                    resolved.apply(&mut entity_mut, &mut bundle_scratch)
+                                && let Err(err) = apply_scene_patch(
+                                    &mut entity_mut,
+                                    id,
+                                    &resolved,
+                                    &mut bundle_scratch,
+                                )
                             {
                                 error!(
                                     "Failed to apply scene (id: {}) to entity {entity}: {}",
@@ -734,6 +761,14 @@ pub fn spawn_queued(
                             }
                         }
                     }
+
+                    reapply_scene_patch(
+                        world,
+                        id,
+                        &resolved,
+                        applied_scene_patches,
+                        &mut bundle_scratch,
+                    );
                 }
             });
             world.resource_scope(
@@ -787,6 +822,116 @@ pub fn spawn_queued(
     });
 }
 
+/// Applies `resolved` to `entity`. If `entity` is a [`ScenePatchInstance`] of the [`ScenePatch`] with the given `id`, this will also
+/// insert an [`AppliedScenePatch`], which enables updating the entity in place when the [`ScenePatch`] is reloaded.
+fn apply_scene_patch(
+    entity: &mut EntityWorldMut,
+    id: AssetId<ScenePatch>,
+    resolved: &Arc<ResolvedSceneRoot>,
+    bundle_scratch: &mut BundleScratch,
+) -> Result<(), ApplySceneError> {
+    let is_instance = entity
+        .get::<ScenePatchInstance>()
+        .is_some_and(|instance| instance.id() == id);
+    if !is_instance {
+        return resolved.apply(entity, bundle_scratch);
+    }
+
+    let applied = resolved.apply_tracked(entity, bundle_scratch)?;
+    if !entity.is_despawned() {
+        entity.insert(AppliedScenePatch {
+            resolved: resolved.clone(),
+            applied,
+        });
+    }
+    Ok(())
+}
+
+/// Applies the [`ScenePatch`] of the [`ScenePatchInstance`] on `entity` right away, the same way [`spawn_queued`] does, instead of
+/// waiting for the next [`SpawnScene`](bevy_app::SpawnScene) schedule. On success, the entity has an [`AppliedScenePatch`].
+///
+/// Returns `Ok(false)` without applying anything if `entity` has no [`ScenePatchInstance`], or its [`ScenePatch`] has not been
+/// resolved yet. In that case, [`spawn_queued`] applies it once it is ready.
+pub fn apply_scene_patch_instance(
+    world: &mut World,
+    entity: Entity,
+) -> Result<bool, ApplySceneError> {
+    let Some(handle) = world
+        .get::<ScenePatchInstance>(entity)
+        .map(|instance| instance.0.clone())
+    else {
+        return Ok(false);
+    };
+    let Some(resolved) = world
+        .get_resource::<Assets<ScenePatch>>()
+        .and_then(|patches| patches.get(&handle))
+        .and_then(|patch| patch.resolved.clone())
+    else {
+        return Ok(false);
+    };
+
+    // The entity no longer needs to be spawned by `spawn_queued`
+    if let Some(mut queued) = world.get_resource_mut::<QueuedScenes>() {
+        queued
+            .new_scene_entities
+            .retain(|(queued, _)| *queued != entity);
+    }
+    if let Some(mut waiting) = world.get_resource_mut::<WaitingScenes>()
+        && let Some(entities) = waiting.scene_entities.get_mut(&handle)
+    {
+        entities.retain(|waiting| *waiting != entity);
+    }
+
+    apply_scene_patch(
+        &mut world.entity_mut(entity),
+        handle.id(),
+        &resolved,
+        &mut BundleScratch::default(),
+    )?;
+    Ok(true)
+}
+
+/// Reapplies `resolved` to every [`ScenePatchInstance`] of the [`ScenePatch`] with the given `id` that was last applied using a different
+/// version of the scene.
+fn reapply_scene_patch(
+    world: &mut World,
+    id: AssetId<ScenePatch>,
+    resolved: &Arc<ResolvedSceneRoot>,
+    applied_scene_patches: &mut QueryState<(Entity, &ScenePatchInstance, &AppliedScenePatch)>,
+    bundle_scratch: &mut BundleScratch,
+) {
+    let outdated = applied_scene_patches
+        .iter(world)
+        .filter(|(_, instance, applied)| {
+            instance.id() == id && !Arc::ptr_eq(&applied.resolved, resolved)
+        })
+        .map(|(entity, ..)| entity)
+        .collect::<Vec<_>>();
+
+    for entity in outdated {
+        let mut entity_mut = world.entity_mut(entity);
+        let Some(previous) = entity_mut.take::<AppliedScenePatch>() else {
+            continue;
+        };
+        let result = resolved.reapply(&mut entity_mut, &previous.applied, bundle_scratch);
+        if entity_mut.is_despawned() {
+            continue;
+        }
+        match result {
+            Ok(applied) => {
+                entity_mut.insert(AppliedScenePatch {
+                    resolved: resolved.clone(),
+                    applied,
+                });
+            }
+            Err(err) => {
+                error!("Failed to reapply scene (id: {id}) to entity {entity}: {err}");
+                entity_mut.insert(previous);
+            }
+        }
+    }
+}
+
 impl QueuedScenes {
     fn is_empty(&self) -> bool {
         self.new_scene_entities.is_empty()
@@ -806,7 +951,9 @@ impl QueuedScenes {
             let patches = world.resource::<Assets<ScenePatch>>();
             if let Some(resolved) = patches.get(&handle).and_then(|p| p.resolved.clone()) {
                 let mut entity_mut = world.get_entity_mut(entity).unwrap();
-                if let Err(err) = resolved.apply(&mut entity_mut, bundle_scratch) {
+                if let Err(err) =
+                    apply_scene_patch(&mut entity_mut, handle.id(), &resolved, bundle_scratch)
+                {
                     let scene_patch_instance = scene_patch_instances.get(world, entity).unwrap();
                     let handle = &scene_patch_instance.0;
                     let id = handle.id();